@@ -127,7 +127,7 @@ async fn sd_jwt_validation_works() -> anyhow::Result<()> {
   .to_json()?;
 
   // Setting the `typ` in the header is required.
-  let options = JwsSignatureOptions::new().typ(KeyBindingJwtClaims::KB_JWT_HEADER_TYP);
+  let options = JwsSignatureOptions::new().typ("kb+jwt");
 
   // Create the KB-JWT.
   let kb_jwt: Jws = holder
@@ -1,7 +1,6 @@
 // Copyright 2020-2024 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
-use identity_iota::core::Object;
 use identity_iota::core::Url;
 use identity_iota::credential::JwtCredentialValidator;
 use identity_iota::credential::JwtCredentialValidatorUtils;
@@ -193,7 +192,7 @@ impl WasmJwtCredentialValidator {
   /// Fails if the issuer field is not a valid DID.
   #[wasm_bindgen(js_name = extractIssuer)]
   pub fn extract_issuer(credential: &WasmCredential) -> Result<WasmCoreDID> {
-    JwtCredentialValidatorUtils::extract_issuer::<CoreDID, Object>(&credential.0)
+    JwtCredentialValidatorUtils::extract_issuer::<CoreDID, _>(&credential.0)
       .map(WasmCoreDID::from)
       .wasm_result()
   }
@@ -561,6 +561,12 @@ impl WasmIotaDocument {
     Ok(self.0.try_read()?.metadata.governor_address.clone())
   }
 
+  /// Returns a copy of the DID controlling the Alias Output of this document, if present.
+  #[wasm_bindgen(js_name = metadataControllingDid)]
+  pub fn metadata_controlling_did(&self) -> Result<Option<WasmIotaDID>> {
+    Ok(self.0.try_read()?.metadata.controlling_did.clone().map(WasmIotaDID::from))
+  }
+
   /// Sets a custom property in the document metadata.
   /// If the value is set to `null`, the custom property will be removed.
   #[wasm_bindgen(js_name = setMetadataPropertyUnchecked)]
@@ -102,7 +102,7 @@ impl WasmKeyBindingJwtClaims {
   /// https://www.ietf.org/archive/id/draft-ietf-oauth-selective-disclosure-jwt-07.html#name-key-binding-jwt
   #[wasm_bindgen(js_name = keyBindingJwtHeaderTyp)]
   pub fn header_type() -> String {
-    KeyBindingJwtClaims::KB_JWT_HEADER_TYP.to_string()
+    "kb+jwt".to_string()
   }
 }
 
@@ -15,13 +15,10 @@ use identity_eddsa_verifier::EdDSAJwsVerifier;
 use identity_iota::core::json;
 use identity_iota::core::FromJson;
 use identity_iota::core::Object;
-use identity_iota::core::Timestamp;
-use identity_iota::core::ToJson;
 use identity_iota::core::Url;
 use identity_iota::credential::Credential;
 use identity_iota::credential::CredentialBuilder;
 use identity_iota::credential::FailFast;
-use identity_iota::credential::Jws;
 use identity_iota::credential::JwtCredentialValidationOptions;
 use identity_iota::credential::KeyBindingJWTValidationOptions;
 use identity_iota::credential::SdJwtCredentialValidator;
@@ -31,17 +28,16 @@ use identity_iota::iota::IotaDocument;
 use identity_iota::storage::JwkDocumentExt;
 use identity_iota::storage::JwkMemStore;
 use identity_iota::storage::JwsSignatureOptions;
+use identity_iota::storage::KeyBindingJwtOptions;
 use identity_iota::storage::KeyIdMemstore;
+use identity_iota::storage::SdJwtCredentialOptions;
 use iota_sdk::client::secret::stronghold::StrongholdSecretManager;
 use iota_sdk::client::secret::SecretManager;
 use iota_sdk::client::Client;
 use iota_sdk::client::Password;
 use iota_sdk::types::block::address::Address;
-use sd_jwt_payload::KeyBindingJwtClaims;
 use sd_jwt_payload::SdJwt;
 use sd_jwt_payload::SdObjectDecoder;
-use sd_jwt_payload::SdObjectEncoder;
-use sd_jwt_payload::Sha256Hasher;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -99,37 +95,25 @@ async fn main() -> anyhow::Result<()> {
     .subject(subject)
     .build()?;
 
-  // In Order to create an selective disclosure JWT, the plain text JWT
-  // claims set must be created first.
-  let payload = credential.serialize_jwt(None)?;
-  pretty_print_json("Claims set in plain text", &payload);
+  pretty_print_json("Claims set in plain text", &credential.serialize_jwt(None)?);
 
-  // Using the crate `sd-jwt` properties of the claims can be made selectively disclosable.
+  // Make "locality", "postal_code" and "street_address" selectively disclosable while keeping
+  // other properties in plain text. The paths are JSON pointers into the JWT claims set, where the
+  // credential is found under `vc`.
   // The default sha-256 hasher will be used to create the digests.
   // Read more in https://github.com/iotaledger/sd-jwt-payload .
-  let mut encoder = SdObjectEncoder::new(&payload)?;
-
-  // Make "locality", "postal_code" and "street_address" selectively disclosable while keeping
-  // other properties in plain text.
-  let disclosures = vec![
-    encoder.conceal("/vc/credentialSubject/address/locality", None)?,
-    encoder.conceal("/vc/credentialSubject/address/postal_code", None)?,
-    encoder.conceal("/vc/credentialSubject/address/street_address", None)?,
-  ];
-
-  // Add the `_sd_alg` property.
-  encoder.add_sd_alg_property();
-
-  let encoded_payload = encoder.try_to_string()?;
-
-  pretty_print_json("Claims set with disclosure digests", &encoded_payload);
-
-  // Create the signed JWT.
-  let jwt: Jws = issuer_document
-    .create_jws(
+  let sd_jwt: SdJwt = issuer_document
+    .create_sd_jwt_credential(
+      &credential,
       &issuer_storage,
       &fragment,
-      encoded_payload.as_bytes(),
+      &[
+        "/vc/credentialSubject/address/locality",
+        "/vc/credentialSubject/address/postal_code",
+        "/vc/credentialSubject/address/street_address",
+      ],
+      // Add decoy digests to hide the number of selectively disclosable address properties.
+      &SdJwtCredentialOptions::new().decoys("/vc/credentialSubject/address", 2),
       &JwsSignatureOptions::default(),
     )
     .await?;
@@ -140,11 +124,7 @@ async fn main() -> anyhow::Result<()> {
 
   // One way to send the JWT and the disclosures, is by creating an SD-JWT with all the
   // disclosures.
-  let disclosures: Vec<String> = disclosures
-    .into_iter()
-    .map(|disclosure| disclosure.to_string())
-    .collect();
-  let sd_jwt_str = SdJwt::new(jwt.into(), disclosures, None).presentation();
+  let sd_jwt_str = sd_jwt.presentation();
 
   // ===========================================================================
   // Step 4: Verifier sends the holder a challenge and requests a signed Verifiable Presentation.
@@ -161,35 +141,24 @@ async fn main() -> anyhow::Result<()> {
   let sd_jwt = SdJwt::parse(&sd_jwt_str)?;
 
   // The holder only wants to present "locality" and "postal_code" but not "street_address".
-  let disclosures = vec![
-    sd_jwt.disclosures.first().unwrap().clone(),
-    sd_jwt.disclosures.get(1).unwrap().clone(),
+  let disclosures: Vec<&str> = vec![
+    sd_jwt.disclosures.first().unwrap().as_str(),
+    sd_jwt.disclosures.get(1).unwrap().as_str(),
   ];
 
   // Optionally, the holder can add a Key Binding JWT (KB-JWT). This is dependent on the verifier's policy.
-  // Issuing the KB-JWT is done by creating the claims set and setting the header `typ` value
-  // with the help of `KeyBindingJwtClaims`.
-  let binding_claims = KeyBindingJwtClaims::new(
-    &Sha256Hasher::new(),
-    sd_jwt.jwt.as_str().to_string(),
-    disclosures.clone(),
-    nonce.to_string(),
-    VERIFIER_DID.to_string(),
-    Timestamp::now_utc().to_unix(),
-  )
-  .to_json()?;
-
-  // Setting the `typ` in the header is required.
-  let options = JwsSignatureOptions::new().typ(KeyBindingJwtClaims::KB_JWT_HEADER_TYP);
-
-  // Create the KB-JWT.
-  let kb_jwt: Jws = alice_document
-    .create_jws(&alice_storage, &alice_fragment, binding_claims.as_bytes(), &options)
+  // The KB-JWT is signed with the holder's key and appended to the final SD-JWT.
+  let sd_jwt_obj: SdJwt = alice_document
+    .create_sd_jwt_presentation(
+      &sd_jwt,
+      &alice_storage,
+      &alice_fragment,
+      &disclosures,
+      &KeyBindingJwtOptions::new(nonce, VERIFIER_DID),
+      &JwsSignatureOptions::default(),
+    )
     .await?;
 
-  // Create the final SD-JWT.
-  let sd_jwt_obj = SdJwt::new(sd_jwt.jwt, disclosures, Some(kb_jwt.into()));
-
   // ===========================================================================
   // Step 6: Holder presents the SD-JWT to the verifier.
   // ===========================================================================
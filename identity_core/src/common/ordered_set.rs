@@ -291,9 +291,9 @@ mod tests {
   use std::hash::Hash;
 
   use super::*;
-  use proptest::prelude::Rng;
+  use proptest::arbitrary::any;
+  use proptest::sample::Index;
   use proptest::strategy::Strategy;
-  use proptest::test_runner::TestRng;
   use proptest::*;
 
   #[test]
@@ -498,30 +498,28 @@ mod tests {
     U: Strategy<Value = (OrderedSet<T>, OrderedSet<T>)>,
     F: Fn() -> U,
   {
-    f().prop_perturb(|(x, y), mut rng| {
-      let sets = [&x, &y];
-
-      let sample = |generator: &mut TestRng| {
-        let set_idx = usize::from(generator.gen_bool(0.5));
-        let set_range = if set_idx == 0 { 0..x.len() } else { 0..y.len() };
-        if set_range.is_empty() {
-          T::default()
-        } else {
-          let entry_idx = generator.gen_range(set_range);
-          (sets[set_idx])[entry_idx].clone()
+    (f(), any::<(bool, Index, bool, Index, bool)>()).prop_map(
+      |((x, y), (pick_y_a, index_a, pick_y_b, index_b, swap_keys))| {
+        let sample = |pick_y: bool, index: Index| {
+          let set = if pick_y { &y } else { &x };
+          if set.is_empty() {
+            T::default()
+          } else {
+            set[index.index(set.len())].clone()
+          }
+        };
+
+        let (mut a, mut b) = (sample(pick_y_a, index_a), sample(pick_y_b, index_b));
+        if swap_keys {
+          let key_a = a.key().clone();
+          let key_b = b.key().clone();
+          a.set_key(key_b);
+          b.set_key(key_a);
         }
-      };
-
-      let (mut a, mut b) = (sample(&mut rng), sample(&mut rng));
-      if rng.gen_bool(0.5) {
-        let key_a = a.key().clone();
-        let key_b = b.key().clone();
-        a.set_key(key_b);
-        b.set_key(key_a);
-      }
 
-      (x, a, b)
-    })
+        (x, a, b)
+      },
+    )
   }
 
   fn set_with_values_comparable_struct(
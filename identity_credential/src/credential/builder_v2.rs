@@ -0,0 +1,300 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Context;
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::common::Value;
+
+use crate::credential::CredentialV2;
+use crate::credential::Evidence;
+use crate::credential::Issuer;
+use crate::credential::Policy;
+use crate::credential::RefreshService;
+use crate::credential::Schema;
+use crate::credential::Status;
+use crate::credential::Subject;
+use crate::error::Result;
+
+use super::Proof;
+
+/// A `CredentialV2Builder` is used to create a customized `CredentialV2`.
+#[derive(Clone, Debug)]
+pub struct CredentialV2Builder<T = Object> {
+  pub(crate) context: Vec<Context>,
+  pub(crate) id: Option<Url>,
+  pub(crate) types: Vec<String>,
+  pub(crate) subject: Vec<Subject>,
+  pub(crate) name: Option<String>,
+  pub(crate) description: Option<String>,
+  pub(crate) issuer: Option<Issuer>,
+  pub(crate) valid_from: Option<Timestamp>,
+  pub(crate) valid_until: Option<Timestamp>,
+  pub(crate) status: Vec<Status>,
+  pub(crate) schema: Vec<Schema>,
+  pub(crate) refresh_service: Vec<RefreshService>,
+  pub(crate) terms_of_use: Vec<Policy>,
+  pub(crate) evidence: Vec<Evidence>,
+  pub(crate) proof: Option<Proof>,
+  pub(crate) properties: T,
+}
+
+impl<T> CredentialV2Builder<T> {
+  /// Creates a new `CredentialV2Builder`.
+  pub fn new(properties: T) -> Self {
+    Self {
+      context: vec![CredentialV2::<T>::base_context().clone()],
+      id: None,
+      types: vec![CredentialV2::<T>::base_type().into()],
+      subject: Vec::new(),
+      name: None,
+      description: None,
+      issuer: None,
+      valid_from: None,
+      valid_until: None,
+      status: Vec::new(),
+      schema: Vec::new(),
+      refresh_service: Vec::new(),
+      terms_of_use: Vec::new(),
+      evidence: Vec::new(),
+      proof: None,
+      properties,
+    }
+  }
+
+  /// Adds a value to the `CredentialV2` context set.
+  #[must_use]
+  pub fn context(mut self, value: impl Into<Context>) -> Self {
+    self.context.push(value.into());
+    self
+  }
+
+  /// Sets the value of the `CredentialV2` `id`.
+  #[must_use]
+  pub fn id(mut self, value: Url) -> Self {
+    self.id = Some(value);
+    self
+  }
+
+  /// Adds a value to the `CredentialV2` type set.
+  #[must_use]
+  pub fn type_(mut self, value: impl Into<String>) -> Self {
+    self.types.push(value.into());
+    self
+  }
+
+  /// Adds a value to the `credentialSubject` set.
+  #[must_use]
+  pub fn subject(mut self, value: Subject) -> Self {
+    self.subject.push(value);
+    self
+  }
+
+  /// Adds the values from the iterator to the `credentialSubject` set.
+  #[must_use]
+  pub fn subjects<I: IntoIterator<Item = Subject>>(mut self, values: I) -> Self {
+    for value in values {
+      self.subject.push(value);
+    }
+    self
+  }
+
+  /// Sets the value of the `CredentialV2` `issuer`.
+  #[must_use]
+  pub fn issuer(mut self, value: impl Into<Issuer>) -> Self {
+    self.issuer = Some(value.into());
+    self
+  }
+
+  /// Sets the value of the `CredentialV2` `name`.
+  #[must_use]
+  pub fn name(mut self, value: impl Into<String>) -> Self {
+    self.name = Some(value.into());
+    self
+  }
+
+  /// Sets the value of the `CredentialV2` `description`.
+  #[must_use]
+  pub fn description(mut self, value: impl Into<String>) -> Self {
+    self.description = Some(value.into());
+    self
+  }
+
+  /// Sets the value of the `CredentialV2` `validFrom`.
+  #[must_use]
+  pub fn valid_from(mut self, value: Timestamp) -> Self {
+    self.valid_from = Some(value);
+    self
+  }
+
+  /// Sets the value of the `CredentialV2` `validUntil`.
+  #[must_use]
+  pub fn valid_until(mut self, value: Timestamp) -> Self {
+    self.valid_until = Some(value);
+    self
+  }
+
+  /// Adds a value to the `credentialStatus` set.
+  #[must_use]
+  pub fn status(mut self, value: impl Into<Status>) -> Self {
+    self.status.push(value.into());
+    self
+  }
+
+  /// Adds a value to the `credentialSchema` set.
+  #[must_use]
+  pub fn schema(mut self, value: Schema) -> Self {
+    self.schema.push(value);
+    self
+  }
+
+  /// Adds a value to the `refreshService` set.
+  #[must_use]
+  pub fn refresh_service(mut self, value: RefreshService) -> Self {
+    self.refresh_service.push(value);
+    self
+  }
+
+  /// Adds a value to the `termsOfUse` set.
+  #[must_use]
+  pub fn terms_of_use(mut self, value: Policy) -> Self {
+    self.terms_of_use.push(value);
+    self
+  }
+
+  /// Adds a value to the `evidence` set.
+  #[must_use]
+  pub fn evidence(mut self, value: Evidence) -> Self {
+    self.evidence.push(value);
+    self
+  }
+
+  /// Sets the value of the `proof` property.
+  #[must_use]
+  pub fn proof(mut self, value: Proof) -> Self {
+    self.proof = Some(value);
+    self
+  }
+
+  /// Returns a new `CredentialV2` based on the `CredentialV2Builder` configuration.
+  pub fn build(self) -> Result<CredentialV2<T>> {
+    CredentialV2::from_builder(self)
+  }
+}
+
+impl CredentialV2Builder {
+  /// Adds a new custom property to the `CredentialV2`.
+  #[must_use]
+  pub fn property<K, V>(mut self, key: K, value: V) -> Self
+  where
+    K: Into<String>,
+    V: Into<Value>,
+  {
+    self.properties.insert(key.into(), value.into());
+    self
+  }
+
+  /// Adds a series of custom properties to the `CredentialV2`.
+  #[must_use]
+  pub fn properties<K, V, I>(mut self, iter: I) -> Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<Value>,
+  {
+    self
+      .properties
+      .extend(iter.into_iter().map(|(k, v)| (k.into(), v.into())));
+    self
+  }
+}
+
+impl<T> Default for CredentialV2Builder<T>
+where
+  T: Default,
+{
+  fn default() -> Self {
+    Self::new(T::default())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Object;
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_core::convert::FromJson;
+  use serde_json::json;
+  use serde_json::Value;
+
+  use crate::credential::CredentialV2;
+  use crate::credential::CredentialV2Builder;
+  use crate::credential::Status;
+  use crate::credential::Subject;
+
+  fn subject() -> Subject {
+    let json: Value = json!({
+      "id": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+      "degree": {
+        "type": "BachelorDegree",
+        "name": "Bachelor of Science and Arts"
+      }
+    });
+
+    Subject::from_json_value(json).unwrap()
+  }
+
+  fn issuer() -> Url {
+    Url::parse("did:example:issuer").unwrap()
+  }
+
+  #[test]
+  fn test_credential_v2_builder_valid() {
+    let status = |id: &str| Status::new(Url::parse(id).unwrap(), "BitstringStatusListEntry".to_owned());
+    let credential: CredentialV2 = CredentialV2Builder::default()
+      .context(Url::parse("https://www.w3.org/ns/credentials/examples/v2").unwrap())
+      .id(Url::parse("http://example.edu/credentials/3732").unwrap())
+      .type_("UniversityDegreeCredential")
+      .name("Degree")
+      .description("A university degree")
+      .subject(subject())
+      .issuer(issuer())
+      .valid_from(Timestamp::parse("2010-01-01T00:00:00Z").unwrap())
+      .valid_until(Timestamp::parse("2030-01-01T00:00:00Z").unwrap())
+      .status(status("https://example.edu/status/1#1"))
+      .status(status("https://example.edu/status/2#1"))
+      .build()
+      .unwrap();
+
+    assert_eq!(credential.context.len(), 2);
+    assert_eq!(
+      credential.context.get(0).unwrap(),
+      CredentialV2::<Object>::base_context()
+    );
+    assert_eq!(credential.types.get(0).unwrap(), CredentialV2::<Object>::base_type());
+    assert_eq!(credential.name.as_deref(), Some("Degree"));
+    assert_eq!(credential.description.as_deref(), Some("A university degree"));
+    assert_eq!(credential.issuer.url(), "did:example:issuer");
+    assert_eq!(credential.valid_from.unwrap().to_string(), "2010-01-01T00:00:00Z");
+    assert_eq!(credential.valid_until.unwrap().to_string(), "2030-01-01T00:00:00Z");
+    assert_eq!(credential.credential_status.len(), 2);
+  }
+
+  #[test]
+  fn test_credential_v2_builder_no_default_valid_from() {
+    let credential: CredentialV2 = CredentialV2Builder::default()
+      .subject(subject())
+      .issuer(issuer())
+      .build()
+      .unwrap();
+    assert!(credential.valid_from.is_none());
+    assert!(credential.credential_status.is_empty());
+  }
+
+  #[test]
+  #[should_panic = "MissingSubject"]
+  fn test_builder_missing_subjects() {
+    let _: CredentialV2 = CredentialV2Builder::default().issuer(issuer()).build().unwrap();
+  }
+}
@@ -0,0 +1,109 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::OneOrMany;
+use identity_core::common::Timestamp;
+
+use crate::credential::Credential;
+use crate::credential::CredentialV2;
+use crate::credential::Issuer;
+use crate::credential::Status;
+use crate::credential::Subject;
+use crate::error::Result;
+
+/// Common interface of the credential data models supported by this crate, namely [`Credential`]
+/// ([VC Data Model v1.1](https://www.w3.org/TR/vc-data-model/)) and [`CredentialV2`]
+/// ([VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/)).
+///
+/// This allows validation logic to be shared between both versions.
+pub trait CredentialT {
+  /// Returns a reference to the issuer of the credential.
+  fn issuer(&self) -> &Issuer;
+
+  /// Returns a reference to the subject(s) of the credential.
+  fn subject(&self) -> &OneOrMany<Subject>;
+
+  /// Returns the point in time from which the credential is valid, if any.
+  ///
+  /// This corresponds to `issuanceDate` for v1.1 credentials and `validFrom` for v2.0 credentials.
+  fn valid_from(&self) -> Option<Timestamp>;
+
+  /// Returns the point in time after which the credential is no longer valid, if any.
+  ///
+  /// This corresponds to `expirationDate` for v1.1 credentials and `validUntil` for v2.0 credentials.
+  fn valid_until(&self) -> Option<Timestamp>;
+
+  /// Returns the entries of the `credentialStatus` property.
+  fn credential_status(&self) -> &[Status];
+
+  /// Returns whether the credential must only be presented by its subject.
+  ///
+  /// Only v1.1 credentials can set this property through `nonTransferable`.
+  fn non_transferable(&self) -> bool;
+
+  /// Validates the semantic structure of the credential.
+  fn check_structure(&self) -> Result<()>;
+}
+
+impl<T> CredentialT for Credential<T> {
+  fn issuer(&self) -> &Issuer {
+    &self.issuer
+  }
+
+  fn subject(&self) -> &OneOrMany<Subject> {
+    &self.credential_subject
+  }
+
+  fn valid_from(&self) -> Option<Timestamp> {
+    Some(self.issuance_date)
+  }
+
+  fn valid_until(&self) -> Option<Timestamp> {
+    self.expiration_date
+  }
+
+  fn credential_status(&self) -> &[Status] {
+    match &self.credential_status {
+      Some(status) => std::slice::from_ref(status),
+      None => &[],
+    }
+  }
+
+  fn non_transferable(&self) -> bool {
+    self.non_transferable.unwrap_or(false)
+  }
+
+  fn check_structure(&self) -> Result<()> {
+    Credential::check_structure(self)
+  }
+}
+
+impl<T> CredentialT for CredentialV2<T> {
+  fn issuer(&self) -> &Issuer {
+    &self.issuer
+  }
+
+  fn subject(&self) -> &OneOrMany<Subject> {
+    &self.credential_subject
+  }
+
+  fn valid_from(&self) -> Option<Timestamp> {
+    self.valid_from
+  }
+
+  fn valid_until(&self) -> Option<Timestamp> {
+    self.valid_until
+  }
+
+  fn credential_status(&self) -> &[Status] {
+    self.credential_status.as_slice()
+  }
+
+  fn non_transferable(&self) -> bool {
+    false
+  }
+
+  fn check_structure(&self) -> Result<()> {
+    CredentialV2::check_structure(self)
+  }
+}
@@ -0,0 +1,232 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_core::common::Context;
+use identity_core::common::Object;
+use identity_core::common::OneOrMany;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::convert::FmtJson;
+use identity_core::convert::ToJson;
+
+use crate::credential::CredentialV2Builder;
+use crate::credential::Evidence;
+use crate::credential::Issuer;
+use crate::credential::Policy;
+use crate::credential::RefreshService;
+use crate::credential::Schema;
+use crate::credential::Status;
+use crate::credential::Subject;
+use crate::error::Error;
+use crate::error::Result;
+
+use super::jwt_serialization::CredentialV2JwtClaims;
+use super::Proof;
+
+static BASE_CONTEXT: Lazy<Context> =
+  Lazy::new(|| Context::Url(Url::parse("https://www.w3.org/ns/credentials/v2").unwrap()));
+
+/// Represents a set of claims describing an entity according to the
+/// [VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CredentialV2<T = Object> {
+  /// The JSON-LD context(s) applicable to the `CredentialV2`.
+  #[serde(rename = "@context")]
+  pub context: OneOrMany<Context>,
+  /// A unique `URI` that may be used to identify the `CredentialV2`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub id: Option<Url>,
+  /// One or more URIs defining the type of the `CredentialV2`.
+  #[serde(rename = "type")]
+  pub types: OneOrMany<String>,
+  /// A human-readable name of the `CredentialV2`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  /// A human-readable description of the `CredentialV2`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub description: Option<String>,
+  /// One or more `Object`s representing the `CredentialV2` subject(s).
+  #[serde(rename = "credentialSubject")]
+  pub credential_subject: OneOrMany<Subject>,
+  /// A reference to the issuer of the `CredentialV2`.
+  pub issuer: Issuer,
+  /// A timestamp of when the `CredentialV2` becomes valid.
+  #[serde(rename = "validFrom", skip_serializing_if = "Option::is_none")]
+  pub valid_from: Option<Timestamp>,
+  /// A timestamp of when the `CredentialV2` should no longer be considered valid.
+  #[serde(rename = "validUntil", skip_serializing_if = "Option::is_none")]
+  pub valid_until: Option<Timestamp>,
+  /// Information used to determine the current status of the `CredentialV2`.
+  #[serde(default, rename = "credentialStatus", skip_serializing_if = "OneOrMany::is_empty")]
+  pub credential_status: OneOrMany<Status>,
+  /// Information used to assist in the enforcement of a specific `CredentialV2` structure.
+  #[serde(default, rename = "credentialSchema", skip_serializing_if = "OneOrMany::is_empty")]
+  pub credential_schema: OneOrMany<Schema>,
+  /// Service(s) used to refresh an expired `CredentialV2`.
+  #[serde(default, rename = "refreshService", skip_serializing_if = "OneOrMany::is_empty")]
+  pub refresh_service: OneOrMany<RefreshService>,
+  /// Terms-of-use specified by the `CredentialV2` issuer.
+  #[serde(default, rename = "termsOfUse", skip_serializing_if = "OneOrMany::is_empty")]
+  pub terms_of_use: OneOrMany<Policy>,
+  /// Human-readable evidence used to support the claims within the `CredentialV2`.
+  #[serde(default, skip_serializing_if = "OneOrMany::is_empty")]
+  pub evidence: OneOrMany<Evidence>,
+  /// Miscellaneous properties.
+  #[serde(flatten)]
+  pub properties: T,
+  /// Optional cryptographic proof, unrelated to JWT.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub proof: Option<Proof>,
+}
+
+impl<T> CredentialV2<T> {
+  /// Returns the base JSON-LD context.
+  pub fn base_context() -> &'static Context {
+    &BASE_CONTEXT
+  }
+
+  /// Returns the base type.
+  pub const fn base_type() -> &'static str {
+    "VerifiableCredential"
+  }
+
+  /// Creates a new `CredentialV2Builder` to configure a `CredentialV2`.
+  ///
+  /// This is the same as [CredentialV2Builder::new].
+  pub fn builder(properties: T) -> CredentialV2Builder<T> {
+    CredentialV2Builder::new(properties)
+  }
+
+  /// Returns a new `CredentialV2` based on the `CredentialV2Builder` configuration.
+  pub fn from_builder(builder: CredentialV2Builder<T>) -> Result<Self> {
+    let this: Self = Self {
+      context: builder.context.into(),
+      id: builder.id,
+      types: builder.types.into(),
+      name: builder.name,
+      description: builder.description,
+      credential_subject: builder.subject.into(),
+      issuer: builder.issuer.ok_or(Error::MissingIssuer)?,
+      valid_from: builder.valid_from,
+      valid_until: builder.valid_until,
+      credential_status: builder.status.into(),
+      credential_schema: builder.schema.into(),
+      refresh_service: builder.refresh_service.into(),
+      terms_of_use: builder.terms_of_use.into(),
+      evidence: builder.evidence.into(),
+      properties: builder.properties,
+      proof: builder.proof,
+    };
+
+    this.check_structure()?;
+
+    Ok(this)
+  }
+
+  /// Validates the semantic structure of the `CredentialV2`.
+  pub fn check_structure(&self) -> Result<()> {
+    // Ensure the base context is present and in the correct location
+    match self.context.get(0) {
+      Some(context) if context == Self::base_context() => {}
+      Some(_) | None => return Err(Error::MissingBaseContext),
+    }
+
+    // The set of types MUST contain the base type
+    if !self.types.iter().any(|type_| type_ == Self::base_type()) {
+      return Err(Error::MissingBaseType);
+    }
+
+    // Credentials MUST have at least one subject
+    if self.credential_subject.is_empty() {
+      return Err(Error::MissingSubject);
+    }
+
+    // Each subject is defined as one or more properties - no empty objects
+    for subject in self.credential_subject.iter() {
+      if subject.id.is_none() && subject.properties.is_empty() {
+        return Err(Error::InvalidSubject);
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Sets the proof property of the `CredentialV2`.
+  ///
+  /// Note that this proof is not related to JWT.
+  pub fn set_proof(&mut self, proof: Option<Proof>) {
+    self.proof = proof;
+  }
+
+  /// Serializes the [`CredentialV2`] as a JWT claims set
+  /// in accordance with [VC-JOSE-COSE](https://www.w3.org/TR/vc-jose-cose/#securing-with-jose).
+  ///
+  /// The claims set is the credential itself, complemented by the registered claims `iss`, `jti`, `sub`, `nbf` and
+  /// `exp` derived from it. The resulting string can be used as the payload of a JWS when issuing the credential, as
+  /// well as the input of an SD-JWT encoder.
+  pub fn serialize_jwt(&self) -> Result<String>
+  where
+    T: Serialize,
+  {
+    CredentialV2JwtClaims::new(self)?
+      .to_json()
+      .map_err(|err| Error::JwtClaimsSetSerializationError(err.into()))
+  }
+}
+
+impl<T> Display for CredentialV2<T>
+where
+  T: Serialize,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    self.fmt_json(f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Context;
+  use identity_core::common::OneOrMany;
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_core::convert::FromJson;
+
+  use crate::credential::CredentialV2;
+  use crate::error::Error;
+
+  const JSON1: &str = include_str!("../../tests/fixtures/credential-v2-1.json");
+  const JSON2: &str = include_str!("../../tests/fixtures/credential-v2-2.json");
+
+  #[test]
+  fn test_from_json() {
+    let credential: CredentialV2 = CredentialV2::from_json(JSON1).unwrap();
+    assert!(credential.check_structure().is_ok());
+    assert_eq!(credential.name.as_deref(), Some("Example University Degree"));
+    assert_eq!(
+      credential.valid_from,
+      Some(Timestamp::parse("2010-01-01T19:23:24Z").unwrap())
+    );
+    assert!(credential.credential_status.is_empty());
+
+    let credential: CredentialV2 = CredentialV2::from_json(JSON2).unwrap();
+    assert!(credential.check_structure().is_ok());
+    assert_eq!(credential.credential_status.len(), 2);
+    assert!(credential.valid_from.is_none());
+  }
+
+  #[test]
+  fn test_check_structure_rejects_v1_context() {
+    let mut credential: CredentialV2 = CredentialV2::from_json(JSON1).unwrap();
+    credential.context = OneOrMany::One(Context::Url(
+      Url::parse("https://www.w3.org/2018/credentials/v1").unwrap(),
+    ));
+    assert!(matches!(credential.check_structure(), Err(Error::MissingBaseContext)));
+  }
+}
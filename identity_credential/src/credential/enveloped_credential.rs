@@ -0,0 +1,158 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_core::common::Context;
+use identity_core::common::OneOrMany;
+use identity_core::common::Url;
+use identity_core::convert::FmtJson;
+
+use crate::credential::CredentialV2;
+use crate::credential::Jwt;
+use crate::error::Error;
+use crate::error::Result;
+
+/// A verifiable credential secured by an enveloping proof, such as a JWT, embedded in a `data:` URL.
+///
+/// This is the way credentials secured as JWTs are referenced from within a
+/// [`PresentationV2`](crate::presentation::PresentationV2).
+///
+/// See [VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/#enveloped-verifiable-credentials).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EnvelopedVerifiableCredential {
+  /// The JSON-LD context(s) applicable to the `EnvelopedVerifiableCredential`.
+  #[serde(rename = "@context")]
+  pub context: OneOrMany<Context>,
+  /// The `data:` URL containing the enveloped credential.
+  pub id: Url,
+  /// One or more URIs defining the type of the `EnvelopedVerifiableCredential`.
+  #[serde(rename = "type")]
+  pub types: OneOrMany<String>,
+}
+
+impl EnvelopedVerifiableCredential {
+  /// Returns the base type.
+  pub const fn base_type() -> &'static str {
+    "EnvelopedVerifiableCredential"
+  }
+
+  /// Media type of credentials secured as JWTs.
+  pub const JWT_MEDIA_TYPE: &'static str = "application/vc+jwt";
+
+  /// Creates a new `EnvelopedVerifiableCredential` wrapping the given `token` of the given `media_type`.
+  pub fn new(media_type: &str, token: &str) -> Result<Self> {
+    let id: Url = Url::parse(format!("data:{media_type},{token}"))
+      .map_err(|_| Error::InvalidEnvelopedCredential("invalid data URL"))?;
+
+    Ok(Self {
+      context: OneOrMany::One(CredentialV2::<()>::base_context().clone()),
+      id,
+      types: OneOrMany::One(Self::base_type().to_owned()),
+    })
+  }
+
+  /// Creates a new `EnvelopedVerifiableCredential` wrapping a credential secured as a JWT.
+  pub fn from_jwt(jwt: &Jwt) -> Result<Self> {
+    Self::new(Self::JWT_MEDIA_TYPE, jwt.as_str())
+  }
+
+  /// Returns the media type of the enveloped credential.
+  pub fn media_type(&self) -> Result<&str> {
+    self.parts().map(|(media_type, _)| media_type)
+  }
+
+  /// Returns the enveloped credential in its serialized form, e.g. a compact JWS.
+  pub fn token(&self) -> Result<&str> {
+    self.parts().map(|(_, token)| token)
+  }
+
+  /// Returns the enveloped credential as a [`Jwt`].
+  ///
+  /// # Errors
+  /// Fails if the media type of the enveloped credential is not [`Self::JWT_MEDIA_TYPE`].
+  pub fn to_jwt(&self) -> Result<Jwt> {
+    let (media_type, token) = self.parts()?;
+    if media_type != Self::JWT_MEDIA_TYPE {
+      return Err(Error::InvalidEnvelopedCredential("unsupported media type"));
+    }
+
+    Ok(Jwt::new(token.to_owned()))
+  }
+
+  /// Validates the semantic structure of the `EnvelopedVerifiableCredential`.
+  pub fn check_structure(&self) -> Result<()> {
+    match self.context.get(0) {
+      Some(context) if context == CredentialV2::<()>::base_context() => {}
+      Some(_) | None => return Err(Error::MissingBaseContext),
+    }
+
+    if !self.types.iter().any(|type_| type_ == Self::base_type()) {
+      return Err(Error::MissingBaseType);
+    }
+
+    self.parts().map(|_| ())
+  }
+
+  fn parts(&self) -> Result<(&str, &str)> {
+    if self.id.scheme() != "data" {
+      return Err(Error::InvalidEnvelopedCredential("expected a data URL"));
+    }
+
+    self
+      .id
+      .path()
+      .split_once(',')
+      .ok_or(Error::InvalidEnvelopedCredential("missing data in data URL"))
+  }
+}
+
+impl Display for EnvelopedVerifiableCredential {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    self.fmt_json(f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use serde_json::json;
+
+  use super::EnvelopedVerifiableCredential;
+  use crate::credential::Jwt;
+
+  #[test]
+  fn test_jwt_roundtrip() {
+    let jwt: Jwt = Jwt::from("eyJhbGciOiJFZERTQSJ9.eyJpc3MiOiJkaWQ6ZXhhbXBsZToxIn0.c2ln".to_owned());
+    let enveloped: EnvelopedVerifiableCredential = EnvelopedVerifiableCredential::from_jwt(&jwt).unwrap();
+
+    assert!(enveloped.check_structure().is_ok());
+    assert_eq!(
+      enveloped.media_type().unwrap(),
+      EnvelopedVerifiableCredential::JWT_MEDIA_TYPE
+    );
+    assert_eq!(enveloped.to_jwt().unwrap(), jwt);
+    assert_eq!(
+      enveloped.id.as_str(),
+      "data:application/vc+jwt,eyJhbGciOiJFZERTQSJ9.eyJpc3MiOiJkaWQ6ZXhhbXBsZToxIn0.c2ln"
+    );
+  }
+
+  #[test]
+  fn test_unsupported_media_type() {
+    let enveloped: EnvelopedVerifiableCredential = EnvelopedVerifiableCredential::from_json_value(json!({
+      "@context": "https://www.w3.org/ns/credentials/v2",
+      "id": "data:application/vc+cose,ABCD",
+      "type": "EnvelopedVerifiableCredential"
+    }))
+    .unwrap();
+
+    assert!(enveloped.check_structure().is_ok());
+    assert_eq!(enveloped.token().unwrap(), "ABCD");
+    assert!(enveloped.to_jwt().is_err());
+  }
+}
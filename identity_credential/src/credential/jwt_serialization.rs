@@ -16,6 +16,7 @@ use identity_core::common::Url;
 use serde::de::DeserializeOwned;
 
 use crate::credential::Credential;
+use crate::credential::CredentialV2;
 use crate::credential::Evidence;
 use crate::credential::Issuer;
 use crate::credential::Policy;
@@ -362,6 +363,126 @@ where
   proof: Option<Cow<'credential, Proof>>,
 }
 
+/// Implementation of JWT Encoding/Decoding of a [`CredentialV2`] according to
+/// [VC-JOSE-COSE](https://www.w3.org/TR/vc-jose-cose/#securing-with-jose).
+///
+/// Unlike v1.1 credentials, the claims set is the credential itself. The registered claims `iss`, `jti`, `sub`, `nbf`
+/// and `exp` are derived from the credential and must be consistent with it when present.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CredentialV2JwtClaims<'credential> {
+  /// Represents the validUntil property encoded as a UNIX timestamp.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  exp: Option<i64>,
+  /// Represents the issuer's identifier.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  iss: Option<Cow<'credential, Url>>,
+  /// Represents the validFrom property encoded as a UNIX timestamp.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  nbf: Option<i64>,
+  /// Never set during serialization, but tolerated when decoding.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  iat: Option<i64>,
+  /// Represents the id property of the credential.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  jti: Option<Cow<'credential, Url>>,
+  /// Represents the subject's id.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  sub: Option<Cow<'credential, Url>>,
+  /// The JSON representation of the credential.
+  #[serde(flatten)]
+  credential: Object,
+}
+
+impl<'credential> CredentialV2JwtClaims<'credential> {
+  pub(super) fn new<T>(credential: &'credential CredentialV2<T>) -> Result<Self>
+  where
+    T: Serialize,
+  {
+    let credential_json: Object = serde_json::to_value(credential)
+      .and_then(serde_json::from_value)
+      .map_err(|err| Error::JwtClaimsSetSerializationError(err.into()))?;
+
+    let sub: Option<&Url> = match &credential.credential_subject {
+      OneOrMany::One(subject) => subject.id.as_ref(),
+      OneOrMany::Many(_) => None,
+    };
+
+    Ok(Self {
+      exp: credential.valid_until.map(|value| value.to_unix()),
+      iss: Some(Cow::Borrowed(credential.issuer.url())),
+      nbf: credential.valid_from.map(|value| value.to_unix()),
+      iat: None,
+      jti: credential.id.as_ref().map(Cow::Borrowed),
+      sub: sub.map(Cow::Borrowed),
+      credential: credential_json,
+    })
+  }
+}
+
+#[cfg(feature = "validator")]
+impl<'credential> CredentialV2JwtClaims<'credential> {
+  /// Converts the JWT representation into a [`CredentialV2`], checking that the registered claims
+  /// are consistent with the credential.
+  pub(crate) fn try_into_credential<T>(self) -> Result<CredentialV2<T>>
+  where
+    T: DeserializeOwned,
+  {
+    let credential: CredentialV2<T> = serde_json::to_value(self.credential)
+      .and_then(serde_json::from_value)
+      .map_err(|err| Error::JwtClaimsSetDeserializationError(err.into()))?;
+
+    if !self
+      .iss
+      .map(|iss| iss.as_ref() == credential.issuer.url())
+      .unwrap_or(true)
+    {
+      return Err(Error::InconsistentCredentialJwtClaims("inconsistent issuer"));
+    }
+
+    if !self
+      .jti
+      .map(|jti| Some(jti.as_ref()) == credential.id.as_ref())
+      .unwrap_or(true)
+    {
+      return Err(Error::InconsistentCredentialJwtClaims("inconsistent credential id"));
+    }
+
+    if let Some(sub) = self.sub {
+      let subject_matches: bool = match &credential.credential_subject {
+        OneOrMany::One(subject) => subject.id.as_ref() == Some(sub.as_ref()),
+        OneOrMany::Many(_) => false,
+      };
+      if !subject_matches {
+        return Err(Error::InconsistentCredentialJwtClaims(
+          "inconsistent credentialSubject: identifiers do not match",
+        ));
+      }
+    }
+
+    if !self
+      .nbf
+      .map(|nbf| credential.valid_from.map(|value| value.to_unix()) == Some(nbf))
+      .unwrap_or(true)
+    {
+      return Err(Error::InconsistentCredentialJwtClaims(
+        "inconsistent credential validFrom",
+      ));
+    }
+
+    if !self
+      .exp
+      .map(|exp| credential.valid_until.map(|value| value.to_unix()) == Some(exp))
+      .unwrap_or(true)
+    {
+      return Err(Error::InconsistentCredentialJwtClaims(
+        "inconsistent credential validUntil",
+      ));
+    }
+
+    Ok(credential)
+  }
+}
+
 #[cfg(feature = "jpt-bbs-plus")]
 impl<'credential, T> From<CredentialJwtClaims<'credential, T>> for JptClaims
 where
@@ -420,9 +541,11 @@ mod tests {
   use identity_core::convert::ToJson;
 
   use crate::credential::Credential;
+  use crate::credential::CredentialV2;
   use crate::Error;
 
   use super::CredentialJwtClaims;
+  use super::CredentialV2JwtClaims;
 
   #[test]
   fn roundtrip() {
@@ -728,4 +851,90 @@ mod tests {
       Error::InconsistentCredentialJwtClaims("inconsistent credential expirationDate")
     ));
   }
+
+  const CREDENTIAL_V2_JSON: &str = r#"
+  {
+    "@context": [
+      "https://www.w3.org/ns/credentials/v2",
+      "https://www.w3.org/ns/credentials/examples/v2"
+    ],
+    "id": "http://example.edu/credentials/3732",
+    "type": ["VerifiableCredential", "UniversityDegreeCredential"],
+    "name": "University Degree",
+    "issuer": "https://example.edu/issuers/14",
+    "validFrom": "2010-01-01T19:23:24Z",
+    "validUntil": "2025-09-13T15:56:23Z",
+    "credentialSubject": {
+      "id": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+      "degree": {
+        "type": "BachelorDegree",
+        "name": "Bachelor of Science in Mechanical Engineering"
+      }
+    }
+  }"#;
+
+  #[test]
+  fn roundtrip_v2() {
+    let expected_serialization_json: &str = r#"
+    {
+      "sub": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+      "jti": "http://example.edu/credentials/3732",
+      "iss": "https://example.edu/issuers/14",
+      "nbf":  1262373804,
+      "exp": 1757778983,
+      "@context": [
+        "https://www.w3.org/ns/credentials/v2",
+        "https://www.w3.org/ns/credentials/examples/v2"
+      ],
+      "id": "http://example.edu/credentials/3732",
+      "type": ["VerifiableCredential", "UniversityDegreeCredential"],
+      "name": "University Degree",
+      "issuer": "https://example.edu/issuers/14",
+      "validFrom": "2010-01-01T19:23:24Z",
+      "validUntil": "2025-09-13T15:56:23Z",
+      "credentialSubject": {
+        "id": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+        "degree": {
+          "type": "BachelorDegree",
+          "name": "Bachelor of Science in Mechanical Engineering"
+        }
+      }
+    }"#;
+
+    let credential: CredentialV2 = CredentialV2::from_json(CREDENTIAL_V2_JSON).unwrap();
+    let serialized: String = credential.serialize_jwt().unwrap();
+    assert_eq!(
+      Object::from_json(expected_serialization_json).unwrap(),
+      Object::from_json(&serialized).unwrap()
+    );
+
+    let retrieved_credential: CredentialV2 = CredentialV2JwtClaims::from_json(&serialized)
+      .unwrap()
+      .try_into_credential()
+      .unwrap();
+    assert_eq!(credential, retrieved_credential);
+  }
+
+  #[test]
+  fn inconsistent_v2_claims() {
+    let mut claims: Object = Object::from_json(CREDENTIAL_V2_JSON).unwrap();
+    claims.insert("iss".to_owned(), "https://example.edu/issuers/15".into());
+    let result: Result<CredentialV2, _> = CredentialV2JwtClaims::from_json(&claims.to_json().unwrap())
+      .unwrap()
+      .try_into_credential();
+    assert!(matches!(
+      result.unwrap_err(),
+      Error::InconsistentCredentialJwtClaims("inconsistent issuer")
+    ));
+
+    let mut claims: Object = Object::from_json(CREDENTIAL_V2_JSON).unwrap();
+    claims.insert("nbf".to_owned(), 0.into());
+    let result: Result<CredentialV2, _> = CredentialV2JwtClaims::from_json(&claims.to_json().unwrap())
+      .unwrap()
+      .try_into_credential();
+    assert!(matches!(
+      result.unwrap_err(),
+      Error::InconsistentCredentialJwtClaims("inconsistent credential validFrom")
+    ));
+  }
 }
@@ -6,7 +6,11 @@
 #![allow(clippy::module_inception)]
 
 mod builder;
+mod builder_v2;
 mod credential;
+mod credential_t;
+mod credential_v2;
+mod enveloped_credential;
 mod evidence;
 mod issuer;
 #[cfg(feature = "jpt-bbs-plus")]
@@ -28,7 +32,11 @@ mod status;
 mod subject;
 
 pub use self::builder::CredentialBuilder;
+pub use self::builder_v2::CredentialV2Builder;
 pub use self::credential::Credential;
+pub use self::credential_t::CredentialT;
+pub use self::credential_v2::CredentialV2;
+pub use self::enveloped_credential::EnvelopedVerifiableCredential;
 pub use self::evidence::Evidence;
 pub use self::issuer::Issuer;
 #[cfg(feature = "jpt-bbs-plus")]
@@ -52,5 +60,7 @@ pub use self::subject::Subject;
 
 #[cfg(feature = "validator")]
 pub(crate) use self::jwt_serialization::CredentialJwtClaims;
+#[cfg(feature = "validator")]
+pub(crate) use self::jwt_serialization::CredentialV2JwtClaims;
 #[cfg(feature = "presentation")]
 pub(crate) use self::jwt_serialization::IssuanceDateClaims;
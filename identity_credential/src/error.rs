@@ -79,4 +79,12 @@ pub enum Error {
   /// Cause by an invalid attribute path
   #[error("Attribute Not found")]
   SelectiveDisclosureError,
+
+  /// Caused when an enveloped credential does not hold a valid `data:` URL.
+  #[error("invalid enveloped credential: {0}")]
+  InvalidEnvelopedCredential(&'static str),
+
+  /// Caused when the claims of an SD-JWT VC are invalid.
+  #[error("invalid SD-JWT VC claims: {0}")]
+  InvalidSdJwtVcClaims(&'static str),
 }
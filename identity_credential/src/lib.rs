@@ -23,6 +23,8 @@ pub mod error;
 pub mod presentation;
 #[cfg(feature = "revocation-bitmap")]
 pub mod revocation;
+#[cfg(feature = "sd-jwt")]
+pub mod sd_jwt_vc;
 mod utils;
 #[cfg(feature = "validator")]
 pub mod validator;
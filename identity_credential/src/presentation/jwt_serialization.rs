@@ -18,7 +18,7 @@ use crate::credential::Policy;
 use crate::credential::Proof;
 use crate::credential::RefreshService;
 use crate::presentation::Presentation;
-#[cfg(feature = "validator")]
+use crate::presentation::PresentationV2;
 use crate::Error;
 use crate::Result;
 
@@ -117,6 +117,7 @@ where
   types: Cow<'presentation, OneOrMany<String>>,
   /// Credential(s) expressing the claims of the `Presentation`.
   #[serde(default = "Default::default", rename = "verifiableCredential")]
+  #[allow(clippy::owned_cow)]
   pub(crate) verifiable_credential: Cow<'presentation, Vec<CRED>>,
   /// The entity that generated the `Presentation`.
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -206,12 +207,113 @@ where
   }
 }
 
+/// Implementation of JWT Encoding/Decoding of a [`PresentationV2`] according to
+/// [VC-JOSE-COSE](https://www.w3.org/TR/vc-jose-cose/#securing-with-jose).
+///
+/// The claims set is the presentation itself, complemented by the registered claims.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct PresentationV2JwtClaims<'presentation> {
+  /// Represents the expirationDate encoded as a UNIX timestamp.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) exp: Option<i64>,
+
+  /// Represents the holder of the verifiable presentation.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) iss: Option<Cow<'presentation, Url>>,
+
+  /// Represents the issuanceDate encoded as a UNIX timestamp.
+  #[serde(flatten)]
+  pub(crate) issuance_date: IssuanceDateClaims,
+
+  /// Represents the id property of the presentation.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  jti: Option<Cow<'presentation, Url>>,
+
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) aud: Option<Url>,
+
+  /// The JSON representation of the presentation, including custom claims.
+  #[serde(flatten)]
+  presentation: Object,
+}
+
+impl<'presentation> PresentationV2JwtClaims<'presentation> {
+  pub(super) fn new<CRED, T>(
+    presentation: &'presentation PresentationV2<CRED, T>,
+    options: &JwtPresentationOptions,
+  ) -> Result<Self>
+  where
+    CRED: Serialize,
+    T: Serialize,
+  {
+    const REGISTERED_CLAIMS: [&str; 6] = ["exp", "iss", "iat", "nbf", "jti", "aud"];
+
+    let mut presentation_json: Object = serde_json::to_value(presentation)
+      .and_then(serde_json::from_value)
+      .map_err(|err| Error::JwtClaimsSetSerializationError(err.into()))?;
+
+    for (key, value) in options.custom_claims.iter().flatten() {
+      if REGISTERED_CLAIMS.contains(&key.as_str()) || presentation_json.contains_key(key) {
+        return Err(Error::JwtClaimsSetSerializationError(
+          format!("custom claim `{key}` conflicts with an existing claim").into(),
+        ));
+      }
+      presentation_json.insert(key.clone(), value.clone());
+    }
+
+    Ok(Self {
+      exp: options.expiration_date.map(|expiration_date| expiration_date.to_unix()),
+      iss: Some(Cow::Borrowed(&presentation.holder)),
+      issuance_date: IssuanceDateClaims {
+        iat: None,
+        nbf: options.issuance_date.map(|issuance_date| issuance_date.to_unix()),
+      },
+      jti: presentation.id.as_ref().map(Cow::Borrowed),
+      aud: options.audience.clone(),
+      presentation: presentation_json,
+    })
+  }
+}
+
+#[cfg(feature = "validator")]
+impl<'presentation> PresentationV2JwtClaims<'presentation> {
+  /// Converts the JWT representation into a [`PresentationV2`], checking that the registered claims
+  /// are consistent with the presentation.
+  pub(crate) fn try_into_presentation<CRED, T>(self) -> Result<PresentationV2<CRED, T>>
+  where
+    CRED: DeserializeOwned,
+    T: DeserializeOwned,
+  {
+    let presentation: PresentationV2<CRED, T> = serde_json::to_value(self.presentation)
+      .and_then(serde_json::from_value)
+      .map_err(|err| Error::JwtClaimsSetDeserializationError(err.into()))?;
+
+    if !self
+      .jti
+      .map(|jti| Some(jti.as_ref()) == presentation.id.as_ref())
+      .unwrap_or(true)
+    {
+      return Err(Error::InconsistentPresentationJwtClaims("inconsistent presentation id"));
+    }
+
+    if !self.iss.map(|iss| iss.as_ref() == &presentation.holder).unwrap_or(true) {
+      return Err(Error::InconsistentPresentationJwtClaims(
+        "inconsistent presentation holder",
+      ));
+    }
+
+    Ok(presentation)
+  }
+}
+
 #[cfg(test)]
 mod test {
   use super::PresentationJwtClaims;
+  use super::PresentationV2JwtClaims;
   use crate::credential::Jwt;
   use crate::presentation::JwtPresentationOptions;
   use crate::presentation::Presentation;
+  use crate::presentation::PresentationV2;
   use crate::Error;
   use identity_core::common::Object;
   use identity_core::common::Timestamp;
@@ -367,4 +469,72 @@ mod test {
       Error::InconsistentPresentationJwtClaims("inconsistent presentation id")
     ));
   }
+
+  const PRESENTATION_V2_JSON: &str = r#"
+  {
+    "id": "http://example.edu/presentations/3732",
+    "@context": "https://www.w3.org/ns/credentials/v2",
+    "type": "VerifiablePresentation",
+    "verifiableCredential": [{
+      "@context": "https://www.w3.org/ns/credentials/v2",
+      "id": "data:application/vc+jwt,eyJhbGciOiJFZERTQSJ9.eyJpc3MiOiJkaWQ6ZXhhbXBsZToxIn0.c2ln",
+      "type": "EnvelopedVerifiableCredential"
+    }],
+    "holder": "did:example:holder"
+  }"#;
+
+  #[test]
+  fn roundtrip_v2() {
+    let presentation: PresentationV2 = PresentationV2::from_json(PRESENTATION_V2_JSON).unwrap();
+    let options = JwtPresentationOptions {
+      expiration_date: Some(Timestamp::from_unix(1694699551).unwrap()),
+      issuance_date: Some(Timestamp::from_unix(1694698951).unwrap()),
+      audience: None,
+      custom_claims: None,
+    };
+    let claims_serialized: String = presentation.serialize_jwt(&options).unwrap();
+
+    let mut expected_claims: Object = Object::from_json(PRESENTATION_V2_JSON).unwrap();
+    expected_claims.insert("jti".to_owned(), "http://example.edu/presentations/3732".into());
+    expected_claims.insert("iss".to_owned(), "did:example:holder".into());
+    expected_claims.insert("exp".to_owned(), 1694699551.into());
+    expected_claims.insert("nbf".to_owned(), 1694698951.into());
+    assert_eq!(Object::from_json(&claims_serialized).unwrap(), expected_claims);
+
+    let retrieved_presentation: PresentationV2 = PresentationV2JwtClaims::from_json(&claims_serialized)
+      .unwrap()
+      .try_into_presentation()
+      .unwrap();
+    assert_eq!(presentation, retrieved_presentation);
+  }
+
+  #[test]
+  fn conflicting_custom_claims_v2() {
+    let presentation: PresentationV2 = PresentationV2::from_json(PRESENTATION_V2_JSON).unwrap();
+    let mut custom_claims: Object = Object::new();
+    custom_claims.insert("holder".to_owned(), "did:example:other".into());
+    let options = JwtPresentationOptions {
+      custom_claims: Some(custom_claims),
+      ..Default::default()
+    };
+
+    assert!(matches!(
+      presentation.serialize_jwt(&options).unwrap_err(),
+      Error::JwtClaimsSetSerializationError(_)
+    ));
+  }
+
+  #[test]
+  fn inconsistent_holder_v2() {
+    let mut claims: Object = Object::from_json(PRESENTATION_V2_JSON).unwrap();
+    claims.insert("iss".to_owned(), "did:example:other".into());
+
+    let result: Result<PresentationV2, Error> = PresentationV2JwtClaims::from_json(&claims.to_json().unwrap())
+      .unwrap()
+      .try_into_presentation();
+    assert!(matches!(
+      result.unwrap_err(),
+      Error::InconsistentPresentationJwtClaims("inconsistent presentation holder")
+    ));
+  }
 }
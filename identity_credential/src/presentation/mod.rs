@@ -13,14 +13,20 @@ mod jwt_presentation_options;
 mod jwt_serialization;
 mod presentation;
 mod presentation_builder;
+mod presentation_v2;
+mod presentation_v2_builder;
 
 #[cfg(feature = "jpt-bbs-plus")]
 pub use self::jwp_presentation_builder::SelectiveDisclosurePresentation;
 pub use self::jwt_presentation_options::JwtPresentationOptions;
 pub use self::presentation::Presentation;
 pub use self::presentation_builder::PresentationBuilder;
+pub use self::presentation_v2::PresentationV2;
+pub use self::presentation_v2_builder::PresentationV2Builder;
 #[cfg(feature = "jpt-bbs-plus")]
 pub use jwp_presentation_options::JwpPresentationOptions;
 
 #[cfg(feature = "validator")]
 pub(crate) use self::jwt_serialization::PresentationJwtClaims;
+#[cfg(feature = "validator")]
+pub(crate) use self::jwt_serialization::PresentationV2JwtClaims;
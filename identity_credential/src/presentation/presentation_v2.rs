@@ -0,0 +1,201 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use identity_core::common::Context;
+use identity_core::common::Object;
+use identity_core::common::OneOrMany;
+use identity_core::common::Url;
+use identity_core::convert::FmtJson;
+use identity_core::convert::ToJson;
+
+use crate::credential::CredentialV2;
+use crate::credential::EnvelopedVerifiableCredential;
+use crate::credential::Policy;
+use crate::credential::Proof;
+use crate::error::Error;
+use crate::error::Result;
+
+use super::jwt_serialization::PresentationV2JwtClaims;
+use super::JwtPresentationOptions;
+use super::PresentationV2Builder;
+
+/// Represents a bundle of zero or more credentials according to the
+/// [VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/#verifiable-presentations).
+///
+/// Credentials secured as JWTs are embedded as [`EnvelopedVerifiableCredential`]s, which is the default for `CRED`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PresentationV2<CRED = EnvelopedVerifiableCredential, T = Object> {
+  /// The JSON-LD context(s) applicable to the `PresentationV2`.
+  #[serde(rename = "@context")]
+  pub context: OneOrMany<Context>,
+  /// A unique `URI` that may be used to identify the `PresentationV2`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub id: Option<Url>,
+  /// One or more URIs defining the type of the `PresentationV2`.
+  #[serde(rename = "type")]
+  pub types: OneOrMany<String>,
+  /// Credential(s) expressing the claims of the `PresentationV2`.
+  #[serde(
+    default = "Default::default",
+    rename = "verifiableCredential",
+    skip_serializing_if = "Vec::is_empty",
+    bound(deserialize = "CRED: serde::de::DeserializeOwned")
+  )]
+  pub verifiable_credential: Vec<CRED>,
+  /// The entity that generated the `PresentationV2`.
+  pub holder: Url,
+  /// Terms-of-use specified by the `PresentationV2` holder.
+  #[serde(default, rename = "termsOfUse", skip_serializing_if = "OneOrMany::is_empty")]
+  pub terms_of_use: OneOrMany<Policy>,
+  /// Miscellaneous properties.
+  #[serde(flatten)]
+  pub properties: T,
+  /// Optional cryptographic proof, unrelated to JWT.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub proof: Option<Proof>,
+}
+
+impl<CRED, T> PresentationV2<CRED, T> {
+  /// Returns the base JSON-LD context for `PresentationV2`s.
+  pub fn base_context() -> &'static Context {
+    CredentialV2::<Object>::base_context()
+  }
+
+  /// Returns the base type for `PresentationV2`s.
+  pub const fn base_type() -> &'static str {
+    "VerifiablePresentation"
+  }
+
+  /// Creates a `PresentationV2Builder` to configure a new presentation.
+  ///
+  /// This is the same as [PresentationV2Builder::new].
+  pub fn builder(holder: Url, properties: T) -> PresentationV2Builder<CRED, T> {
+    PresentationV2Builder::new(holder, properties)
+  }
+
+  /// Returns a new `PresentationV2` based on the `PresentationV2Builder` configuration.
+  pub fn from_builder(builder: PresentationV2Builder<CRED, T>) -> Result<Self> {
+    let this: Self = Self {
+      context: builder.context.into(),
+      id: builder.id,
+      types: builder.types.into(),
+      verifiable_credential: builder.credentials,
+      holder: builder.holder,
+      terms_of_use: builder.terms_of_use.into(),
+      properties: builder.properties,
+      proof: None,
+    };
+    this.check_structure()?;
+
+    Ok(this)
+  }
+
+  /// Validates the semantic structure of the `PresentationV2`.
+  ///
+  /// # Warning
+  ///
+  /// This does not check the semantic structure of the contained credentials. This needs to be done as part of
+  /// signature validation on the credentials as they are encoded as JWTs.
+  pub fn check_structure(&self) -> Result<()> {
+    // Ensure the base context is present and in the correct location
+    match self.context.get(0) {
+      Some(context) if context == Self::base_context() => {}
+      Some(_) | None => return Err(Error::MissingBaseContext),
+    }
+
+    // The set of types MUST contain the base type
+    if !self.types.iter().any(|type_| type_ == Self::base_type()) {
+      return Err(Error::MissingBaseType);
+    }
+    Ok(())
+  }
+
+  /// Serializes the [`PresentationV2`] as a JWT claims set
+  /// in accordance with [VC-JOSE-COSE](https://www.w3.org/TR/vc-jose-cose/#securing-with-jose).
+  ///
+  /// The claims set is the presentation itself, complemented by the registered claims derived from it and `options`.
+  /// Custom claims set in `options` are added at the top level and are therefore part of the presentation's properties
+  /// once decoded.
+  ///
+  /// The resulting string can be used as the payload of a JWS when issuing the presentation.
+  pub fn serialize_jwt(&self, options: &JwtPresentationOptions) -> Result<String>
+  where
+    T: Serialize,
+    CRED: Serialize,
+  {
+    PresentationV2JwtClaims::new(self, options)?
+      .to_json()
+      .map_err(|err| Error::JwtClaimsSetSerializationError(err.into()))
+  }
+
+  /// Sets the value of the proof property.
+  ///
+  /// Note that this proof is not related to JWT.
+  pub fn set_proof(&mut self, proof: Option<Proof>) {
+    self.proof = proof;
+  }
+}
+
+impl<CRED, T> Display for PresentationV2<CRED, T>
+where
+  CRED: Serialize,
+  T: Serialize,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    self.fmt_json(f)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use identity_core::convert::FromJson;
+
+  use crate::credential::EnvelopedVerifiableCredential;
+  use crate::presentation::PresentationV2;
+
+  #[test]
+  fn test_presentation_v2_deserialization() {
+    let presentation: PresentationV2 = PresentationV2::from_json_value(json!({
+      "@context": [
+        "https://www.w3.org/ns/credentials/v2",
+        "https://www.w3.org/ns/credentials/examples/v2"
+      ],
+      "holder": "did:test:abc1",
+      "type": "VerifiablePresentation",
+      "verifiableCredential": [{
+        "@context": "https://www.w3.org/ns/credentials/v2",
+        "id": "data:application/vc+jwt,eyJhbGciOiJFZERTQSJ9.eyJpc3MiOiJkaWQ6ZXhhbXBsZToxIn0.c2ln",
+        "type": "EnvelopedVerifiableCredential"
+      }]
+    }))
+    .unwrap();
+
+    assert!(presentation.check_structure().is_ok());
+    let credential: &EnvelopedVerifiableCredential = &presentation.verifiable_credential[0];
+    assert_eq!(
+      credential.to_jwt().unwrap().as_str(),
+      "eyJhbGciOiJFZERTQSJ9.eyJpc3MiOiJkaWQ6ZXhhbXBsZToxIn0.c2ln"
+    );
+  }
+
+  #[test]
+  fn test_presentation_v2_deserialization_with_empty_credential_array() {
+    // Unlike v1.1, the v2.0 data model allows presentations without credentials.
+    let presentation: PresentationV2 = PresentationV2::from_json_value(json!({
+      "@context": "https://www.w3.org/ns/credentials/v2",
+      "holder": "did:test:abc1",
+      "type": "VerifiablePresentation",
+      "verifiableCredential": []
+    }))
+    .unwrap();
+    assert!(presentation.verifiable_credential.is_empty());
+  }
+}
@@ -0,0 +1,191 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Context;
+use identity_core::common::Object;
+use identity_core::common::Url;
+use identity_core::common::Value;
+
+use crate::credential::Policy;
+use crate::error::Result;
+
+use super::PresentationV2;
+
+/// A `PresentationV2Builder` is used to create a customized [PresentationV2].
+#[derive(Clone, Debug)]
+pub struct PresentationV2Builder<CRED, T = Object> {
+  pub(crate) context: Vec<Context>,
+  pub(crate) id: Option<Url>,
+  pub(crate) types: Vec<String>,
+  pub(crate) credentials: Vec<CRED>,
+  pub(crate) holder: Url,
+  pub(crate) terms_of_use: Vec<Policy>,
+  pub(crate) properties: T,
+}
+
+impl<CRED, T> PresentationV2Builder<CRED, T> {
+  /// Creates a new `PresentationV2Builder`.
+  pub fn new(holder: Url, properties: T) -> Self {
+    Self {
+      context: vec![PresentationV2::<CRED, T>::base_context().clone()],
+      id: None,
+      types: vec![PresentationV2::<CRED, T>::base_type().into()],
+      credentials: Vec::new(),
+      holder,
+      terms_of_use: Vec::new(),
+      properties,
+    }
+  }
+
+  /// Adds a value to the `context` set.
+  #[must_use]
+  pub fn context(mut self, value: impl Into<Context>) -> Self {
+    self.context.push(value.into());
+    self
+  }
+
+  /// Sets the unique identifier of the presentation.
+  #[must_use]
+  pub fn id(mut self, value: Url) -> Self {
+    self.id = Some(value);
+    self
+  }
+
+  /// Adds a value to the `type` set.
+  #[must_use]
+  pub fn type_(mut self, value: impl Into<String>) -> Self {
+    self.types.push(value.into());
+    self
+  }
+
+  /// Adds a value to the `verifiableCredential` set.
+  #[must_use]
+  pub fn credential(mut self, value: CRED) -> Self {
+    self.credentials.push(value);
+    self
+  }
+
+  /// Adds a value to the `termsOfUse` set.
+  #[must_use]
+  pub fn terms_of_use(mut self, value: Policy) -> Self {
+    self.terms_of_use.push(value);
+    self
+  }
+
+  /// Returns a new `PresentationV2` based on the `PresentationV2Builder` configuration.
+  pub fn build(self) -> Result<PresentationV2<CRED, T>> {
+    PresentationV2::from_builder(self)
+  }
+}
+
+impl<CRED> PresentationV2Builder<CRED> {
+  /// Adds a new custom property.
+  #[must_use]
+  pub fn property<K, V>(mut self, key: K, value: V) -> Self
+  where
+    K: Into<String>,
+    V: Into<Value>,
+  {
+    self.properties.insert(key.into(), value.into());
+    self
+  }
+
+  /// Adds a series of custom properties.
+  #[must_use]
+  pub fn properties<K, V, I>(mut self, iter: I) -> Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<Value>,
+  {
+    self
+      .properties
+      .extend(iter.into_iter().map(|(k, v)| (k.into(), v.into())));
+    self
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+  use serde_json::Value;
+
+  use identity_core::common::Object;
+  use identity_core::common::OneOrMany;
+  use identity_core::common::Url;
+  use identity_core::convert::FromJson;
+
+  use crate::credential::CredentialV2;
+  use crate::credential::CredentialV2Builder;
+  use crate::credential::EnvelopedVerifiableCredential;
+  use crate::credential::Jwt;
+  use crate::credential::Subject;
+  use crate::error::Error;
+  use crate::presentation::Presentation;
+  use crate::presentation::PresentationV2;
+  use crate::presentation::PresentationV2Builder;
+
+  fn subject() -> Subject {
+    let json: Value = json!({
+      "id": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+      "degree": {
+        "type": "BachelorDegree",
+        "name": "Bachelor of Science and Arts"
+      }
+    });
+
+    Subject::from_json_value(json).unwrap()
+  }
+
+  fn issuer() -> Url {
+    Url::parse("did:example:issuer").unwrap()
+  }
+
+  #[test]
+  fn test_presentation_v2_builder_valid() {
+    let credential: CredentialV2 = CredentialV2Builder::default()
+      .type_("ExampleCredential")
+      .subject(subject())
+      .issuer(issuer())
+      .build()
+      .unwrap();
+
+    // The signature is irrelevant for this test.
+    let credential_jwt = Jwt::new(format!(
+      "eyJhbGciOiJFZERTQSJ9.{}.c2ln",
+      credential.serialize_jwt().unwrap()
+    ));
+    let enveloped_credential = EnvelopedVerifiableCredential::from_jwt(&credential_jwt).unwrap();
+
+    let presentation: PresentationV2 = PresentationV2Builder::new(Url::parse("did:test:abc1").unwrap(), Object::new())
+      .type_("ExamplePresentation")
+      .credential(enveloped_credential)
+      .property("name", "Example presentation")
+      .build()
+      .unwrap();
+
+    assert_eq!(presentation.context.len(), 1);
+    assert_eq!(
+      presentation.context.get(0).unwrap(),
+      PresentationV2::<Object>::base_context()
+    );
+    assert_eq!(presentation.types.len(), 2);
+    assert_eq!(
+      presentation.types.get(0).unwrap(),
+      PresentationV2::<Object>::base_type()
+    );
+    assert_eq!(presentation.types.get(1).unwrap(), "ExamplePresentation");
+    assert_eq!(presentation.verifiable_credential.len(), 1);
+    assert_eq!(presentation.properties["name"], "Example presentation");
+  }
+
+  #[test]
+  fn test_presentation_v2_rejects_v1_context() {
+    let mut presentation: PresentationV2<Jwt> =
+      PresentationV2Builder::new(Url::parse("did:test:abc1").unwrap(), Object::new())
+        .build()
+        .unwrap();
+    presentation.context = OneOrMany::One(Presentation::<Jwt>::base_context().clone());
+    assert!(matches!(presentation.check_structure(), Err(Error::MissingBaseContext)));
+  }
+}
@@ -0,0 +1,635 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::Display;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use identity_core::common::Context;
+use identity_core::common::Object;
+use identity_core::common::OneOrMany;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::credential::CredentialV2;
+use crate::credential::CredentialV2Builder;
+use crate::credential::Issuer;
+use crate::credential::Proof;
+use crate::credential::Subject;
+
+use super::BitstringStatusList;
+use super::BitstringStatusListEntry;
+use super::BitstringStatusListError;
+
+/// The type of a `BitstringStatusListCredential`.
+pub const BITSTRING_STATUS_LIST_CREDENTIAL_TYPE: &str = "BitstringStatusListCredential";
+const CREDENTIAL_SUBJECT_TYPE: &str = "BitstringStatusList";
+
+/// [Error](std::error::Error) type that represents the possible errors that can be
+/// encountered when dealing with [`BitstringStatusListCredential`]s.
+#[derive(Clone, Debug, Error, strum::IntoStaticStr, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BitstringStatusListCredentialError {
+  /// The provided credential has more than one `credentialSubject`.
+  #[error("A BitstringStatusListCredential may only have one credentialSubject")]
+  MultipleCredentialSubject,
+  /// The provided credential has an invalid property.
+  #[error("Invalid property \"{0}\"")]
+  InvalidProperty(&'static str),
+  /// The provided credential doesn't have a mandatory property.
+  #[error("Missing property \"{0}\"")]
+  MissingProperty(&'static str),
+  /// Inner status list failures.
+  #[error(transparent)]
+  StatusListError(#[from] BitstringStatusListError),
+  /// Missing status list id.
+  #[error("Cannot set the status of a credential without an \"id\".")]
+  Unreferenceable,
+  /// The entry references another status list credential.
+  #[error("The entry references the status list credential \"{0}\"")]
+  StatusListMismatch(Url),
+  /// The purpose of the entry is not among the purposes of the status list.
+  #[error("The status list is not used for the purpose \"{0}\"")]
+  PurposeMismatch(BitstringStatusPurpose),
+  /// Credentials cannot be unrevoked.
+  #[error("A previously revoked credential cannot be unrevoked.")]
+  UnreversibleRevocation,
+}
+
+/// A parsed [BitstringStatusListCredential](https://www.w3.org/TR/vc-bitstring-status-list/#bitstringstatuslistcredential).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "CredentialV2", into = "CredentialV2")]
+pub struct BitstringStatusListCredential {
+  inner: CredentialV2,
+  subject: BitstringStatusListCredentialSubject,
+}
+
+impl Display for BitstringStatusListCredential {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", CredentialV2::from(self.clone()))
+  }
+}
+
+impl From<BitstringStatusListCredential> for CredentialV2 {
+  fn from(value: BitstringStatusListCredential) -> Self {
+    value.into_inner()
+  }
+}
+
+impl Deref for BitstringStatusListCredential {
+  type Target = CredentialV2;
+  fn deref(&self) -> &Self::Target {
+    &self.inner
+  }
+}
+
+impl TryFrom<CredentialV2> for BitstringStatusListCredential {
+  type Error = BitstringStatusListCredentialError;
+  fn try_from(mut credential: CredentialV2) -> Result<Self, Self::Error> {
+    let has_right_credential_type = credential
+      .types
+      .iter()
+      .any(|type_| type_ == BITSTRING_STATUS_LIST_CREDENTIAL_TYPE);
+    let subject = BitstringStatusListCredentialSubject::try_from_credential(&mut credential)?;
+
+    if has_right_credential_type {
+      Ok(Self {
+        inner: credential,
+        subject,
+      })
+    } else {
+      Err(BitstringStatusListCredentialError::InvalidProperty("type"))
+    }
+  }
+}
+
+impl BitstringStatusListCredential {
+  /// Returns the inner "raw" [`CredentialV2`].
+  pub fn into_inner(self) -> CredentialV2 {
+    let Self { mut inner, subject } = self;
+    inner.credential_subject = OneOrMany::One(subject.into());
+    inner
+  }
+
+  /// Returns the id of this credential, which entries use to reference it.
+  pub fn id(&self) -> Option<&Url> {
+    self.inner.id.as_ref()
+  }
+
+  /// Returns the purposes of this status list.
+  pub fn purposes(&self) -> &[BitstringStatusPurpose] {
+    &self.subject.status_purpose
+  }
+
+  /// Returns the time in milliseconds for which this status list may be cached, if specified.
+  pub fn ttl(&self) -> Option<u64> {
+    self.subject.ttl
+  }
+
+  /// Returns the status list encoded in this credential, interpreting it as consisting of `status_size` bit entries.
+  pub fn status_list(&self, status_size: u8) -> Result<BitstringStatusList, BitstringStatusListError> {
+    BitstringStatusList::try_from_encoded_str(&self.subject.encoded_list, status_size)
+  }
+
+  /// Creates a new [`BitstringStatusListEntry`] referencing the `index`-th entry of this credential and adds it to the
+  /// `credentialStatus` of `credential`.
+  ///
+  /// The entry is created for the first purpose of this status list, with a status size of 1 bit.
+  pub fn add_credential_status<T>(
+    &self,
+    credential: &mut CredentialV2<T>,
+    index: usize,
+  ) -> Result<BitstringStatusListEntry, BitstringStatusListCredentialError> {
+    let id = self
+      .id()
+      .cloned()
+      .ok_or(BitstringStatusListCredentialError::Unreferenceable)?;
+    let purpose = self
+      .purposes()
+      .first()
+      .copied()
+      .ok_or(BitstringStatusListCredentialError::MissingProperty(
+        "credentialSubject.statusPurpose",
+      ))?;
+    // Make sure the entry exists.
+    self.status_list(1)?.get(index)?;
+
+    let entry = BitstringStatusListEntry::new(id, purpose, index, None);
+    let mut statuses: Vec<_> = std::mem::take(&mut credential.credential_status).into_vec();
+    statuses.push(entry.clone().into());
+    credential.credential_status = statuses.into();
+
+    Ok(entry)
+  }
+
+  /// Sets the status value of the given `entry` to `value`.
+  ///
+  /// ## Note:
+  /// - A revoked credential cannot ever be unrevoked and will lead to a
+  ///   [`BitstringStatusListCredentialError::UnreversibleRevocation`].
+  pub fn set_entry(
+    &mut self,
+    entry: &BitstringStatusListEntry,
+    value: u8,
+  ) -> Result<(), BitstringStatusListCredentialError> {
+    self.check_entry(entry)?;
+    self.update(entry.status_size(), |status_list| {
+      let entry_status = status_list.get(entry.index())?;
+      if entry.purpose() == BitstringStatusPurpose::Revocation && entry_status != 0 && value == 0 {
+        return Err(BitstringStatusListCredentialError::UnreversibleRevocation);
+      }
+      status_list.set(entry.index(), value)?;
+      Ok(())
+    })
+  }
+
+  /// Apply `update_fn` to the status list encoded in this credential, interpreting it as consisting of `status_size`
+  /// bit entries.
+  pub fn update<F>(&mut self, status_size: u8, update_fn: F) -> Result<(), BitstringStatusListCredentialError>
+  where
+    F: FnOnce(&mut BitstringStatusList) -> Result<(), BitstringStatusListCredentialError>,
+  {
+    let mut status_list = self.status_list(status_size)?;
+    update_fn(&mut status_list)?;
+
+    self.subject.encoded_list = status_list.into_encoded_str();
+    Ok(())
+  }
+
+  /// Returns the status of the given `entry`, following the
+  /// [validation algorithm](https://www.w3.org/TR/vc-bitstring-status-list/#validate-algorithm).
+  pub fn entry(&self, entry: &BitstringStatusListEntry) -> Result<BitstringStatus, BitstringStatusListCredentialError> {
+    self.check_entry(entry)?;
+    let value = self.status_list(entry.status_size())?.get(entry.index())?;
+
+    Ok(BitstringStatus {
+      purpose: entry.purpose(),
+      value,
+      message: entry.message_for(value).map(ToOwned::to_owned),
+    })
+  }
+
+  fn check_entry(&self, entry: &BitstringStatusListEntry) -> Result<(), BitstringStatusListCredentialError> {
+    if Some(entry.status_list_credential()) != self.id() {
+      return Err(BitstringStatusListCredentialError::StatusListMismatch(
+        entry.status_list_credential().clone(),
+      ));
+    }
+    if !self.purposes().contains(&entry.purpose()) {
+      return Err(BitstringStatusListCredentialError::PurposeMismatch(entry.purpose()));
+    }
+
+    Ok(())
+  }
+}
+
+/// The status of a credential referenced inside a [`BitstringStatusListCredential`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct BitstringStatus {
+  /// The purpose of the status.
+  pub purpose: BitstringStatusPurpose,
+  /// The status value.
+  pub value: u8,
+  /// The message describing the status value, if the entry defines one.
+  pub message: Option<String>,
+}
+
+impl BitstringStatus {
+  /// Returns whether the status value is set, e.g. whether a credential is revoked for an entry with the
+  /// [`BitstringStatusPurpose::Revocation`] purpose.
+  pub const fn is_set(&self) -> bool {
+    self.value != 0
+  }
+}
+
+/// [`BitstringStatusListCredential`]'s purpose.
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BitstringStatusPurpose {
+  /// Used for revocation.
+  #[default]
+  Revocation,
+  /// Used for suspension.
+  Suspension,
+  /// Used to signal that an updated version of the credential is available.
+  Refresh,
+  /// Used to convey an arbitrary message related to the status of the credential.
+  Message,
+}
+
+impl Display for BitstringStatusPurpose {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let s = match self {
+      Self::Revocation => "revocation",
+      Self::Suspension => "suspension",
+      Self::Refresh => "refresh",
+      Self::Message => "message",
+    };
+    write!(f, "{s}")
+  }
+}
+
+impl FromStr for BitstringStatusPurpose {
+  type Err = ();
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "revocation" => Ok(Self::Revocation),
+      "suspension" => Ok(Self::Suspension),
+      "refresh" => Ok(Self::Refresh),
+      "message" => Ok(Self::Message),
+      _ => Err(()),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+struct BitstringStatusListCredentialSubject {
+  status_purpose: Vec<BitstringStatusPurpose>,
+  encoded_list: String,
+  ttl: Option<u64>,
+  id: Option<Url>,
+}
+
+impl From<BitstringStatusListCredentialSubject> for Subject {
+  fn from(value: BitstringStatusListCredentialSubject) -> Self {
+    let status_purpose: Value = match value.status_purpose.as_slice() {
+      [purpose] => Value::String(purpose.to_string()),
+      purposes => purposes
+        .iter()
+        .map(|purpose| Value::String(purpose.to_string()))
+        .collect(),
+    };
+    let mut properties: Object = [
+      ("type".to_owned(), Value::String(CREDENTIAL_SUBJECT_TYPE.to_owned())),
+      ("statusPurpose".to_owned(), status_purpose),
+      ("encodedList".to_owned(), Value::String(value.encoded_list)),
+    ]
+    .into_iter()
+    .collect();
+    if let Some(ttl) = value.ttl {
+      properties.insert("ttl".to_owned(), ttl.into());
+    }
+
+    if let Some(id) = value.id {
+      Subject::with_id_and_properties(id, properties)
+    } else {
+      Subject::with_properties(properties)
+    }
+  }
+}
+
+impl BitstringStatusListCredentialSubject {
+  /// Parse a BitstringStatusListCredentialSubject out of a credential, without copying.
+  fn try_from_credential(credential: &mut CredentialV2) -> Result<Self, BitstringStatusListCredentialError> {
+    let OneOrMany::One(mut subject) = std::mem::take(&mut credential.credential_subject) else {
+      return Err(BitstringStatusListCredentialError::MultipleCredentialSubject);
+    };
+    if let Some(subject_type) = subject.properties.get("type") {
+      if subject_type.as_str() != Some(CREDENTIAL_SUBJECT_TYPE) {
+        return Err(BitstringStatusListCredentialError::InvalidProperty(
+          "credentialSubject.type",
+        ));
+      }
+    } else {
+      return Err(BitstringStatusListCredentialError::MissingProperty(
+        "credentialSubject.type",
+      ));
+    }
+    let status_purpose = subject
+      .properties
+      .get("statusPurpose")
+      .ok_or(BitstringStatusListCredentialError::MissingProperty(
+        "credentialSubject.statusPurpose",
+      ))
+      .and_then(|value| {
+        serde_json::from_value::<OneOrMany<BitstringStatusPurpose>>(value.clone())
+          .ok()
+          .map(OneOrMany::into_vec)
+          .filter(|purposes| !purposes.is_empty())
+          .ok_or(BitstringStatusListCredentialError::InvalidProperty(
+            "credentialSubject.statusPurpose",
+          ))
+      })?;
+    let encoded_list = subject
+      .properties
+      .get_mut("encodedList")
+      .ok_or(BitstringStatusListCredentialError::MissingProperty(
+        "credentialSubject.encodedList",
+      ))
+      .and_then(|value| {
+        if let Value::String(ref mut s) = value {
+          Ok(s)
+        } else {
+          Err(BitstringStatusListCredentialError::InvalidProperty(
+            "credentialSubject.encodedList",
+          ))
+        }
+      })
+      .map(std::mem::take)?;
+    let ttl = subject
+      .properties
+      .get("ttl")
+      .map(|value| {
+        value
+          .as_u64()
+          .ok_or(BitstringStatusListCredentialError::InvalidProperty(
+            "credentialSubject.ttl",
+          ))
+      })
+      .transpose()?;
+
+    Ok(BitstringStatusListCredentialSubject {
+      id: subject.id,
+      encoded_list,
+      status_purpose,
+      ttl,
+    })
+  }
+}
+
+/// Builder type for [`BitstringStatusListCredential`].
+#[derive(Debug)]
+pub struct BitstringStatusListCredentialBuilder {
+  inner_builder: CredentialV2Builder,
+  credential_subject: BitstringStatusListCredentialSubject,
+}
+
+impl BitstringStatusListCredentialBuilder {
+  /// Creates a new [`BitstringStatusListCredentialBuilder`] from a [`BitstringStatusList`].
+  pub fn new(status_list: BitstringStatusList) -> Self {
+    let credential_subject = BitstringStatusListCredentialSubject {
+      encoded_list: status_list.into_encoded_str(),
+      ..Default::default()
+    };
+    Self {
+      inner_builder: CredentialV2Builder::new(Object::new()),
+      credential_subject,
+    }
+  }
+
+  /// Adds a `credentialSubject.statusPurpose`.
+  ///
+  /// If no purpose is added, [`BitstringStatusPurpose::Revocation`] is used.
+  pub fn purpose(mut self, purpose: BitstringStatusPurpose) -> Self {
+    if !self.credential_subject.status_purpose.contains(&purpose) {
+      self.credential_subject.status_purpose.push(purpose);
+    }
+    self
+  }
+
+  /// Sets `id`.
+  ///
+  /// If unset, the `credentialSubject.id` without fragment is used.
+  pub fn id(mut self, id: Url) -> Self {
+    self.inner_builder = self.inner_builder.id(id);
+    self
+  }
+
+  /// Sets `credentialSubject.id`.
+  pub fn subject_id(mut self, id: Url) -> Self {
+    self.credential_subject.id = Some(id);
+    self
+  }
+
+  /// Sets `credentialSubject.ttl`, the time in milliseconds for which the status list may be cached.
+  pub const fn ttl(mut self, ttl: u64) -> Self {
+    self.credential_subject.ttl = Some(ttl);
+    self
+  }
+
+  /// Sets `validFrom`.
+  ///
+  /// If unset, the current time is used.
+  pub fn valid_from(mut self, time: Timestamp) -> Self {
+    self.inner_builder = self.inner_builder.valid_from(time);
+    self
+  }
+
+  /// Sets `validUntil`.
+  pub fn valid_until(mut self, time: Timestamp) -> Self {
+    self.inner_builder = self.inner_builder.valid_until(time);
+    self
+  }
+
+  /// Sets `issuer`.
+  pub fn issuer(mut self, issuer: Issuer) -> Self {
+    self.inner_builder = self.inner_builder.issuer(issuer);
+    self
+  }
+
+  /// Adds a `@context` entry.
+  pub fn context(mut self, ctx: Context) -> Self {
+    self.inner_builder = self.inner_builder.context(ctx);
+    self
+  }
+
+  /// Adds a `type` entry.
+  pub fn add_type(mut self, type_: String) -> Self {
+    self.inner_builder = self.inner_builder.type_(type_);
+    self
+  }
+
+  /// Adds a credential proof.
+  pub fn proof(mut self, proof: Proof) -> Self {
+    self.inner_builder = self.inner_builder.proof(proof);
+    self
+  }
+
+  /// Consumes this [`BitstringStatusListCredentialBuilder`] into a [`BitstringStatusListCredential`].
+  pub fn build(mut self) -> Result<BitstringStatusListCredential, crate::Error> {
+    if self.credential_subject.status_purpose.is_empty() {
+      self
+        .credential_subject
+        .status_purpose
+        .push(BitstringStatusPurpose::default());
+    }
+    let mut inner_builder = self.inner_builder;
+    if inner_builder.id.is_none() {
+      if let Some(mut id) = self.credential_subject.id.clone() {
+        id.set_fragment(None);
+        inner_builder = inner_builder.id(id);
+      }
+    }
+    if inner_builder.valid_from.is_none() {
+      inner_builder = inner_builder.valid_from(Timestamp::now_utc());
+    }
+
+    inner_builder
+      .type_(BITSTRING_STATUS_LIST_CREDENTIAL_TYPE)
+      .subject(self.credential_subject.clone().into())
+      .build()
+      .map(|mut credential| {
+        credential.credential_subject = OneOrMany::default();
+        BitstringStatusListCredential {
+          subject: self.credential_subject,
+          inner: credential,
+        }
+      })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::super::StatusMessage;
+  use super::*;
+
+  const BITSTRING_STATUS_LIST_CREDENTIAL_SAMPLE: &str = r#"
+{
+  "@context": [
+    "https://www.w3.org/ns/credentials/v2"
+  ],
+  "id": "https://example.com/credentials/status/3",
+  "type": ["VerifiableCredential", "BitstringStatusListCredential"],
+  "issuer": "did:example:12345",
+  "validFrom": "2021-04-05T14:27:40Z",
+  "credentialSubject": {
+    "id": "https://example.com/status/3#list",
+    "type": "BitstringStatusList",
+    "statusPurpose": "revocation",
+    "encodedList": "uH4sIAAAAAAAAA-3BMQEAAADCoPVPbQwfoAAAAAAAAAAAAAAAAAAAAIC3AYbSVKsAQAAA"
+  }
+}
+  "#;
+
+  fn status_list_credential(purposes: &[BitstringStatusPurpose], status_size: u8) -> BitstringStatusListCredential {
+    let status_list = BitstringStatusList::new(BitstringStatusList::MINIMUM_LENGTH, status_size).unwrap();
+    purposes
+      .iter()
+      .fold(
+        BitstringStatusListCredentialBuilder::new(status_list),
+        |builder, purpose| builder.purpose(*purpose),
+      )
+      .issuer(Issuer::Url(Url::parse("did:example:12345").unwrap()))
+      .subject_id(Url::parse("https://example.com/status/3#list").unwrap())
+      .build()
+      .unwrap()
+  }
+
+  #[test]
+  fn bitstring_status_list_credential_deserialization_works() {
+    let credential = serde_json::from_str::<BitstringStatusListCredential>(BITSTRING_STATUS_LIST_CREDENTIAL_SAMPLE)
+      .expect("Failed to deserialize");
+    assert_eq!(credential.purposes(), &[BitstringStatusPurpose::Revocation]);
+    assert_eq!(
+      credential.id().map(|id| id.as_str()),
+      Some("https://example.com/credentials/status/3")
+    );
+    assert_eq!(
+      serde_json::to_value(&credential).unwrap(),
+      serde_json::from_str::<Value>(BITSTRING_STATUS_LIST_CREDENTIAL_SAMPLE).unwrap()
+    );
+  }
+
+  #[test]
+  fn builder_derives_id_from_subject() {
+    let credential = status_list_credential(&[BitstringStatusPurpose::Suspension], 1);
+    assert_eq!(
+      credential.id().map(|id| id.as_str()),
+      Some("https://example.com/status/3")
+    );
+    assert!(credential.valid_from.is_some());
+    assert!(credential.into_inner().check_structure().is_ok());
+  }
+
+  #[test]
+  fn revoked_credential_cannot_be_unrevoked() {
+    let mut status_list_credential = status_list_credential(&[BitstringStatusPurpose::Revocation], 1);
+    let mut credential = CredentialV2::builder(Object::new())
+      .issuer(Url::parse("did:example:12345").unwrap())
+      .subject(Subject::with_id(Url::parse("did:example:holder").unwrap()))
+      .build()
+      .unwrap();
+    let entry = status_list_credential
+      .add_credential_status(&mut credential, 420)
+      .unwrap();
+    assert_eq!(credential.credential_status.len(), 1);
+
+    status_list_credential.set_entry(&entry, 1).unwrap();
+    assert!(status_list_credential.entry(&entry).unwrap().is_set());
+    assert_eq!(
+      status_list_credential.set_entry(&entry, 0),
+      Err(BitstringStatusListCredentialError::UnreversibleRevocation)
+    );
+  }
+
+  #[test]
+  fn status_messages_are_resolved() {
+    let mut status_list_credential = status_list_credential(&[BitstringStatusPurpose::Message], 2);
+    let entry = BitstringStatusListEntry::new(
+      status_list_credential.id().unwrap().clone(),
+      BitstringStatusPurpose::Message,
+      7,
+      None,
+    )
+    .status_messages(vec![
+      StatusMessage::new(0, "pending_review"),
+      StatusMessage::new(1, "accepted"),
+      StatusMessage::new(2, "rejected"),
+      StatusMessage::new(3, "undefined"),
+    ])
+    .unwrap();
+
+    status_list_credential.set_entry(&entry, 2).unwrap();
+    let status = status_list_credential.entry(&entry).unwrap();
+    assert_eq!(status.value, 2);
+    assert_eq!(status.message.as_deref(), Some("rejected"));
+
+    let revocation_entry = BitstringStatusListEntry::new(
+      status_list_credential.id().unwrap().clone(),
+      BitstringStatusPurpose::Revocation,
+      7,
+      None,
+    );
+    assert_eq!(
+      status_list_credential.entry(&revocation_entry),
+      Err(BitstringStatusListCredentialError::PurposeMismatch(
+        BitstringStatusPurpose::Revocation
+      ))
+    );
+  }
+}
@@ -0,0 +1,293 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::OneOrMany;
+use identity_core::common::Url;
+use serde::de::Error as _;
+use serde::Deserialize;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::credential::Status;
+
+use super::credential::BitstringStatusPurpose;
+use super::BitstringStatusListCredentialError;
+
+const CREDENTIAL_STATUS_TYPE: &str = "BitstringStatusListEntry";
+
+fn serialize_status_list_index<S>(index: &usize, serializer: S) -> Result<S::Ok, S::Error>
+where
+  S: Serializer,
+{
+  serializer.collect_str(index)
+}
+
+const fn default_status_size() -> u8 {
+  1
+}
+
+fn is_default_status_size(status_size: &u8) -> bool {
+  *status_size == default_status_size()
+}
+
+/// A message describing the meaning of a status value of a [`BitstringStatusListEntry`].
+#[derive(Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq)]
+pub struct StatusMessage {
+  /// The status value as a hexadecimal string, e.g. `0x2`.
+  pub status: String,
+  /// The message describing the status.
+  pub message: String,
+}
+
+impl StatusMessage {
+  /// Creates a new [`StatusMessage`] for the status `value`.
+  pub fn new(value: u8, message: impl Into<String>) -> Self {
+    Self {
+      status: format!("{value:#x}"),
+      message: message.into(),
+    }
+  }
+
+  /// Returns the status value this message describes, if it is a valid hexadecimal string.
+  pub fn value(&self) -> Option<u8> {
+    self
+      .status
+      .strip_prefix("0x")
+      .and_then(|value| u8::from_str_radix(value, 16).ok())
+  }
+}
+
+/// [BitstringStatusListEntry](https://www.w3.org/TR/vc-bitstring-status-list/#bitstringstatuslistentry) implementation.
+#[derive(Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BitstringStatusListEntry {
+  id: Url,
+  #[serde(rename = "type")]
+  type_: String,
+  status_purpose: BitstringStatusPurpose,
+  #[serde(
+    serialize_with = "serialize_status_list_index",
+    deserialize_with = "serde_aux::prelude::deserialize_number_from_string"
+  )]
+  status_list_index: usize,
+  status_list_credential: Url,
+  #[serde(default = "default_status_size", skip_serializing_if = "is_default_status_size")]
+  status_size: u8,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  status_message: Vec<StatusMessage>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  status_reference: Option<OneOrMany<Url>>,
+}
+
+impl TryFrom<&Status> for BitstringStatusListEntry {
+  type Error = serde_json::Error;
+  fn try_from(status: &Status) -> Result<Self, Self::Error> {
+    let json_status = serde_json::to_value(status)?;
+    let entry: Self = serde_json::from_value(json_status)?;
+    entry.check_structure().map_err(serde_json::Error::custom)?;
+
+    Ok(entry)
+  }
+}
+
+impl From<BitstringStatusListEntry> for Status {
+  fn from(entry: BitstringStatusListEntry) -> Self {
+    let json_status = serde_json::to_value(entry).unwrap(); // Safety: shouldn't go out of memory
+    serde_json::from_value(json_status).unwrap() // Safety: `BitstringStatusListEntry` is a credential status
+  }
+}
+
+impl BitstringStatusListEntry {
+  /// The type of a [`BitstringStatusListEntry`].
+  pub const TYPE: &'static str = CREDENTIAL_STATUS_TYPE;
+
+  /// Creates a new [`BitstringStatusListEntry`] with a status size of 1 bit.
+  ///
+  /// If `id` is not given, it is derived from `status_list` and `index`.
+  pub fn new(status_list: Url, purpose: BitstringStatusPurpose, index: usize, id: Option<Url>) -> Self {
+    let id = id.unwrap_or_else(|| {
+      let mut id = status_list.clone();
+      id.set_fragment(Some(&index.to_string()));
+      id
+    });
+
+    Self {
+      id,
+      type_: CREDENTIAL_STATUS_TYPE.to_owned(),
+      status_purpose: purpose,
+      status_list_index: index,
+      status_list_credential: status_list,
+      status_size: default_status_size(),
+      status_message: Vec::new(),
+      status_reference: None,
+    }
+  }
+
+  /// Sets the messages describing the possible status values of this entry.
+  ///
+  /// The status size is derived from the number of messages, which must be a power of two between 2 and 256, with one
+  /// message per possible status value.
+  pub fn status_messages(mut self, messages: Vec<StatusMessage>) -> Result<Self, BitstringStatusListCredentialError> {
+    if !messages.len().is_power_of_two() || !(2..=256).contains(&messages.len()) {
+      return Err(BitstringStatusListCredentialError::InvalidProperty("statusMessage"));
+    }
+    self.status_size = messages.len().trailing_zeros() as u8;
+    self.status_message = messages;
+    self.check_structure()?;
+
+    Ok(self)
+  }
+
+  /// Sets the URLs referencing material related to the status values of this entry.
+  pub fn status_reference(mut self, reference: impl Into<OneOrMany<Url>>) -> Self {
+    self.status_reference = Some(reference.into());
+    self
+  }
+
+  /// Returns this `credentialStatus`'s `id`.
+  pub const fn id(&self) -> &Url {
+    &self.id
+  }
+
+  /// Returns the purpose of this entry.
+  pub const fn purpose(&self) -> BitstringStatusPurpose {
+    self.status_purpose
+  }
+
+  /// Returns the index of this entry.
+  pub const fn index(&self) -> usize {
+    self.status_list_index
+  }
+
+  /// Returns the referenced [`BitstringStatusListCredential`](super::BitstringStatusListCredential)'s [`Url`].
+  pub const fn status_list_credential(&self) -> &Url {
+    &self.status_list_credential
+  }
+
+  /// Returns the size of the status value in bits.
+  pub const fn status_size(&self) -> u8 {
+    self.status_size
+  }
+
+  /// Returns the messages describing the possible status values.
+  pub fn status_message(&self) -> &[StatusMessage] {
+    &self.status_message
+  }
+
+  /// Returns the message describing the status `value`, if any.
+  pub fn message_for(&self, value: u8) -> Option<&str> {
+    self
+      .status_message
+      .iter()
+      .find(|message| message.value() == Some(value))
+      .map(|message| message.message.as_str())
+  }
+
+  /// Returns the URLs referencing material related to the status values.
+  pub fn status_reference_urls(&self) -> Option<&OneOrMany<Url>> {
+    self.status_reference.as_ref()
+  }
+
+  fn check_structure(&self) -> Result<(), BitstringStatusListCredentialError> {
+    if self.type_ != CREDENTIAL_STATUS_TYPE {
+      return Err(BitstringStatusListCredentialError::InvalidProperty("type"));
+    }
+    if !(1..=8).contains(&self.status_size) {
+      return Err(BitstringStatusListCredentialError::InvalidProperty("statusSize"));
+    }
+
+    // Status messages are mandatory for multi-bit entries and must describe every possible value.
+    let num_values: usize = 1 << self.status_size;
+    if self.status_size > 1 && self.status_message.is_empty() {
+      return Err(BitstringStatusListCredentialError::MissingProperty("statusMessage"));
+    }
+    if !self.status_message.is_empty()
+      && (self.status_message.len() != num_values
+        || self
+          .status_message
+          .iter()
+          .any(|message| message.value().map_or(true, |value| value as usize >= num_values)))
+    {
+      return Err(BitstringStatusListCredentialError::InvalidProperty("statusMessage"));
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const STATUS_LIST_ENTRY_SAMPLE: &str = r#"
+{
+  "id": "https://example.com/credentials/status/8#492847",
+  "type": "BitstringStatusListEntry",
+  "statusPurpose": "message",
+  "statusListIndex": "492847",
+  "statusSize": 2,
+  "statusListCredential": "https://example.com/credentials/status/8",
+  "statusMessage": [
+    {"status":"0x0", "message":"pending_review"},
+    {"status":"0x1", "message":"accepted"},
+    {"status":"0x2", "message":"rejected"},
+    {"status":"0x3", "message":"undefined"}
+  ],
+  "statusReference": "https://example.org/status-dictionary/"
+}"#;
+
+  #[test]
+  fn entry_deserialization_works() {
+    let status: Status = serde_json::from_str(STATUS_LIST_ENTRY_SAMPLE).unwrap();
+    let deserialized = BitstringStatusListEntry::try_from(&status).expect("Failed to deserialize");
+    let entry = BitstringStatusListEntry::new(
+      Url::parse("https://example.com/credentials/status/8").unwrap(),
+      BitstringStatusPurpose::Message,
+      492847,
+      None,
+    )
+    .status_messages(vec![
+      StatusMessage::new(0, "pending_review"),
+      StatusMessage::new(1, "accepted"),
+      StatusMessage::new(2, "rejected"),
+      StatusMessage::new(3, "undefined"),
+    ])
+    .unwrap()
+    .status_reference(Url::parse("https://example.org/status-dictionary/").unwrap());
+
+    assert_eq!(entry, deserialized);
+    assert_eq!(entry.status_size(), 2);
+    assert_eq!(entry.message_for(2), Some("rejected"));
+    assert_eq!(
+      serde_json::to_value(Status::from(entry)).unwrap(),
+      serde_json::from_str::<serde_json::Value>(STATUS_LIST_ENTRY_SAMPLE).unwrap()
+    );
+  }
+
+  #[test]
+  fn multi_bit_entry_without_messages_fails() {
+    let status: Status = serde_json::from_value(serde_json::json!({
+      "id": "https://example.com/credentials/status/8#492847",
+      "type": "BitstringStatusListEntry",
+      "statusPurpose": "message",
+      "statusListIndex": "492847",
+      "statusSize": 2,
+      "statusListCredential": "https://example.com/credentials/status/8"
+    }))
+    .unwrap();
+    assert!(BitstringStatusListEntry::try_from(&status).is_err());
+  }
+
+  #[test]
+  fn deserializing_wrong_status_type_fails() {
+    let status: Status = serde_json::from_value(serde_json::json!({
+      "id": "https://example.com/credentials/status/3#94567",
+      "type": "StatusList2021Entry",
+      "statusPurpose": "revocation",
+      "statusListIndex": "94567",
+      "statusListCredential": "https://example.com/credentials/status/3"
+    }))
+    .unwrap();
+    assert!(BitstringStatusListEntry::try_from(&status).is_err());
+  }
+}
@@ -0,0 +1,12 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of [Bitstring Status List v1.0](https://www.w3.org/TR/vc-bitstring-status-list/).
+
+mod credential;
+mod entry;
+mod status_list;
+
+pub use credential::*;
+pub use entry::*;
+pub use status_list::*;
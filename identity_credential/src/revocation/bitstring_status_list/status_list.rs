@@ -0,0 +1,242 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Read;
+use std::io::Write;
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use identity_core::convert::Base;
+use identity_core::convert::BaseEncoding;
+use thiserror::Error;
+
+/// The minimum number of bits of a bitstring status list, corresponding to 16KB.
+const MINIMUM_BITSTRING_LENGTH: usize = 16 * 1024 * 8;
+/// The maximum supported size of a status entry in bits.
+const MAXIMUM_STATUS_SIZE: u8 = 8;
+
+/// [`std::error::Error`] type for [`BitstringStatusList`]'s operations.
+#[derive(Debug, Error, PartialEq, Eq, Clone, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum BitstringStatusListError {
+  /// Requested entry is not in the list.
+  #[error("the requested entry is not in the list")]
+  IndexOutOfBounds,
+  /// Improperly encoded status list.
+  #[error("\"{0}\" is not a valid encoded bitstring status list")]
+  InvalidEncoding(String),
+  /// Invalid list size.
+  #[error("a bitstring status list must be at least {MINIMUM_BITSTRING_LENGTH} bits long")]
+  InvalidListSize,
+  /// Invalid status size.
+  #[error("the status size must be between 1 and {MAXIMUM_STATUS_SIZE} bits, found {0}")]
+  InvalidStatusSize(u8),
+  /// The value does not fit into a status entry.
+  #[error("the value {0} does not fit into a status entry")]
+  InvalidStatusValue(u8),
+}
+
+/// Bitstring status list as described in [W3C's Bitstring Status List v1.0](https://www.w3.org/TR/vc-bitstring-status-list/).
+///
+/// Each entry consists of `status_size` consecutive bits, the first entry starting at the left-most bit of the
+/// bitstring.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BitstringStatusList {
+  bitstring: Box<[u8]>,
+  status_size: u8,
+}
+
+impl Default for BitstringStatusList {
+  fn default() -> Self {
+    BitstringStatusList::new(MINIMUM_BITSTRING_LENGTH, 1).unwrap()
+  }
+}
+
+impl BitstringStatusList {
+  /// The minimum number of bits of a bitstring status list, corresponding to 16KB.
+  pub const MINIMUM_LENGTH: usize = MINIMUM_BITSTRING_LENGTH;
+
+  /// Returns a new zero-filled [`BitstringStatusList`] that can hold `num_entries` statuses of `status_size` bits.
+  ///
+  /// ## Notes:
+  /// - The length of the bitstring will be rounded up to the closest multiple of 8 to accomodate for byte sizes.
+  /// - The bitstring must be at least 131,072 bits long, which corresponds to a size of 16KB.
+  pub fn new(num_entries: usize, status_size: u8) -> Result<Self, BitstringStatusListError> {
+    Self::check_status_size(status_size)?;
+    let num_bits: usize = num_entries
+      .checked_mul(status_size as usize)
+      .ok_or(BitstringStatusListError::InvalidListSize)?;
+    if num_bits < MINIMUM_BITSTRING_LENGTH {
+      return Err(BitstringStatusListError::InvalidListSize);
+    }
+
+    let size = num_bits / 8 + (num_bits % 8 != 0) as usize;
+    Ok(Self {
+      bitstring: vec![0; size].into_boxed_slice(),
+      status_size,
+    })
+  }
+
+  /// Returns the number of entries.
+  #[allow(clippy::len_without_is_empty)]
+  pub const fn len(&self) -> usize {
+    self.bitstring.len() * 8 / self.status_size as usize
+  }
+
+  /// Returns the size of the entries in bits.
+  pub const fn status_size(&self) -> u8 {
+    self.status_size
+  }
+
+  /// Returns the status value of the `index`-th entry, if it exists.
+  pub fn get(&self, index: usize) -> Result<u8, BitstringStatusListError> {
+    if index >= self.len() {
+      return Err(BitstringStatusListError::IndexOutOfBounds);
+    }
+
+    let first_bit: usize = index * self.status_size as usize;
+    let value: u8 =
+      (first_bit..first_bit + self.status_size as usize).fold(0, |value, bit| (value << 1) | self.get_bit(bit) as u8);
+    Ok(value)
+  }
+
+  /// Sets the status value of the `index`-th entry to `value`.
+  pub fn set(&mut self, index: usize, value: u8) -> Result<(), BitstringStatusListError> {
+    if index >= self.len() {
+      return Err(BitstringStatusListError::IndexOutOfBounds);
+    }
+    if (value as u16) >> self.status_size != 0 {
+      return Err(BitstringStatusListError::InvalidStatusValue(value));
+    }
+
+    let first_bit: usize = index * self.status_size as usize;
+    for (offset, bit) in (first_bit..first_bit + self.status_size as usize).enumerate() {
+      let shift: usize = self.status_size as usize - 1 - offset;
+      self.set_bit(bit, (value >> shift) & 1 == 1);
+    }
+    Ok(())
+  }
+
+  /// Attempts to parse a [`BitstringStatusList`] with entries of `status_size` bits from a string, following the
+  /// [bitstring expansion algorithm](https://www.w3.org/TR/vc-bitstring-status-list/#bitstring-expansion-algorithm).
+  pub fn try_from_encoded_str(s: &str, status_size: u8) -> Result<Self, BitstringStatusListError> {
+    Self::check_status_size(status_size)?;
+    let compressed_status_list =
+      BaseEncoding::decode_multibase(s).or(Err(BitstringStatusListError::InvalidEncoding(s.to_owned())))?;
+
+    let mut decompressor = GzDecoder::new(&compressed_status_list[..]);
+    let mut bitstring = vec![];
+    decompressor
+      .read_to_end(&mut bitstring)
+      .or(Err(BitstringStatusListError::InvalidEncoding(s.to_owned())))?;
+    if bitstring.len() * 8 < MINIMUM_BITSTRING_LENGTH {
+      return Err(BitstringStatusListError::InvalidListSize);
+    }
+
+    Ok(Self {
+      bitstring: bitstring.into_boxed_slice(),
+      status_size,
+    })
+  }
+
+  /// Encode this [`BitstringStatusList`] into its string representation following the
+  /// [bitstring generation algorithm](https://www.w3.org/TR/vc-bitstring-status-list/#bitstring-generation-algorithm).
+  pub fn into_encoded_str(self) -> String {
+    let compressed_status_list = {
+      let mut compressor = GzEncoder::new(vec![], Compression::best());
+      compressor.write_all(&self.bitstring).unwrap();
+      compressor.finish().unwrap()
+    };
+
+    BaseEncoding::encode_multibase(&compressed_status_list, Some(Base::Base64Url))
+  }
+
+  fn check_status_size(status_size: u8) -> Result<(), BitstringStatusListError> {
+    if (1..=MAXIMUM_STATUS_SIZE).contains(&status_size) {
+      Ok(())
+    } else {
+      Err(BitstringStatusListError::InvalidStatusSize(status_size))
+    }
+  }
+
+  const fn get_bit(&self, bit: usize) -> bool {
+    self.bitstring[bit / 8] & (0b1000_0000 >> (bit % 8)) != 0
+  }
+
+  fn set_bit(&mut self, bit: usize, value: bool) {
+    if value {
+      self.bitstring[bit / 8] |= 0b1000_0000 >> (bit % 8);
+    } else {
+      self.bitstring[bit / 8] &= !(0b1000_0000 >> (bit % 8));
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn default_status_list_is_empty() {
+    let status_list = BitstringStatusList::default();
+    assert_eq!(status_list.len(), MINIMUM_BITSTRING_LENGTH);
+    assert!((0..status_list.len()).all(|index| status_list.get(index) == Ok(0)));
+  }
+
+  #[test]
+  fn status_list_too_short_fails() {
+    assert_eq!(
+      BitstringStatusList::new(MINIMUM_BITSTRING_LENGTH / 2 - 1, 2),
+      Err(BitstringStatusListError::InvalidListSize)
+    );
+    assert_eq!(
+      BitstringStatusList::new(MINIMUM_BITSTRING_LENGTH, 9),
+      Err(BitstringStatusListError::InvalidStatusSize(9))
+    );
+  }
+
+  #[test]
+  fn multi_bit_entries_work() {
+    let mut status_list = BitstringStatusList::new(MINIMUM_BITSTRING_LENGTH / 2, 2).unwrap();
+    status_list.set(0, 0b11).unwrap();
+    status_list.set(1, 0b01).unwrap();
+    status_list.set(5, 0b10).unwrap();
+
+    assert_eq!(status_list.get(0), Ok(0b11));
+    assert_eq!(status_list.get(1), Ok(0b01));
+    assert_eq!(status_list.get(2), Ok(0));
+    assert_eq!(status_list.get(5), Ok(0b10));
+    // The first entry occupies the left-most bits.
+    assert_eq!(status_list.bitstring[0], 0b1101_0000);
+    assert_eq!(
+      status_list.set(3, 0b100),
+      Err(BitstringStatusListError::InvalidStatusValue(0b100))
+    );
+
+    status_list.set(0, 0b01).unwrap();
+    assert_eq!(status_list.get(0), Ok(0b01));
+  }
+
+  #[test]
+  fn status_list_encoding_roundtrip() {
+    let mut status_list = BitstringStatusList::new(MINIMUM_BITSTRING_LENGTH / 4, 4).unwrap();
+    status_list.set(42, 0xA).unwrap();
+    status_list.set(4095, 0xF).unwrap();
+
+    let encoded = status_list.clone().into_encoded_str();
+    assert!(encoded.starts_with('u'));
+    let decoded = BitstringStatusList::try_from_encoded_str(&encoded, 4).unwrap();
+    assert_eq!(decoded, status_list);
+    assert_eq!(decoded.get(42), Ok(0xA));
+  }
+
+  #[test]
+  fn decoding_spec_example_works() {
+    // Taken from https://www.w3.org/TR/vc-bitstring-status-list/#example-example-bitstringstatuslistcredential
+    let encoded = "uH4sIAAAAAAAAA-3BMQEAAADCoPVPbQwfoAAAAAAAAAAAAAAAAAAAAIC3AYbSVKsAQAAA";
+    let status_list = BitstringStatusList::try_from_encoded_str(encoded, 1).unwrap();
+    assert_eq!(status_list.len(), MINIMUM_BITSTRING_LENGTH);
+    assert_eq!(status_list.get(94567), Ok(0));
+  }
+}
@@ -4,10 +4,14 @@
 //! Contains the implementations for all the credential revocation methods that can be used with IOTA's Identity
 //! framework.
 
+#[cfg(feature = "bitstring-status-list")]
+pub mod bitstring_status_list;
 mod error;
 mod revocation_bitmap_2022;
 #[cfg(feature = "status-list-2021")]
 pub mod status_list_2021;
+#[cfg(feature = "token-status-list")]
+pub mod token_status_list;
 
 #[cfg(feature = "jpt-bbs-plus")]
 pub mod validity_timeframe_2024;
@@ -66,6 +66,9 @@ impl From<StatusList2021Entry> for Status {
 }
 
 impl StatusList2021Entry {
+  /// The type of a [`StatusList2021Entry`].
+  pub const TYPE: &'static str = CREDENTIAL_STATUS_TYPE;
+
   /// Creates a new [`StatusList2021Entry`].
   pub fn new(status_list: Url, purpose: StatusPurpose, index: usize, id: Option<Url>) -> Self {
     let id = id.unwrap_or_else(|| {
@@ -0,0 +1,18 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of the [Token Status List](https://datatracker.ietf.org/doc/html/draft-ietf-oauth-status-list-06).
+//!
+//! Status list tokens in JWT format are fully supported, including signature verification with
+//! [`StatusListToken::decode_jwt`]. Support for the CWT format is limited to the claims set:
+//! [`StatusListToken::to_cwt_claims`] and [`StatusListToken::from_cwt_claims`] neither sign nor verify the
+//! `COSE_Sign1` structure carrying the claims, so signing, verifying and validating the header of CWT status list
+//! tokens is up to the caller.
+
+mod reference;
+mod status_list;
+mod token;
+
+pub use reference::*;
+pub use status_list::*;
+pub use token::*;
@@ -0,0 +1,76 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Url;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::TokenStatusListError;
+
+/// The reference to an entry of a status list token, contained in the `status_list` member of the `status` claim of a
+/// [referenced token](https://datatracker.ietf.org/doc/html/draft-ietf-oauth-status-list-06#name-referenced-token), e.g.
+/// an SD-JWT VC.
+#[derive(Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq)]
+pub struct StatusListReference {
+  /// The index of the status of the referenced token in the status list.
+  pub idx: usize,
+  /// The URI identifying the status list token.
+  pub uri: Url,
+}
+
+impl StatusListReference {
+  /// The name of the member of the `status` claim containing the reference.
+  pub const STATUS_CLAIM_MEMBER: &'static str = "status_list";
+
+  /// Creates a new [`StatusListReference`] to the `idx`-th entry of the status list token identified by `uri`.
+  pub fn new(uri: Url, idx: usize) -> Self {
+    Self { idx, uri }
+  }
+
+  /// Extracts the [`StatusListReference`] from the `status` claim of a referenced token.
+  pub fn from_status_claim(status: &Object) -> Result<Self, TokenStatusListError> {
+    let reference = status
+      .get(Self::STATUS_CLAIM_MEMBER)
+      .ok_or(TokenStatusListError::InvalidClaims(
+        "missing `status_list` in `status` claim",
+      ))?;
+
+    serde_json::from_value(reference.clone())
+      .map_err(|_| TokenStatusListError::InvalidClaims("invalid `status_list` in `status` claim"))
+  }
+
+  /// Returns the `status` claim of a referenced token containing this reference.
+  pub fn to_status_claim(&self) -> Object {
+    let reference = serde_json::to_value(self).unwrap(); // Safety: shouldn't go out of memory
+    [(Self::STATUS_CLAIM_MEMBER.to_owned(), reference)]
+      .into_iter()
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+
+  use super::*;
+
+  #[test]
+  fn status_claim_roundtrip() {
+    let status = Object::from_json_value(serde_json::json!({
+      "status_list": {
+        "idx": 0,
+        "uri": "https://example.com/statuslists/1"
+      }
+    }))
+    .unwrap();
+
+    let reference = StatusListReference::from_status_claim(&status).unwrap();
+    assert_eq!(
+      reference,
+      StatusListReference::new(Url::parse("https://example.com/statuslists/1").unwrap(), 0)
+    );
+    assert_eq!(reference.to_status_claim(), status);
+    assert!(StatusListReference::from_status_claim(&Object::new()).is_err());
+  }
+}
@@ -0,0 +1,258 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Read;
+use std::io::Write;
+
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use identity_core::convert::Base;
+use identity_core::convert::BaseEncoding;
+use thiserror::Error;
+
+/// [`std::error::Error`] type for the operations of the Token Status List.
+#[derive(Debug, Error, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum TokenStatusListError {
+  /// Requested entry is not in the list.
+  #[error("the requested entry is not in the list")]
+  IndexOutOfBounds,
+  /// Unsupported number of bits per status.
+  #[error("the number of bits per status must be 1, 2, 4 or 8, found {0}")]
+  InvalidBits(u8),
+  /// The value does not fit into a status entry.
+  #[error("the value {0} does not fit into a status entry")]
+  InvalidStatusValue(u8),
+  /// Improperly encoded status list.
+  #[error("\"{0}\" is not a valid encoded status list")]
+  InvalidEncoding(String),
+  /// The claims of a status list token or a referenced token are invalid.
+  #[error("invalid status list claims: {0}")]
+  InvalidClaims(&'static str),
+  /// The status list token is not the one referenced.
+  #[error("the status list token's subject does not match the referenced uri")]
+  SubjectMismatch,
+  /// The status list token is expired.
+  #[error("the status list token is expired")]
+  Expired,
+  /// The `typ` header parameter of the status list token is invalid.
+  #[error("invalid `typ` header parameter value")]
+  InvalidHeaderTyp,
+  /// The status list token could not be decoded or its signature is invalid.
+  #[error("invalid status list token")]
+  InvalidToken(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+/// The status of a referenced token, as defined in
+/// [Token Status List](https://datatracker.ietf.org/doc/html/draft-ietf-oauth-status-list-06#name-status-types).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TokenStatus {
+  /// The status of the token is valid, correct or legal.
+  Valid,
+  /// The status of the token is revoked, annulled, taken back, recalled or cancelled.
+  Invalid,
+  /// The status of the token is temporarily invalid, hanging or debarred from privilege.
+  Suspended,
+  /// An application specific or reserved status value.
+  Other(u8),
+}
+
+impl From<u8> for TokenStatus {
+  fn from(value: u8) -> Self {
+    match value {
+      0x00 => Self::Valid,
+      0x01 => Self::Invalid,
+      0x02 => Self::Suspended,
+      value => Self::Other(value),
+    }
+  }
+}
+
+impl From<TokenStatus> for u8 {
+  fn from(status: TokenStatus) -> Self {
+    match status {
+      TokenStatus::Valid => 0x00,
+      TokenStatus::Invalid => 0x01,
+      TokenStatus::Suspended => 0x02,
+      TokenStatus::Other(value) => value,
+    }
+  }
+}
+
+/// A status list as described in [Token Status List](https://datatracker.ietf.org/doc/html/draft-ietf-oauth-status-list-06#name-status-list).
+///
+/// Each status consists of `bits` bits. The status at index 0 occupies the least significant bits of the first byte.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TokenStatusList {
+  list: Box<[u8]>,
+  bits: u8,
+}
+
+impl TokenStatusList {
+  /// Returns a new [`TokenStatusList`] that can hold `num_entries` statuses of `bits` bits, all set to
+  /// [`TokenStatus::Valid`].
+  ///
+  /// The length of the list is rounded up to the closest multiple of the number of statuses per byte.
+  pub fn new(num_entries: usize, bits: u8) -> Result<Self, TokenStatusListError> {
+    Self::check_bits(bits)?;
+    let entries_per_byte: usize = 8 / bits as usize;
+    let size: usize = num_entries / entries_per_byte + (num_entries % entries_per_byte != 0) as usize;
+
+    Ok(Self {
+      list: vec![0; size].into_boxed_slice(),
+      bits,
+    })
+  }
+
+  /// Returns the number of entries.
+  #[allow(clippy::len_without_is_empty)]
+  pub const fn len(&self) -> usize {
+    self.list.len() * 8 / self.bits as usize
+  }
+
+  /// Returns the number of bits per status.
+  pub const fn bits(&self) -> u8 {
+    self.bits
+  }
+
+  /// Returns the status value of the `index`-th entry, if it exists.
+  pub fn get(&self, index: usize) -> Result<u8, TokenStatusListError> {
+    if index >= self.len() {
+      return Err(TokenStatusListError::IndexOutOfBounds);
+    }
+    let (byte, shift) = self.position(index);
+
+    Ok((self.list[byte] >> shift) & self.mask())
+  }
+
+  /// Sets the status value of the `index`-th entry to `value`.
+  pub fn set(&mut self, index: usize, value: impl Into<u8>) -> Result<(), TokenStatusListError> {
+    let value: u8 = value.into();
+    if index >= self.len() {
+      return Err(TokenStatusListError::IndexOutOfBounds);
+    }
+    if value & !self.mask() != 0 {
+      return Err(TokenStatusListError::InvalidStatusValue(value));
+    }
+    let (byte, shift) = self.position(index);
+    self.list[byte] = (self.list[byte] & !(self.mask() << shift)) | (value << shift);
+
+    Ok(())
+  }
+
+  /// Returns the status of the `index`-th entry, if it exists.
+  pub fn status(&self, index: usize) -> Result<TokenStatus, TokenStatusListError> {
+    self.get(index).map(TokenStatus::from)
+  }
+
+  /// Attempts to decompress a [`TokenStatusList`] of `bits` bits per status from the ZLIB compressed byte array
+  /// `compressed`.
+  pub fn try_from_compressed(compressed: &[u8], bits: u8) -> Result<Self, TokenStatusListError> {
+    Self::check_bits(bits)?;
+    let mut decompressor = ZlibDecoder::new(compressed);
+    let mut list = vec![];
+    decompressor
+      .read_to_end(&mut list)
+      .map_err(|err| TokenStatusListError::InvalidEncoding(err.to_string()))?;
+
+    Ok(Self {
+      list: list.into_boxed_slice(),
+      bits,
+    })
+  }
+
+  /// Compresses this [`TokenStatusList`] using ZLIB, as used in the CWT representation.
+  pub fn to_compressed(&self) -> Vec<u8> {
+    let mut compressor = ZlibEncoder::new(vec![], Compression::best());
+    compressor.write_all(&self.list).unwrap();
+    compressor.finish().unwrap()
+  }
+
+  /// Attempts to parse a [`TokenStatusList`] of `bits` bits per status from the base64url encoded, compressed `lst`
+  /// value of a status list in JSON representation.
+  pub fn try_from_encoded_str(s: &str, bits: u8) -> Result<Self, TokenStatusListError> {
+    let compressed =
+      BaseEncoding::decode(s, Base::Base64Url).map_err(|_| TokenStatusListError::InvalidEncoding(s.to_owned()))?;
+    Self::try_from_compressed(&compressed, bits)
+  }
+
+  /// Encodes this [`TokenStatusList`] as the base64url encoded, compressed `lst` value of a status list in JSON
+  /// representation.
+  pub fn to_encoded_str(&self) -> String {
+    BaseEncoding::encode(&self.to_compressed(), Base::Base64Url)
+  }
+
+  fn check_bits(bits: u8) -> Result<(), TokenStatusListError> {
+    if matches!(bits, 1 | 2 | 4 | 8) {
+      Ok(())
+    } else {
+      Err(TokenStatusListError::InvalidBits(bits))
+    }
+  }
+
+  const fn mask(&self) -> u8 {
+    ((1u16 << self.bits) - 1) as u8
+  }
+
+  const fn position(&self, index: usize) -> (usize, usize) {
+    let bit: usize = index * self.bits as usize;
+    (bit / 8, bit % 8)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn single_bit_list_matches_spec_example() {
+    // Taken from https://datatracker.ietf.org/doc/html/draft-ietf-oauth-status-list-06#name-status-list
+    let statuses: [u8; 16] = [1, 0, 0, 1, 1, 1, 0, 1, 1, 1, 0, 0, 0, 1, 0, 1];
+    let mut status_list = TokenStatusList::new(16, 1).unwrap();
+    for (index, status) in statuses.iter().enumerate() {
+      status_list.set(index, *status).unwrap();
+    }
+
+    assert_eq!(&status_list.list[..], &[0xB9, 0xA3]);
+    let decoded = TokenStatusList::try_from_encoded_str("eNrbuRgAAhcBXQ", 1).unwrap();
+    assert_eq!(decoded, status_list);
+    assert_eq!(decoded.status(0).unwrap(), TokenStatus::Invalid);
+    assert_eq!(decoded.status(1).unwrap(), TokenStatus::Valid);
+  }
+
+  #[test]
+  fn two_bit_list_matches_spec_example() {
+    let statuses: [u8; 12] = [1, 2, 0, 3, 0, 1, 0, 1, 1, 2, 3, 3];
+    let mut status_list = TokenStatusList::new(12, 2).unwrap();
+    for (index, status) in statuses.iter().enumerate() {
+      status_list.set(index, *status).unwrap();
+    }
+
+    assert_eq!(&status_list.list[..], &[0xC9, 0x44, 0xF9]);
+    assert_eq!(status_list.status(1).unwrap(), TokenStatus::Suspended);
+    assert_eq!(status_list.status(3).unwrap(), TokenStatus::Other(3));
+    assert!(matches!(
+      status_list.set(0, 4),
+      Err(TokenStatusListError::InvalidStatusValue(4))
+    ));
+    assert!(matches!(
+      status_list.get(12),
+      Err(TokenStatusListError::IndexOutOfBounds)
+    ));
+  }
+
+  #[test]
+  fn encoding_roundtrip() {
+    let mut status_list = TokenStatusList::new(1000, 4).unwrap();
+    status_list.set(999, TokenStatus::Suspended).unwrap();
+    let encoded = status_list.to_encoded_str();
+    let decoded = TokenStatusList::try_from_encoded_str(&encoded, 4).unwrap();
+    assert_eq!(decoded.status(999).unwrap(), TokenStatus::Suspended);
+    assert!(matches!(
+      TokenStatusList::new(8, 3),
+      Err(TokenStatusListError::InvalidBits(3))
+    ));
+  }
+}
@@ -0,0 +1,426 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use ciborium::Value as CborValue;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_verification::jwk::Jwk;
+use identity_verification::jws::Decoder;
+use identity_verification::jws::JwsVerifier;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::StatusListReference;
+use super::TokenStatus;
+use super::TokenStatusList;
+use super::TokenStatusListError;
+
+/// The CWT claim keys of a status list token.
+mod cwt_keys {
+  pub(super) const SUB: i64 = 2;
+  pub(super) const EXP: i64 = 4;
+  pub(super) const IAT: i64 = 6;
+  pub(super) const STATUS_LIST: i64 = 65533;
+  pub(super) const TTL: i64 = 65534;
+}
+
+/// The `status_list` claim of a status list token in JSON representation.
+#[derive(Debug, Clone, Serialize, Deserialize, Hash, Eq, PartialEq)]
+pub struct StatusListClaim {
+  /// The number of bits per status.
+  pub bits: u8,
+  /// The base64url encoded, compressed status list.
+  pub lst: String,
+  /// The URI to retrieve all status list tokens of the issuer from.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub aggregation_uri: Option<Url>,
+}
+
+impl StatusListClaim {
+  /// Decodes the status list contained in this claim.
+  pub fn status_list(&self) -> Result<TokenStatusList, TokenStatusListError> {
+    TokenStatusList::try_from_encoded_str(&self.lst, self.bits)
+  }
+}
+
+/// The claims of a [status list token](https://datatracker.ietf.org/doc/html/draft-ietf-oauth-status-list-06#name-status-list-token).
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct StatusListToken {
+  /// The URI of the status list token, which referenced tokens use to reference it.
+  pub sub: Url,
+  /// The time of issuance of the status list token.
+  #[serde(with = "unix_timestamp")]
+  pub iat: Timestamp,
+  /// The expiration time of the status list token.
+  #[serde(default, skip_serializing_if = "Option::is_none", with = "unix_timestamp::option")]
+  pub exp: Option<Timestamp>,
+  /// The maximum time in seconds the status list token can be cached before retrieving a fresh copy.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub ttl: Option<u64>,
+  /// The status list.
+  pub status_list: StatusListClaim,
+}
+
+impl StatusListToken {
+  /// The `typ` header parameter of status list tokens in JWT format.
+  pub const HEADER_TYP: &'static str = "statuslist+jwt";
+  /// The `type` header parameter of status list tokens in CWT format.
+  pub const CWT_HEADER_TYPE: &'static str = "application/statuslist+cwt";
+
+  /// Creates a new [`StatusListTokenBuilder`] to configure a status list token with the given `status_list`,
+  /// identified by `sub`.
+  pub fn builder(sub: Url, status_list: &TokenStatusList) -> StatusListTokenBuilder {
+    StatusListTokenBuilder::new(sub, status_list)
+  }
+
+  /// Decodes the status list contained in this token.
+  pub fn status_list(&self) -> Result<TokenStatusList, TokenStatusListError> {
+    self.status_list.status_list()
+  }
+
+  /// Returns the status of the token referenced by `reference`.
+  pub fn status(&self, reference: &StatusListReference) -> Result<TokenStatus, TokenStatusListError> {
+    if reference.uri != self.sub {
+      return Err(TokenStatusListError::SubjectMismatch);
+    }
+    self.status_list()?.status(reference.idx)
+  }
+
+  /// Returns whether the status list token is expired at the given time.
+  pub fn is_expired_at(&self, time: Timestamp) -> bool {
+    self.exp.map_or(false, |exp| exp < time)
+  }
+
+  /// Serializes the claims as a JWT claims set, which can be used as the payload of a JWS.
+  ///
+  /// The JWS header must contain the `typ` parameter set to [`Self::HEADER_TYP`].
+  pub fn serialize_jwt(&self) -> Result<String, TokenStatusListError> {
+    serde_json::to_string(self).map_err(|err| TokenStatusListError::InvalidToken(err.into()))
+  }
+
+  /// Decodes and validates a status list token in JWT format, verifying its signature with `public_key`.
+  ///
+  /// The `typ` header parameter and the expiration time are validated as well. Retrieving the status list token and
+  /// the public key of its issuer, as well as checking that the `sub` claim matches the URI the token was retrieved
+  /// from, is up to the caller.
+  pub fn decode_jwt<V: JwsVerifier>(jwt: &str, verifier: &V, public_key: &Jwk) -> Result<Self, TokenStatusListError> {
+    let decoded = Decoder::new()
+      .decode_compact_serialization(jwt.as_bytes(), None)
+      .map_err(|err| TokenStatusListError::InvalidToken(err.into()))?;
+    if decoded.protected_header().and_then(|header| header.typ()) != Some(Self::HEADER_TYP) {
+      return Err(TokenStatusListError::InvalidHeaderTyp);
+    }
+    let decoded = decoded
+      .verify(verifier, public_key)
+      .map_err(|err| TokenStatusListError::InvalidToken(err.into()))?;
+
+    let token: Self =
+      serde_json::from_slice(&decoded.claims).map_err(|err| TokenStatusListError::InvalidToken(err.into()))?;
+    if token.is_expired_at(Timestamp::now_utc()) {
+      return Err(TokenStatusListError::Expired);
+    }
+
+    Ok(token)
+  }
+
+  /// Encodes the claims as a CBOR encoded CWT claims set, which can be used as the payload of a `COSE_Sign1`
+  /// structure.
+  ///
+  /// The protected header of the `COSE_Sign1` structure must contain the `type` parameter set to
+  /// [`Self::CWT_HEADER_TYPE`].
+  pub fn to_cwt_claims(&self) -> Result<Vec<u8>, TokenStatusListError> {
+    let compressed_list: Vec<u8> = self.status_list()?.to_compressed();
+    let mut status_list: Vec<(CborValue, CborValue)> = vec![
+      (CborValue::from("bits"), CborValue::from(self.status_list.bits)),
+      (CborValue::from("lst"), CborValue::Bytes(compressed_list)),
+    ];
+    if let Some(ref aggregation_uri) = self.status_list.aggregation_uri {
+      status_list.push((
+        CborValue::from("aggregation_uri"),
+        CborValue::from(aggregation_uri.as_str()),
+      ));
+    }
+
+    let mut claims: Vec<(CborValue, CborValue)> = vec![
+      (CborValue::from(cwt_keys::SUB), CborValue::from(self.sub.as_str())),
+      (CborValue::from(cwt_keys::IAT), CborValue::from(self.iat.to_unix())),
+    ];
+    if let Some(exp) = self.exp {
+      claims.push((CborValue::from(cwt_keys::EXP), CborValue::from(exp.to_unix())));
+    }
+    if let Some(ttl) = self.ttl {
+      claims.push((CborValue::from(cwt_keys::TTL), CborValue::from(ttl)));
+    }
+    claims.push((CborValue::from(cwt_keys::STATUS_LIST), CborValue::Map(status_list)));
+
+    let mut encoded: Vec<u8> = Vec::new();
+    ciborium::ser::into_writer(&CborValue::Map(claims), &mut encoded)
+      .map_err(|err| TokenStatusListError::InvalidToken(err.into()))?;
+    Ok(encoded)
+  }
+
+  /// Decodes the claims of a status list token from a CBOR encoded CWT claims set.
+  ///
+  /// The claims are **not** verified: checking the signature and the `type` header parameter of the `COSE_Sign1`
+  /// structure the claims were taken from, as well as the expiration time of the token, is up to the caller.
+  pub fn from_cwt_claims(bytes: &[u8]) -> Result<Self, TokenStatusListError> {
+    let claims: CborValue =
+      ciborium::de::from_reader(bytes).map_err(|err| TokenStatusListError::InvalidToken(err.into()))?;
+    let claims: Vec<(CborValue, CborValue)> = claims
+      .into_map()
+      .map_err(|_| TokenStatusListError::InvalidClaims("the claims set must be a map"))?;
+    let claim = |key: i64| -> Option<&CborValue> {
+      claims
+        .iter()
+        .find(|(claim_key, _)| claim_key.as_integer() == Some(key.into()))
+        .map(|(_, value)| value)
+    };
+    let timestamp = |key: i64, name: &'static str| -> Result<Option<Timestamp>, TokenStatusListError> {
+      claim(key)
+        .map(|value| {
+          value
+            .as_integer()
+            .and_then(|seconds| i64::try_from(seconds).ok())
+            .and_then(|seconds| Timestamp::from_unix(seconds).ok())
+            .ok_or(TokenStatusListError::InvalidClaims(name))
+        })
+        .transpose()
+    };
+
+    let sub: Url = claim(cwt_keys::SUB)
+      .and_then(CborValue::as_text)
+      .and_then(|sub| Url::parse(sub).ok())
+      .ok_or(TokenStatusListError::InvalidClaims("missing or invalid `sub` claim"))?;
+    let iat: Timestamp = timestamp(cwt_keys::IAT, "invalid `iat` claim")?
+      .ok_or(TokenStatusListError::InvalidClaims("missing `iat` claim"))?;
+    let exp: Option<Timestamp> = timestamp(cwt_keys::EXP, "invalid `exp` claim")?;
+    let ttl: Option<u64> = claim(cwt_keys::TTL)
+      .map(|value| {
+        value
+          .as_integer()
+          .and_then(|ttl| u64::try_from(ttl).ok())
+          .ok_or(TokenStatusListError::InvalidClaims("invalid `ttl` claim"))
+      })
+      .transpose()?;
+
+    let status_list: &Vec<(CborValue, CborValue)> =
+      claim(cwt_keys::STATUS_LIST)
+        .and_then(CborValue::as_map)
+        .ok_or(TokenStatusListError::InvalidClaims(
+          "missing or invalid `status_list` claim",
+        ))?;
+    let member = |name: &str| -> Option<&CborValue> {
+      status_list
+        .iter()
+        .find(|(key, _)| key.as_text() == Some(name))
+        .map(|(_, value)| value)
+    };
+    let bits: u8 = member("bits")
+      .and_then(CborValue::as_integer)
+      .and_then(|bits| u8::try_from(bits).ok())
+      .ok_or(TokenStatusListError::InvalidClaims("missing or invalid `bits`"))?;
+    let lst: TokenStatusList = member("lst")
+      .and_then(CborValue::as_bytes)
+      .ok_or(TokenStatusListError::InvalidClaims("missing or invalid `lst`"))
+      .and_then(|compressed| TokenStatusList::try_from_compressed(compressed, bits))?;
+    let aggregation_uri: Option<Url> = member("aggregation_uri")
+      .map(|uri| {
+        uri
+          .as_text()
+          .and_then(|uri| Url::parse(uri).ok())
+          .ok_or(TokenStatusListError::InvalidClaims("invalid `aggregation_uri`"))
+      })
+      .transpose()?;
+
+    Ok(Self {
+      sub,
+      iat,
+      exp,
+      ttl,
+      status_list: StatusListClaim {
+        bits,
+        lst: lst.to_encoded_str(),
+        aggregation_uri,
+      },
+    })
+  }
+}
+
+/// Builder type for [`StatusListToken`].
+#[derive(Debug, Clone)]
+pub struct StatusListTokenBuilder {
+  sub: Url,
+  status_list: StatusListClaim,
+  iat: Option<Timestamp>,
+  exp: Option<Timestamp>,
+  ttl: Option<u64>,
+}
+
+impl StatusListTokenBuilder {
+  /// Creates a new [`StatusListTokenBuilder`] for a status list token with the given `status_list`, identified by
+  /// `sub`.
+  pub fn new(sub: Url, status_list: &TokenStatusList) -> Self {
+    Self {
+      sub,
+      status_list: StatusListClaim {
+        bits: status_list.bits(),
+        lst: status_list.to_encoded_str(),
+        aggregation_uri: None,
+      },
+      iat: None,
+      exp: None,
+      ttl: None,
+    }
+  }
+
+  /// Sets the value of the `iat` claim.
+  ///
+  /// If unset, the current time is used.
+  #[must_use]
+  pub fn issuance_date(mut self, value: Timestamp) -> Self {
+    self.iat = Some(value);
+    self
+  }
+
+  /// Sets the value of the `exp` claim.
+  #[must_use]
+  pub fn expiration_date(mut self, value: Timestamp) -> Self {
+    self.exp = Some(value);
+    self
+  }
+
+  /// Sets the value of the `ttl` claim in seconds.
+  #[must_use]
+  pub fn ttl(mut self, value: u64) -> Self {
+    self.ttl = Some(value);
+    self
+  }
+
+  /// Sets the `aggregation_uri` of the status list.
+  #[must_use]
+  pub fn aggregation_uri(mut self, value: Url) -> Self {
+    self.status_list.aggregation_uri = Some(value);
+    self
+  }
+
+  /// Returns a new [`StatusListToken`] based on the `StatusListTokenBuilder` configuration.
+  pub fn build(self) -> StatusListToken {
+    StatusListToken {
+      sub: self.sub,
+      iat: self.iat.unwrap_or_else(Timestamp::now_utc),
+      exp: self.exp,
+      ttl: self.ttl,
+      status_list: self.status_list,
+    }
+  }
+}
+
+/// (De)serializes timestamps as seconds since the Unix epoch.
+mod unix_timestamp {
+  use identity_core::common::Timestamp;
+  use serde::de::Error;
+  use serde::Deserialize;
+  use serde::Deserializer;
+  use serde::Serializer;
+
+  pub(super) fn serialize<S>(value: &Timestamp, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.serialize_i64(value.to_unix())
+  }
+
+  pub(super) fn deserialize<'de, D>(deserializer: D) -> Result<Timestamp, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    Timestamp::from_unix(i64::deserialize(deserializer)?).map_err(D::Error::custom)
+  }
+
+  pub(super) mod option {
+    use identity_core::common::Timestamp;
+    use serde::de::Error;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+
+    pub(in super::super) fn serialize<S>(value: &Option<Timestamp>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+      S: Serializer,
+    {
+      value.map(|timestamp| timestamp.to_unix()).serialize(serializer)
+    }
+
+    pub(in super::super) fn deserialize<'de, D>(deserializer: D) -> Result<Option<Timestamp>, D::Error>
+    where
+      D: Deserializer<'de>,
+    {
+      Option::<i64>::deserialize(deserializer)?
+        .map(Timestamp::from_unix)
+        .transpose()
+        .map_err(D::Error::custom)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Duration;
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+
+  use super::*;
+
+  fn status_list_token() -> StatusListToken {
+    let mut status_list = TokenStatusList::new(16, 2).unwrap();
+    status_list.set(1, TokenStatus::Invalid).unwrap();
+    status_list.set(2, TokenStatus::Suspended).unwrap();
+
+    StatusListToken::builder(Url::parse("https://example.com/statuslists/1").unwrap(), &status_list)
+      .issuance_date(Timestamp::from_unix(1686920170).unwrap())
+      .expiration_date(Timestamp::from_unix(2291720170).unwrap())
+      .ttl(43200)
+      .build()
+  }
+
+  #[test]
+  fn jwt_claims_roundtrip() {
+    let token = status_list_token();
+    let json = token.to_json_value().unwrap();
+    assert_eq!(json["iat"], 1686920170);
+    assert_eq!(json["status_list"]["bits"], 2);
+    assert_eq!(StatusListToken::from_json_value(json).unwrap(), token);
+  }
+
+  #[test]
+  fn cwt_claims_roundtrip() {
+    let token = status_list_token();
+    let cwt_claims = token.to_cwt_claims().unwrap();
+    let decoded = StatusListToken::from_cwt_claims(&cwt_claims).unwrap();
+
+    assert_eq!(decoded.sub, token.sub);
+    assert_eq!(decoded.exp, token.exp);
+    assert_eq!(decoded.ttl, Some(43200));
+    assert_eq!(decoded.status_list().unwrap(), token.status_list().unwrap());
+  }
+
+  #[test]
+  fn status_lookup() {
+    let token = status_list_token();
+    let reference = StatusListReference::new(token.sub.clone(), 2);
+    assert_eq!(token.status(&reference).unwrap(), TokenStatus::Suspended);
+
+    let other = StatusListReference::new(Url::parse("https://example.com/statuslists/2").unwrap(), 2);
+    assert!(matches!(
+      token.status(&other),
+      Err(TokenStatusListError::SubjectMismatch)
+    ));
+    assert!(token.is_expired_at(
+      Timestamp::from_unix(2291720170)
+        .unwrap()
+        .checked_add(Duration::seconds(1))
+        .unwrap()
+    ));
+  }
+}
@@ -0,0 +1,142 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::common::Value;
+use identity_verification::jwk::Jwk;
+
+use super::SdJwtVcClaims;
+use crate::error::Result;
+
+/// A `SdJwtVcBuilder` is used to create customized [`SdJwtVcClaims`].
+#[derive(Clone, Debug)]
+pub struct SdJwtVcBuilder {
+  pub(crate) issuer: Url,
+  pub(crate) vct: String,
+  pub(crate) not_before: Option<Timestamp>,
+  pub(crate) expiration_date: Option<Timestamp>,
+  pub(crate) issuance_date: Option<Timestamp>,
+  pub(crate) subject: Option<String>,
+  pub(crate) holder_key: Option<Jwk>,
+  pub(crate) status: Option<Object>,
+  pub(crate) properties: Object,
+}
+
+impl SdJwtVcBuilder {
+  /// Creates a new `SdJwtVcBuilder` for an SD-JWT VC issued by `issuer` with the given type `vct`.
+  pub fn new(issuer: Url, vct: impl Into<String>) -> Self {
+    Self {
+      issuer,
+      vct: vct.into(),
+      not_before: None,
+      expiration_date: None,
+      issuance_date: None,
+      subject: None,
+      holder_key: None,
+      status: None,
+      properties: Object::new(),
+    }
+  }
+
+  /// Sets the value of the `nbf` claim.
+  #[must_use]
+  pub fn not_before(mut self, value: Timestamp) -> Self {
+    self.not_before = Some(value);
+    self
+  }
+
+  /// Sets the value of the `exp` claim.
+  #[must_use]
+  pub fn expiration_date(mut self, value: Timestamp) -> Self {
+    self.expiration_date = Some(value);
+    self
+  }
+
+  /// Sets the value of the `iat` claim.
+  #[must_use]
+  pub fn issuance_date(mut self, value: Timestamp) -> Self {
+    self.issuance_date = Some(value);
+    self
+  }
+
+  /// Sets the value of the `sub` claim.
+  #[must_use]
+  pub fn subject(mut self, value: impl Into<String>) -> Self {
+    self.subject = Some(value.into());
+    self
+  }
+
+  /// Binds the credential to the holder's public key, which is set as the `jwk` of the `cnf` claim.
+  #[must_use]
+  pub fn holder_key(mut self, value: Jwk) -> Self {
+    self.holder_key = Some(value);
+    self
+  }
+
+  /// Sets the value of the `status` claim.
+  #[must_use]
+  pub fn status(mut self, value: Object) -> Self {
+    self.status = Some(value);
+    self
+  }
+
+  /// Adds a new claim about the subject.
+  #[must_use]
+  pub fn claim(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+    self.properties.insert(key.into(), value.into());
+    self
+  }
+
+  /// Adds a series of claims about the subject.
+  #[must_use]
+  pub fn claims<K, V, I>(mut self, iter: I) -> Self
+  where
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<Value>,
+  {
+    self
+      .properties
+      .extend(iter.into_iter().map(|(key, value)| (key.into(), value.into())));
+    self
+  }
+
+  /// Returns new [`SdJwtVcClaims`] based on the `SdJwtVcBuilder` configuration.
+  pub fn build(self) -> Result<SdJwtVcClaims> {
+    SdJwtVcClaims::from_builder(self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Url;
+  use serde_json::json;
+
+  use super::SdJwtVcBuilder;
+  use crate::error::Error;
+
+  #[test]
+  fn test_builder() {
+    let claims = SdJwtVcBuilder::new(Url::parse("did:example:issuer").unwrap(), "IdentityCredential")
+      .subject("did:example:holder")
+      .claim("given_name", "John")
+      .claim("address", json!({ "locality": "Anytown" }))
+      .build()
+      .unwrap();
+
+    assert_eq!(claims.vct, "IdentityCredential");
+    assert_eq!(claims.sub.as_deref(), Some("did:example:holder"));
+    assert_eq!(claims.properties["given_name"], "John");
+  }
+
+  #[test]
+  fn test_builder_rejects_registered_claims() {
+    let result = SdJwtVcBuilder::new(Url::parse("did:example:issuer").unwrap(), "IdentityCredential")
+      .claim("vct", "OtherCredential")
+      .build();
+
+    assert!(matches!(result, Err(Error::InvalidSdJwtVcClaims(_))));
+  }
+}
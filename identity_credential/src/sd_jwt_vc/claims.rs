@@ -0,0 +1,235 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_core::convert::FmtJson;
+use identity_core::convert::ToJson;
+use identity_verification::jwk::Jwk;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::SdJwtVcBuilder;
+use crate::error::Error;
+use crate::error::Result;
+
+/// The claims set of an [SD-JWT VC](https://www.ietf.org/archive/id/draft-ietf-oauth-sd-jwt-vc-05.html).
+///
+/// Unlike W3C credentials, the claims of an SD-JWT VC are not wrapped in an envelope: the registered claims describing
+/// the credential and the claims about the subject share the top level of the claims set.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SdJwtVcClaims {
+  /// The issuer of the credential, either an HTTPS URL or a DID.
+  pub iss: Url,
+  /// The time before which the credential must not be accepted.
+  #[serde(default, skip_serializing_if = "Option::is_none", with = "unix_timestamp")]
+  pub nbf: Option<Timestamp>,
+  /// The expiration time of the credential.
+  #[serde(default, skip_serializing_if = "Option::is_none", with = "unix_timestamp")]
+  pub exp: Option<Timestamp>,
+  /// The time of issuance of the credential.
+  #[serde(default, skip_serializing_if = "Option::is_none", with = "unix_timestamp")]
+  pub iat: Option<Timestamp>,
+  /// The type of the credential.
+  pub vct: String,
+  /// The identifier of the subject of the credential.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sub: Option<String>,
+  /// The key the holder must prove possession of when presenting the credential.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub cnf: Option<ConfirmationClaim>,
+  /// Information used to determine the status of the credential, e.g. a reference to a status list.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub status: Option<Object>,
+  /// The claims about the subject.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+impl SdJwtVcClaims {
+  /// The `typ` header parameter of SD-JWT VCs.
+  pub const HEADER_TYP: &'static str = "dc+sd-jwt";
+  /// The `typ` header parameter used by earlier drafts of SD-JWT VC, still accepted during validation.
+  pub const LEGACY_HEADER_TYP: &'static str = "vc+sd-jwt";
+  /// The claims that must not be selectively disclosable.
+  pub const NON_DISCLOSABLE_CLAIMS: &'static [&'static str] = &["iss", "nbf", "exp", "cnf", "vct", "status"];
+  /// The registered claims that cannot be set as properties.
+  const REGISTERED_CLAIMS: &'static [&'static str] = &[
+    "iss", "nbf", "exp", "iat", "vct", "sub", "cnf", "status", "_sd", "_sd_alg", "...",
+  ];
+
+  /// Creates a new [`SdJwtVcBuilder`] to configure an SD-JWT VC issued by `issuer` with the given type `vct`.
+  ///
+  /// This is the same as [`SdJwtVcBuilder::new`].
+  pub fn builder(issuer: Url, vct: impl Into<String>) -> SdJwtVcBuilder {
+    SdJwtVcBuilder::new(issuer, vct)
+  }
+
+  /// Returns new [`SdJwtVcClaims`] based on the [`SdJwtVcBuilder`] configuration.
+  pub fn from_builder(builder: SdJwtVcBuilder) -> Result<Self> {
+    let this: Self = Self {
+      iss: builder.issuer,
+      nbf: builder.not_before,
+      exp: builder.expiration_date,
+      iat: builder.issuance_date,
+      vct: builder.vct,
+      sub: builder.subject,
+      cnf: builder.holder_key.map(ConfirmationClaim::from_jwk),
+      status: builder.status,
+      properties: builder.properties,
+    };
+    this.check_structure()?;
+
+    Ok(this)
+  }
+
+  /// Validates the semantic structure of the claims.
+  pub fn check_structure(&self) -> Result<()> {
+    if self.vct.is_empty() {
+      return Err(Error::InvalidSdJwtVcClaims("`vct` must not be empty"));
+    }
+
+    if self
+      .properties
+      .keys()
+      .any(|key| Self::REGISTERED_CLAIMS.contains(&key.as_str()))
+    {
+      return Err(Error::InvalidSdJwtVcClaims(
+        "properties must not contain registered claims",
+      ));
+    }
+
+    if let Some(jwk) = self.cnf.as_ref().and_then(|cnf| cnf.jwk.as_ref()) {
+      if !jwk.is_public() {
+        return Err(Error::InvalidSdJwtVcClaims("`cnf.jwk` must be a public key"));
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Serializes the claims as a JWT claims set, which can be used as the input of an SD-JWT encoder.
+  pub fn serialize_jwt(&self) -> Result<String> {
+    self
+      .to_json()
+      .map_err(|err| Error::JwtClaimsSetSerializationError(err.into()))
+  }
+}
+
+impl Display for SdJwtVcClaims {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    self.fmt_json(f)
+  }
+}
+
+/// The `cnf` claim of an SD-JWT VC, identifying the key the holder must prove possession of.
+///
+/// See [RFC 7800](https://www.rfc-editor.org/rfc/rfc7800.html#section-3.1).
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[non_exhaustive]
+pub struct ConfirmationClaim {
+  /// The public key of the holder.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub jwk: Option<Jwk>,
+  /// An identifier of the key of the holder.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub kid: Option<String>,
+  /// Other confirmation methods.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+impl ConfirmationClaim {
+  /// Creates a new [`ConfirmationClaim`] containing the public key `jwk`.
+  pub fn from_jwk(jwk: Jwk) -> Self {
+    Self {
+      jwk: Some(jwk),
+      kid: None,
+      properties: Object::new(),
+    }
+  }
+
+  /// Creates a new [`ConfirmationClaim`] containing the key identifier `kid`.
+  pub fn from_kid(kid: impl Into<String>) -> Self {
+    Self {
+      jwk: None,
+      kid: Some(kid.into()),
+      properties: Object::new(),
+    }
+  }
+}
+
+/// (De)serializes optional timestamps as seconds since the Unix epoch.
+mod unix_timestamp {
+  use identity_core::common::Timestamp;
+  use serde::de::Error;
+  use serde::Deserialize;
+  use serde::Deserializer;
+  use serde::Serialize;
+  use serde::Serializer;
+
+  pub(super) fn serialize<S>(value: &Option<Timestamp>, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    value.map(|timestamp| timestamp.to_unix()).serialize(serializer)
+  }
+
+  pub(super) fn deserialize<'de, D>(deserializer: D) -> Result<Option<Timestamp>, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    Option::<i64>::deserialize(deserializer)?
+      .map(Timestamp::from_unix)
+      .transpose()
+      .map_err(D::Error::custom)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Timestamp;
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+  use serde_json::json;
+
+  use super::SdJwtVcClaims;
+
+  #[test]
+  fn test_roundtrip() {
+    let json = json!({
+      "iss": "https://example.com/issuer",
+      "iat": 1683000000,
+      "exp": 1883000000,
+      "vct": "https://credentials.example.com/identity_credential",
+      "cnf": {
+        "jwk": {
+          "kty": "OKP",
+          "crv": "Ed25519",
+          "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"
+        }
+      },
+      "status": {
+        "status_list": {
+          "idx": 412,
+          "uri": "https://example.com/statuslists/1"
+        }
+      },
+      "given_name": "John",
+      "address": {
+        "locality": "Anytown"
+      }
+    });
+    let claims: SdJwtVcClaims = SdJwtVcClaims::from_json_value(json.clone()).unwrap();
+
+    assert!(claims.check_structure().is_ok());
+    assert_eq!(claims.iat, Some(Timestamp::from_unix(1683000000).unwrap()));
+    assert!(claims.cnf.as_ref().unwrap().jwk.is_some());
+    assert_eq!(claims.properties.len(), 2);
+    assert_eq!(claims.to_json_value().unwrap(), json);
+  }
+}
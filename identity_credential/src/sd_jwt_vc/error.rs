@@ -0,0 +1,41 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::validator::JwtValidationError;
+use crate::validator::KeyBindingJwtError;
+
+/// An error caused by a failure to validate an SD-JWT VC.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum SdJwtVcValidationError {
+  /// Caused by a `typ` header parameter that does not identify an SD-JWT VC.
+  #[error("header `typ` value is missing or is neither `dc+sd-jwt` nor `vc+sd-jwt`")]
+  InvalidHeaderTypValue,
+  /// Caused by an issuer-signed JWT that could not be decoded or verified.
+  #[error("invalid issuer-signed JWT")]
+  JwtValidationError(#[from] JwtValidationError),
+  /// Caused by an issuer-signed JWT without an `x5c` header parameter.
+  #[error("the protected header does not contain an `x5c` certificate chain")]
+  MissingX5c,
+  /// Caused by an X.509 certificate chain that could not be verified.
+  #[error("the X.509 certificate chain could not be verified")]
+  X509ChainError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// Caused by an `iss` claim that does not identify the owner of the key used to sign the credential.
+  #[error("the `iss` claim does not match the key used to sign the credential")]
+  IssuerMismatch,
+  /// Caused by a failure to decode the disclosures.
+  #[error("failed to decode the disclosures")]
+  SdJwtError(#[from] sd_jwt_payload::Error),
+  /// Caused by a claims set that does not represent a valid SD-JWT VC.
+  #[error("invalid SD-JWT VC claims")]
+  InvalidClaims(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// Caused by an expired credential.
+  #[error("the credential has expired")]
+  Expired,
+  /// Caused by a credential whose `nbf` or `iat` claim is in the future.
+  #[error("the credential is not yet valid")]
+  NotYetValid,
+  /// Caused by an invalid key binding JWT.
+  #[error("invalid KB-JWT")]
+  KeyBindingJwtError(#[from] KeyBindingJwtError),
+}
@@ -0,0 +1,16 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Implementation of [SD-JWT-based Verifiable Credentials (SD-JWT VC)](https://www.ietf.org/archive/id/draft-ietf-oauth-sd-jwt-vc-05.html).
+
+mod builder;
+mod claims;
+mod error;
+mod validation_options;
+mod validator;
+
+pub use builder::*;
+pub use claims::*;
+pub use error::*;
+pub use validation_options::*;
+pub use validator::*;
@@ -0,0 +1,70 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+use identity_document::verifiable::JwsVerificationOptions;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::validator::KeyBindingJWTValidationOptions;
+
+/// Options to declare validation criteria for SD-JWT VCs.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+#[serde(rename_all = "camelCase")]
+pub struct SdJwtVcValidationOptions {
+  /// Declares that the credential is **not** considered valid if it expires before this [`Timestamp`].
+  /// Uses the current datetime during validation if not set.
+  #[serde(default)]
+  pub earliest_expiry_date: Option<Timestamp>,
+
+  /// Declares that the credential is **not** considered valid if its `nbf` or `iat` claim is later than this
+  /// [`Timestamp`]. Uses the current datetime during validation if not set.
+  #[serde(default)]
+  pub latest_issuance_date: Option<Timestamp>,
+
+  /// Options which affect the verification of the signature on the issuer-signed JWT if the issuer's key is resolved
+  /// from a DID document.
+  #[serde(default)]
+  pub verification_options: JwsVerificationOptions,
+
+  /// Requires a key binding JWT signed with the key in the `cnf` claim of the credential and validates it according
+  /// to these options.
+  ///
+  /// The key binding JWT is not validated if unset.
+  #[serde(default)]
+  pub key_binding: Option<KeyBindingJWTValidationOptions>,
+}
+
+impl SdJwtVcValidationOptions {
+  /// Constructor that sets all options to their defaults.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Declare that the credential is **not** considered valid if it expires before this [`Timestamp`].
+  /// Uses the current datetime during validation if not set.
+  pub fn earliest_expiry_date(mut self, timestamp: Timestamp) -> Self {
+    self.earliest_expiry_date = Some(timestamp);
+    self
+  }
+
+  /// Declare that the credential is **not** considered valid if its `nbf` or `iat` claim is later than this
+  /// [`Timestamp`]. Uses the current datetime during validation if not set.
+  pub fn latest_issuance_date(mut self, timestamp: Timestamp) -> Self {
+    self.latest_issuance_date = Some(timestamp);
+    self
+  }
+
+  /// Set options which affect the verification of the signature on the issuer-signed JWT.
+  pub fn verification_options(mut self, options: JwsVerificationOptions) -> Self {
+    self.verification_options = options;
+    self
+  }
+
+  /// Require a key binding JWT and validate it according to `options`.
+  pub fn key_binding(mut self, options: KeyBindingJWTValidationOptions) -> Self {
+    self.key_binding = Some(options);
+    self
+  }
+}
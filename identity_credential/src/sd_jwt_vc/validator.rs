@@ -0,0 +1,408 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_did::DID;
+use identity_document::document::CoreDocument;
+use identity_verification::jwk::Jwk;
+use identity_verification::jws::Decoder;
+use identity_verification::jws::JwsHeader;
+use identity_verification::jws::JwsValidationItem;
+use identity_verification::jws::JwsVerifier;
+use sd_jwt_payload::KeyBindingJwtClaims;
+use sd_jwt_payload::SdJwt;
+use sd_jwt_payload::SdObjectDecoder;
+use serde_json::Map;
+use serde_json::Value;
+
+use super::SdJwtVcClaims;
+use super::SdJwtVcValidationError;
+use super::SdJwtVcValidationOptions;
+use crate::validator::JwtCredentialValidator;
+use crate::validator::JwtValidationError;
+use crate::validator::KeyBindingJwtError;
+use crate::validator::SignerContext;
+
+/// Verifies the X.509 certificate chain an SD-JWT VC issuer references with the `x5c` header parameter.
+///
+/// This crate does not include an X.509 implementation, so verifying the chain against the trust anchors of the
+/// verifier is delegated to implementors of this trait.
+pub trait X509ChainVerifier {
+  /// Verifies the certificate chain `x5c`, given as base64 encoded DER certificates in the order of the `x5c` header
+  /// parameter, and checks that the leaf certificate was issued to `iss`, e.g. through a DNS name or URI subject
+  /// alternative name.
+  ///
+  /// Returns the public key of the leaf certificate, which is used to verify the signature of the credential.
+  fn verify_chain(&self, x5c: &[String], iss: &Url) -> Result<Jwk, Box<dyn std::error::Error + Send + Sync + 'static>>;
+}
+
+/// A validated SD-JWT VC.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct DecodedSdJwtVc {
+  /// The claims of the credential with all presented disclosures applied.
+  pub claims: SdJwtVcClaims,
+  /// The protected header of the issuer-signed JWT.
+  pub header: Box<JwsHeader>,
+  /// The claims of the key binding JWT, if it was validated.
+  pub key_binding: Option<KeyBindingJwtClaims>,
+}
+
+/// A type for decoding and validating SD-JWT VCs.
+#[non_exhaustive]
+pub struct SdJwtVcValidator<V: JwsVerifier>(V, SdObjectDecoder);
+
+impl<V: JwsVerifier> SdJwtVcValidator<V> {
+  /// Creates a new [`SdJwtVcValidator`] that delegates cryptographic signature verification to the given
+  /// `signature_verifier`.
+  pub fn with_signature_verifier(signature_verifier: V, sd_decoder: SdObjectDecoder) -> Self {
+    Self(signature_verifier, sd_decoder)
+  }
+
+  /// Decodes and validates an SD-JWT VC whose issuer key is identified by a DID URL in the `kid` header parameter.
+  ///
+  /// The key is looked up in the document among `trusted_issuers` identified by the DID of the `kid`, which must also
+  /// be the value of the `iss` claim.
+  ///
+  /// The following properties are validated according to `options`:
+  /// - the `typ` header parameter,
+  /// - the issuer's signature on the JWT,
+  /// - the disclosures,
+  /// - the `exp`, `nbf` and `iat` claims,
+  /// - the key binding JWT against the `cnf` claim, if required.
+  ///
+  /// # Errors
+  /// An error is returned whenever a validated condition is not satisfied.
+  pub fn validate<DOC>(
+    &self,
+    sd_jwt: &SdJwt,
+    trusted_issuers: &[DOC],
+    options: &SdJwtVcValidationOptions,
+  ) -> Result<DecodedSdJwtVc, SdJwtVcValidationError>
+  where
+    DOC: AsRef<CoreDocument>,
+  {
+    self.validate_with_issuer_key(sd_jwt, options, |jws, iss| {
+      let (jwk, method_id) =
+        JwtCredentialValidator::<V>::parse_jwk(jws, trusted_issuers, &options.verification_options)?;
+      if iss.as_str() != method_id.did().as_str() {
+        return Err(SdJwtVcValidationError::IssuerMismatch);
+      }
+
+      Ok(jwk.clone())
+    })
+  }
+
+  /// Decodes and validates an SD-JWT VC whose issuer key is contained in the X.509 certificate chain of the `x5c`
+  /// header parameter.
+  ///
+  /// The chain is verified with `chain_verifier`, which must also ensure that the leaf certificate belongs to the
+  /// issuer identified by the `iss` claim. Apart from that, the same properties as in [`Self::validate`] are validated.
+  ///
+  /// # Errors
+  /// An error is returned whenever a validated condition is not satisfied.
+  pub fn validate_with_x5c<C>(
+    &self,
+    sd_jwt: &SdJwt,
+    chain_verifier: &C,
+    options: &SdJwtVcValidationOptions,
+  ) -> Result<DecodedSdJwtVc, SdJwtVcValidationError>
+  where
+    C: X509ChainVerifier + ?Sized,
+  {
+    self.validate_with_issuer_key(sd_jwt, options, |jws, iss| {
+      let x5c: &[String] = jws
+        .protected_header()
+        .and_then(|header| header.x5c())
+        .ok_or(SdJwtVcValidationError::MissingX5c)?;
+
+      chain_verifier
+        .verify_chain(x5c, iss)
+        .map_err(SdJwtVcValidationError::X509ChainError)
+    })
+  }
+
+  fn validate_with_issuer_key<F>(
+    &self,
+    sd_jwt: &SdJwt,
+    options: &SdJwtVcValidationOptions,
+    resolve_issuer_key: F,
+  ) -> Result<DecodedSdJwtVc, SdJwtVcValidationError>
+  where
+    F: FnOnce(&JwsValidationItem<'_>, &Url) -> Result<Jwk, SdJwtVcValidationError>,
+  {
+    let decoded: JwsValidationItem<'_> = Decoder::new()
+      .decode_compact_serialization(sd_jwt.jwt.as_bytes(), None)
+      .map_err(JwtValidationError::JwsDecodingError)?;
+
+    let header: JwsHeader = decoded
+      .protected_header()
+      .cloned()
+      .ok_or(SdJwtVcValidationError::InvalidHeaderTypValue)?;
+    match header.typ() {
+      Some(SdJwtVcClaims::HEADER_TYP) | Some(SdJwtVcClaims::LEGACY_HEADER_TYP) => {}
+      _ => return Err(SdJwtVcValidationError::InvalidHeaderTypValue),
+    }
+
+    // The issuer is needed to resolve its key, so it is read before the signature is verified.
+    let encoded_claims: Map<String, Value> =
+      serde_json::from_slice(decoded.claims()).map_err(|err| SdJwtVcValidationError::InvalidClaims(err.into()))?;
+    let iss: Url = encoded_claims
+      .get("iss")
+      .and_then(Value::as_str)
+      .and_then(|iss| Url::parse(iss).ok())
+      .ok_or_else(|| {
+        SdJwtVcValidationError::InvalidClaims(
+          crate::Error::InvalidSdJwtVcClaims("missing or invalid `iss` claim").into(),
+        )
+      })?;
+
+    let public_key: Jwk = resolve_issuer_key(&decoded, &iss)?;
+    decoded
+      .verify(&self.0, &public_key)
+      .map_err(|source| JwtValidationError::Signature {
+        source,
+        signer_ctx: SignerContext::Issuer,
+      })?;
+
+    let disclosed_claims: Map<String, Value> = self.1.decode(&encoded_claims, &sd_jwt.disclosures)?;
+    let claims: SdJwtVcClaims = serde_json::from_value(Value::Object(disclosed_claims))
+      .map_err(|err| SdJwtVcValidationError::InvalidClaims(err.into()))?;
+    claims
+      .check_structure()
+      .map_err(|err| SdJwtVcValidationError::InvalidClaims(err.into()))?;
+
+    Self::check_timeframe(&claims, options)?;
+
+    let key_binding: Option<KeyBindingJwtClaims> = options
+      .key_binding
+      .as_ref()
+      .map(|kb_options| {
+        crate::validator::validate_key_binding_jwt(&self.0, &self.1, sd_jwt, kb_options, |_| {
+          claims.cnf.as_ref().and_then(|cnf| cnf.jwk.as_ref()).ok_or_else(|| {
+            KeyBindingJwtError::from(JwtValidationError::MethodDataLookupError {
+              source: None,
+              message: "the credential does not contain a `cnf.jwk` claim",
+              signer_ctx: SignerContext::Holder,
+            })
+          })
+        })
+      })
+      .transpose()?;
+
+    Ok(DecodedSdJwtVc {
+      claims,
+      header: Box::new(header),
+      key_binding,
+    })
+  }
+
+  fn check_timeframe(claims: &SdJwtVcClaims, options: &SdJwtVcValidationOptions) -> Result<(), SdJwtVcValidationError> {
+    let now: Timestamp = Timestamp::now_utc();
+
+    if let Some(exp) = claims.exp {
+      if exp < options.earliest_expiry_date.unwrap_or(now) {
+        return Err(SdJwtVcValidationError::Expired);
+      }
+    }
+
+    let latest_issuance_date: Timestamp = options.latest_issuance_date.unwrap_or(now);
+    if [claims.nbf, claims.iat]
+      .into_iter()
+      .flatten()
+      .any(|timestamp| timestamp > latest_issuance_date)
+    {
+      return Err(SdJwtVcValidationError::NotYetValid);
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crypto::signatures::ed25519::SecretKey;
+  use identity_core::common::Duration;
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_did::DID;
+  use identity_document::document::CoreDocument;
+  use identity_eddsa_verifier::EdDSAJwsVerifier;
+  use identity_verification::jwk::Jwk;
+  use identity_verification::jws::CharSet;
+  use identity_verification::jws::CompactJwsEncoder;
+  use identity_verification::jws::CompactJwsEncodingOptions;
+  use identity_verification::jws::JwsAlgorithm;
+  use identity_verification::jws::JwsHeader;
+  use identity_verification::MethodData;
+  use sd_jwt_payload::SdJwt;
+  use sd_jwt_payload::SdObjectDecoder;
+  use sd_jwt_payload::SdObjectEncoder;
+
+  use super::SdJwtVcValidator;
+  use super::X509ChainVerifier;
+  use crate::sd_jwt_vc::SdJwtVcClaims;
+  use crate::sd_jwt_vc::SdJwtVcValidationError;
+  use crate::sd_jwt_vc::SdJwtVcValidationOptions;
+  use crate::validator::test_utils::generate_jwk_document_with_keys;
+
+  struct StaticChainVerifier(Jwk);
+
+  impl X509ChainVerifier for StaticChainVerifier {
+    fn verify_chain(
+      &self,
+      x5c: &[String],
+      iss: &Url,
+    ) -> Result<Jwk, Box<dyn std::error::Error + Send + Sync + 'static>> {
+      if x5c.len() == 1 && iss.as_str() == "https://issuer.example.com/" {
+        Ok(self.0.clone())
+      } else {
+        Err("untrusted certificate chain".into())
+      }
+    }
+  }
+
+  fn validator() -> SdJwtVcValidator<EdDSAJwsVerifier> {
+    SdJwtVcValidator::with_signature_verifier(EdDSAJwsVerifier::default(), SdObjectDecoder::new_with_sha256())
+  }
+
+  fn public_key(document: &CoreDocument, fragment: &str) -> Jwk {
+    match document.resolve_method(fragment, None).unwrap().data() {
+      MethodData::PublicKeyJwk(jwk) => jwk.clone(),
+      _ => panic!("not a jwk"),
+    }
+  }
+
+  fn issue(claims: &SdJwtVcClaims, header: &JwsHeader, secret_key: &SecretKey) -> SdJwt {
+    let mut encoder: SdObjectEncoder = SdObjectEncoder::new(&claims.serialize_jwt().unwrap()).unwrap();
+    let disclosures: Vec<String> = vec![encoder.conceal("/given_name", None).unwrap().into_string()];
+    encoder.add_sd_alg_property();
+    let payload: String = encoder.try_to_string().unwrap();
+
+    let encoding_options: CompactJwsEncodingOptions = CompactJwsEncodingOptions::NonDetached {
+      charset_requirements: CharSet::Default,
+    };
+    let jws_encoder: CompactJwsEncoder<'_> =
+      CompactJwsEncoder::new_with_options(payload.as_bytes(), header, encoding_options).unwrap();
+    let signature: [u8; 64] = secret_key.sign(jws_encoder.signing_input()).to_bytes();
+
+    SdJwt::new(jws_encoder.into_jws(&signature), disclosures, None)
+  }
+
+  fn did_header(document: &CoreDocument, fragment: &str, typ: &str) -> JwsHeader {
+    let mut header: JwsHeader = JwsHeader::new();
+    header.set_alg(JwsAlgorithm::EdDSA);
+    header.set_typ(typ);
+    header.set_kid(document.resolve_method(fragment, None).unwrap().id().to_string());
+    header
+  }
+
+  fn claims(issuer: &str) -> SdJwtVcClaims {
+    SdJwtVcClaims::builder(Url::parse(issuer).unwrap(), "IdentityCredential")
+      .issuance_date(Timestamp::now_utc())
+      .claim("given_name", "John")
+      .claim("family_name", "Doe")
+      .build()
+      .unwrap()
+  }
+
+  #[test]
+  fn test_validate_with_did_issuer() {
+    let (document, secret_key, fragment) = generate_jwk_document_with_keys();
+    let sd_jwt: SdJwt = issue(
+      &claims(document.id().as_str()),
+      &did_header(&document, &fragment, SdJwtVcClaims::HEADER_TYP),
+      &secret_key,
+    );
+
+    let decoded = validator()
+      .validate(&sd_jwt, &[&document], &SdJwtVcValidationOptions::default())
+      .unwrap();
+    assert_eq!(decoded.claims.properties["given_name"], "John");
+    assert_eq!(decoded.claims.properties["family_name"], "Doe");
+    assert!(decoded.key_binding.is_none());
+
+    // Withholding the disclosure hides the claim.
+    let sd_jwt: SdJwt = SdJwt::new(sd_jwt.jwt, Vec::new(), None);
+    let decoded = validator()
+      .validate(&sd_jwt, &[&document], &SdJwtVcValidationOptions::default())
+      .unwrap();
+    assert!(!decoded.claims.properties.contains_key("given_name"));
+  }
+
+  #[test]
+  fn test_validate_rejects_invalid_header_typ() {
+    let (document, secret_key, fragment) = generate_jwk_document_with_keys();
+    let sd_jwt: SdJwt = issue(
+      &claims(document.id().as_str()),
+      &did_header(&document, &fragment, "JWT"),
+      &secret_key,
+    );
+
+    let result = validator().validate(&sd_jwt, &[&document], &SdJwtVcValidationOptions::default());
+    assert!(matches!(result, Err(SdJwtVcValidationError::InvalidHeaderTypValue)));
+  }
+
+  #[test]
+  fn test_validate_rejects_issuer_mismatch() {
+    let (document, secret_key, fragment) = generate_jwk_document_with_keys();
+    let sd_jwt: SdJwt = issue(
+      &claims("did:example:other"),
+      &did_header(&document, &fragment, SdJwtVcClaims::LEGACY_HEADER_TYP),
+      &secret_key,
+    );
+
+    let result = validator().validate(&sd_jwt, &[&document], &SdJwtVcValidationOptions::default());
+    assert!(matches!(result, Err(SdJwtVcValidationError::IssuerMismatch)));
+  }
+
+  #[test]
+  fn test_validate_timeframe() {
+    let (document, secret_key, fragment) = generate_jwk_document_with_keys();
+    let expiration_date: Timestamp = Timestamp::now_utc().checked_add(Duration::days(1)).unwrap();
+    let mut claims: SdJwtVcClaims = claims(document.id().as_str());
+    claims.exp = Some(expiration_date);
+    let sd_jwt: SdJwt = issue(
+      &claims,
+      &did_header(&document, &fragment, SdJwtVcClaims::HEADER_TYP),
+      &secret_key,
+    );
+
+    let options =
+      SdJwtVcValidationOptions::new().earliest_expiry_date(expiration_date.checked_add(Duration::seconds(1)).unwrap());
+    let result = validator().validate(&sd_jwt, &[&document], &options);
+    assert!(matches!(result, Err(SdJwtVcValidationError::Expired)));
+
+    let options = SdJwtVcValidationOptions::new()
+      .latest_issuance_date(Timestamp::now_utc().checked_sub(Duration::days(1)).unwrap());
+    let result = validator().validate(&sd_jwt, &[&document], &options);
+    assert!(matches!(result, Err(SdJwtVcValidationError::NotYetValid)));
+  }
+
+  #[test]
+  fn test_validate_with_x5c() {
+    let (document, secret_key, fragment) = generate_jwk_document_with_keys();
+    let chain_verifier = StaticChainVerifier(public_key(&document, &fragment));
+    let claims: SdJwtVcClaims = claims("https://issuer.example.com/");
+
+    let mut header: JwsHeader = JwsHeader::new();
+    header.set_alg(JwsAlgorithm::EdDSA);
+    header.set_typ(SdJwtVcClaims::HEADER_TYP);
+    let sd_jwt: SdJwt = issue(&claims, &header, &secret_key);
+    let result = validator().validate_with_x5c(&sd_jwt, &chain_verifier, &SdJwtVcValidationOptions::default());
+    assert!(matches!(result, Err(SdJwtVcValidationError::MissingX5c)));
+
+    header.set_x5c(vec!["MIIB".to_owned()]);
+    let sd_jwt: SdJwt = issue(&claims, &header, &secret_key);
+    let decoded = validator()
+      .validate_with_x5c(&sd_jwt, &chain_verifier, &SdJwtVcValidationOptions::default())
+      .unwrap();
+    assert_eq!(decoded.claims.iss.as_str(), "https://issuer.example.com/");
+
+    header.set_x5c(vec!["MIIB".to_owned(), "MIIC".to_owned()]);
+    let sd_jwt: SdJwt = issue(&claims, &header, &secret_key);
+    let result = validator().validate_with_x5c(&sd_jwt, &chain_verifier, &SdJwtVcValidationOptions::default());
+    assert!(matches!(result, Err(SdJwtVcValidationError::X509ChainError(_))));
+  }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::credential::Credential;
+use crate::credential::CredentialV2;
 use identity_core::common::Object;
 use identity_verification::jose::jws::JwsHeader;
 
@@ -19,3 +20,16 @@ pub struct DecodedJwtCredential<T = Object> {
   /// The custom claims parsed from the JWT.
   pub custom_claims: Option<Object>,
 }
+
+/// Decoded [`CredentialV2`] from a cryptographically verified JWS.
+///
+/// Note that having an instance of this type only means the JWS it was constructed from was verified.
+/// It does not imply anything about a potentially present proof property on the credential itself.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct DecodedJwtCredentialV2<T = Object> {
+  /// The decoded credential parsed to the [Verifiable Credentials Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/).
+  pub credential: CredentialV2<T>,
+  /// The protected header parsed from the JWS.
+  pub header: Box<JwsHeader>,
+}
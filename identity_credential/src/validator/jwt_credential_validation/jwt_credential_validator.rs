@@ -14,12 +14,16 @@ use identity_verification::jws::JwsVerifier;
 
 use super::CompoundCredentialValidationError;
 use super::DecodedJwtCredential;
+use super::DecodedJwtCredentialV2;
 use super::JwtCredentialValidationOptions;
 use super::JwtCredentialValidatorUtils;
 use super::JwtValidationError;
 use super::SignerContext;
 use crate::credential::Credential;
 use crate::credential::CredentialJwtClaims;
+use crate::credential::CredentialT;
+use crate::credential::CredentialV2;
+use crate::credential::CredentialV2JwtClaims;
 use crate::credential::Jwt;
 use crate::validator::FailFast;
 
@@ -115,6 +119,81 @@ impl<V: JwsVerifier> JwtCredentialValidator<V> {
     Self::verify_signature_with_verifier(&self.0, credential, trusted_issuers, options)
   }
 
+  /// Decodes and validates a [`CredentialV2`] issued as a JWT. A [`DecodedJwtCredentialV2`] is returned upon success.
+  ///
+  /// This is the [VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/) counterpart of [`Self::validate`],
+  /// with `validFrom` and `validUntil` taking the place of the issuance and expiration dates and every entry of
+  /// `credentialStatus` being checked. The same warnings apply.
+  ///
+  /// # Errors
+  /// An error is returned whenever a validated condition is not satisfied.
+  pub fn validate_v2<DOC, T>(
+    &self,
+    credential_jwt: &Jwt,
+    issuer: &DOC,
+    options: &JwtCredentialValidationOptions,
+    fail_fast: FailFast,
+  ) -> Result<DecodedJwtCredentialV2<T>, CompoundCredentialValidationError>
+  where
+    T: serde::de::DeserializeOwned,
+    DOC: AsRef<CoreDocument>,
+  {
+    let credential_token = self
+      .verify_signature_v2(
+        credential_jwt,
+        std::slice::from_ref(issuer.as_ref()),
+        &options.verification_options,
+      )
+      .map_err(|err| CompoundCredentialValidationError {
+        validation_errors: [err].into(),
+      })?;
+
+    Self::validate_decoded_credential_v2::<CoreDocument, T>(
+      credential_token,
+      std::slice::from_ref(issuer.as_ref()),
+      options,
+      fail_fast,
+    )
+  }
+
+  /// Decode and verify the JWS signature of a [`CredentialV2`] issued as a JWT using the DID Document of a trusted
+  /// issuer.
+  ///
+  /// This is the [VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/) counterpart of
+  /// [`Self::verify_signature`]. A [`DecodedJwtCredentialV2`] is returned upon success.
+  ///
+  /// # Errors
+  /// This method immediately returns an error if
+  /// the credential issuer' url cannot be parsed to a DID belonging to one of the trusted issuers. Otherwise an attempt
+  /// to verify the credential's signature will be made and an error is returned upon failure.
+  pub fn verify_signature_v2<DOC, T>(
+    &self,
+    credential: &Jwt,
+    trusted_issuers: &[DOC],
+    options: &JwsVerificationOptions,
+  ) -> Result<DecodedJwtCredentialV2<T>, JwtValidationError>
+  where
+    T: serde::de::DeserializeOwned,
+    DOC: AsRef<CoreDocument>,
+  {
+    let (DecodedJws { protected, claims, .. }, method_id) =
+      Self::verify_jws(&self.0, credential.as_str(), trusted_issuers, options)?;
+
+    let credential: CredentialV2<T> = CredentialV2JwtClaims::from_json_slice(&claims)
+      .map_err(|err| {
+        JwtValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
+      })?
+      .try_into_credential()
+      .map_err(JwtValidationError::CredentialStructure)?;
+
+    Self::check_issuer_matches_method(&credential, &method_id)?;
+
+    Ok(DecodedJwtCredentialV2 {
+      credential,
+      header: Box::new(protected),
+    })
+  }
+
   // This method takes a slice of issuer's instead of a single issuer in order to better accommodate presentation
   // validation. It also validates the relationship between a holder and the credential subjects when
   // `relationship_criterion` is Some.
@@ -128,31 +207,59 @@ impl<V: JwsVerifier> JwtCredentialValidator<V> {
     T: ToOwned<Owned = T> + serde::Serialize + serde::de::DeserializeOwned,
     DOC: AsRef<CoreDocument>,
   {
-    let credential: &Credential<T> = &credential_token.credential;
+    Self::validate_credential(&credential_token.credential, issuers, options, fail_fast)?;
+    Ok(credential_token)
+  }
+
+  // Version 2.0 counterpart of `validate_decoded_credential`.
+  pub(crate) fn validate_decoded_credential_v2<DOC, T>(
+    credential_token: DecodedJwtCredentialV2<T>,
+    issuers: &[DOC],
+    options: &JwtCredentialValidationOptions,
+    fail_fast: FailFast,
+  ) -> Result<DecodedJwtCredentialV2<T>, CompoundCredentialValidationError>
+  where
+    DOC: AsRef<CoreDocument>,
+  {
+    Self::validate_credential(&credential_token.credential, issuers, options, fail_fast)?;
+    Ok(credential_token)
+  }
+
+  fn validate_credential<DOC, C>(
+    credential: &C,
+    issuers: &[DOC],
+    options: &JwtCredentialValidationOptions,
+    fail_fast: FailFast,
+  ) -> Result<(), CompoundCredentialValidationError>
+  where
+    C: CredentialT,
+    DOC: AsRef<CoreDocument>,
+  {
     // Run all single concern Credential validations in turn and fail immediately if `fail_fast` is true.
 
     let expiry_date_validation = std::iter::once_with(|| {
-      JwtCredentialValidatorUtils::check_expires_on_or_after(
-        &credential_token.credential,
+      JwtCredentialValidatorUtils::check_expires_on_or_after_generic(
+        credential,
         options.earliest_expiry_date.unwrap_or_default(),
       )
     });
 
     let issuance_date_validation = std::iter::once_with(|| {
-      JwtCredentialValidatorUtils::check_issued_on_or_before(
+      JwtCredentialValidatorUtils::check_issued_on_or_before_generic(
         credential,
         options.latest_issuance_date.unwrap_or_default(),
       )
     });
 
-    let structure_validation = std::iter::once_with(|| JwtCredentialValidatorUtils::check_structure(credential));
+    let structure_validation =
+      std::iter::once_with(|| JwtCredentialValidatorUtils::check_structure_generic(credential));
 
     let subject_holder_validation = std::iter::once_with(|| {
       options
         .subject_holder_relationship
         .as_ref()
         .map(|(holder, relationship)| {
-          JwtCredentialValidatorUtils::check_subject_holder_relationship(credential, holder, *relationship)
+          JwtCredentialValidatorUtils::check_subject_holder_relationship_generic(credential, holder, *relationship)
         })
         .unwrap_or(Ok(()))
     });
@@ -165,7 +272,7 @@ impl<V: JwsVerifier> JwtCredentialValidator<V> {
     #[cfg(feature = "revocation-bitmap")]
     let validation_units_iter = {
       let revocation_validation =
-        std::iter::once_with(|| JwtCredentialValidatorUtils::check_status(credential, issuers, options.status));
+        std::iter::once_with(|| JwtCredentialValidatorUtils::check_status_generic(credential, issuers, options.status));
       validation_units_iter.chain(revocation_validation)
     };
 
@@ -176,7 +283,7 @@ impl<V: JwsVerifier> JwtCredentialValidator<V> {
     };
 
     if validation_errors.is_empty() {
-      Ok(credential_token)
+      Ok(())
     } else {
       Err(CompoundCredentialValidationError { validation_errors })
     }
@@ -252,26 +359,67 @@ impl<V: JwsVerifier> JwtCredentialValidator<V> {
     T: ToOwned<Owned = T> + serde::Serialize + serde::de::DeserializeOwned,
     DOC: AsRef<CoreDocument>,
     S: JwsVerifier,
+  {
+    let (DecodedJws { protected, claims, .. }, method_id) =
+      Self::verify_jws(signature_verifier, credential.as_str(), trusted_issuers, options)?;
+
+    let credential_claims: CredentialJwtClaims<'_, T> =
+      CredentialJwtClaims::from_json_slice(&claims).map_err(|err| {
+        JwtValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
+      })?;
+
+    let custom_claims = credential_claims.custom.clone();
+
+    // Construct the credential token containing the credential and the protected header.
+    let credential: Credential<T> = credential_claims
+      .try_into_credential()
+      .map_err(JwtValidationError::CredentialStructure)?;
+
+    Self::check_issuer_matches_method(&credential, &method_id)?;
+
+    Ok(DecodedJwtCredential {
+      credential,
+      header: Box::new(protected),
+      custom_claims,
+    })
+  }
+
+  /// Decodes `credential` and verifies its JWS signature with the method identified by `kid` in one of the
+  /// `trusted_issuers`, returning the decoded JWS together with the identifier of the method.
+  fn verify_jws<'a, DOC, S>(
+    signature_verifier: &S,
+    credential: &'a str,
+    trusted_issuers: &'a [DOC],
+    options: &JwsVerificationOptions,
+  ) -> Result<(DecodedJws<'a>, DIDUrl), JwtValidationError>
+  where
+    DOC: AsRef<CoreDocument>,
+    S: JwsVerifier,
   {
     // Note the below steps are necessary because `CoreDocument::verify_jws` decodes the JWS and then searches for a
     // method with a fragment (or full DID Url) matching `kid` in the given document. We do not want to carry out
     // that process for potentially every document in `trusted_issuers`.
 
     // Start decoding the credential
-    let decoded: JwsValidationItem<'_> = Self::decode(credential.as_str())?;
+    let decoded: JwsValidationItem<'_> = Self::decode(credential)?;
     let (public_key, method_id) = Self::parse_jwk(&decoded, trusted_issuers, options)?;
 
-    let credential_token = Self::verify_decoded_signature(decoded, public_key, signature_verifier)?;
+    let decoded_jws: DecodedJws<'_> = Self::verify_signature_raw(decoded, public_key, signature_verifier)?;
+    Ok((decoded_jws, method_id))
+  }
 
-    // Check that the DID component of the parsed `kid` does indeed correspond to the issuer in the credential before
-    // returning.
-    let issuer_id: CoreDID = JwtCredentialValidatorUtils::extract_issuer(&credential_token.credential)?;
+  /// Check that the DID component of the parsed `kid` does indeed correspond to the issuer in the credential.
+  pub(crate) fn check_issuer_matches_method<C: CredentialT>(
+    credential: &C,
+    method_id: &DIDUrl,
+  ) -> Result<(), JwtValidationError> {
+    let issuer_id: CoreDID = JwtCredentialValidatorUtils::extract_issuer_generic(credential)?;
     if &issuer_id != method_id.did() {
       return Err(JwtValidationError::IdentifierMismatch {
         signer_ctx: SignerContext::Issuer,
       });
     };
-    Ok(credential_token)
+    Ok(())
   }
 
   /// Decode the credential into a [`JwsValidationItem`].
@@ -295,45 +443,18 @@ impl<V: JwsVerifier> JwtCredentialValidator<V> {
         signer_ctx: SignerContext::Issuer,
       })
   }
-
-  /// Verify the signature using the given `public_key` and `signature_verifier`.
-  fn verify_decoded_signature<S: JwsVerifier, T>(
-    decoded: JwsValidationItem<'_>,
-    public_key: &Jwk,
-    signature_verifier: &S,
-  ) -> Result<DecodedJwtCredential<T>, JwtValidationError>
-  where
-    T: ToOwned<Owned = T> + serde::Serialize + serde::de::DeserializeOwned,
-  {
-    // Verify the JWS signature and obtain the decoded token containing the protected header and raw claims
-    let DecodedJws { protected, claims, .. } = Self::verify_signature_raw(decoded, public_key, signature_verifier)?;
-
-    let credential_claims: CredentialJwtClaims<'_, T> =
-      CredentialJwtClaims::from_json_slice(&claims).map_err(|err| {
-        JwtValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
-      })?;
-
-    let custom_claims = credential_claims.custom.clone();
-
-    // Construct the credential token containing the credential and the protected header.
-    let credential: Credential<T> = credential_claims
-      .try_into_credential()
-      .map_err(JwtValidationError::CredentialStructure)?;
-
-    Ok(DecodedJwtCredential {
-      credential,
-      header: Box::new(protected),
-      custom_claims,
-    })
-  }
 }
 
 #[cfg(test)]
 mod tests {
   use crate::credential::Subject;
+  use crate::validator::test_utils::generate_jwk_document_with_keys;
+  use crate::validator::test_utils::sign_bytes;
   use crate::validator::SubjectHolderRelationship;
   use identity_core::common::Duration;
   use identity_core::common::Url;
+  use identity_did::DID;
+  use identity_eddsa_verifier::EdDSAJwsVerifier;
   use once_cell::sync::Lazy;
 
   // All tests here are essentially adaptations of the old JwtCredentialValidator tests.
@@ -539,4 +660,126 @@ mod tests {
       assert!(JwtCredentialValidatorUtils::check_issued_on_or_before(&SIMPLE_CREDENTIAL, later_than_issuance_date).is_ok());
     }
   }
+
+  fn credential_v2(issuer: &CoreDocument, valid_until: Timestamp) -> CredentialV2 {
+    CredentialV2::builder(Object::new())
+      .id(Url::parse("http://example.edu/credentials/3732").unwrap())
+      .issuer(Url::parse(issuer.id().as_str()).unwrap())
+      .valid_from(Timestamp::parse("2010-01-01T19:23:24Z").unwrap())
+      .valid_until(valid_until)
+      .subject(Subject::with_id(
+        Url::parse("did:example:ebfeb1f712ebc6f1c276e12ec21").unwrap(),
+      ))
+      .build()
+      .unwrap()
+  }
+
+  #[test]
+  fn validate_v2() {
+    let (document, secret_key, fragment) = generate_jwk_document_with_keys();
+    let credential: CredentialV2 =
+      credential_v2(&document, Timestamp::now_utc().checked_add(Duration::days(1)).unwrap());
+    let jwt: Jwt = Jwt::new(
+      sign_bytes(
+        &document,
+        &fragment,
+        credential.serialize_jwt().unwrap().as_bytes(),
+        &secret_key,
+      )
+      .into(),
+    );
+
+    let validator = JwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default());
+    let decoded: DecodedJwtCredentialV2 = validator
+      .validate_v2(
+        &jwt,
+        &document,
+        &JwtCredentialValidationOptions::default(),
+        FailFast::FirstError,
+      )
+      .unwrap();
+    assert_eq!(decoded.credential, credential);
+
+    let issuer: CoreDID = JwtCredentialValidatorUtils::extract_issuer_from_jwt(&jwt).unwrap();
+    assert_eq!(&issuer, document.id());
+
+    // A v2.0 credential cannot be decoded as a v1.1 credential.
+    assert!(validator
+      .validate::<_, Object>(
+        &jwt,
+        &document,
+        &JwtCredentialValidationOptions::default(),
+        FailFast::FirstError,
+      )
+      .is_err());
+  }
+
+  #[test]
+  fn validate_v2_expired() {
+    let (document, secret_key, fragment) = generate_jwk_document_with_keys();
+    let credential: CredentialV2 = credential_v2(&document, Timestamp::parse("2020-01-01T19:23:24Z").unwrap());
+    let jwt: Jwt = Jwt::new(
+      sign_bytes(
+        &document,
+        &fragment,
+        credential.serialize_jwt().unwrap().as_bytes(),
+        &secret_key,
+      )
+      .into(),
+    );
+
+    let error: CompoundCredentialValidationError =
+      JwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default())
+        .validate_v2::<_, Object>(
+          &jwt,
+          &document,
+          &JwtCredentialValidationOptions::default(),
+          FailFast::AllErrors,
+        )
+        .unwrap_err();
+    assert!(matches!(
+      error.validation_errors.as_slice(),
+      [JwtValidationError::ExpirationDate]
+    ));
+  }
+
+  #[test]
+  fn check_v2_utils() {
+    let (document, _, _) = generate_jwk_document_with_keys();
+    let valid_until: Timestamp = Timestamp::parse("2030-01-01T00:00:00Z").unwrap();
+    let credential: CredentialV2 = credential_v2(&document, valid_until);
+
+    assert!(JwtCredentialValidatorUtils::check_structure_v2(&credential).is_ok());
+    assert!(JwtCredentialValidatorUtils::check_expires_on_or_after_v2(&credential, valid_until).is_ok());
+    assert!(JwtCredentialValidatorUtils::check_expires_on_or_after_v2(
+      &credential,
+      valid_until.checked_add(Duration::seconds(1)).unwrap()
+    )
+    .is_err());
+
+    let valid_from: Timestamp = credential.valid_from.unwrap();
+    assert!(JwtCredentialValidatorUtils::check_issued_on_or_before_v2(&credential, valid_from).is_ok());
+    assert!(JwtCredentialValidatorUtils::check_issued_on_or_before_v2(
+      &credential,
+      valid_from.checked_sub(Duration::seconds(1)).unwrap()
+    )
+    .is_err());
+
+    let holder: Url = Url::parse("did:example:ebfeb1f712ebc6f1c276e12ec21").unwrap();
+    assert!(JwtCredentialValidatorUtils::check_subject_holder_relationship_v2(
+      &credential,
+      &holder,
+      SubjectHolderRelationship::AlwaysSubject
+    )
+    .is_ok());
+    assert!(JwtCredentialValidatorUtils::check_subject_holder_relationship_v2(
+      &credential,
+      &Url::parse("did:example:other").unwrap(),
+      SubjectHolderRelationship::AlwaysSubject
+    )
+    .is_err());
+
+    let issuer: CoreDID = JwtCredentialValidatorUtils::extract_issuer_v2(&credential).unwrap();
+    assert_eq!(&issuer, document.id());
+  }
 }
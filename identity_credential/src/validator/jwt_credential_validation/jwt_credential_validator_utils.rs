@@ -13,10 +13,16 @@ use identity_verification::jws::Decoder;
 use super::JwtValidationError;
 use super::SignerContext;
 use crate::credential::Credential;
-use crate::credential::CredentialJwtClaims;
+use crate::credential::CredentialT;
+use crate::credential::CredentialV2;
+use crate::credential::Issuer;
 use crate::credential::Jwt;
+#[cfg(feature = "bitstring-status-list")]
+use crate::revocation::bitstring_status_list::BitstringStatusListCredential;
 #[cfg(feature = "status-list-2021")]
 use crate::revocation::status_list_2021::StatusList2021Credential;
+#[cfg(feature = "token-status-list")]
+use crate::revocation::token_status_list::StatusListToken;
 use crate::validator::SubjectHolderRelationship;
 
 /// Utility functions for verifying JWT credentials.
@@ -32,6 +38,18 @@ impl JwtCredentialValidatorUtils {
   /// # Warning
   /// This does not validate against the credential's schema nor the structure of the subject claims.
   pub fn check_structure<T>(credential: &Credential<T>) -> ValidationUnitResult {
+    Self::check_structure_generic(credential)
+  }
+
+  /// Validates the semantic structure of the [`CredentialV2`].
+  ///
+  /// # Warning
+  /// This does not validate against the credential's schema nor the structure of the subject claims.
+  pub fn check_structure_v2<T>(credential: &CredentialV2<T>) -> ValidationUnitResult {
+    Self::check_structure_generic(credential)
+  }
+
+  pub(crate) fn check_structure_generic<C: CredentialT>(credential: &C) -> ValidationUnitResult {
     credential
       .check_structure()
       .map_err(JwtValidationError::CredentialStructure)
@@ -39,7 +57,20 @@ impl JwtCredentialValidatorUtils {
 
   /// Validate that the [`Credential`] expires on or after the specified [`Timestamp`].
   pub fn check_expires_on_or_after<T>(credential: &Credential<T>, timestamp: Timestamp) -> ValidationUnitResult {
-    let expiration_date: Option<Timestamp> = credential.expiration_date;
+    Self::check_expires_on_or_after_generic(credential, timestamp)
+  }
+
+  /// Validate that the [`CredentialV2`] is valid until on or after the specified [`Timestamp`], according to its
+  /// `validUntil` property.
+  pub fn check_expires_on_or_after_v2<T>(credential: &CredentialV2<T>, timestamp: Timestamp) -> ValidationUnitResult {
+    Self::check_expires_on_or_after_generic(credential, timestamp)
+  }
+
+  pub(crate) fn check_expires_on_or_after_generic<C: CredentialT>(
+    credential: &C,
+    timestamp: Timestamp,
+  ) -> ValidationUnitResult {
+    let expiration_date: Option<Timestamp> = credential.valid_until();
     (expiration_date.is_none() || expiration_date >= Some(timestamp))
       .then_some(())
       .ok_or(JwtValidationError::ExpirationDate)
@@ -47,7 +78,23 @@ impl JwtCredentialValidatorUtils {
 
   /// Validate that the [`Credential`] is issued on or before the specified [`Timestamp`].
   pub fn check_issued_on_or_before<T>(credential: &Credential<T>, timestamp: Timestamp) -> ValidationUnitResult {
-    (credential.issuance_date <= timestamp)
+    Self::check_issued_on_or_before_generic(credential, timestamp)
+  }
+
+  /// Validate that the [`CredentialV2`] is valid from on or before the specified [`Timestamp`], according to its
+  /// `validFrom` property.
+  ///
+  /// A credential without `validFrom` always passes this check.
+  pub fn check_issued_on_or_before_v2<T>(credential: &CredentialV2<T>, timestamp: Timestamp) -> ValidationUnitResult {
+    Self::check_issued_on_or_before_generic(credential, timestamp)
+  }
+
+  pub(crate) fn check_issued_on_or_before_generic<C: CredentialT>(
+    credential: &C,
+    timestamp: Timestamp,
+  ) -> ValidationUnitResult {
+    let issuance_date: Option<Timestamp> = credential.valid_from();
+    (issuance_date.is_none() || issuance_date <= Some(timestamp))
       .then_some(())
       .ok_or(JwtValidationError::IssuanceDate)
   }
@@ -59,7 +106,25 @@ impl JwtCredentialValidatorUtils {
     holder: &Url,
     relationship: SubjectHolderRelationship,
   ) -> ValidationUnitResult {
-    let url_matches: bool = match &credential.credential_subject {
+    Self::check_subject_holder_relationship_generic(credential, holder, relationship)
+  }
+
+  /// Validate that the relationship between the `holder` and the subjects of the [`CredentialV2`] is in accordance
+  /// with `relationship`.
+  pub fn check_subject_holder_relationship_v2<T>(
+    credential: &CredentialV2<T>,
+    holder: &Url,
+    relationship: SubjectHolderRelationship,
+  ) -> ValidationUnitResult {
+    Self::check_subject_holder_relationship_generic(credential, holder, relationship)
+  }
+
+  pub(crate) fn check_subject_holder_relationship_generic<C: CredentialT>(
+    credential: &C,
+    holder: &Url,
+    relationship: SubjectHolderRelationship,
+  ) -> ValidationUnitResult {
+    let url_matches: bool = match credential.subject() {
       OneOrMany::One(ref credential_subject) => credential_subject.id.as_ref() == Some(holder),
       OneOrMany::Many(subjects) => {
         // need to check the case where the Many variant holds a vector of exactly one subject
@@ -75,9 +140,7 @@ impl JwtCredentialValidatorUtils {
     Some(relationship)
       .filter(|relationship| match relationship {
         SubjectHolderRelationship::AlwaysSubject => url_matches,
-        SubjectHolderRelationship::SubjectOnNonTransferable => {
-          url_matches || !credential.non_transferable.unwrap_or(false)
-        }
+        SubjectHolderRelationship::SubjectOnNonTransferable => url_matches || !credential.non_transferable(),
         SubjectHolderRelationship::Any => true,
       })
       .map(|_| ())
@@ -126,13 +189,144 @@ impl JwtCredentialValidatorUtils {
   }
   /// Checks whether the credential status has been revoked.
   ///
-  /// Only supports `RevocationBitmap2022`.
+  /// Only supports `RevocationBitmap2022`. Use `check_status_with_bitstring_status_lists` (feature
+  /// `bitstring-status-list`) to also check `BitstringStatusListEntry` statuses, or
+  /// `check_status_with_token_status_list` (feature `token-status-list`) for the `status` claim of tokens referencing
+  /// a Token Status List.
   #[cfg(feature = "revocation-bitmap")]
   pub fn check_status<DOC: AsRef<identity_document::document::CoreDocument>, T>(
     credential: &Credential<T>,
     trusted_issuers: &[DOC],
     status_check: crate::validator::StatusCheck,
   ) -> ValidationUnitResult {
+    Self::check_status_generic(credential, trusted_issuers, status_check)
+  }
+
+  /// Checks whether the status of the [`CredentialV2`] has been revoked.
+  ///
+  /// Only supports `RevocationBitmap2022`. For credentials with several `credentialStatus` entries, each entry is
+  /// checked in turn. Use `check_status_with_bitstring_status_lists` (feature `bitstring-status-list`) to also check
+  /// `BitstringStatusListEntry` statuses.
+  #[cfg(feature = "revocation-bitmap")]
+  pub fn check_status_v2<DOC: AsRef<identity_document::document::CoreDocument>, T>(
+    credential: &CredentialV2<T>,
+    trusted_issuers: &[DOC],
+    status_check: crate::validator::StatusCheck,
+  ) -> ValidationUnitResult {
+    Self::check_status_generic(credential, trusted_issuers, status_check)
+  }
+
+  #[cfg(feature = "revocation-bitmap")]
+  pub(crate) fn check_status_generic<DOC: AsRef<identity_document::document::CoreDocument>, C: CredentialT>(
+    credential: &C,
+    trusted_issuers: &[DOC],
+    status_check: crate::validator::StatusCheck,
+  ) -> ValidationUnitResult {
+    Self::check_status_entries(credential, trusted_issuers, status_check, |_| None)
+  }
+
+  /// Checks whether the credential status has been revoked or suspended.
+  ///
+  /// Supports `RevocationBitmap2022`, checked against the issuer's DID Document among `trusted_issuers`, and
+  /// `BitstringStatusListEntry`, checked against the matching credential among `status_list_credentials`. The status
+  /// list credentials are expected to have been retrieved and validated by the caller.
+  ///
+  /// Entries with the purpose `revocation` or `suspension` whose status is set result in
+  /// [`JwtValidationError::Revoked`] or [`JwtValidationError::Suspended`], while entries with other purposes are
+  /// informational and never fail the check.
+  #[cfg(feature = "bitstring-status-list")]
+  pub fn check_status_with_bitstring_status_lists<DOC, C>(
+    credential: &C,
+    trusted_issuers: &[DOC],
+    status_list_credentials: &[BitstringStatusListCredential],
+    status_check: crate::validator::StatusCheck,
+  ) -> ValidationUnitResult
+  where
+    DOC: AsRef<identity_document::document::CoreDocument>,
+    C: CredentialT,
+  {
+    use crate::revocation::bitstring_status_list::BitstringStatusListEntry;
+    use crate::revocation::bitstring_status_list::BitstringStatusPurpose;
+
+    Self::check_status_entries(credential, trusted_issuers, status_check, |status| {
+      if status.type_ != BitstringStatusListEntry::TYPE {
+        return None;
+      }
+
+      let result = BitstringStatusListEntry::try_from(status)
+        .map_err(|e| JwtValidationError::InvalidStatus(crate::Error::InvalidStatus(e.to_string())))
+        .and_then(|entry| {
+          let status_list_credential = status_list_credentials
+            .iter()
+            .find(|status_list| status_list.id() == Some(entry.status_list_credential()))
+            .ok_or_else(|| {
+              JwtValidationError::InvalidStatus(crate::Error::InvalidStatus(format!(
+                "no status list credential \"{}\" was provided",
+                entry.status_list_credential()
+              )))
+            })?;
+          status_list_credential
+            .entry(&entry)
+            .map_err(|e| JwtValidationError::InvalidStatus(crate::Error::InvalidStatus(e.to_string())))
+        })
+        .and_then(|entry_status| match entry_status.purpose {
+          BitstringStatusPurpose::Revocation if entry_status.is_set() => Err(JwtValidationError::Revoked),
+          BitstringStatusPurpose::Suspension if entry_status.is_set() => Err(JwtValidationError::Suspended),
+          _ => Ok(()),
+        });
+      Some(result)
+    })
+  }
+
+  /// Checks whether the `status` claim of a referenced token, e.g. an SD-JWT VC, has been set in
+  /// `status_list_token`.
+  ///
+  /// Only supports `status_list` references to a [Token Status List](https://datatracker.ietf.org/doc/html/draft-ietf-oauth-status-list-06).
+  /// The status list token is expected to have been retrieved and validated by the caller. Application specific status
+  /// values are rejected unless `status_check` is [`StatusCheck::SkipUnsupported`](crate::validator::StatusCheck).
+  #[cfg(feature = "token-status-list")]
+  pub fn check_status_with_token_status_list(
+    status: &Object,
+    status_list_token: &StatusListToken,
+    status_check: crate::validator::StatusCheck,
+  ) -> ValidationUnitResult {
+    use crate::revocation::token_status_list::StatusListReference;
+    use crate::revocation::token_status_list::TokenStatus;
+
+    if status_check == crate::validator::StatusCheck::SkipAll {
+      return Ok(());
+    }
+
+    let reference = StatusListReference::from_status_claim(status)
+      .map_err(|e| JwtValidationError::InvalidStatus(crate::Error::InvalidStatus(e.to_string())))?;
+    match status_list_token
+      .status(&reference)
+      .map_err(|e| JwtValidationError::InvalidStatus(crate::Error::InvalidStatus(e.to_string())))?
+    {
+      TokenStatus::Valid => Ok(()),
+      TokenStatus::Invalid => Err(JwtValidationError::Revoked),
+      TokenStatus::Suspended => Err(JwtValidationError::Suspended),
+      _ if status_check == crate::validator::StatusCheck::SkipUnsupported => Ok(()),
+      TokenStatus::Other(value) => Err(JwtValidationError::InvalidStatus(crate::Error::InvalidStatus(format!(
+        "unsupported status value {value:#x}"
+      )))),
+    }
+  }
+
+  /// Checks each `credentialStatus` entry of `credential`, handling `RevocationBitmap2022` entries and delegating any
+  /// other type to `check_other`, which returns `None` for unsupported types.
+  #[cfg(feature = "revocation-bitmap")]
+  fn check_status_entries<DOC, C, F>(
+    credential: &C,
+    trusted_issuers: &[DOC],
+    status_check: crate::validator::StatusCheck,
+    mut check_other: F,
+  ) -> ValidationUnitResult
+  where
+    DOC: AsRef<identity_document::document::CoreDocument>,
+    C: CredentialT,
+    F: FnMut(&crate::credential::Status) -> Option<ValidationUnitResult>,
+  {
     use identity_did::CoreDID;
     use identity_document::document::CoreDocument;
 
@@ -140,32 +334,36 @@ impl JwtCredentialValidatorUtils {
       return Ok(());
     }
 
-    match &credential.credential_status {
-      None => Ok(()),
-      Some(status) => {
-        // Check status is supported.
-        if status.type_ != crate::revocation::RevocationBitmap::TYPE {
-          if status_check == crate::validator::StatusCheck::SkipUnsupported {
-            return Ok(());
+    for status in credential.credential_status() {
+      if status.type_ != crate::revocation::RevocationBitmap::TYPE {
+        match check_other(status) {
+          Some(result) => {
+            result?;
+            continue;
+          }
+          None if status_check == crate::validator::StatusCheck::SkipUnsupported => continue,
+          None => {
+            return Err(JwtValidationError::InvalidStatus(crate::Error::InvalidStatus(format!(
+              "unsupported type '{}'",
+              status.type_
+            ))))
           }
-          return Err(JwtValidationError::InvalidStatus(crate::Error::InvalidStatus(format!(
-            "unsupported type '{}'",
-            status.type_
-          ))));
         }
-        let status: crate::credential::RevocationBitmapStatus =
-          crate::credential::RevocationBitmapStatus::try_from(status.clone())
-            .map_err(JwtValidationError::InvalidStatus)?;
-
-        // Check the credential index against the issuer's DID Document.
-        let issuer_did: CoreDID = Self::extract_issuer(credential)?;
-        trusted_issuers
-          .iter()
-          .find(|issuer| <CoreDocument>::id(issuer.as_ref()) == &issuer_did)
-          .ok_or(JwtValidationError::DocumentMismatch(SignerContext::Issuer))
-          .and_then(|issuer| Self::check_revocation_bitmap_status(issuer, status))
       }
+      let status: crate::credential::RevocationBitmapStatus =
+        crate::credential::RevocationBitmapStatus::try_from(status.clone())
+          .map_err(JwtValidationError::InvalidStatus)?;
+
+      // Check the credential index against the issuer's DID Document.
+      let issuer_did: CoreDID = Self::extract_issuer_generic(credential)?;
+      trusted_issuers
+        .iter()
+        .find(|issuer| <CoreDocument>::id(issuer.as_ref()) == &issuer_did)
+        .ok_or(JwtValidationError::DocumentMismatch(SignerContext::Issuer))
+        .and_then(|issuer| Self::check_revocation_bitmap_status(issuer, status))?;
     }
+
+    Ok(())
   }
 
   /// Check the given `status` against the matching [`RevocationBitmap`] service in the
@@ -202,7 +400,29 @@ impl JwtCredentialValidatorUtils {
     D: DID,
     <D as FromStr>::Err: std::error::Error + Send + Sync + 'static,
   {
-    D::from_str(credential.issuer.url().as_str()).map_err(|err| JwtValidationError::SignerUrl {
+    Self::extract_issuer_generic(credential)
+  }
+
+  /// Utility for extracting the issuer field of a [`CredentialV2`] as a DID.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the issuer field is not a valid DID.
+  pub fn extract_issuer_v2<D, T>(credential: &CredentialV2<T>) -> std::result::Result<D, JwtValidationError>
+  where
+    D: DID,
+    <D as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+  {
+    Self::extract_issuer_generic(credential)
+  }
+
+  pub(crate) fn extract_issuer_generic<D, C>(credential: &C) -> std::result::Result<D, JwtValidationError>
+  where
+    D: DID,
+    <D as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+    C: CredentialT,
+  {
+    D::from_str(credential.issuer().url().as_str()).map_err(|err| JwtValidationError::SignerUrl {
       signer_ctx: SignerContext::Issuer,
       source: err.into(),
     })
@@ -210,6 +430,9 @@ impl JwtCredentialValidatorUtils {
 
   /// Utility for extracting the issuer field of a credential in JWT representation as DID.
   ///
+  /// Both v1.1 and v2.0 credentials are supported: the issuer is read from the `iss` claim, falling back to the
+  /// `issuer` property of v2.0 claims sets.
+  ///
   /// # Errors
   ///
   /// If the JWT decoding fails or the issuer field is not a valid DID.
@@ -222,14 +445,193 @@ impl JwtCredentialValidatorUtils {
       .decode_compact_serialization(credential.as_str().as_bytes(), None)
       .map_err(JwtValidationError::JwsDecodingError)?;
 
-    let claims: CredentialJwtClaims<'_, Object> = CredentialJwtClaims::from_json_slice(&validation_item.claims())
-      .map_err(|err| {
-        JwtValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
+    let mut claims: Object = Object::from_json_slice(&validation_item.claims()).map_err(|err| {
+      JwtValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
+    })?;
+    let issuer: Issuer = claims
+      .remove("iss")
+      .or_else(|| claims.remove("issuer"))
+      .ok_or_else(|| {
+        JwtValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(
+          "missing issuer claim".into(),
+        ))
+      })
+      .and_then(|issuer| {
+        Issuer::from_json_value(issuer).map_err(|err| {
+          JwtValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
+        })
       })?;
 
-    D::from_str(claims.iss.url().as_str()).map_err(|err| JwtValidationError::SignerUrl {
+    D::from_str(issuer.url().as_str()).map_err(|err| JwtValidationError::SignerUrl {
       signer_ctx: SignerContext::Issuer,
       source: err.into(),
     })
   }
 }
+
+#[cfg(all(test, any(feature = "bitstring-status-list", feature = "token-status-list")))]
+mod tests {
+  use identity_core::common::Object;
+  use identity_core::common::Url;
+
+  use super::*;
+  use crate::validator::StatusCheck;
+
+  #[cfg(feature = "bitstring-status-list")]
+  mod bitstring_status_list {
+    use identity_document::document::CoreDocument;
+
+    use crate::credential::CredentialV2;
+    use crate::credential::Status;
+    use crate::credential::Subject;
+    use crate::revocation::bitstring_status_list::BitstringStatusList;
+    use crate::revocation::bitstring_status_list::BitstringStatusListCredential;
+    use crate::revocation::bitstring_status_list::BitstringStatusListCredentialBuilder;
+    use crate::revocation::bitstring_status_list::BitstringStatusListEntry;
+    use crate::revocation::bitstring_status_list::BitstringStatusPurpose;
+    use crate::revocation::bitstring_status_list::StatusMessage;
+
+    use super::*;
+
+    fn status_list_credential(
+      id: &str,
+      purpose: BitstringStatusPurpose,
+      status_size: u8,
+    ) -> BitstringStatusListCredential {
+      BitstringStatusListCredentialBuilder::new(
+        BitstringStatusList::new(BitstringStatusList::MINIMUM_LENGTH, status_size).unwrap(),
+      )
+      .id(Url::parse(id).unwrap())
+      .purpose(purpose)
+      .issuer(Url::parse("did:example:issuer").unwrap().into())
+      .build()
+      .unwrap()
+    }
+
+    fn credential(statuses: Vec<Status>) -> CredentialV2 {
+      let mut credential = CredentialV2::builder(Object::new())
+        .issuer(Url::parse("did:example:issuer").unwrap())
+        .subject(Subject::with_id(Url::parse("did:example:holder").unwrap()))
+        .build()
+        .unwrap();
+      credential.credential_status = statuses.into();
+      credential
+    }
+
+    #[test]
+    fn check_status_with_bitstring_status_lists() {
+      let mut revocation_list = status_list_credential(
+        "https://example.com/status/revocation",
+        BitstringStatusPurpose::Revocation,
+        1,
+      );
+      let mut message_list =
+        status_list_credential("https://example.com/status/message", BitstringStatusPurpose::Message, 2);
+      let revocation_entry = BitstringStatusListEntry::new(
+        revocation_list.id().unwrap().clone(),
+        BitstringStatusPurpose::Revocation,
+        42,
+        None,
+      );
+      let message_entry = BitstringStatusListEntry::new(
+        message_list.id().unwrap().clone(),
+        BitstringStatusPurpose::Message,
+        42,
+        None,
+      )
+      .status_messages(
+        (0..4)
+          .map(|value| StatusMessage::new(value, format!("message {value}")))
+          .collect(),
+      )
+      .unwrap();
+      let credential = credential(vec![revocation_entry.clone().into(), message_entry.clone().into()]);
+      let check = |status_lists: &[BitstringStatusListCredential], status_check| {
+        JwtCredentialValidatorUtils::check_status_with_bitstring_status_lists(
+          &credential,
+          &[] as &[CoreDocument],
+          status_lists,
+          status_check,
+        )
+      };
+
+      // Informational statuses never fail the check.
+      message_list.set_entry(&message_entry, 3).unwrap();
+      let status_lists = [revocation_list.clone(), message_list.clone()];
+      assert!(check(&status_lists, StatusCheck::Strict).is_ok());
+
+      // All referenced status lists must be provided.
+      assert!(matches!(
+        check(&status_lists[..1], StatusCheck::Strict),
+        Err(JwtValidationError::InvalidStatus(_))
+      ));
+
+      revocation_list.set_entry(&revocation_entry, 1).unwrap();
+      let status_lists = [revocation_list, message_list];
+      assert!(matches!(
+        check(&status_lists, StatusCheck::Strict),
+        Err(JwtValidationError::Revoked)
+      ));
+      assert!(check(&status_lists, StatusCheck::SkipAll).is_ok());
+    }
+
+    #[test]
+    fn check_status_with_bitstring_status_lists_unsupported_type() {
+      let status = Status::new(
+        Url::parse("https://example.com/status/1#0").unwrap(),
+        "UnsupportedStatus2024".to_owned(),
+      );
+      let credential = credential(vec![status]);
+
+      for (status_check, expected_ok) in [(StatusCheck::Strict, false), (StatusCheck::SkipUnsupported, true)] {
+        let result = JwtCredentialValidatorUtils::check_status_with_bitstring_status_lists(
+          &credential,
+          &[] as &[CoreDocument],
+          &[],
+          status_check,
+        );
+        assert_eq!(result.is_ok(), expected_ok);
+      }
+    }
+  }
+
+  #[cfg(feature = "token-status-list")]
+  #[test]
+  fn check_status_with_token_status_list() {
+    use crate::revocation::token_status_list::StatusListReference;
+    use crate::revocation::token_status_list::StatusListToken;
+    use crate::revocation::token_status_list::TokenStatus;
+    use crate::revocation::token_status_list::TokenStatusList;
+
+    let uri = Url::parse("https://example.com/statuslists/1").unwrap();
+    let mut status_list = TokenStatusList::new(16, 2).unwrap();
+    status_list.set(1, TokenStatus::Invalid).unwrap();
+    status_list.set(2, TokenStatus::Suspended).unwrap();
+    status_list.set(3, TokenStatus::Other(3)).unwrap();
+    let token = StatusListToken::builder(uri.clone(), &status_list).build();
+    let check = |idx: usize, status_check| {
+      let status: Object = StatusListReference::new(uri.clone(), idx).to_status_claim();
+      JwtCredentialValidatorUtils::check_status_with_token_status_list(&status, &token, status_check)
+    };
+
+    assert!(check(0, StatusCheck::Strict).is_ok());
+    assert!(matches!(
+      check(1, StatusCheck::Strict),
+      Err(JwtValidationError::Revoked)
+    ));
+    assert!(matches!(
+      check(2, StatusCheck::Strict),
+      Err(JwtValidationError::Suspended)
+    ));
+    assert!(matches!(
+      check(3, StatusCheck::Strict),
+      Err(JwtValidationError::InvalidStatus(_))
+    ));
+    assert!(check(3, StatusCheck::SkipUnsupported).is_ok());
+    assert!(check(1, StatusCheck::SkipAll).is_ok());
+    assert!(matches!(
+      check(16, StatusCheck::Strict),
+      Err(JwtValidationError::InvalidStatus(_))
+    ));
+  }
+}
@@ -7,9 +7,13 @@ mod error;
 mod jwt_credential_validation_options;
 mod jwt_credential_validator;
 mod jwt_credential_validator_utils;
+#[cfg(feature = "status-list-2021")]
+mod status_list_2021_checker;
 
 pub use decoded_jwt_credential::*;
 pub use error::*;
 pub use jwt_credential_validation_options::*;
 pub use jwt_credential_validator::*;
 pub use jwt_credential_validator_utils::*;
+#[cfg(feature = "status-list-2021")]
+pub use status_list_2021_checker::*;
@@ -0,0 +1,626 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use identity_core::common::Duration;
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_did::CoreDID;
+use identity_document::document::CoreDocument;
+use identity_verification::jws::JwsVerifier;
+
+use super::CompoundCredentialValidationError;
+use super::JwtCredentialValidationOptions;
+use super::JwtCredentialValidator;
+use super::JwtCredentialValidatorUtils;
+use super::JwtValidationError;
+use crate::credential::Credential;
+use crate::credential::Jwt;
+use crate::revocation::status_list_2021::CredentialStatus;
+use crate::revocation::status_list_2021::StatusList2021Credential;
+use crate::revocation::status_list_2021::StatusList2021CredentialError;
+use crate::revocation::status_list_2021::StatusList2021Entry;
+use crate::revocation::status_list_2021::StatusPurpose;
+use crate::validator::FailFast;
+use crate::validator::StatusCheck;
+
+/// Fetches the [`StatusList2021Credential`]s referenced by the `statusListCredential` property of
+/// `StatusList2021Entry` statuses.
+///
+/// A fetcher is passed to a [`StatusList2021Checker`], which allows replacing the HTTP client, e.g. with a local stub
+/// in tests.
+#[async_trait::async_trait]
+pub trait StatusListFetcher {
+  /// Fetches the status list credential at `url` in JWT representation.
+  async fn fetch(&self, url: &Url) -> Result<Jwt, Box<dyn std::error::Error + Send + Sync + 'static>>;
+}
+
+/// Resolves the DID Documents of credential issuers.
+///
+/// With the `status-list-2021` feature of `identity_resolver`, this trait is implemented by its `Resolver`.
+#[async_trait::async_trait]
+pub trait IssuerResolver {
+  /// The type of the resolved DID Documents.
+  type Document: AsRef<CoreDocument> + Send + Sync;
+
+  /// Resolves the DID Document of `issuer`.
+  async fn resolve_issuer(
+    &self,
+    issuer: &CoreDID,
+  ) -> Result<Self::Document, Box<dyn std::error::Error + Send + Sync + 'static>>;
+}
+
+/// Options for a [`StatusList2021Checker`].
+#[non_exhaustive]
+#[derive(Clone)]
+pub struct StatusList2021CheckerOptions {
+  /// Options for validating the fetched status list credentials.
+  ///
+  /// Default: [`JwtCredentialValidationOptions::default`].
+  pub validation_options: JwtCredentialValidationOptions,
+
+  /// How long a validated status list credential is served from the cache at most.
+  ///
+  /// A status list credential is never served from the cache after its `expirationDate`. Default: 5 minutes.
+  pub max_cache_ttl: Duration,
+
+  /// Whether a status list credential must be issued by the issuer of the credential whose status it holds.
+  ///
+  /// Default: `true`.
+  pub require_matching_issuer: bool,
+}
+
+impl StatusList2021CheckerOptions {
+  /// Creates a new [`StatusList2021CheckerOptions`] with default values.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Replace the value of the `validation_options` field.
+  pub fn validation_options(mut self, value: JwtCredentialValidationOptions) -> Self {
+    self.validation_options = value;
+    self
+  }
+
+  /// Replace the value of the `max_cache_ttl` field.
+  pub fn max_cache_ttl(mut self, value: Duration) -> Self {
+    self.max_cache_ttl = value;
+    self
+  }
+
+  /// Replace the value of the `require_matching_issuer` field.
+  pub fn require_matching_issuer(mut self, value: bool) -> Self {
+    self.require_matching_issuer = value;
+    self
+  }
+}
+
+impl Default for StatusList2021CheckerOptions {
+  fn default() -> Self {
+    Self {
+      validation_options: JwtCredentialValidationOptions::default(),
+      max_cache_ttl: Duration::minutes(5),
+      require_matching_issuer: true,
+    }
+  }
+}
+
+impl std::fmt::Debug for StatusList2021CheckerOptions {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    // `Duration` does not implement `Debug`.
+    f.debug_struct("StatusList2021CheckerOptions")
+      .field("validation_options", &self.validation_options)
+      .field("require_matching_issuer", &self.require_matching_issuer)
+      .finish_non_exhaustive()
+  }
+}
+
+/// Reasons for which the status of a credential could not be determined by a [`StatusList2021Checker`].
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum StatusList2021CheckError {
+  /// The `credentialStatus` is not a `StatusList2021Entry`.
+  #[error("unsupported credential status type `{0}`")]
+  UnsupportedStatus(String),
+  /// The `credentialStatus` is an invalid `StatusList2021Entry`.
+  #[error("invalid StatusList2021Entry")]
+  InvalidEntry(#[source] serde_json::Error),
+  /// The status list credential could not be fetched.
+  #[error("failed to fetch the status list credential")]
+  FetchError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// The DID Document of the status list credential's issuer could not be resolved.
+  #[error("failed to resolve the issuer of the status list credential")]
+  IssuerResolutionError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// The fetched status list credential is not valid.
+  #[error("the status list credential is not valid")]
+  InvalidCredential(#[source] CompoundCredentialValidationError),
+  /// The fetched credential is not a well-formed [`StatusList2021Credential`].
+  #[error("the fetched credential is not a StatusList2021Credential")]
+  InvalidStatusList(#[source] StatusList2021CredentialError),
+  /// The fetched status list credential is not the one referenced by the entry.
+  #[error("the fetched status list credential does not match the referenced url `{0}`")]
+  StatusListMismatch(Url),
+  /// The purpose of the status list differs from the purpose of the entry.
+  #[error("the status list's purpose `{found}` does not match the entry's purpose `{expected}`")]
+  PurposeMismatch {
+    /// The purpose of the entry.
+    expected: StatusPurpose,
+    /// The purpose of the status list.
+    found: StatusPurpose,
+  },
+  /// The status list credential was not issued by the issuer of the credential.
+  #[error("the status list credential was not issued by the credential's issuer")]
+  IssuerMismatch,
+}
+
+/// The status of a credential determined by a [`StatusList2021Checker`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum StatusList2021CheckOutcome {
+  /// The credential has no status or its status is not set.
+  Valid,
+  /// The credential has been revoked, as recorded by the given entry.
+  Revoked(StatusList2021Entry),
+  /// The credential has been suspended, as recorded by the given entry.
+  Suspended(StatusList2021Entry),
+  /// The status of the credential could not be determined.
+  Unknown(StatusList2021CheckError),
+}
+
+struct CacheEntry {
+  credential: StatusList2021Credential,
+  expires: Timestamp,
+}
+
+/// Checks the `StatusList2021Entry` status of credentials.
+///
+/// The referenced [`StatusList2021Credential`]s are retrieved with a [`StatusListFetcher`] and validated against the
+/// DID Document of their issuer obtained from an [`IssuerResolver`]. Validated status list credentials are cached until
+/// their `expirationDate`, but at most for [`StatusList2021CheckerOptions::max_cache_ttl`].
+pub struct StatusList2021Checker<F, R, V: JwsVerifier> {
+  fetcher: F,
+  resolver: R,
+  validator: JwtCredentialValidator<V>,
+  options: StatusList2021CheckerOptions,
+  cache: Mutex<HashMap<Url, CacheEntry>>,
+}
+
+impl<F, R, V> StatusList2021Checker<F, R, V>
+where
+  F: StatusListFetcher,
+  R: IssuerResolver,
+  V: JwsVerifier,
+{
+  /// Creates a new [`StatusList2021Checker`] that fetches status list credentials with `fetcher` and verifies their
+  /// signatures with `signature_verifier`, using the DID Documents resolved by `resolver`.
+  pub fn new(fetcher: F, resolver: R, signature_verifier: V, options: StatusList2021CheckerOptions) -> Self {
+    Self {
+      fetcher,
+      resolver,
+      validator: JwtCredentialValidator::with_signature_verifier(signature_verifier),
+      options,
+      cache: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Determines the status of `credential`.
+  ///
+  /// Only a `credentialStatus` of type `StatusList2021Entry` is supported, any other type results in
+  /// [`StatusList2021CheckOutcome::Unknown`].
+  pub async fn status<T>(&self, credential: &Credential<T>) -> StatusList2021CheckOutcome {
+    let Some(status) = credential.credential_status.as_ref() else {
+      return StatusList2021CheckOutcome::Valid;
+    };
+    if status.type_ != StatusList2021Entry::TYPE {
+      return StatusList2021CheckOutcome::Unknown(StatusList2021CheckError::UnsupportedStatus(status.type_.clone()));
+    }
+    let entry: StatusList2021Entry = match StatusList2021Entry::try_from(status) {
+      Ok(entry) => entry,
+      Err(err) => return StatusList2021CheckOutcome::Unknown(StatusList2021CheckError::InvalidEntry(err)),
+    };
+
+    match self.entry_status(credential, &entry).await {
+      Ok(CredentialStatus::Revoked) => StatusList2021CheckOutcome::Revoked(entry),
+      Ok(CredentialStatus::Suspended) => StatusList2021CheckOutcome::Suspended(entry),
+      Ok(CredentialStatus::Valid) => StatusList2021CheckOutcome::Valid,
+      Err(err) => StatusList2021CheckOutcome::Unknown(err),
+    }
+  }
+
+  /// Checks whether `credential` has been revoked or suspended, according to `status_check`.
+  ///
+  /// With [`StatusCheck::SkipUnsupported`] statuses that are not a `StatusList2021Entry` are ignored, while any other
+  /// failure to determine the status results in [`JwtValidationError::InvalidStatus`].
+  pub async fn check_status<T>(
+    &self,
+    credential: &Credential<T>,
+    status_check: StatusCheck,
+  ) -> Result<(), JwtValidationError> {
+    if status_check == StatusCheck::SkipAll {
+      return Ok(());
+    }
+
+    match self.status(credential).await {
+      StatusList2021CheckOutcome::Valid => Ok(()),
+      StatusList2021CheckOutcome::Revoked(_) => Err(JwtValidationError::Revoked),
+      StatusList2021CheckOutcome::Suspended(_) => Err(JwtValidationError::Suspended),
+      StatusList2021CheckOutcome::Unknown(StatusList2021CheckError::UnsupportedStatus(_))
+        if status_check == StatusCheck::SkipUnsupported =>
+      {
+        Ok(())
+      }
+      StatusList2021CheckOutcome::Unknown(err) => Err(JwtValidationError::InvalidStatus(crate::Error::InvalidStatus(
+        error_chain(&err),
+      ))),
+    }
+  }
+
+  /// Returns the validated [`StatusList2021Credential`] at `url`, served from the cache if possible.
+  pub async fn status_list_credential(&self, url: &Url) -> Result<StatusList2021Credential, StatusList2021CheckError> {
+    let now: Timestamp = Timestamp::now_utc();
+    if let Some(entry) = self.lock_cache().get(url).filter(|entry| entry.expires > now) {
+      return Ok(entry.credential.clone());
+    }
+
+    let jwt: Jwt = self
+      .fetcher
+      .fetch(url)
+      .await
+      .map_err(StatusList2021CheckError::FetchError)?;
+    let issuer: CoreDID = JwtCredentialValidatorUtils::extract_issuer_from_jwt(&jwt).map_err(|err| {
+      StatusList2021CheckError::InvalidCredential(CompoundCredentialValidationError {
+        validation_errors: vec![err],
+      })
+    })?;
+    let issuer_document: R::Document = self
+      .resolver
+      .resolve_issuer(&issuer)
+      .await
+      .map_err(StatusList2021CheckError::IssuerResolutionError)?;
+    let credential: Credential = self
+      .validator
+      .validate::<_, Object>(
+        &jwt,
+        &issuer_document,
+        &self.options.validation_options,
+        FailFast::FirstError,
+      )
+      .map_err(StatusList2021CheckError::InvalidCredential)?
+      .credential;
+    let status_list_credential: StatusList2021Credential =
+      StatusList2021Credential::try_from(credential).map_err(StatusList2021CheckError::InvalidStatusList)?;
+    if status_list_credential.id.as_ref() != Some(url) {
+      return Err(StatusList2021CheckError::StatusListMismatch(url.clone()));
+    }
+
+    let max_expires: Timestamp = now.checked_add(self.options.max_cache_ttl).unwrap_or(now);
+    let expires: Timestamp = match status_list_credential.expiration_date {
+      Some(expiration_date) if expiration_date < max_expires => expiration_date,
+      _ => max_expires,
+    };
+    let mut cache = self.lock_cache();
+    cache.retain(|_, entry| entry.expires > now);
+    cache.insert(
+      url.clone(),
+      CacheEntry {
+        credential: status_list_credential.clone(),
+        expires,
+      },
+    );
+
+    Ok(status_list_credential)
+  }
+
+  /// Removes all status list credentials from the cache.
+  pub fn clear_cache(&self) {
+    self.lock_cache().clear();
+  }
+
+  async fn entry_status<T>(
+    &self,
+    credential: &Credential<T>,
+    entry: &StatusList2021Entry,
+  ) -> Result<CredentialStatus, StatusList2021CheckError> {
+    let status_list_credential: StatusList2021Credential =
+      self.status_list_credential(entry.status_list_credential()).await?;
+    if status_list_credential.purpose() != entry.purpose() {
+      return Err(StatusList2021CheckError::PurposeMismatch {
+        expected: entry.purpose(),
+        found: status_list_credential.purpose(),
+      });
+    }
+    if self.options.require_matching_issuer && status_list_credential.issuer.url() != credential.issuer.url() {
+      return Err(StatusList2021CheckError::IssuerMismatch);
+    }
+
+    status_list_credential
+      .entry(entry.index())
+      .map_err(StatusList2021CheckError::InvalidStatusList)
+  }
+
+  fn lock_cache(&self) -> MutexGuard<'_, HashMap<Url, CacheEntry>> {
+    // A panic while holding the lock cannot leave the map in an inconsistent state.
+    self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+/// Formats `err` together with its sources.
+fn error_chain(err: &dyn std::error::Error) -> String {
+  let mut message: String = err.to_string();
+  let mut source: Option<&dyn std::error::Error> = err.source();
+  while let Some(err) = source {
+    message.push_str(": ");
+    message.push_str(&err.to_string());
+    source = err.source();
+  }
+  message
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::atomic::AtomicUsize;
+  use std::sync::atomic::Ordering;
+  use std::sync::Arc;
+
+  use identity_did::DID;
+  use identity_eddsa_verifier::EdDSAJwsVerifier;
+
+  use super::*;
+  use crate::credential::CredentialBuilder;
+  use crate::credential::Subject;
+  use crate::revocation::status_list_2021::StatusList2021;
+  use crate::revocation::status_list_2021::StatusList2021CredentialBuilder;
+  use crate::validator::test_utils::generate_jwk_document_with_keys;
+  use crate::validator::test_utils::sign_bytes;
+
+  const STATUS_LIST_URL: &str = "https://example.com/credentials/status/3";
+
+  #[derive(Default, Clone)]
+  struct StubFetcher {
+    status_lists: Arc<Mutex<HashMap<Url, Jwt>>>,
+    fetches: Arc<AtomicUsize>,
+  }
+
+  #[async_trait::async_trait]
+  impl StatusListFetcher for StubFetcher {
+    async fn fetch(&self, url: &Url) -> Result<Jwt, Box<dyn std::error::Error + Send + Sync + 'static>> {
+      self.fetches.fetch_add(1, Ordering::SeqCst);
+      self
+        .status_lists
+        .lock()
+        .unwrap()
+        .get(url)
+        .cloned()
+        .ok_or_else(|| format!("no status list at {url}").into())
+    }
+  }
+
+  struct StubResolver(CoreDocument);
+
+  #[async_trait::async_trait]
+  impl IssuerResolver for StubResolver {
+    type Document = CoreDocument;
+
+    async fn resolve_issuer(
+      &self,
+      issuer: &CoreDID,
+    ) -> Result<CoreDocument, Box<dyn std::error::Error + Send + Sync + 'static>> {
+      if issuer == self.0.id() {
+        Ok(self.0.clone())
+      } else {
+        Err(format!("unknown issuer {issuer}").into())
+      }
+    }
+  }
+
+  struct Setup {
+    checker: StatusList2021Checker<StubFetcher, StubResolver, EdDSAJwsVerifier>,
+    fetcher: StubFetcher,
+    issuer: CoreDocument,
+    publish: Box<dyn Fn(&StatusList2021Credential)>,
+  }
+
+  fn setup(options: StatusList2021CheckerOptions) -> Setup {
+    let (issuer, secret_key, fragment) = generate_jwk_document_with_keys();
+    let fetcher = StubFetcher::default();
+    let publish = {
+      let (issuer, fetcher) = (issuer.clone(), fetcher.clone());
+      Box::new(move |status_list_credential: &StatusList2021Credential| {
+        let payload: String = status_list_credential.clone().into_inner().serialize_jwt(None).unwrap();
+        let jwt = Jwt::new(sign_bytes(&issuer, &fragment, payload.as_bytes(), &secret_key).into());
+        let url: Url = status_list_credential.id.clone().unwrap();
+        fetcher.status_lists.lock().unwrap().insert(url, jwt);
+      })
+    };
+    let checker = StatusList2021Checker::new(
+      fetcher.clone(),
+      StubResolver(issuer.clone()),
+      EdDSAJwsVerifier::default(),
+      options,
+    );
+
+    Setup {
+      checker,
+      fetcher,
+      issuer,
+      publish,
+    }
+  }
+
+  fn status_list_credential(issuer: &CoreDocument, purpose: StatusPurpose) -> StatusList2021Credential {
+    StatusList2021CredentialBuilder::new(StatusList2021::default())
+      .issuer(Url::parse(issuer.id().as_str()).unwrap().into())
+      .purpose(purpose)
+      .subject_id(Url::parse(STATUS_LIST_URL).unwrap())
+      .build()
+      .unwrap()
+  }
+
+  fn credential(issuer: &str) -> Credential {
+    CredentialBuilder::default()
+      .issuer(Url::parse(issuer).unwrap())
+      .subject(Subject::with_id(Url::parse("did:example:holder").unwrap()))
+      .build()
+      .unwrap()
+  }
+
+  #[tokio::test]
+  async fn status_is_read_from_the_cached_status_list() {
+    let Setup {
+      checker,
+      fetcher,
+      issuer,
+      publish,
+    } = setup(StatusList2021CheckerOptions::default());
+    let mut status_list_credential = status_list_credential(&issuer, StatusPurpose::Revocation);
+    let mut credential = credential(issuer.id().as_str());
+    let entry = status_list_credential
+      .set_credential_status(&mut credential, 42, false)
+      .unwrap();
+    publish(&status_list_credential);
+
+    assert!(matches!(
+      checker.status(&credential).await,
+      StatusList2021CheckOutcome::Valid
+    ));
+    assert!(checker.check_status(&credential, StatusCheck::Strict).await.is_ok());
+    assert_eq!(fetcher.fetches.load(Ordering::SeqCst), 1);
+
+    // The revocation only becomes visible once the cached status list is dropped.
+    status_list_credential
+      .update(|status_list| status_list.set_entry(42, true))
+      .unwrap();
+    publish(&status_list_credential);
+    assert!(matches!(
+      checker.status(&credential).await,
+      StatusList2021CheckOutcome::Valid
+    ));
+
+    checker.clear_cache();
+    assert!(matches!(
+      checker.status(&credential).await,
+      StatusList2021CheckOutcome::Revoked(revoked) if revoked == entry
+    ));
+    assert!(matches!(
+      checker.check_status(&credential, StatusCheck::Strict).await,
+      Err(JwtValidationError::Revoked)
+    ));
+    assert!(checker.check_status(&credential, StatusCheck::SkipAll).await.is_ok());
+    assert_eq!(fetcher.fetches.load(Ordering::SeqCst), 2);
+  }
+
+  #[tokio::test]
+  async fn cache_ttl_is_respected() {
+    let Setup {
+      checker,
+      fetcher,
+      issuer,
+      publish,
+    } = setup(StatusList2021CheckerOptions::new().max_cache_ttl(Duration::seconds(0)));
+    let mut status_list_credential = status_list_credential(&issuer, StatusPurpose::Suspension);
+    let mut credential = credential(issuer.id().as_str());
+    status_list_credential
+      .set_credential_status(&mut credential, 7, true)
+      .unwrap();
+    publish(&status_list_credential);
+
+    assert!(matches!(
+      checker.status(&credential).await,
+      StatusList2021CheckOutcome::Suspended(_)
+    ));
+    assert!(matches!(
+      checker.check_status(&credential, StatusCheck::Strict).await,
+      Err(JwtValidationError::Suspended)
+    ));
+    assert_eq!(fetcher.fetches.load(Ordering::SeqCst), 2);
+  }
+
+  #[tokio::test]
+  async fn unknown_status_reports_reason() {
+    let Setup {
+      checker,
+      issuer,
+      publish,
+      ..
+    } = setup(StatusList2021CheckerOptions::default());
+    let mut status_list_credential = status_list_credential(&issuer, StatusPurpose::Suspension);
+    let mut credential = credential(issuer.id().as_str());
+    status_list_credential
+      .set_credential_status(&mut credential, 0, false)
+      .unwrap();
+
+    // The status list has not been published yet.
+    assert!(matches!(
+      checker.status(&credential).await,
+      StatusList2021CheckOutcome::Unknown(StatusList2021CheckError::FetchError(_))
+    ));
+    assert!(matches!(
+      checker.check_status(&credential, StatusCheck::SkipUnsupported).await,
+      Err(JwtValidationError::InvalidStatus(_))
+    ));
+
+    publish(&status_list_credential);
+    let url = Url::parse(STATUS_LIST_URL).unwrap();
+    credential.credential_status = Some(StatusList2021Entry::new(url, StatusPurpose::Revocation, 0, None).into());
+    assert!(matches!(
+      checker.status(&credential).await,
+      StatusList2021CheckOutcome::Unknown(StatusList2021CheckError::PurposeMismatch {
+        expected: StatusPurpose::Revocation,
+        found: StatusPurpose::Suspension,
+      })
+    ));
+
+    let mut status = credential.credential_status.clone().unwrap();
+    status.type_ = "RevocationBitmap2022".to_owned();
+    credential.credential_status = Some(status);
+    assert!(matches!(
+      checker.status(&credential).await,
+      StatusList2021CheckOutcome::Unknown(StatusList2021CheckError::UnsupportedStatus(_))
+    ));
+    assert!(matches!(
+      checker.check_status(&credential, StatusCheck::Strict).await,
+      Err(JwtValidationError::InvalidStatus(_))
+    ));
+    assert!(checker
+      .check_status(&credential, StatusCheck::SkipUnsupported)
+      .await
+      .is_ok());
+  }
+
+  #[tokio::test]
+  async fn status_list_issuer_must_match() {
+    let Setup {
+      checker,
+      fetcher,
+      issuer,
+      publish,
+    } = setup(StatusList2021CheckerOptions::default());
+    let mut status_list_credential = status_list_credential(&issuer, StatusPurpose::Revocation);
+    let mut credential = credential("did:example:other");
+    status_list_credential
+      .set_credential_status(&mut credential, 0, false)
+      .unwrap();
+    publish(&status_list_credential);
+
+    assert!(matches!(
+      checker.status(&credential).await,
+      StatusList2021CheckOutcome::Unknown(StatusList2021CheckError::IssuerMismatch)
+    ));
+
+    let checker = StatusList2021Checker::new(
+      fetcher,
+      StubResolver(issuer),
+      EdDSAJwsVerifier::default(),
+      StatusList2021CheckerOptions::new().require_matching_issuer(false),
+    );
+    assert!(matches!(
+      checker.status(&credential).await,
+      StatusList2021CheckOutcome::Valid
+    ));
+  }
+}
@@ -7,6 +7,7 @@ use identity_core::common::Url;
 use identity_verification::jws::JwsHeader;
 
 use crate::presentation::Presentation;
+use crate::presentation::PresentationV2;
 
 /// Decoded [`Presentation`] from a cryptographically verified JWS.
 ///
@@ -28,3 +29,22 @@ pub struct DecodedJwtPresentation<CRED, T = Object> {
   /// The custom claims parsed from the JWT.
   pub custom_claims: Option<Object>,
 }
+
+/// Decoded [`PresentationV2`] from a cryptographically verified JWS.
+///
+/// Note that having an instance of this type only means the JWS it was constructed from was verified.
+/// It does not imply anything about a potentially present proof property on the presentation itself.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct DecodedJwtPresentationV2<CRED, T = Object> {
+  /// The decoded presentation parsed to the [Verifiable Credentials Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/).
+  pub presentation: PresentationV2<CRED, T>,
+  /// The protected header parsed from the JWS.
+  pub header: Box<JwsHeader>,
+  /// The expiration date parsed from the JWT claims.
+  pub expiration_date: Option<Timestamp>,
+  /// The issuance date parsed from the JWT claims.
+  pub issuance_date: Option<Timestamp>,
+  /// The `aud` property parsed from the JWT claims.
+  pub aud: Option<Url>,
+}
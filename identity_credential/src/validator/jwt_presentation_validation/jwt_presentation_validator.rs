@@ -11,14 +11,18 @@ use identity_verification::jws::DecodedJws;
 use identity_verification::jws::JwsVerifier;
 use std::str::FromStr;
 
+use crate::credential::IssuanceDateClaims;
 use crate::credential::Jwt;
 use crate::presentation::Presentation;
 use crate::presentation::PresentationJwtClaims;
+use crate::presentation::PresentationV2;
+use crate::presentation::PresentationV2JwtClaims;
 use crate::validator::jwt_credential_validation::JwtValidationError;
 use crate::validator::jwt_credential_validation::SignerContext;
 
 use super::CompoundJwtPresentationValidationError;
 use super::DecodedJwtPresentation;
+use super::DecodedJwtPresentationV2;
 use super::JwtPresentationValidationOptions;
 
 /// Struct for validating [`Presentation`].
@@ -71,8 +75,98 @@ where
     T: ToOwned<Owned = T> + serde::Serialize + serde::de::DeserializeOwned,
     CRED: ToOwned<Owned = CRED> + serde::Serialize + serde::de::DeserializeOwned + Clone,
   {
-    // Verify JWS.
-    let decoded_jws: DecodedJws<'_> = holder
+    let decoded_jws: DecodedJws<'_> = self.verify_jws(presentation, holder, options)?;
+
+    let claims: PresentationJwtClaims<'_, CRED, T> = PresentationJwtClaims::from_json_slice(&decoded_jws.claims)
+      .map_err(|err| {
+        CompoundJwtPresentationValidationError::one_presentation_error(JwtValidationError::PresentationStructure(
+          crate::Error::JwtClaimsSetDeserializationError(err.into()),
+        ))
+      })?;
+
+    // Verify that holder document matches holder in presentation.
+    Self::check_holder(claims.iss.as_ref(), holder)?;
+
+    let (expiration_date, issuance_date) = Self::check_dates(claims.exp, claims.issuance_date, options)?;
+
+    let aud: Option<Url> = claims.aud.clone();
+    let custom_claims: Option<Object> = claims.custom.clone();
+
+    let presentation: Presentation<CRED, T> = claims.try_into_presentation().map_err(|err| {
+      CompoundJwtPresentationValidationError::one_presentation_error(JwtValidationError::PresentationStructure(err))
+    })?;
+
+    let decoded_jwt_presentation: DecodedJwtPresentation<CRED, T> = DecodedJwtPresentation {
+      presentation,
+      header: Box::new(decoded_jws.protected),
+      expiration_date,
+      issuance_date,
+      aud,
+      custom_claims,
+    };
+
+    Ok(decoded_jwt_presentation)
+  }
+
+  /// Validates a [`PresentationV2`].
+  ///
+  /// This is the [VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/) counterpart of [`Self::validate`].
+  /// The same properties are validated and the same warnings apply. Credentials secured as JWTs are usually embedded
+  /// as [`EnvelopedVerifiableCredential`](crate::credential::EnvelopedVerifiableCredential)s, which is the default
+  /// for `CRED`.
+  ///
+  /// # Errors
+  ///
+  /// An error is returned whenever a validated condition is not satisfied or when decoding fails.
+  pub fn validate_v2<HDOC, CRED, T>(
+    &self,
+    presentation: &Jwt,
+    holder: &HDOC,
+    options: &JwtPresentationValidationOptions,
+  ) -> Result<DecodedJwtPresentationV2<CRED, T>, CompoundJwtPresentationValidationError>
+  where
+    HDOC: AsRef<CoreDocument> + ?Sized,
+    T: serde::de::DeserializeOwned,
+    CRED: serde::de::DeserializeOwned,
+  {
+    let decoded_jws: DecodedJws<'_> = self.verify_jws(presentation, holder, options)?;
+
+    let claims: PresentationV2JwtClaims<'_> =
+      PresentationV2JwtClaims::from_json_slice(&decoded_jws.claims).map_err(|err| {
+        CompoundJwtPresentationValidationError::one_presentation_error(JwtValidationError::PresentationStructure(
+          crate::Error::JwtClaimsSetDeserializationError(err.into()),
+        ))
+      })?;
+
+    let (expiration_date, issuance_date) = Self::check_dates(claims.exp, Some(claims.issuance_date), options)?;
+    let aud: Option<Url> = claims.aud.clone();
+
+    let presentation: PresentationV2<CRED, T> = claims.try_into_presentation().map_err(|err| {
+      CompoundJwtPresentationValidationError::one_presentation_error(JwtValidationError::PresentationStructure(err))
+    })?;
+
+    // Verify that holder document matches holder in presentation.
+    Self::check_holder(&presentation.holder, holder)?;
+
+    Ok(DecodedJwtPresentationV2 {
+      presentation,
+      header: Box::new(decoded_jws.protected),
+      expiration_date,
+      issuance_date,
+      aud,
+    })
+  }
+
+  fn verify_jws<'jwt, HDOC>(
+    &self,
+    presentation: &'jwt Jwt,
+    holder: &HDOC,
+    options: &JwtPresentationValidationOptions,
+  ) -> Result<DecodedJws<'jwt>, CompoundJwtPresentationValidationError>
+  where
+    HDOC: AsRef<CoreDocument> + ?Sized,
+  {
+    holder
       .as_ref()
       .verify_jws(
         presentation.as_str(),
@@ -82,17 +176,14 @@ where
       )
       .map_err(|err| {
         CompoundJwtPresentationValidationError::one_presentation_error(JwtValidationError::PresentationJwsError(err))
-      })?;
-
-    let claims: PresentationJwtClaims<'_, CRED, T> = PresentationJwtClaims::from_json_slice(&decoded_jws.claims)
-      .map_err(|err| {
-        CompoundJwtPresentationValidationError::one_presentation_error(JwtValidationError::PresentationStructure(
-          crate::Error::JwtClaimsSetDeserializationError(err.into()),
-        ))
-      })?;
+      })
+  }
 
-    // Verify that holder document matches holder in presentation.
-    let holder_did: CoreDID = CoreDID::from_str(claims.iss.as_str()).map_err(|err| {
+  fn check_holder<HDOC>(holder_url: &Url, holder: &HDOC) -> Result<(), CompoundJwtPresentationValidationError>
+  where
+    HDOC: AsRef<CoreDocument> + ?Sized,
+  {
+    let holder_did: CoreDID = CoreDID::from_str(holder_url.as_str()).map_err(|err| {
       CompoundJwtPresentationValidationError::one_presentation_error(JwtValidationError::SignerUrl {
         signer_ctx: SignerContext::Holder,
         source: err.into(),
@@ -105,9 +196,17 @@ where
       ));
     }
 
+    Ok(())
+  }
+
+  /// Checks the expiration and issuance dates set in the JWT claims, returning them upon success.
+  fn check_dates(
+    exp: Option<i64>,
+    issuance_date: Option<IssuanceDateClaims>,
+    options: &JwtPresentationValidationOptions,
+  ) -> Result<(Option<Timestamp>, Option<Timestamp>), CompoundJwtPresentationValidationError> {
     // Check the expiration date.
-    let expiration_date: Option<Timestamp> = claims
-      .exp
+    let expiration_date: Option<Timestamp> = exp
       .map(|exp| {
         Timestamp::from_unix(exp).map_err(|err| {
           CompoundJwtPresentationValidationError::one_presentation_error(JwtValidationError::PresentationStructure(
@@ -124,7 +223,7 @@ where
       ))?;
 
     // Check issuance date.
-    let issuance_date: Option<Timestamp> = match claims.issuance_date {
+    let issuance_date: Option<Timestamp> = match issuance_date {
       Some(iss) => {
         if iss.iat.is_some() || iss.nbf.is_some() {
           Some(iss.to_issuance_date().map_err(|err| {
@@ -145,22 +244,55 @@ where
         JwtValidationError::IssuanceDate,
       ))?;
 
-    let aud: Option<Url> = claims.aud.clone();
-    let custom_claims: Option<Object> = claims.custom.clone();
+    Ok((expiration_date, issuance_date))
+  }
+}
 
-    let presentation: Presentation<CRED, T> = claims.try_into_presentation().map_err(|err| {
-      CompoundJwtPresentationValidationError::one_presentation_error(JwtValidationError::PresentationStructure(err))
-    })?;
+#[cfg(test)]
+mod tests {
+  use identity_core::common::Duration;
+  use identity_core::common::Object;
+  use identity_core::common::Timestamp;
+  use identity_core::common::Url;
+  use identity_did::DID;
+  use identity_eddsa_verifier::EdDSAJwsVerifier;
 
-    let decoded_jwt_presentation: DecodedJwtPresentation<CRED, T> = DecodedJwtPresentation {
-      presentation,
-      header: Box::new(decoded_jws.protected),
-      expiration_date,
-      issuance_date,
-      aud,
-      custom_claims,
-    };
+  use super::*;
+  use crate::credential::EnvelopedVerifiableCredential;
+  use crate::presentation::JwtPresentationOptions;
+  use crate::validator::test_utils::generate_jwk_document_with_keys;
+  use crate::validator::test_utils::sign_bytes;
 
-    Ok(decoded_jwt_presentation)
+  #[test]
+  fn validate_v2() {
+    let (document, secret_key, fragment) = generate_jwk_document_with_keys();
+    let credential_jwt: Jwt = Jwt::new("eyJhbGciOiJFZERTQSJ9.eyJpc3MiOiJkaWQ6ZXhhbXBsZToxIn0.c2ln".to_owned());
+    let presentation: PresentationV2 =
+      PresentationV2::builder(Url::parse(document.id().as_str()).unwrap(), Object::new())
+        .credential(EnvelopedVerifiableCredential::from_jwt(&credential_jwt).unwrap())
+        .build()
+        .unwrap();
+
+    let options: JwtPresentationOptions = JwtPresentationOptions::default()
+      .expiration_date(Timestamp::now_utc().checked_add(Duration::hours(1)).unwrap())
+      .audience(Url::parse("https://verifier.example").unwrap());
+    let payload: String = presentation.serialize_jwt(&options).unwrap();
+    let presentation_jwt: Jwt = Jwt::new(sign_bytes(&document, &fragment, payload.as_bytes(), &secret_key).into());
+
+    let decoded: DecodedJwtPresentationV2<EnvelopedVerifiableCredential> =
+      JwtPresentationValidator::with_signature_verifier(EdDSAJwsVerifier::default())
+        .validate_v2(
+          &presentation_jwt,
+          &document,
+          &JwtPresentationValidationOptions::default(),
+        )
+        .unwrap();
+
+    assert_eq!(decoded.presentation, presentation);
+    assert_eq!(decoded.aud, options.audience);
+    assert_eq!(
+      decoded.presentation.verifiable_credential[0].to_jwt().unwrap(),
+      credential_jwt
+    );
   }
 }
@@ -6,7 +6,7 @@ use serde::Serialize;
 
 /// Controls validation behaviour when checking whether or not a credential has been revoked by its
 /// [`credentialStatus`](https://www.w3.org/TR/vc-data-model/#status).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
 #[repr(u8)]
 pub enum StatusCheck {
   /// Validate the status if supported, reject any unsupported
@@ -15,6 +15,7 @@ pub enum StatusCheck {
   /// Only `RevocationBitmap2022` is currently supported.
   ///
   /// This is the default.
+  #[default]
   Strict = 0,
   /// Validate the status if supported, skip any unsupported
   /// [`credentialStatus`](https://www.w3.org/TR/vc-data-model/#status) types.
@@ -23,22 +24,17 @@ pub enum StatusCheck {
   SkipAll = 2,
 }
 
-impl Default for StatusCheck {
-  fn default() -> Self {
-    Self::Strict
-  }
-}
-
 /// Declares how credential subjects must relate to the presentation holder during validation.
 ///
 /// See also the [Subject-Holder Relationship](https://www.w3.org/TR/vc-data-model/#subject-holder-relationships) section of the specification.
 // Need to use serde_repr to make this work with duck typed interfaces in the Wasm bindings.
-#[derive(Debug, Clone, Copy, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
+#[derive(Debug, Default, Clone, Copy, serde_repr::Serialize_repr, serde_repr::Deserialize_repr)]
 #[repr(u8)]
 pub enum SubjectHolderRelationship {
   /// The holder must always match the subject on all credentials, regardless of their [`nonTransferable`](https://www.w3.org/TR/vc-data-model/#nontransferable-property) property.
   /// This is the variant returned by [Self::default](Self::default()) and the default used in
   /// [`crate::validator::JwtPresentationValidationOptions`].
+  #[default]
   AlwaysSubject = 0,
   /// The holder must match the subject only for credentials where the [`nonTransferable`](https://www.w3.org/TR/vc-data-model/#nontransferable-property) property is `true`.
   SubjectOnNonTransferable = 1,
@@ -46,12 +42,6 @@ pub enum SubjectHolderRelationship {
   Any = 2,
 }
 
-impl Default for SubjectHolderRelationship {
-  fn default() -> Self {
-    Self::AlwaysSubject
-  }
-}
-
 /// Declares when validation should return if an error occurs.
 #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum FailFast {
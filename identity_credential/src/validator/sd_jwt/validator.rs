@@ -2,23 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::credential::CredentialJwtClaims;
+use crate::credential::CredentialV2;
+use crate::credential::CredentialV2JwtClaims;
 use crate::validator::CompoundCredentialValidationError;
 use crate::validator::DecodedJwtCredential;
+use crate::validator::DecodedJwtCredentialV2;
 use crate::validator::FailFast;
 use crate::validator::JwtCredentialValidationOptions;
 use crate::validator::JwtCredentialValidator;
-use crate::validator::JwtCredentialValidatorUtils;
 use crate::validator::JwtValidationError;
 use crate::validator::SignerContext;
 use identity_core::common::Timestamp;
 use identity_core::convert::FromJson;
-use identity_did::CoreDID;
 use identity_did::DIDUrl;
 use identity_document::document::CoreDocument;
 use identity_document::verifiable::JwsVerificationOptions;
 use identity_verification::jwk::Jwk;
 use identity_verification::jws::DecodedJws;
 use identity_verification::jws::Decoder;
+use identity_verification::jws::JwsHeader;
 use identity_verification::jws::JwsValidationItem;
 use identity_verification::jws::JwsVerifier;
 use itertools::Itertools;
@@ -114,6 +116,102 @@ impl<V: JwsVerifier> SdJwtCredentialValidator<V> {
   where
     T: ToOwned<Owned = T> + serde::Serialize + serde::de::DeserializeOwned,
     DOC: AsRef<CoreDocument>,
+  {
+    let (protected, decoded, method_id) = self.verify_and_decode(credential, trusted_issuers, options)?;
+
+    let claims = CredentialJwtClaims::from_json(&decoded).map_err(|err| {
+      JwtValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
+    })?;
+    let custom_claims = claims.custom.clone();
+    let credential = claims
+      .try_into_credential()
+      .map_err(JwtValidationError::CredentialStructure)?;
+
+    JwtCredentialValidator::<V>::check_issuer_matches_method(&credential, &method_id)?;
+
+    Ok(DecodedJwtCredential {
+      credential,
+      header: Box::new(protected),
+      custom_claims,
+    })
+  }
+
+  /// Decodes and validates a [`CredentialV2`](crate::credential::CredentialV2) issued as an SD-JWT.
+  /// A [`DecodedJwtCredentialV2`] is returned upon success.
+  ///
+  /// This is the [VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/) counterpart of
+  /// [`Self::validate_credential`]. The same warnings apply.
+  ///
+  /// # Errors
+  /// An error is returned whenever a validated condition is not satisfied.
+  pub fn validate_credential_v2<DOC, T>(
+    &self,
+    sd_jwt: &SdJwt,
+    issuer: &DOC,
+    options: &JwtCredentialValidationOptions,
+    fail_fast: FailFast,
+  ) -> Result<DecodedJwtCredentialV2<T>, CompoundCredentialValidationError>
+  where
+    T: serde::de::DeserializeOwned,
+    DOC: AsRef<CoreDocument>,
+  {
+    let issuers = std::slice::from_ref(issuer.as_ref());
+    let credential = self
+      .verify_signature_v2(sd_jwt, issuers, &options.verification_options)
+      .map_err(|err| CompoundCredentialValidationError {
+        validation_errors: [err].into(),
+      })?;
+
+    JwtCredentialValidator::<V>::validate_decoded_credential_v2(credential, issuers, options, fail_fast)
+  }
+
+  /// Decode and verify the JWS signature of a [`CredentialV2`](crate::credential::CredentialV2) issued as an SD-JWT
+  /// using the DID Document of a trusted issuer and replaces the disclosures.
+  ///
+  /// This is the [VC Data Model v2.0](https://www.w3.org/TR/vc-data-model-2.0/) counterpart of
+  /// [`Self::verify_signature`].
+  ///
+  /// # Errors
+  /// * If the issuer' URL cannot be parsed.
+  /// * If Signature verification fails.
+  /// * If SD decoding fails.
+  pub fn verify_signature_v2<DOC, T>(
+    &self,
+    credential: &SdJwt,
+    trusted_issuers: &[DOC],
+    options: &JwsVerificationOptions,
+  ) -> Result<DecodedJwtCredentialV2<T>, JwtValidationError>
+  where
+    T: serde::de::DeserializeOwned,
+    DOC: AsRef<CoreDocument>,
+  {
+    let (protected, decoded, method_id) = self.verify_and_decode(credential, trusted_issuers, options)?;
+
+    let credential: CredentialV2<T> = CredentialV2JwtClaims::from_json(&decoded)
+      .map_err(|err| {
+        JwtValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
+      })?
+      .try_into_credential()
+      .map_err(JwtValidationError::CredentialStructure)?;
+
+    JwtCredentialValidator::<V>::check_issuer_matches_method(&credential, &method_id)?;
+
+    Ok(DecodedJwtCredentialV2 {
+      credential,
+      header: Box::new(protected),
+    })
+  }
+
+  /// Verifies the signature of the SD-JWT and replaces the disclosures, returning the protected header,
+  /// the decoded claims set and the identifier of the method used to verify the signature.
+  fn verify_and_decode<DOC>(
+    &self,
+    credential: &SdJwt,
+    trusted_issuers: &[DOC],
+    options: &JwsVerificationOptions,
+  ) -> Result<(JwsHeader, String, DIDUrl), JwtValidationError>
+  where
+    DOC: AsRef<CoreDocument>,
   {
     let SdJwt { jwt, disclosures, .. } = credential;
     let signature = JwtCredentialValidator::<V>::decode(jwt.as_str())?;
@@ -135,36 +233,17 @@ impl<V: JwsVerifier> SdJwtCredentialValidator<V> {
     })?)
     .to_string();
 
-    let claims = CredentialJwtClaims::from_json(&decoded).map_err(|err| {
-      JwtValidationError::CredentialStructure(crate::Error::JwtClaimsSetDeserializationError(err.into()))
-    })?;
-    let custom_claims = claims.custom.clone();
-    let credential = claims
-      .try_into_credential()
-      .map_err(JwtValidationError::CredentialStructure)?;
-
-    let decoded_credential = DecodedJwtCredential {
-      credential,
-      header: Box::new(protected),
-      custom_claims,
-    };
-
-    // Check that the DID component of the parsed `kid` does indeed correspond to the issuer in the credential before
-    // returning.
-    let issuer_id = JwtCredentialValidatorUtils::extract_issuer::<CoreDID, _>(&decoded_credential.credential)?;
-    if &issuer_id != method_id.did() {
-      return Err(JwtValidationError::IdentifierMismatch {
-        signer_ctx: SignerContext::Issuer,
-      });
-    };
-
-    Ok(decoded_credential)
+    Ok((protected, decoded, method_id))
   }
 
   /// Validates a Key Binding JWT (KB-JWT) according to `https://www.ietf.org/archive/id/draft-ietf-oauth-selective-disclosure-jwt-07.html#name-key-binding-jwt`.
   /// The Validation process includes:
   ///   * Signature validation using public key materials defined in the `holder` document.
-  ///   * `typ` value in KB-JWT header.
+  ///   * `typ` value in KB-JWT header, which must be `kb+jwt`.
+  ///
+  /// KB-JWTs whose `typ` is [`KeyBindingJwtClaims::KB_JWT_HEADER_TYP`], which has a leading whitespace, are accepted
+  /// as well for compatibility with KB-JWTs created by previous versions. This is deprecated and will be removed in a
+  /// future release.
   ///   * `sd_hash` claim value in the KB-JWT claim.
   ///   * Optional `nonce`, `aud` and issuance date validation.
   pub fn validate_key_binding_jwt<DOC>(
@@ -176,119 +255,143 @@ impl<V: JwsVerifier> SdJwtCredentialValidator<V> {
   where
     DOC: AsRef<CoreDocument>,
   {
-    // Check if KB exists in the SD-JWT.
-    let kb_jwt = if let Some(kb_jwt) = &sd_jwt.key_binding_jwt {
-      kb_jwt.clone()
-    } else {
-      return Err(KeyBindingJwtError::MissingKeyBindingJwt);
-    };
-
-    // Calculate the digest from the `sd_jwt.jwt` and the disclosures.
-    let jws_decoder = Decoder::new();
-    let decoded: JwsValidationItem<'_> = jws_decoder
-      .decode_compact_serialization(sd_jwt.jwt.as_bytes(), None)
-      .map_err(|err| KeyBindingJwtError::JwtValidationError(JwtValidationError::JwsDecodingError(err)))?;
-    let sd_jwt_claims: Value = serde_json::from_slice(decoded.claims())
-      .map_err(|_| KeyBindingJwtError::DeserializationError("failed to deserialize sd-jwt claims".to_string()))?;
-    let sd_jwt_claims_object = sd_jwt_claims
-      .as_object()
-      .ok_or(KeyBindingJwtError::DeserializationError(
-        "failed to deserialize sd-jwt claims".to_string(),
-      ))?;
-    let hasher = self.1.determine_hasher(sd_jwt_claims_object)?;
-    let disclosures = sd_jwt.disclosures.iter().join("~");
-    let hash_payload = format!("{}~{}~", sd_jwt.jwt, disclosures);
-    let digest = hasher.encoded_digest(&hash_payload);
-
-    // Verify the signature of the KB-JWT and extract claims.
-    let kb_decoded: JwsValidationItem<'_> = jws_decoder
-      .decode_compact_serialization(kb_jwt.as_bytes(), None)
-      .map_err(JwtValidationError::JwsDecodingError)?;
-    let typ: &str = kb_decoded
-      .protected_header()
-      .ok_or(KeyBindingJwtError::InvalidHeaderTypValue)?
-      .typ()
-      .ok_or(KeyBindingJwtError::InvalidHeaderTypValue)?;
-
-    if typ != KeyBindingJwtClaims::KB_JWT_HEADER_TYP {
-      return Err(KeyBindingJwtError::InvalidHeaderTypValue);
-    }
-    let method_id: DIDUrl = match &options.jws_options.method_id {
-      Some(method_id) => method_id.clone(),
-      None => {
-        let kid: &str = kb_decoded.protected_header().and_then(|header| header.kid()).ok_or(
+    validate_key_binding_jwt(&self.0, &self.1, sd_jwt, options, |kb_header| {
+      let method_id: DIDUrl = match &options.jws_options.method_id {
+        Some(method_id) => method_id.clone(),
+        None => {
+          let kid: &str = kb_header
+            .kid()
+            .ok_or(JwtValidationError::MethodDataLookupError {
+              source: None,
+              message: "could not extract kid from protected header",
+              signer_ctx: SignerContext::Holder,
+            })?;
+
+          // Convert kid to DIDUrl
+          DIDUrl::parse(kid).map_err(|err| JwtValidationError::MethodDataLookupError {
+            source: Some(err.into()),
+            message: "could not parse kid as a DID Url",
+            signer_ctx: SignerContext::Issuer,
+          })?
+        }
+      };
+
+      // Obtain the public key from the holder's DID document
+      holder
+        .as_ref()
+        .resolve_method(&method_id, options.jws_options.method_scope)
+        .and_then(|method| method.data().public_key_jwk())
+        .ok_or_else(|| {
           JwtValidationError::MethodDataLookupError {
             source: None,
-            message: "could not extract kid from protected header",
+            message: "could not extract JWK from a method identified by kid",
             signer_ctx: SignerContext::Holder,
-          },
-        )?;
-
-        // Convert kid to DIDUrl
-        DIDUrl::parse(kid).map_err(|err| JwtValidationError::MethodDataLookupError {
-          source: Some(err.into()),
-          message: "could not parse kid as a DID Url",
-          signer_ctx: SignerContext::Issuer,
-        })?
-      }
-    };
-
-    // Obtain the public key from the holder's DID document
-    let public_key: &Jwk = holder
-      .as_ref()
-      .resolve_method(&method_id, options.jws_options.method_scope)
-      .and_then(|method| method.data().public_key_jwk())
-      .ok_or_else(|| JwtValidationError::MethodDataLookupError {
-        source: None,
-        message: "could not extract JWK from a method identified by kid",
-        signer_ctx: SignerContext::Holder,
-      })?;
-    let decoded: JwsValidationItem<'_> = jws_decoder
-      .decode_compact_serialization(kb_jwt.as_bytes(), None)
-      .map_err(|err| KeyBindingJwtError::JwtValidationError(JwtValidationError::JwsDecodingError(err)))?;
-    let decoded_kb_jws = decoded.verify(&self.0, public_key).unwrap();
+          }
+          .into()
+        })
+    })
+  }
+}
 
-    let kb_jwt_claims: KeyBindingJwtClaims = serde_json::from_slice(&decoded_kb_jws.claims)
-      .map_err(|_| KeyBindingJwtError::DeserializationError("failed to deserialize kb-jwt claims".into()))?;
+/// Validates the key binding JWT of `sd_jwt` according to `options`, verifying its signature with the public key
+/// returned by `resolve_key` for the protected header of the KB-JWT.
+pub(crate) fn validate_key_binding_jwt<'key, V, F>(
+  verifier: &V,
+  sd_decoder: &SdObjectDecoder,
+  sd_jwt: &SdJwt,
+  options: &KeyBindingJWTValidationOptions,
+  resolve_key: F,
+) -> Result<KeyBindingJwtClaims, KeyBindingJwtError>
+where
+  V: JwsVerifier,
+  F: FnOnce(&JwsHeader) -> Result<&'key Jwk, KeyBindingJwtError>,
+{
+  // Check if KB exists in the SD-JWT.
+  let kb_jwt = if let Some(kb_jwt) = &sd_jwt.key_binding_jwt {
+    kb_jwt.clone()
+  } else {
+    return Err(KeyBindingJwtError::MissingKeyBindingJwt);
+  };
 
-    // Check if the `_sd_hash` matches.
-    if kb_jwt_claims.sd_hash != digest {
-      return Err(KeyBindingJwtError::InvalidDigest);
-    }
+  // Calculate the digest from the `sd_jwt.jwt` and the disclosures.
+  let jws_decoder = Decoder::new();
+  let decoded: JwsValidationItem<'_> = jws_decoder
+    .decode_compact_serialization(sd_jwt.jwt.as_bytes(), None)
+    .map_err(|err| KeyBindingJwtError::JwtValidationError(JwtValidationError::JwsDecodingError(err)))?;
+  let sd_jwt_claims: Value = serde_json::from_slice(decoded.claims())
+    .map_err(|_| KeyBindingJwtError::DeserializationError("failed to deserialize sd-jwt claims".to_string()))?;
+  let sd_jwt_claims_object = sd_jwt_claims
+    .as_object()
+    .ok_or(KeyBindingJwtError::DeserializationError(
+      "failed to deserialize sd-jwt claims".to_string(),
+    ))?;
+  let hasher = sd_decoder.determine_hasher(sd_jwt_claims_object)?;
+  let disclosures = sd_jwt.disclosures.iter().join("~");
+  let hash_payload = format!("{}~{}~", sd_jwt.jwt, disclosures);
+  let digest = hasher.encoded_digest(&hash_payload);
+
+  // Verify the signature of the KB-JWT and extract claims.
+  let kb_decoded: JwsValidationItem<'_> = jws_decoder
+    .decode_compact_serialization(kb_jwt.as_bytes(), None)
+    .map_err(JwtValidationError::JwsDecodingError)?;
+  let kb_header: &JwsHeader = kb_decoded
+    .protected_header()
+    .ok_or(KeyBindingJwtError::InvalidHeaderTypValue)?;
+  let typ: &str = kb_header.typ().ok_or(KeyBindingJwtError::InvalidHeaderTypValue)?;
 
-    if let Some(nonce) = &options.nonce {
-      if *nonce != kb_jwt_claims.nonce {
-        return Err(KeyBindingJwtError::InvalidNonce);
-      }
+  // `KB_JWT_HEADER_TYP` has a leading whitespace. KB-JWTs created with it are still accepted, but this is deprecated.
+  if typ != "kb+jwt" && typ != KeyBindingJwtClaims::KB_JWT_HEADER_TYP {
+    return Err(KeyBindingJwtError::InvalidHeaderTypValue);
+  }
+
+  let public_key: &Jwk = resolve_key(kb_header)?;
+  let decoded_kb_jws = kb_decoded
+    .verify(verifier, public_key)
+    .map_err(|source| JwtValidationError::Signature {
+      source,
+      signer_ctx: SignerContext::Holder,
+    })?;
+
+  let kb_jwt_claims: KeyBindingJwtClaims = serde_json::from_slice(&decoded_kb_jws.claims)
+    .map_err(|_| KeyBindingJwtError::DeserializationError("failed to deserialize kb-jwt claims".into()))?;
+
+  // Check if the `_sd_hash` matches.
+  if kb_jwt_claims.sd_hash != digest {
+    return Err(KeyBindingJwtError::InvalidDigest);
+  }
+
+  if let Some(nonce) = &options.nonce {
+    if *nonce != kb_jwt_claims.nonce {
+      return Err(KeyBindingJwtError::InvalidNonce);
     }
+  }
 
-    if let Some(aud) = &options.aud {
-      if *aud != kb_jwt_claims.aud {
-        return Err(KeyBindingJwtError::AudianceMismatch);
-      }
+  if let Some(aud) = &options.aud {
+    if *aud != kb_jwt_claims.aud {
+      return Err(KeyBindingJwtError::AudianceMismatch);
     }
+  }
 
-    let issuance_date = Timestamp::from_unix(kb_jwt_claims.iat)
-      .map_err(|_| KeyBindingJwtError::IssuanceDate("deserialization of `iat` failed".to_string()))?;
+  let issuance_date = Timestamp::from_unix(kb_jwt_claims.iat)
+    .map_err(|_| KeyBindingJwtError::IssuanceDate("deserialization of `iat` failed".to_string()))?;
 
-    if let Some(earliest_issuance_date) = options.earliest_issuance_date {
-      if issuance_date < earliest_issuance_date {
-        return Err(KeyBindingJwtError::IssuanceDate(
-          "value is earlier than `earliest_issuance_date`".to_string(),
-        ));
-      }
+  if let Some(earliest_issuance_date) = options.earliest_issuance_date {
+    if issuance_date < earliest_issuance_date {
+      return Err(KeyBindingJwtError::IssuanceDate(
+        "value is earlier than `earliest_issuance_date`".to_string(),
+      ));
     }
+  }
 
-    if let Some(latest_issuance_date) = options.latest_issuance_date {
-      if issuance_date > latest_issuance_date {
-        return Err(KeyBindingJwtError::IssuanceDate(
-          "value is later than `latest_issuance_date`".to_string(),
-        ));
-      }
-    } else if issuance_date > Timestamp::now_utc() {
-      return Err(KeyBindingJwtError::IssuanceDate("value is in the future".to_string()));
+  if let Some(latest_issuance_date) = options.latest_issuance_date {
+    if issuance_date > latest_issuance_date {
+      return Err(KeyBindingJwtError::IssuanceDate(
+        "value is later than `latest_issuance_date`".to_string(),
+      ));
     }
-
-    Ok(kb_jwt_claims)
+  } else if issuance_date > Timestamp::now_utc() {
+    return Err(KeyBindingJwtError::IssuanceDate("value is in the future".to_string()));
   }
+
+  Ok(kb_jwt_claims)
 }
@@ -10,10 +10,17 @@ use identity_document::document::CoreDocument;
 use identity_verification::jwk::EdCurve;
 use identity_verification::jwk::Jwk;
 use identity_verification::jwk::JwkParamsOkp;
+use identity_verification::jws::CharSet;
+use identity_verification::jws::CompactJwsEncoder;
+use identity_verification::jws::CompactJwsEncodingOptions;
 use identity_verification::jws::JwsAlgorithm;
+use identity_verification::jws::JwsHeader;
 use identity_verification::jwu;
+use identity_verification::MethodData;
 use identity_verification::VerificationMethod;
 
+use crate::credential::Jws;
+
 pub(crate) fn encode_public_ed25519_jwk(public_key: &PublicKey) -> Jwk {
   let x = jwu::encode_b64(public_key.as_ref());
   let mut params = JwkParamsOkp::new();
@@ -39,3 +46,29 @@ pub(crate) fn generate_jwk_document_with_keys() -> (CoreDocument, SecretKey, Str
     .unwrap();
   (document, secret, fragment)
 }
+
+pub(crate) fn sign_bytes(document: &CoreDocument, fragment: &str, payload: &[u8], secret_key: &SecretKey) -> Jws {
+  let method: &VerificationMethod = document.resolve_method(fragment, None).unwrap();
+  let MethodData::PublicKeyJwk(ref jwk) = method.data() else {
+    panic!("not a jwk");
+  };
+  let alg: JwsAlgorithm = jwk.alg().unwrap_or("").parse().unwrap();
+
+  let header: JwsHeader = {
+    let mut header = JwsHeader::new();
+    header.set_alg(alg);
+    header.set_kid(method.id().to_string());
+    header
+  };
+
+  let encoding_options: CompactJwsEncodingOptions = CompactJwsEncodingOptions::NonDetached {
+    charset_requirements: CharSet::Default,
+  };
+
+  let jws_encoder: CompactJwsEncoder<'_> =
+    CompactJwsEncoder::new_with_options(payload, &header, encoding_options).unwrap();
+
+  let signature: [u8; 64] = secret_key.sign(jws_encoder.signing_input()).to_bytes();
+
+  Jws::new(jws_encoder.into_jws(&signature))
+}
@@ -0,0 +1,322 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use bls12_381_plus::G1Affine;
+use bls12_381_plus::G2Affine;
+use crypto::keys::x25519;
+use crypto::signatures::ed25519;
+use identity_core::convert::Base;
+use identity_core::convert::BaseEncoding;
+
+use crate::CoreDID;
+use crate::Error;
+use crate::DID;
+
+/// The type of the public key encoded in a [`DIDKey`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DIDKeyType {
+  /// An Ed25519 public key.
+  Ed25519,
+  /// An X25519 public key.
+  X25519,
+  /// A compressed P-256 public key.
+  P256,
+  /// A compressed secp256k1 public key.
+  Secp256k1,
+  /// A BLS12-381 public key in G1.
+  Bls12381G1,
+  /// A BLS12-381 public key in G2.
+  Bls12381G2,
+}
+
+impl DIDKeyType {
+  const ALL: [Self; 6] = [
+    Self::Ed25519,
+    Self::X25519,
+    Self::P256,
+    Self::Secp256k1,
+    Self::Bls12381G1,
+    Self::Bls12381G2,
+  ];
+
+  /// Returns the varint encoded multicodec identifying the key type.
+  fn multicodec(self) -> &'static [u8] {
+    match self {
+      Self::Ed25519 => &[0xed, 0x01],
+      Self::X25519 => &[0xec, 0x01],
+      Self::P256 => &[0x80, 0x24],
+      Self::Secp256k1 => &[0xe7, 0x01],
+      Self::Bls12381G1 => &[0xea, 0x01],
+      Self::Bls12381G2 => &[0xeb, 0x01],
+    }
+  }
+
+  /// Returns the length of an encoded public key of this type in bytes.
+  fn key_len(self) -> usize {
+    match self {
+      Self::Ed25519 | Self::X25519 => 32,
+      Self::P256 | Self::Secp256k1 => 33,
+      Self::Bls12381G1 => 48,
+      Self::Bls12381G2 => 96,
+    }
+  }
+
+  /// Returns whether keys of this type can only be used for key agreement.
+  pub fn is_key_agreement(self) -> bool {
+    matches!(self, Self::X25519)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize)]
+#[repr(transparent)]
+#[serde(into = "CoreDID", try_from = "CoreDID")]
+/// A type representing a `did:key` DID.
+pub struct DIDKey(CoreDID);
+
+impl DIDKey {
+  /// [`DIDKey`]'s method.
+  pub const METHOD: &'static str = "key";
+
+  /// Tries to parse a [`DIDKey`] from a string.
+  pub fn parse(s: &str) -> Result<Self, Error> {
+    s.parse()
+  }
+
+  /// Creates a [`DIDKey`] encoding the given `public_key` of type `key_type`.
+  ///
+  /// Elliptic curve keys other than Ed25519 and X25519 are expected in their compressed form.
+  ///
+  /// # Errors
+  /// [`Error::InvalidMethodId`] if `public_key` is not a valid point of `key_type`.
+  pub fn new(key_type: DIDKeyType, public_key: &[u8]) -> Result<Self, Error> {
+    validate_public_key(key_type, public_key)?;
+    let mut bytes: Vec<u8> = key_type.multicodec().to_vec();
+    bytes.extend_from_slice(public_key);
+    let multibase: String = BaseEncoding::encode_multibase(&bytes, Some(Base::Base58Btc));
+
+    CoreDID::parse(format!("did:{}:{multibase}", Self::METHOD)).map(Self)
+  }
+
+  /// Returns the type of the public key encoded inside this did:key.
+  pub fn key_type(&self) -> DIDKeyType {
+    decode_method_id(self.method_id())
+      .expect("did:key encodes a valid public key")
+      .0
+  }
+
+  /// Returns the public key encoded inside this did:key.
+  pub fn public_key(&self) -> Vec<u8> {
+    decode_method_id(self.method_id())
+      .expect("did:key encodes a valid public key")
+      .1
+  }
+
+  /// Returns the multibase encoded multicodec public key, i.e. the method specific id of this did:key.
+  pub fn multibase(&self) -> &str {
+    self.method_id()
+  }
+
+  /// Returns a [`DIDKey`] encoding the X25519 key agreement key derived from the Ed25519 key of this
+  /// did:key, or `None` if this did:key does not encode an Ed25519 key.
+  pub fn derive_key_agreement_key(&self) -> Option<DIDKey> {
+    let (DIDKeyType::Ed25519, public_key) = decode_method_id(self.method_id()).ok()? else {
+      return None;
+    };
+    let public_key: ed25519::PublicKey = ed25519_public_key(&public_key).expect("the ed25519 key was validated");
+    let x25519_key: x25519::PublicKey =
+      x25519::PublicKey::try_from(&public_key).expect("a valid ed25519 key can be converted to x25519");
+
+    Some(Self::new(DIDKeyType::X25519, x25519_key.as_slice()).expect("an x25519 key is always valid"))
+  }
+}
+
+/// Decodes the type and public key of a did:key method specific id.
+fn decode_method_id(method_id: &str) -> Result<(DIDKeyType, Vec<u8>), Error> {
+  // did:key only uses the base58-btc multibase encoding.
+  if !method_id.starts_with('z') {
+    return Err(Error::InvalidMethodId);
+  }
+  let bytes: Vec<u8> = BaseEncoding::decode_multibase(method_id).map_err(|_| Error::InvalidMethodId)?;
+  let key_type: DIDKeyType = DIDKeyType::ALL
+    .into_iter()
+    .find(|key_type| bytes.starts_with(key_type.multicodec()))
+    .ok_or(Error::InvalidMethodId)?;
+  let public_key: Vec<u8> = bytes[key_type.multicodec().len()..].to_vec();
+  validate_public_key(key_type, &public_key)?;
+
+  Ok((key_type, public_key))
+}
+
+/// Checks that `public_key` is a valid key of type `key_type`, i.e. a point on the respective curve.
+fn validate_public_key(key_type: DIDKeyType, public_key: &[u8]) -> Result<(), Error> {
+  if public_key.len() != key_type.key_len() {
+    return Err(Error::InvalidMethodId);
+  }
+  let is_valid: bool = match key_type {
+    DIDKeyType::Ed25519 => ed25519_public_key(public_key).is_ok(),
+    DIDKeyType::X25519 => true,
+    // Only compressed points are allowed, which `from_sec1_bytes` would accept in uncompressed form as well.
+    DIDKeyType::P256 | DIDKeyType::Secp256k1 if !matches!(public_key[0], 0x02 | 0x03) => false,
+    DIDKeyType::P256 => p256::PublicKey::from_sec1_bytes(public_key).is_ok(),
+    DIDKeyType::Secp256k1 => k256::PublicKey::from_sec1_bytes(public_key).is_ok(),
+    DIDKeyType::Bls12381G1 => {
+      let bytes: &[u8; 48] = public_key.try_into().map_err(|_| Error::InvalidMethodId)?;
+      Option::<G1Affine>::from(G1Affine::from_compressed(bytes))
+        .filter(|point| !bool::from(point.is_identity()))
+        .is_some()
+    }
+    DIDKeyType::Bls12381G2 => {
+      let bytes: &[u8; 96] = public_key.try_into().map_err(|_| Error::InvalidMethodId)?;
+      Option::<G2Affine>::from(G2Affine::from_compressed(bytes))
+        .filter(|point| !bool::from(point.is_identity()))
+        .is_some()
+    }
+  };
+
+  is_valid.then_some(()).ok_or(Error::InvalidMethodId)
+}
+
+fn ed25519_public_key(public_key: &[u8]) -> Result<ed25519::PublicKey, Error> {
+  let bytes: [u8; ed25519::PublicKey::LENGTH] = public_key.try_into().map_err(|_| Error::InvalidMethodId)?;
+  ed25519::PublicKey::try_from_bytes(bytes).map_err(|_| Error::InvalidMethodId)
+}
+
+impl AsRef<CoreDID> for DIDKey {
+  fn as_ref(&self) -> &CoreDID {
+    &self.0
+  }
+}
+
+impl From<DIDKey> for CoreDID {
+  fn from(value: DIDKey) -> Self {
+    value.0
+  }
+}
+
+impl<'a> TryFrom<&'a str> for DIDKey {
+  type Error = Error;
+  fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+    value.parse()
+  }
+}
+
+impl Display for DIDKey {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl FromStr for DIDKey {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    s.parse::<CoreDID>().and_then(TryFrom::try_from)
+  }
+}
+
+impl From<DIDKey> for String {
+  fn from(value: DIDKey) -> Self {
+    value.to_string()
+  }
+}
+
+impl TryFrom<CoreDID> for DIDKey {
+  type Error = Error;
+  fn try_from(value: CoreDID) -> Result<Self, Self::Error> {
+    let Self::METHOD = value.method() else {
+      return Err(Error::InvalidMethodName);
+    };
+    decode_method_id(value.method_id()).map(|_| Self(value))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_valid_deserialization() {
+    for (did, key_type) in [
+      (
+        "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+        DIDKeyType::Ed25519,
+      ),
+      (
+        "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F",
+        DIDKeyType::X25519,
+      ),
+      (
+        "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169",
+        DIDKeyType::P256,
+      ),
+      (
+        "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme",
+        DIDKeyType::Secp256k1,
+      ),
+      (
+        "did:key:z3tEFALUKUzzCAvytMHX8X4SnsNsq6T5tC5Zb18oQEt1FqNcJXqJ3AA9umgzA9yoqPBeWA",
+        DIDKeyType::Bls12381G1,
+      ),
+      (
+        "did:key:zUC7EK3ZakmukHhuncwkbySmomv3FmrkmS36E4Ks5rsb6VQSRpoCrx6Hb8e2Nk6UvJFSdyw9NK1scFXJp21gNNYFjVWNgaqyGnkyhtagagCpQb5B7tagJu3HDbjQ8h5ypoHjwBb",
+        DIDKeyType::Bls12381G2,
+      ),
+    ] {
+      let did_key: DIDKey = did.parse().unwrap();
+      assert_eq!(did_key.key_type(), key_type);
+      assert_eq!(did_key.public_key().len(), key_type.key_len());
+      assert_eq!(DIDKey::new(key_type, &did_key.public_key()).unwrap(), did_key);
+    }
+  }
+
+  #[test]
+  fn test_derive_key_agreement_key() {
+    let did_key = DIDKey::parse("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").unwrap();
+    let key_agreement_key: DIDKey = did_key.derive_key_agreement_key().unwrap();
+    assert_eq!(
+      key_agreement_key.multibase(),
+      "z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p"
+    );
+    assert!(key_agreement_key.derive_key_agreement_key().is_none());
+  }
+
+  #[test]
+  fn test_invalid_deserialization() {
+    assert!(
+      "did:iota:0xf4d6f08f5a1b80dd578da7dc1b49c886d580acd4cf7d48119dfeb82b538ad88a"
+        .parse::<DIDKey>()
+        .is_err()
+    );
+    assert!("did:key:".parse::<DIDKey>().is_err());
+    // Not base58-btc encoded.
+    assert!("did:key:mO0BAQ".parse::<DIDKey>().is_err());
+    // Truncated key.
+    assert!("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2do"
+      .parse::<DIDKey>()
+      .is_err());
+    // Unknown multicodec.
+    let mut bytes: Vec<u8> = vec![0x00, 0x01];
+    bytes.extend_from_slice(&[1; 32]);
+    let did: String = format!(
+      "did:key:{}",
+      BaseEncoding::encode_multibase(&bytes, Some(Base::Base58Btc))
+    );
+    assert!(did.parse::<DIDKey>().is_err());
+    // Uncompressed P-256 key.
+    assert!(DIDKey::new(DIDKeyType::P256, &[0x04; 33]).is_err());
+    // Compressed keys whose x-coordinates are not field elements.
+    let mut not_on_curve: [u8; 33] = [0xff; 33];
+    not_on_curve[0] = 0x02;
+    assert!(DIDKey::new(DIDKeyType::P256, &not_on_curve).is_err());
+    assert!(DIDKey::new(DIDKeyType::Secp256k1, &not_on_curve).is_err());
+    // BLS12-381 keys that are not compressed points or the point at infinity.
+    assert!(DIDKey::new(DIDKeyType::Bls12381G1, &[0; 48]).is_err());
+    assert!(DIDKey::new(DIDKeyType::Bls12381G1, &G1Affine::identity().to_compressed()).is_err());
+    assert!(DIDKey::new(DIDKeyType::Bls12381G2, &[0xff; 96]).is_err());
+    assert!(DIDKey::new(DIDKeyType::Bls12381G2, &G2Affine::identity().to_compressed()).is_err());
+  }
+}
@@ -0,0 +1,405 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use identity_core::common::Object;
+use identity_core::common::Value;
+use identity_core::convert::Base;
+use identity_core::convert::BaseEncoding;
+use identity_core::convert::FromJson;
+use identity_core::convert::ToJson;
+
+use crate::CoreDID;
+use crate::DIDKey;
+use crate::Error;
+use crate::DID;
+
+/// The abbreviations of the keys of encoded services.
+const SERVICE_KEY_ABBREVIATIONS: [(&str, &str); 4] = [
+  ("type", "t"),
+  ("serviceEndpoint", "s"),
+  ("routingKeys", "r"),
+  ("accept", "a"),
+];
+/// The abbreviations of the values of the `type` of encoded services.
+const SERVICE_TYPE_ABBREVIATIONS: [(&str, &str); 1] = [("DIDCommMessaging", "dm")];
+
+/// The verification relationship of a key encoded in a numalgo 2 [`DIDPeer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DIDPeerPurpose {
+  /// An `assertionMethod` key, encoded with the purpose code `A`.
+  AssertionMethod,
+  /// A `keyAgreement` key, encoded with the purpose code `E`.
+  KeyAgreement,
+  /// An `authentication` key, encoded with the purpose code `V`.
+  Authentication,
+  /// A `capabilityInvocation` key, encoded with the purpose code `I`.
+  CapabilityInvocation,
+  /// A `capabilityDelegation` key, encoded with the purpose code `D`.
+  CapabilityDelegation,
+}
+
+impl DIDPeerPurpose {
+  const ALL: [Self; 5] = [
+    Self::AssertionMethod,
+    Self::KeyAgreement,
+    Self::Authentication,
+    Self::CapabilityInvocation,
+    Self::CapabilityDelegation,
+  ];
+
+  /// Returns the purpose code of the relationship.
+  pub fn code(self) -> char {
+    match self {
+      Self::AssertionMethod => 'A',
+      Self::KeyAgreement => 'E',
+      Self::Authentication => 'V',
+      Self::CapabilityInvocation => 'I',
+      Self::CapabilityDelegation => 'D',
+    }
+  }
+
+  fn from_code(code: char) -> Option<Self> {
+    Self::ALL.into_iter().find(|purpose| purpose.code() == code)
+  }
+}
+
+/// An element encoded in a numalgo 2 [`DIDPeer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DIDPeerElement {
+  /// A public key, encoded as in a [`DIDKey`], with its verification relationship.
+  Key {
+    /// The verification relationship of the key.
+    purpose: DIDPeerPurpose,
+    /// The key.
+    key: DIDKey,
+  },
+  /// A service, as JSON object without abbreviations.
+  Service(Object),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Deserialize, serde::Serialize)]
+#[repr(transparent)]
+#[serde(into = "CoreDID", try_from = "CoreDID")]
+/// A type representing a `did:peer` DID using numalgo 0 or 2.
+pub struct DIDPeer(CoreDID);
+
+impl DIDPeer {
+  /// [`DIDPeer`]'s method.
+  pub const METHOD: &'static str = "peer";
+
+  /// Tries to parse a [`DIDPeer`] from a string.
+  pub fn parse(s: &str) -> Result<Self, Error> {
+    s.parse()
+  }
+
+  /// Creates a numalgo 0 [`DIDPeer`] from an inception `key`.
+  pub fn new_numalgo_0(key: &DIDKey) -> Self {
+    Self::parse(&format!("did:{}:0{}", Self::METHOD, key.multibase())).expect("a did:key is a valid inception key")
+  }
+
+  /// Creates a numalgo 2 [`DIDPeer`] encoding the given `elements` in order.
+  ///
+  /// Services are encoded with the abbreviations of the did:peer specification.
+  pub fn new_numalgo_2(elements: impl IntoIterator<Item = DIDPeerElement>) -> Result<Self, Error> {
+    let mut method_id: String = "2".to_owned();
+    for element in elements {
+      method_id.push('.');
+      match element {
+        DIDPeerElement::Key { purpose, key } => {
+          method_id.push(purpose.code());
+          method_id.push_str(key.multibase());
+        }
+        DIDPeerElement::Service(service) => {
+          let json: Vec<u8> = abbreviate(Value::Object(service.into_iter().collect()))
+            .to_json_vec()
+            .map_err(|_| Error::Other("failed to serialize did:peer service"))?;
+          method_id.push('S');
+          method_id.push_str(&BaseEncoding::encode(&json, Base::Base64Url));
+        }
+      }
+    }
+
+    Self::parse(&format!("did:{}:{method_id}", Self::METHOD))
+  }
+
+  /// Returns the numalgo of this did:peer, i.e. 0 or 2.
+  pub fn numalgo(&self) -> u8 {
+    if self.method_id().starts_with('0') {
+      0
+    } else {
+      2
+    }
+  }
+
+  /// Returns the inception key of a numalgo 0 did:peer, or `None` for numalgo 2.
+  pub fn inception_key(&self) -> Option<DIDKey> {
+    self
+      .method_id()
+      .strip_prefix('0')
+      .map(|key| DIDKey::parse(&format!("did:{}:{key}", DIDKey::METHOD)).expect("did:peer encodes a valid key"))
+  }
+
+  /// Returns the elements encoded in a numalgo 2 did:peer, or an empty list for numalgo 0.
+  pub fn elements(&self) -> Vec<DIDPeerElement> {
+    decode_method_id(self.method_id()).expect("did:peer encodes valid elements")
+  }
+}
+
+/// Decodes the elements of a did:peer method specific id, validating the encoded keys and services.
+fn decode_method_id(method_id: &str) -> Result<Vec<DIDPeerElement>, Error> {
+  if let Some(key) = method_id.strip_prefix('0') {
+    DIDKey::parse(&format!("did:{}:{key}", DIDKey::METHOD)).map_err(|_| Error::InvalidMethodId)?;
+    return Ok(Vec::new());
+  }
+
+  let encoded_elements: &str = method_id.strip_prefix("2.").ok_or(Error::InvalidMethodId)?;
+  let mut elements: Vec<DIDPeerElement> = Vec::new();
+  for encoded_element in encoded_elements.split('.') {
+    let mut chars = encoded_element.chars();
+    let code: char = chars.next().ok_or(Error::InvalidMethodId)?;
+    let value: &str = chars.as_str();
+    if code == 'S' {
+      elements.extend(decode_services(value)?.into_iter().map(DIDPeerElement::Service));
+    } else {
+      let purpose: DIDPeerPurpose = DIDPeerPurpose::from_code(code).ok_or(Error::InvalidMethodId)?;
+      let key: DIDKey =
+        DIDKey::parse(&format!("did:{}:{value}", DIDKey::METHOD)).map_err(|_| Error::InvalidMethodId)?;
+      elements.push(DIDPeerElement::Key { purpose, key });
+    }
+  }
+
+  Ok(elements)
+}
+
+/// Decodes an encoded service element, which contains a single service or a list of services.
+fn decode_services(value: &str) -> Result<Vec<Object>, Error> {
+  let json: Vec<u8> = BaseEncoding::decode(value, Base::Base64Url).map_err(|_| Error::InvalidMethodId)?;
+  let services: Vec<Value> = match Value::from_json_slice(&json).map_err(|_| Error::InvalidMethodId)? {
+    Value::Array(services) => services,
+    service => vec![service],
+  };
+
+  services
+    .into_iter()
+    .map(|service| match expand(service) {
+      Value::Object(service) => Ok(service.into_iter().collect()),
+      _ => Err(Error::InvalidMethodId),
+    })
+    .collect()
+}
+
+/// Replaces the keys and `type` values of a service with their abbreviations.
+fn abbreviate(value: Value) -> Value {
+  replace_abbreviations(value, true)
+}
+
+/// Replaces the abbreviated keys and `type` values of a service with their full form.
+fn expand(value: Value) -> Value {
+  replace_abbreviations(value, false)
+}
+
+fn replace_abbreviations(value: Value, abbreviate: bool) -> Value {
+  let replace = |table: &[(&str, &str)], string: String| -> String {
+    table
+      .iter()
+      .map(|&(full, abbreviation)| {
+        if abbreviate {
+          (full, abbreviation)
+        } else {
+          (abbreviation, full)
+        }
+      })
+      .find(|(from, _)| *from == string)
+      .map_or(string, |(_, to)| to.to_owned())
+  };
+  let type_key: &str = if abbreviate { "type" } else { "t" };
+
+  match value {
+    Value::Object(object) => Value::Object(
+      object
+        .into_iter()
+        .map(|(key, value)| {
+          let value: Value = match value {
+            Value::String(type_) if key == type_key => Value::String(replace(&SERVICE_TYPE_ABBREVIATIONS, type_)),
+            value => replace_abbreviations(value, abbreviate),
+          };
+          (replace(&SERVICE_KEY_ABBREVIATIONS, key), value)
+        })
+        .collect(),
+    ),
+    Value::Array(values) => Value::Array(
+      values
+        .into_iter()
+        .map(|value| replace_abbreviations(value, abbreviate))
+        .collect(),
+    ),
+    value => value,
+  }
+}
+
+impl AsRef<CoreDID> for DIDPeer {
+  fn as_ref(&self) -> &CoreDID {
+    &self.0
+  }
+}
+
+impl From<DIDPeer> for CoreDID {
+  fn from(value: DIDPeer) -> Self {
+    value.0
+  }
+}
+
+impl<'a> TryFrom<&'a str> for DIDPeer {
+  type Error = Error;
+  fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+    value.parse()
+  }
+}
+
+impl Display for DIDPeer {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl FromStr for DIDPeer {
+  type Err = Error;
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    s.parse::<CoreDID>().and_then(TryFrom::try_from)
+  }
+}
+
+impl From<DIDPeer> for String {
+  fn from(value: DIDPeer) -> Self {
+    value.to_string()
+  }
+}
+
+impl TryFrom<CoreDID> for DIDPeer {
+  type Error = Error;
+  fn try_from(value: CoreDID) -> Result<Self, Self::Error> {
+    let Self::METHOD = value.method() else {
+      return Err(Error::InvalidMethodName);
+    };
+    decode_method_id(value.method_id()).map(|_| Self(value))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use serde_json::json;
+
+  use super::*;
+
+  const NUMALGO_2: &str = "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.Vz6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg.SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIiwiZGlkY29tbS9haXAyO2Vudj1yZmM1ODciXX0";
+
+  #[test]
+  fn test_numalgo_0() {
+    let key = DIDKey::parse("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").unwrap();
+    let did_peer: DIDPeer = DIDPeer::new_numalgo_0(&key);
+    assert_eq!(
+      did_peer.as_str(),
+      "did:peer:0z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+    );
+    assert_eq!(did_peer.numalgo(), 0);
+    assert_eq!(did_peer.inception_key(), Some(key));
+    assert!(did_peer.elements().is_empty());
+  }
+
+  #[test]
+  fn test_numalgo_2() {
+    let did_peer: DIDPeer = NUMALGO_2.parse().unwrap();
+    assert_eq!(did_peer.numalgo(), 2);
+    assert!(did_peer.inception_key().is_none());
+
+    let elements: Vec<DIDPeerElement> = did_peer.elements();
+    assert_eq!(elements.len(), 4);
+    assert!(matches!(
+      &elements[0],
+      DIDPeerElement::Key { purpose: DIDPeerPurpose::KeyAgreement, key } if key.multibase() == "z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc"
+    ));
+    assert!(matches!(
+      &elements[1],
+      DIDPeerElement::Key {
+        purpose: DIDPeerPurpose::Authentication,
+        ..
+      }
+    ));
+    let DIDPeerElement::Service(service) = &elements[3] else {
+      panic!("expected a service");
+    };
+    assert_eq!(
+      Value::Object(service.clone().into_iter().collect()),
+      json!({
+        "type": "DIDCommMessaging",
+        "serviceEndpoint": "https://example.com/endpoint",
+        "routingKeys": ["did:example:somemediator#somekey"],
+        "accept": ["didcomm/v2", "didcomm/aip2;env=rfc587"]
+      })
+    );
+
+    // The elements roundtrip, services are encoded with abbreviations.
+    let generated: DIDPeer = DIDPeer::new_numalgo_2(elements.clone()).unwrap();
+    assert_eq!(generated.elements(), elements);
+    assert!(generated.as_str().contains(".SeyJ"));
+    let service: &str = generated.method_id().rsplit('.').next().unwrap();
+    let json: Vec<u8> = BaseEncoding::decode(&service[1..], Base::Base64Url).unwrap();
+    let abbreviated: Value = Value::from_json_slice(&json).unwrap();
+    assert_eq!(abbreviated["t"], "dm");
+    assert_eq!(abbreviated["s"], "https://example.com/endpoint");
+  }
+
+  #[test]
+  fn test_service_list_and_endpoint_object() {
+    let services: Value = json!([
+      { "t": "dm", "s": { "uri": "https://example.com", "a": ["didcomm/v2"], "r": [] } },
+      { "t": "LinkedDomains", "s": "https://example.org" }
+    ]);
+    let encoded: String = BaseEncoding::encode(&services.to_json_vec().unwrap(), Base::Base64Url);
+    let did_peer: DIDPeer = DIDPeer::parse(&format!(
+      "did:peer:2.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.S{encoded}"
+    ))
+    .unwrap();
+
+    let services: Vec<Value> = did_peer
+      .elements()
+      .into_iter()
+      .filter_map(|element| match element {
+        DIDPeerElement::Service(service) => Some(Value::Object(service.into_iter().collect())),
+        _ => None,
+      })
+      .collect();
+    assert_eq!(
+      services,
+      vec![
+        json!({
+          "type": "DIDCommMessaging",
+          "serviceEndpoint": { "uri": "https://example.com", "accept": ["didcomm/v2"], "routingKeys": [] }
+        }),
+        json!({ "type": "LinkedDomains", "serviceEndpoint": "https://example.org" }),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_invalid_deserialization() {
+    for did in [
+      "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+      "did:peer:",
+      "did:peer:1zQmZMygzYqNwU6Uhmewx5Xepf2VLp5S4HLSwwgf2aiKZuwa",
+      "did:peer:0z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2do",
+      "did:peer:2",
+      "did:peer:2.",
+      "did:peer:2.Xz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V",
+      "did:peer:2.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.",
+      "did:peer:2.SeyJ0Ijoi",
+    ] {
+      assert!(did.parse::<DIDPeer>().is_err(), "{did}");
+    }
+  }
+}
@@ -19,6 +19,11 @@
 #[allow(clippy::module_inception)]
 mod did;
 mod did_jwk;
+#[cfg(feature = "did-key")]
+mod did_key;
+#[cfg(feature = "did-key")]
+mod did_peer;
+mod did_web;
 mod did_url;
 mod error;
 
@@ -28,4 +33,9 @@ pub use ::did_url_parser::DID as BaseDIDUrl;
 pub use did::CoreDID;
 pub use did::DID;
 pub use did_jwk::*;
+#[cfg(feature = "did-key")]
+pub use did_key::*;
+#[cfg(feature = "did-key")]
+pub use did_peer::*;
+pub use did_web::*;
 pub use error::Error;
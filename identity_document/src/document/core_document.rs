@@ -8,6 +8,14 @@ use std::collections::HashMap;
 use std::convert::Infallible;
 
 use identity_did::DIDJwk;
+#[cfg(feature = "did-key")]
+use identity_did::DIDKey;
+#[cfg(feature = "did-key")]
+use identity_did::DIDPeer;
+#[cfg(feature = "did-key")]
+use identity_did::DIDPeerElement;
+#[cfg(feature = "did-key")]
+use identity_did::DIDPeerPurpose;
 use identity_verification::jose::jwk::Jwk;
 use identity_verification::jose::jws::DecodedJws;
 use identity_verification::jose::jws::Decoder;
@@ -18,21 +26,33 @@ use identity_core::common::Object;
 use identity_core::common::OneOrSet;
 use identity_core::common::OrderedSet;
 use identity_core::common::Url;
+#[cfg(feature = "did-key")]
+use identity_core::common::Value;
 use identity_core::convert::FmtJson;
+#[cfg(feature = "did-key")]
+use identity_core::convert::FromJson;
 use serde::Serializer;
 
 use crate::document::DocumentBuilder;
 use crate::error::Error;
 use crate::error::Result;
 use crate::service::Service;
+#[cfg(feature = "did-key")]
+use crate::service::ServiceEndpoint;
 use crate::utils::DIDUrlQuery;
 use crate::utils::Queryable;
 use crate::verifiable::JwsVerificationOptions;
 use identity_did::CoreDID;
 use identity_did::DIDUrl;
+#[cfg(feature = "did-key")]
+use identity_did::DID;
+#[cfg(feature = "did-key")]
+use identity_verification::MethodData;
 use identity_verification::MethodRef;
 use identity_verification::MethodRelationship;
 use identity_verification::MethodScope;
+#[cfg(feature = "did-key")]
+use identity_verification::MethodType;
 use identity_verification::VerificationMethod;
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
@@ -690,7 +710,7 @@ impl CoreDocument {
     &'me self,
     method_query: Q,
     scope: Option<MethodScope>,
-  ) -> Option<&VerificationMethod>
+  ) -> Option<&'me VerificationMethod>
   where
     Q: Into<DIDUrlQuery<'query>>,
   {
@@ -773,7 +793,7 @@ impl CoreDocument {
   /// Returns the first [`Service`] with an `id` property matching the provided `service_query`, if present.
   // NOTE: This method demonstrates unexpected behavior in the edge cases where the document contains
   // services whose ids are of the form <did different from this document's>#<fragment>.
-  pub fn resolve_service<'query, 'me, Q>(&'me self, service_query: Q) -> Option<&Service>
+  pub fn resolve_service<'query, 'me, Q>(&'me self, service_query: Q) -> Option<&'me Service>
   where
     Q: Into<DIDUrlQuery<'query>>,
   {
@@ -1002,6 +1022,122 @@ impl CoreDocument {
   }
 }
 
+#[cfg(feature = "did-key")]
+impl CoreDocument {
+  /// Creates a [`CoreDocument`] from a did:key DID.
+  ///
+  /// For an Ed25519 key, an X25519 key agreement method derived from it is added as well.
+  pub fn expand_did_key(did_key: DIDKey) -> Result<Self, Error> {
+    Self::expand_key(did_key.clone().into(), &did_key)
+  }
+
+  /// Creates a [`CoreDocument`] from a did:peer DID using numalgo 0 or 2.
+  ///
+  /// The document of a numalgo 0 did:peer is expanded like the document of its inception key's did:key.
+  ///
+  /// The keys of a numalgo 2 did:peer are expanded to `Multikey` methods with the fragments `key-1`, `key-2`, ...
+  /// in the order of their appearance. Services without an `id` receive the fragments `service`, `service-1`, ...
+  ///
+  /// # Errors
+  ///
+  /// Fails with [`Error::InvalidService`] if a service endpoint cannot be represented as a [`ServiceEndpoint`],
+  /// e.g. a DIDComm service endpoint object with `uri`, `accept` and `routingKeys` entries.
+  pub fn expand_did_peer(did_peer: DIDPeer) -> Result<Self, Error> {
+    if let Some(inception_key) = did_peer.inception_key() {
+      return Self::expand_key(did_peer.into(), &inception_key);
+    }
+
+    let did: CoreDID = did_peer.clone().into();
+    let mut builder = DocumentBuilder::default().id(did.clone());
+    let mut key_index: usize = 0;
+    let mut service_index: usize = 0;
+    for element in did_peer.elements() {
+      match element {
+        DIDPeerElement::Key { purpose, key } => {
+          key_index += 1;
+          let method_id: DIDUrl = did
+            .to_url()
+            .join(format!("#key-{key_index}"))
+            .map_err(|err| Error::InvalidKeyMaterial(identity_verification::Error::DIDUrlConstructionError(err)))?;
+          let method: VerificationMethod = VerificationMethod::builder(Object::new())
+            .id(method_id.clone())
+            .controller(did.clone())
+            .type_(MethodType::MULTIKEY)
+            .data(MethodData::PublicKeyMultibase(key.multibase().to_owned()))
+            .build()
+            .map_err(Error::InvalidKeyMaterial)?;
+          builder = builder.verification_method(method);
+          builder = match purpose {
+            DIDPeerPurpose::AssertionMethod => builder.assertion_method(method_id),
+            DIDPeerPurpose::KeyAgreement => builder.key_agreement(method_id),
+            DIDPeerPurpose::Authentication => builder.authentication(method_id),
+            DIDPeerPurpose::CapabilityInvocation => builder.capability_invocation(method_id),
+            DIDPeerPurpose::CapabilityDelegation => builder.capability_delegation(method_id),
+            _ => return Err(Error::InvalidDocument("unsupported did:peer key purpose", None)),
+          };
+        }
+        DIDPeerElement::Service(service) => {
+          builder = builder.service(peer_service(&did, service, service_index)?);
+          service_index += 1;
+        }
+      }
+    }
+    builder.build()
+  }
+
+  /// Creates a [`CoreDocument`] with the given `id` from a did:key, see [`CoreDocument::expand_did_key`].
+  fn expand_key(id: CoreDID, did_key: &DIDKey) -> Result<Self, Error> {
+    let verification_method =
+      VerificationMethod::new_from_did_key(id.clone(), did_key).map_err(Error::InvalidKeyMaterial)?;
+    let verification_method_id = verification_method.id().clone();
+
+    let builder = DocumentBuilder::default()
+      .id(id.clone())
+      .verification_method(verification_method);
+    if did_key.key_type().is_key_agreement() {
+      return builder.key_agreement(verification_method_id).build();
+    }
+
+    let mut builder = builder
+      .assertion_method(verification_method_id.clone())
+      .authentication(verification_method_id.clone())
+      .capability_invocation(verification_method_id.clone())
+      .capability_delegation(verification_method_id.clone());
+    if let Some(key_agreement_key) = did_key.derive_key_agreement_key() {
+      let key_agreement_method =
+        VerificationMethod::new_from_did_key(id, &key_agreement_key).map_err(Error::InvalidKeyMaterial)?;
+      let key_agreement_method_id = key_agreement_method.id().clone();
+      builder = builder
+        .verification_method(key_agreement_method)
+        .key_agreement(key_agreement_method_id);
+    }
+    builder.build()
+  }
+}
+
+/// Converts the `index`-th service encoded in the did:peer `did` to a [`Service`].
+#[cfg(feature = "did-key")]
+fn peer_service(did: &CoreDID, mut service: Object, index: usize) -> Result<Service, Error> {
+  let id: String = match service.remove("id") {
+    Some(Value::String(id)) if id.starts_with('#') => format!("{did}{id}"),
+    Some(Value::String(id)) => id,
+    Some(_) => return Err(Error::InvalidService("invalid did:peer service id")),
+    None if index == 0 => format!("{did}#service"),
+    None => format!("{did}#service-{index}"),
+  };
+  service.insert("id".to_owned(), Value::String(id));
+
+  // Service endpoint objects other than maps of URL sets, e.g. DIDComm endpoints, cannot be represented without
+  // losing the distinction between the entries of the endpoint and the properties of the service.
+  if let Some(endpoint) = service.get("serviceEndpoint") {
+    ServiceEndpoint::from_json_value(endpoint.clone())
+      .map_err(|_| Error::InvalidService("did:peer service endpoint cannot be represented"))?;
+  }
+
+  Service::from_json_value(Value::Object(service.into_iter().collect()))
+    .map_err(|_| Error::InvalidService("invalid did:peer service"))
+}
+
 #[cfg(test)]
 mod tests {
   use identity_core::convert::FromJson;
@@ -1729,4 +1865,154 @@ mod tests {
 
     assert_eq!(CoreDocument::expand_did_jwk(did_jwk).unwrap(), target_doc);
   }
+
+  #[cfg(feature = "did-key")]
+  #[test]
+  fn test_did_key_expansion() {
+    let did_key = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+      .parse::<DIDKey>()
+      .unwrap();
+    let target_doc = serde_json::from_value(serde_json::json!({
+      "id": "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+      "verificationMethod": [
+        {
+          "id": "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+          "type": "Multikey",
+          "controller": "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+          "publicKeyMultibase": "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+        },
+        {
+          "id": "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p",
+          "type": "Multikey",
+          "controller": "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+          "publicKeyMultibase": "z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p"
+        }
+      ],
+      "authentication": ["did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"],
+      "assertionMethod": ["did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"],
+      "capabilityInvocation": ["did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"],
+      "capabilityDelegation": ["did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"],
+      "keyAgreement": ["did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK#z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p"]
+    })).unwrap();
+
+    assert_eq!(CoreDocument::expand_did_key(did_key).unwrap(), target_doc);
+  }
+
+  #[cfg(feature = "did-key")]
+  #[test]
+  fn test_did_key_expansion_key_agreement() {
+    let did_key = "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F"
+      .parse::<DIDKey>()
+      .unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_key(did_key).unwrap();
+
+    assert_eq!(document.methods(None).len(), 1);
+    assert_eq!(document.key_agreement().len(), 1);
+    assert!(document.authentication().is_empty());
+    assert!(document.assertion_method().is_empty());
+  }
+
+  #[cfg(feature = "did-key")]
+  #[test]
+  fn test_did_peer_numalgo_0_expansion() {
+    let did_key = DIDKey::parse("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").unwrap();
+    let did_peer = DIDPeer::new_numalgo_0(&did_key);
+    let document: CoreDocument = CoreDocument::expand_did_peer(did_peer.clone()).unwrap();
+    let did_key_document: CoreDocument = CoreDocument::expand_did_key(did_key).unwrap();
+
+    assert_eq!(document.id(), did_peer.as_ref());
+    assert_eq!(document.methods(None).len(), did_key_document.methods(None).len());
+    for (method, did_key_method) in document.methods(None).into_iter().zip(did_key_document.methods(None)) {
+      assert_eq!(method.id().fragment(), did_key_method.id().fragment());
+      assert_eq!(method.controller(), did_peer.as_ref());
+      assert_eq!(method.data(), did_key_method.data());
+    }
+    assert_eq!(document.key_agreement().len(), 1);
+  }
+
+  #[cfg(feature = "did-key")]
+  #[test]
+  fn test_did_peer_numalgo_2_expansion() {
+    let did_peer: DIDPeer = "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.Vz6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg.SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIiwiZGlkY29tbS9haXAyO2Vudj1yZmM1ODciXX0"
+      .parse()
+      .unwrap();
+    let document: CoreDocument = CoreDocument::expand_did_peer(did_peer.clone()).unwrap();
+
+    let fragments: Vec<&str> = document
+      .methods(None)
+      .into_iter()
+      .map(|method| method.id().fragment().unwrap())
+      .collect();
+    assert_eq!(fragments, ["key-1", "key-2", "key-3"]);
+    assert_eq!(
+      document.methods(None)[0].data(),
+      &MethodData::PublicKeyMultibase("z6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc".to_owned())
+    );
+    assert_eq!(document.key_agreement().len(), 1);
+    assert_eq!(document.authentication().len(), 2);
+    assert!(document.assertion_method().is_empty());
+
+    let service: &Service = document.service().first().unwrap();
+    assert_eq!(service.id().to_string(), format!("{did_peer}#service"));
+    assert_eq!(service.type_().as_slice(), ["DIDCommMessaging"]);
+    assert_eq!(
+      service.service_endpoint(),
+      &ServiceEndpoint::One(Url::parse("https://example.com/endpoint").unwrap())
+    );
+    assert_eq!(
+      service.properties().get("accept").unwrap(),
+      &serde_json::json!(["didcomm/v2", "didcomm/aip2;env=rfc587"])
+    );
+  }
+
+  #[cfg(feature = "did-key")]
+  #[test]
+  fn test_did_peer_service_expansion() {
+    let key: DIDKey = DIDKey::parse("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK").unwrap();
+    let did_peer = |services: serde_json::Value| -> DIDPeer {
+      let services: Vec<Object> = serde_json::from_value(services).unwrap();
+      let mut elements: Vec<DIDPeerElement> = vec![DIDPeerElement::Key {
+        purpose: DIDPeerPurpose::Authentication,
+        key: key.clone(),
+      }];
+      elements.extend(services.into_iter().map(DIDPeerElement::Service));
+      DIDPeer::new_numalgo_2(elements).unwrap()
+    };
+
+    let document: CoreDocument = CoreDocument::expand_did_peer(did_peer(serde_json::json!([
+      { "type": "DIDCommMessaging", "serviceEndpoint": "https://example.com", "routingKeys": [] },
+      { "id": "#linked", "type": "LinkedDomains", "serviceEndpoint": "https://example.org" },
+      { "type": "LinkedDomains", "serviceEndpoint": { "origins": ["https://example.net"] } }
+    ])))
+    .unwrap();
+
+    let fragments: Vec<&str> = document
+      .service()
+      .iter()
+      .map(|service| service.id().fragment().unwrap())
+      .collect();
+    assert_eq!(fragments, ["service", "linked", "service-2"]);
+    assert_eq!(
+      document
+        .service()
+        .first()
+        .unwrap()
+        .properties()
+        .get("routingKeys")
+        .unwrap(),
+      &serde_json::json!([])
+    );
+    assert!(matches!(
+      document.service()[2].service_endpoint(),
+      ServiceEndpoint::Map(_)
+    ));
+
+    // INVALID: a DIDComm service endpoint object cannot be represented as a service endpoint.
+    let err: Error = CoreDocument::expand_did_peer(did_peer(serde_json::json!([{
+      "type": "DIDCommMessaging",
+      "serviceEndpoint": { "uri": "https://example.com", "accept": ["didcomm/v2"], "routingKeys": [] }
+    }])))
+    .unwrap_err();
+    assert!(matches!(err, Error::InvalidService(_)));
+  }
 }
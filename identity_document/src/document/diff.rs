@@ -0,0 +1,481 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+
+use identity_core::common::KeyComparable;
+use identity_core::common::Object;
+use identity_core::common::OneOrSet;
+use identity_core::common::OrderedSet;
+use identity_core::common::Url;
+use identity_core::convert::FmtJson;
+use identity_did::CoreDID;
+use identity_verification::MethodRef;
+use identity_verification::VerificationMethod;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::document::CoreDocument;
+use crate::error::Error;
+use crate::error::Result;
+use crate::service::Service;
+
+/// The entries added to and removed from a set of a DID document.
+///
+/// An entry whose content changed between two documents is listed as removed with its previous content and as
+/// added with its new content.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SetDiff<T> {
+  #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+  added: Vec<T>,
+  #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+  removed: Vec<T>,
+}
+
+impl<T> SetDiff<T> {
+  /// Returns the added entries.
+  pub fn added(&self) -> &[T] {
+    &self.added
+  }
+
+  /// Returns the removed entries.
+  pub fn removed(&self) -> &[T] {
+    &self.removed
+  }
+
+  /// Returns whether no entries were added or removed.
+  pub fn is_empty(&self) -> bool {
+    self.added.is_empty() && self.removed.is_empty()
+  }
+}
+
+impl<T> Default for SetDiff<T> {
+  fn default() -> Self {
+    Self {
+      added: Vec::new(),
+      removed: Vec::new(),
+    }
+  }
+}
+
+impl<T> SetDiff<T>
+where
+  T: KeyComparable + PartialEq + Clone,
+{
+  fn new(old: &[T], new: &[T]) -> Self {
+    Self {
+      added: new.iter().filter(|entry| !old.contains(entry)).cloned().collect(),
+      removed: old.iter().filter(|entry| !new.contains(entry)).cloned().collect(),
+    }
+  }
+
+  /// Applies the diff to `set`, replacing changed entries in place and appending added entries.
+  fn patch(&self, set: &mut OrderedSet<T>, field: &'static str) -> Result<()> {
+    if !self
+      .removed
+      .iter()
+      .all(|entry| set.iter().any(|current| current == entry))
+    {
+      return Err(Error::InvalidDocumentDiff(field));
+    }
+
+    let is_changed = |entry: &T| self.added.iter().any(|added| added.key() == entry.key());
+    for entry in self.removed.iter().filter(|entry| !is_changed(entry)) {
+      set.remove(entry);
+    }
+    for entry in self.added.iter().cloned() {
+      let is_replacement: bool = self.removed.iter().any(|removed| removed.key() == entry.key());
+      let updated: bool = if is_replacement {
+        set.update(entry)
+      } else {
+        set.append(entry)
+      };
+      if !updated {
+        return Err(Error::InvalidDocumentDiff(field));
+      }
+    }
+    Ok(())
+  }
+}
+
+/// The custom properties added to and removed from a DID document.
+///
+/// A property whose value changed between two documents is listed as removed with its previous value and as added
+/// with its new value.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct PropertiesDiff {
+  #[serde(default = "Object::new", skip_serializing_if = "Object::is_empty")]
+  added: Object,
+  #[serde(default = "Object::new", skip_serializing_if = "Object::is_empty")]
+  removed: Object,
+}
+
+impl PropertiesDiff {
+  fn new(old: &Object, new: &Object) -> Self {
+    let difference = |this: &Object, other: &Object| -> Object {
+      this
+        .iter()
+        .filter(|(key, value)| other.get(*key) != Some(value))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+    };
+
+    Self {
+      added: difference(new, old),
+      removed: difference(old, new),
+    }
+  }
+
+  /// Returns the added properties.
+  pub fn added(&self) -> &Object {
+    &self.added
+  }
+
+  /// Returns the removed properties.
+  pub fn removed(&self) -> &Object {
+    &self.removed
+  }
+
+  /// Returns whether no properties were added or removed.
+  pub fn is_empty(&self) -> bool {
+    self.added.is_empty() && self.removed.is_empty()
+  }
+
+  fn patch(&self, properties: &mut Object) -> Result<()> {
+    if !self
+      .removed
+      .iter()
+      .all(|(key, value)| properties.get(key) == Some(value))
+    {
+      return Err(Error::InvalidDocumentDiff("properties"));
+    }
+
+    for key in self.removed.keys() {
+      properties.remove(key);
+    }
+    for (key, value) in self.added.iter() {
+      if properties.insert(key.clone(), value.clone()).is_some() {
+        return Err(Error::InvalidDocumentDiff("properties"));
+      }
+    }
+    Ok(())
+  }
+}
+
+/// The changes between two versions of a DID document.
+///
+/// A diff is created with [`CoreDocument::diff`] and applied with [`CoreDocument::patch`]. It lists the controllers,
+/// `alsoKnownAs` entries, verification methods, verification relationships, services and custom properties that
+/// were added or removed, and can be serialized to JSON, e.g. to review an update before it is published.
+///
+/// The `id` of the documents is not part of the diff.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoreDocumentDiff {
+  #[serde(default, skip_serializing_if = "SetDiff::is_empty")]
+  controller: SetDiff<CoreDID>,
+  #[serde(default, skip_serializing_if = "SetDiff::is_empty")]
+  also_known_as: SetDiff<Url>,
+  #[serde(default, skip_serializing_if = "SetDiff::is_empty")]
+  verification_method: SetDiff<VerificationMethod>,
+  #[serde(default, skip_serializing_if = "SetDiff::is_empty")]
+  authentication: SetDiff<MethodRef>,
+  #[serde(default, skip_serializing_if = "SetDiff::is_empty")]
+  assertion_method: SetDiff<MethodRef>,
+  #[serde(default, skip_serializing_if = "SetDiff::is_empty")]
+  key_agreement: SetDiff<MethodRef>,
+  #[serde(default, skip_serializing_if = "SetDiff::is_empty")]
+  capability_delegation: SetDiff<MethodRef>,
+  #[serde(default, skip_serializing_if = "SetDiff::is_empty")]
+  capability_invocation: SetDiff<MethodRef>,
+  #[serde(default, skip_serializing_if = "SetDiff::is_empty")]
+  service: SetDiff<Service>,
+  #[serde(default, skip_serializing_if = "PropertiesDiff::is_empty")]
+  properties: PropertiesDiff,
+}
+
+impl CoreDocumentDiff {
+  /// Computes the changes from the `old` to the `new` document.
+  pub fn new(old: &CoreDocument, new: &CoreDocument) -> Self {
+    Self {
+      controller: SetDiff::new(&controllers(old), &controllers(new)),
+      also_known_as: SetDiff::new(old.also_known_as(), new.also_known_as()),
+      verification_method: SetDiff::new(old.verification_method(), new.verification_method()),
+      authentication: SetDiff::new(old.authentication(), new.authentication()),
+      assertion_method: SetDiff::new(old.assertion_method(), new.assertion_method()),
+      key_agreement: SetDiff::new(old.key_agreement(), new.key_agreement()),
+      capability_delegation: SetDiff::new(old.capability_delegation(), new.capability_delegation()),
+      capability_invocation: SetDiff::new(old.capability_invocation(), new.capability_invocation()),
+      service: SetDiff::new(old.service(), new.service()),
+      properties: PropertiesDiff::new(old.properties(), new.properties()),
+    }
+  }
+
+  /// Returns the changes of the `controller` set.
+  pub fn controller(&self) -> &SetDiff<CoreDID> {
+    &self.controller
+  }
+
+  /// Returns the changes of the `alsoKnownAs` set.
+  pub fn also_known_as(&self) -> &SetDiff<Url> {
+    &self.also_known_as
+  }
+
+  /// Returns the changes of the `verificationMethod` set.
+  pub fn verification_method(&self) -> &SetDiff<VerificationMethod> {
+    &self.verification_method
+  }
+
+  /// Returns the changes of the `authentication` set.
+  pub fn authentication(&self) -> &SetDiff<MethodRef> {
+    &self.authentication
+  }
+
+  /// Returns the changes of the `assertionMethod` set.
+  pub fn assertion_method(&self) -> &SetDiff<MethodRef> {
+    &self.assertion_method
+  }
+
+  /// Returns the changes of the `keyAgreement` set.
+  pub fn key_agreement(&self) -> &SetDiff<MethodRef> {
+    &self.key_agreement
+  }
+
+  /// Returns the changes of the `capabilityDelegation` set.
+  pub fn capability_delegation(&self) -> &SetDiff<MethodRef> {
+    &self.capability_delegation
+  }
+
+  /// Returns the changes of the `capabilityInvocation` set.
+  pub fn capability_invocation(&self) -> &SetDiff<MethodRef> {
+    &self.capability_invocation
+  }
+
+  /// Returns the changes of the `service` set.
+  pub fn service(&self) -> &SetDiff<Service> {
+    &self.service
+  }
+
+  /// Returns the changes of the custom properties.
+  pub fn properties(&self) -> &PropertiesDiff {
+    &self.properties
+  }
+
+  /// Returns whether the documents are equal, apart from their `id`.
+  pub fn is_empty(&self) -> bool {
+    self.controller.is_empty()
+      && self.also_known_as.is_empty()
+      && self.verification_method.is_empty()
+      && self.authentication.is_empty()
+      && self.assertion_method.is_empty()
+      && self.key_agreement.is_empty()
+      && self.capability_delegation.is_empty()
+      && self.capability_invocation.is_empty()
+      && self.service.is_empty()
+      && self.properties.is_empty()
+  }
+}
+
+impl Display for CoreDocumentDiff {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    self.fmt_json(f)
+  }
+}
+
+fn controllers(document: &CoreDocument) -> Vec<CoreDID> {
+  document
+    .controller()
+    .map(|controllers| controllers.iter().cloned().collect())
+    .unwrap_or_default()
+}
+
+impl CoreDocument {
+  /// Returns the changes from this document to `other`, see [`CoreDocumentDiff`].
+  pub fn diff(&self, other: &CoreDocument) -> CoreDocumentDiff {
+    CoreDocumentDiff::new(self, other)
+  }
+
+  /// Applies the changes of `diff` to this document.
+  ///
+  /// Changed entries keep their position, while added entries are appended to their set.
+  ///
+  /// # Errors
+  ///
+  /// Fails without modifying the document if a removed entry is not part of this document, i.e. the diff was not
+  /// computed from this version of the document, if an added entry already exists, or if the patched document is
+  /// invalid.
+  pub fn patch(&mut self, diff: &CoreDocumentDiff) -> Result<()> {
+    let mut data = self.data.clone();
+
+    let mut controller: OrderedSet<CoreDID> = data.controller.take().map(OrderedSet::from).unwrap_or_default();
+    diff.controller.patch(&mut controller, "controller")?;
+    data.controller = Some(controller)
+      .filter(|controller| !controller.is_empty())
+      .map(|controller| OneOrSet::new_set(controller).expect("controller is checked to be not empty"));
+    diff.also_known_as.patch(&mut data.also_known_as, "alsoKnownAs")?;
+    diff
+      .verification_method
+      .patch(&mut data.verification_method, "verificationMethod")?;
+    diff.authentication.patch(&mut data.authentication, "authentication")?;
+    diff
+      .assertion_method
+      .patch(&mut data.assertion_method, "assertionMethod")?;
+    diff.key_agreement.patch(&mut data.key_agreement, "keyAgreement")?;
+    diff
+      .capability_delegation
+      .patch(&mut data.capability_delegation, "capabilityDelegation")?;
+    diff
+      .capability_invocation
+      .patch(&mut data.capability_invocation, "capabilityInvocation")?;
+    diff.service.patch(&mut data.service, "service")?;
+    diff.properties.patch(&mut data.properties)?;
+
+    *self = CoreDocument::try_from(data)?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use identity_core::convert::FromJson;
+  use identity_core::convert::ToJson;
+  use identity_did::DIDUrl;
+  use identity_verification::MethodRelationship;
+  use identity_verification::MethodScope;
+
+  use super::*;
+  use crate::document::DocumentBuilder;
+  use crate::service::ServiceEndpoint;
+
+  fn document() -> CoreDocument {
+    CoreDocument::from_json_value(serde_json::json!({
+      "id": "did:example:123",
+      "controller": "did:example:controller",
+      "verificationMethod": [
+        {
+          "id": "did:example:123#key-1",
+          "controller": "did:example:123",
+          "type": "Multikey",
+          "publicKeyMultibase": "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+        },
+        {
+          "id": "did:example:123#key-2",
+          "controller": "did:example:123",
+          "type": "Multikey",
+          "publicKeyMultibase": "z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V"
+        }
+      ],
+      "authentication": ["did:example:123#key-1"],
+      "service": [
+        {
+          "id": "did:example:123#linked",
+          "type": "LinkedDomains",
+          "serviceEndpoint": "https://example.com"
+        }
+      ],
+      "custom": true
+    }))
+    .unwrap()
+  }
+
+  fn updated_document() -> CoreDocument {
+    let mut document: CoreDocument = document();
+    *document.controller_mut() = None;
+    document
+      .remove_method(&DIDUrl::parse("did:example:123#key-2").unwrap())
+      .unwrap();
+    document
+      .attach_method_relationship(
+        DIDUrl::parse("did:example:123#key-1").unwrap(),
+        MethodRelationship::AssertionMethod,
+      )
+      .unwrap();
+    let service: &Service = document.service().head().unwrap();
+    let service: Service = Service::builder(Object::new())
+      .id(service.id().clone())
+      .type_("LinkedDomains")
+      .service_endpoint(ServiceEndpoint::One(Url::parse("https://example.org").unwrap()))
+      .build()
+      .unwrap();
+    document.service_mut_unchecked().update(service);
+    document
+      .properties_mut_unchecked()
+      .insert("custom".to_owned(), false.into());
+    document
+  }
+
+  #[test]
+  fn test_diff() {
+    let document: CoreDocument = document();
+    assert!(document.diff(&document).is_empty());
+
+    let diff: CoreDocumentDiff = document.diff(&updated_document());
+    assert_eq!(diff.controller().removed().len(), 1);
+    assert!(diff.controller().added().is_empty());
+    assert!(diff.verification_method().added().is_empty());
+    assert_eq!(diff.verification_method().removed()[0].id().fragment(), Some("key-2"));
+    assert_eq!(diff.assertion_method().added().len(), 1);
+    assert!(diff.authentication().is_empty());
+    assert_eq!(diff.service().added().len(), 1);
+    assert_eq!(diff.service().removed().len(), 1);
+    assert_eq!(diff.properties().added().get("custom"), Some(&false.into()));
+    assert_eq!(diff.properties().removed().get("custom"), Some(&true.into()));
+  }
+
+  #[test]
+  fn test_patch() {
+    let document: CoreDocument = document();
+    let updated: CoreDocument = updated_document();
+    let diff: CoreDocumentDiff = document.diff(&updated);
+
+    let mut patched: CoreDocument = document.clone();
+    patched.patch(&diff).unwrap();
+    assert_eq!(patched, updated);
+
+    // The inverse diff restores the original document.
+    patched.patch(&updated.diff(&document)).unwrap();
+    assert_eq!(patched, document);
+
+    // The diff survives a JSON roundtrip.
+    let json: String = diff.to_json().unwrap();
+    assert_eq!(CoreDocumentDiff::from_json(&json).unwrap(), diff);
+    assert!(json.contains("\"verificationMethod\":{\"removed\""));
+  }
+
+  #[test]
+  fn test_patch_stale_document() {
+    let document: CoreDocument = document();
+    let diff: CoreDocumentDiff = document.diff(&updated_document());
+
+    // The diff was computed against a different version of the document.
+    let mut updated: CoreDocument = updated_document();
+    assert!(matches!(
+      updated.patch(&diff).unwrap_err(),
+      Error::InvalidDocumentDiff("controller")
+    ));
+    assert_eq!(updated, updated_document());
+
+    // Adding a method whose id is already in use by a service fails.
+    let empty: CoreDocument = DocumentBuilder::default().id(document.id().clone()).build().unwrap();
+    let method: VerificationMethod = document.verification_method().head().unwrap().clone();
+    let mut with_method: CoreDocument = empty.clone();
+    with_method
+      .insert_method(method, MethodScope::VerificationMethod)
+      .unwrap();
+    let mut with_service: CoreDocument = empty.clone();
+    with_service
+      .insert_service(
+        Service::builder(Object::new())
+          .id(DIDUrl::parse("did:example:123#key-1").unwrap())
+          .type_("LinkedDomains")
+          .service_endpoint(ServiceEndpoint::One(Url::parse("https://example.com").unwrap()))
+          .build()
+          .unwrap(),
+      )
+      .unwrap();
+    let mut patched: CoreDocument = with_service.clone();
+    assert!(patched.patch(&empty.diff(&with_method)).is_err());
+    assert_eq!(patched, with_service);
+  }
+}
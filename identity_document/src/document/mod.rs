@@ -7,6 +7,10 @@
 
 pub use self::builder::DocumentBuilder;
 pub use self::core_document::CoreDocument;
+pub use self::diff::CoreDocumentDiff;
+pub use self::diff::PropertiesDiff;
+pub use self::diff::SetDiff;
 
 mod builder;
 mod core_document;
+mod diff;
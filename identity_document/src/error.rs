@@ -39,4 +39,7 @@ pub enum Error {
   /// Caused by a failure to verify a JSON Web Signature.
   #[error("jws verification failed")]
   JwsVerificationError(#[source] identity_verification::jose::error::Error),
+  /// Caused by a [`CoreDocumentDiff`](crate::document::CoreDocumentDiff) that can not be applied to a document.
+  #[error("unable to apply the document diff: conflicting `{0}`")]
+  InvalidDocumentDiff(&'static str),
 }
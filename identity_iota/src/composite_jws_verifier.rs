@@ -0,0 +1,232 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use identity_verification::jwk::Jwk;
+use identity_verification::jws::JwsAlgorithm;
+use identity_verification::jws::JwsVerifier;
+use identity_verification::jws::SignatureVerificationError;
+use identity_verification::jws::SignatureVerificationErrorKind;
+use identity_verification::jws::VerificationInput;
+
+type Handler = Box<dyn JwsVerifier + Send + Sync>;
+
+/// An implementor of [`JwsVerifier`] that dispatches the verification of a JWS to the handler
+/// registered for its `alg`.
+///
+/// [`CompositeJwsVerifier::new`] registers the verifiers shipped with IOTA Identity for the
+/// algorithms enabled through the following features:
+///
+/// - `ed25519`: [`JwsAlgorithm::EdDSA`].
+/// - `es256`: [`JwsAlgorithm::ES256`].
+/// - `es256k`: [`JwsAlgorithm::ES256K`].
+/// - `rsa`: [`JwsAlgorithm::RS256`], [`JwsAlgorithm::RS384`], [`JwsAlgorithm::RS512`],
+///   [`JwsAlgorithm::PS256`], [`JwsAlgorithm::PS384`] and [`JwsAlgorithm::PS512`].
+///
+/// Handlers for further algorithms, including custom ones when the `custom-alg` feature is enabled,
+/// can be attached with [`CompositeJwsVerifier::attach_handler`].
+///
+/// Verifying a JWS whose `alg` has no registered handler fails with
+/// [`SignatureVerificationErrorKind::UnsupportedAlg`].
+pub struct CompositeJwsVerifier {
+  handlers: HashMap<JwsAlgorithm, Handler>,
+}
+
+impl CompositeJwsVerifier {
+  /// Constructs a new [`CompositeJwsVerifier`] with a handler for each algorithm enabled through the
+  /// crate features.
+  pub fn new() -> Self {
+    #[allow(unused_mut)]
+    let mut verifier: Self = Self::empty();
+
+    #[cfg(feature = "ed25519")]
+    verifier.attach_handler(
+      JwsAlgorithm::EdDSA,
+      identity_eddsa_verifier::EdDSAJwsVerifier::default(),
+    );
+    #[cfg(feature = "es256")]
+    verifier.attach_handler(
+      JwsAlgorithm::ES256,
+      identity_ecdsa_verifier::EcDSAJwsVerifier::default(),
+    );
+    #[cfg(feature = "es256k")]
+    verifier.attach_handler(
+      JwsAlgorithm::ES256K,
+      identity_ecdsa_verifier::EcDSAJwsVerifier::default(),
+    );
+    #[cfg(feature = "rsa")]
+    for alg in [
+      JwsAlgorithm::RS256,
+      JwsAlgorithm::RS384,
+      JwsAlgorithm::RS512,
+      JwsAlgorithm::PS256,
+      JwsAlgorithm::PS384,
+      JwsAlgorithm::PS512,
+    ] {
+      verifier.attach_handler(alg, identity_rsa_verifier::RsaJwsVerifier::default());
+    }
+
+    verifier
+  }
+
+  /// Constructs a new [`CompositeJwsVerifier`] without any handlers.
+  pub fn empty() -> Self {
+    Self {
+      handlers: HashMap::new(),
+    }
+  }
+
+  /// Attaches a `handler` verifying JWS with the given `alg`.
+  ///
+  /// If a handler was already registered for `alg` it is replaced.
+  ///
+  /// Closures can be attached by converting them into a
+  /// [`JwsVerifierFn`](identity_verification::jws::JwsVerifierFn).
+  pub fn attach_handler<V>(&mut self, alg: JwsAlgorithm, handler: V)
+  where
+    V: JwsVerifier + Send + Sync + 'static,
+  {
+    self.handlers.insert(alg, Box::new(handler));
+  }
+
+  /// Returns whether a handler is registered for `alg`.
+  pub fn supports(&self, alg: &JwsAlgorithm) -> bool {
+    self.handlers.contains_key(alg)
+  }
+}
+
+impl Default for CompositeJwsVerifier {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl JwsVerifier for CompositeJwsVerifier {
+  fn verify(&self, input: VerificationInput, public_key: &Jwk) -> Result<(), SignatureVerificationError> {
+    match self.handlers.get(&input.alg) {
+      Some(handler) => handler.verify(input, public_key),
+      None => Err(SignatureVerificationErrorKind::UnsupportedAlg.into()),
+    }
+  }
+}
+
+impl std::fmt::Debug for CompositeJwsVerifier {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut algorithms: Vec<&JwsAlgorithm> = self.handlers.keys().collect();
+    algorithms.sort();
+    f.debug_struct("CompositeJwsVerifier")
+      .field("algorithms", &algorithms)
+      .finish()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::atomic::AtomicUsize;
+  use std::sync::atomic::Ordering;
+  use std::sync::Arc;
+
+  use identity_core::convert::FromJson;
+  use identity_verification::jwk::Jwk;
+  use identity_verification::jws::JwsAlgorithm;
+  use identity_verification::jws::JwsVerifier;
+  use identity_verification::jws::JwsVerifierFn;
+  use identity_verification::jws::SignatureVerificationError;
+  use identity_verification::jws::SignatureVerificationErrorKind;
+  use identity_verification::jws::VerificationInput;
+  use identity_verification::jwu;
+
+  use super::CompositeJwsVerifier;
+
+  // Test vector taken from https://datatracker.ietf.org/doc/html/rfc8037#appendix-A.4.
+  const ED25519_PUBLIC_KEY: &str = r#"{"kty":"OKP","crv":"Ed25519","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#;
+  const ED25519_SIGNING_INPUT: &[u8] = b"eyJhbGciOiJFZERTQSJ9.RXhhbXBsZSBvZiBFZDI1NTE5IHNpZ25pbmc";
+  const ED25519_SIGNATURE: &str =
+    "hgyY0il_MGCjP0JzlnLWG1PPOt7-09PGcvMg3AIbQR6dWbhijcNR4ki4iylGjg5BhVsPt9g7sVvpAr_MuM0KAg";
+
+  fn ed25519_input(alg: JwsAlgorithm) -> VerificationInput {
+    VerificationInput {
+      alg,
+      signing_input: ED25519_SIGNING_INPUT.into(),
+      decoded_signature: jwu::decode_b64(ED25519_SIGNATURE).unwrap().into(),
+    }
+  }
+
+  fn counting_handler(
+    counter: &Arc<AtomicUsize>,
+  ) -> JwsVerifierFn<impl Fn(VerificationInput, &Jwk) -> Result<(), SignatureVerificationError>> {
+    let counter: Arc<AtomicUsize> = counter.clone();
+    JwsVerifierFn::from(move |_: VerificationInput, _: &Jwk| {
+      counter.fetch_add(1, Ordering::SeqCst);
+      Ok(())
+    })
+  }
+
+  #[test]
+  fn unregistered_alg_is_unsupported() {
+    let public_key: Jwk = Jwk::from_json(ED25519_PUBLIC_KEY).unwrap();
+    let verifier = CompositeJwsVerifier::empty();
+
+    assert!(!verifier.supports(&JwsAlgorithm::EdDSA));
+    let err = verifier
+      .verify(ed25519_input(JwsAlgorithm::EdDSA), &public_key)
+      .unwrap_err();
+    assert!(matches!(err.kind(), SignatureVerificationErrorKind::UnsupportedAlg));
+  }
+
+  #[cfg(feature = "ed25519")]
+  #[test]
+  fn dispatches_to_shipped_verifiers() {
+    let public_key: Jwk = Jwk::from_json(ED25519_PUBLIC_KEY).unwrap();
+    let verifier = CompositeJwsVerifier::new();
+
+    assert!(verifier.supports(&JwsAlgorithm::EdDSA));
+    verifier
+      .verify(ed25519_input(JwsAlgorithm::EdDSA), &public_key)
+      .unwrap();
+
+    // INVALID: the input is routed to the handler of its alg.
+    let err = verifier
+      .verify(ed25519_input(JwsAlgorithm::HS256), &public_key)
+      .unwrap_err();
+    assert!(matches!(err.kind(), SignatureVerificationErrorKind::UnsupportedAlg));
+  }
+
+  #[test]
+  fn attached_handlers_take_precedence() {
+    let public_key: Jwk = Jwk::from_json(ED25519_PUBLIC_KEY).unwrap();
+    let counter: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+    let mut verifier = CompositeJwsVerifier::new();
+
+    verifier.attach_handler(JwsAlgorithm::HS256, counting_handler(&counter));
+    verifier.attach_handler(JwsAlgorithm::EdDSA, counting_handler(&counter));
+
+    verifier
+      .verify(ed25519_input(JwsAlgorithm::HS256), &public_key)
+      .unwrap();
+    verifier
+      .verify(ed25519_input(JwsAlgorithm::EdDSA), &public_key)
+      .unwrap();
+    assert_eq!(counter.load(Ordering::SeqCst), 2);
+  }
+
+  #[cfg(feature = "custom-alg")]
+  #[test]
+  fn custom_alg_handler() {
+    let public_key: Jwk = Jwk::from_json(ED25519_PUBLIC_KEY).unwrap();
+    let counter: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+    let mut verifier = CompositeJwsVerifier::new();
+
+    let custom_alg: JwsAlgorithm = JwsAlgorithm::Custom("test-alg".to_owned());
+    verifier.attach_handler(custom_alg.clone(), counting_handler(&counter));
+    assert!(verifier.supports(&custom_alg));
+
+    verifier.verify(ed25519_input(custom_alg), &public_key).unwrap();
+    let err = verifier
+      .verify(ed25519_input(JwsAlgorithm::Custom("other-alg".to_owned())), &public_key)
+      .unwrap_err();
+    assert!(matches!(err.kind(), SignatureVerificationErrorKind::UnsupportedAlg));
+    assert_eq!(counter.load(Ordering::SeqCst), 1);
+  }
+}
@@ -40,6 +40,8 @@ pub mod credential {
   pub use identity_credential::presentation::*;
   #[cfg(feature = "revocation-bitmap")]
   pub use identity_credential::revocation::*;
+  #[cfg(feature = "sd-jwt")]
+  pub use identity_credential::sd_jwt_vc::*;
   pub use identity_credential::validator::*;
 }
 
@@ -98,9 +100,22 @@ pub mod resolver {
   pub use identity_resolver::*;
 }
 
+mod composite_jws_verifier;
+
 pub mod verification {
   //! Types for verifiable data
   pub use identity_verification::*;
+
+  pub use crate::composite_jws_verifier::CompositeJwsVerifier;
+  #[cfg(any(feature = "es256", feature = "es256k"))]
+  #[cfg_attr(docsrs, doc(cfg(any(feature = "es256", feature = "es256k"))))]
+  pub use identity_ecdsa_verifier::*;
+  #[cfg(feature = "ed25519")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "ed25519")))]
+  pub use identity_eddsa_verifier::*;
+  #[cfg(feature = "rsa")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "rsa")))]
+  pub use identity_rsa_verifier::*;
 }
 
 pub mod storage {
@@ -118,6 +133,13 @@ pub mod storage {
   pub mod storage {
     pub use identity_storage::storage::*;
   }
+  /// Encrypted, file-backed storage.
+  #[cfg(feature = "file-storage")]
+  pub mod file_storage {
+    pub use identity_storage::file_storage::*;
+  }
+  #[cfg(feature = "file-storage")]
+  pub use identity_storage::file_storage::*;
   pub use identity_storage::key_id_storage::*;
   pub use identity_storage::key_storage::*;
   pub use identity_storage::storage::*;
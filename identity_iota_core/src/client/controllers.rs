@@ -0,0 +1,280 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use identity_did::DIDUrl;
+use identity_verification::MethodScope;
+use identity_verification::VerificationMethod;
+
+use crate::IotaDID;
+use crate::IotaDocument;
+
+/// The default maximum depth of controllers resolved by
+/// [`IotaIdentityClientExt::resolve_did_with_controllers`](crate::IotaIdentityClientExt::resolve_did_with_controllers).
+pub const DEFAULT_MAX_CONTROLLER_DEPTH: usize = 4;
+
+/// An IOTA DID Document together with the documents of its (transitive) controllers.
+///
+/// Obtained from
+/// [`IotaIdentityClientExt::resolve_did_with_controllers`](crate::IotaIdentityClientExt::resolve_did_with_controllers).
+#[derive(Clone, Debug)]
+pub struct IotaControllerGraph {
+  did: IotaDID,
+  documents: HashMap<IotaDID, IotaDocument>,
+  unresolved: Vec<IotaDID>,
+}
+
+impl IotaControllerGraph {
+  /// Creates a graph containing only `document`.
+  pub(crate) fn new(document: IotaDocument) -> Self {
+    let did: IotaDID = document.id().clone();
+    let mut documents: HashMap<IotaDID, IotaDocument> = HashMap::new();
+    documents.insert(did.clone(), document);
+    Self {
+      did,
+      documents,
+      unresolved: Vec::new(),
+    }
+  }
+
+  /// Returns whether the document of `did` is part of the graph.
+  pub(crate) fn contains(&self, did: &IotaDID) -> bool {
+    self.documents.contains_key(did)
+  }
+
+  /// Adds the document of a controller to the graph.
+  pub(crate) fn insert(&mut self, document: IotaDocument) {
+    self.unresolved.retain(|did| did != document.id());
+    self.documents.insert(document.id().clone(), document);
+  }
+
+  /// Records a controller that was not resolved because it exceeds the maximum depth.
+  pub(crate) fn insert_unresolved(&mut self, did: IotaDID) {
+    if !self.unresolved.contains(&did) {
+      self.unresolved.push(did);
+    }
+  }
+
+  /// Returns the resolved DID Document.
+  pub fn document(&self) -> &IotaDocument {
+    self
+      .documents
+      .get(&self.did)
+      .expect("the graph always contains the resolved document")
+  }
+
+  /// Returns the document of `did` if it is the resolved document or one of its resolved controllers.
+  pub fn get(&self, did: &IotaDID) -> Option<&IotaDocument> {
+    self.documents.get(did)
+  }
+
+  /// Returns the documents of the (transitive) controllers of the resolved document.
+  pub fn controller_documents(&self) -> impl Iterator<Item = &IotaDocument> + '_ {
+    self.documents.values().filter(|document| document.id() != &self.did)
+  }
+
+  /// Returns the controllers that were not resolved because they exceed the maximum depth.
+  ///
+  /// If this is not empty, [`Self::authorized_methods`] and [`Self::may_update`] only take the
+  /// resolved part of the graph into account.
+  pub fn unresolved_controllers(&self) -> &[IotaDID] {
+    &self.unresolved
+  }
+
+  /// Returns the verification methods that are authorized to update the document of `did`.
+  ///
+  /// These are the `capabilityInvocation` methods of
+  /// - the document of `did` itself, unless it names controllers other than itself, and
+  /// - the documents of its resolved (transitive) controllers.
+  ///
+  /// Returns an empty list if `did` is not part of the graph.
+  pub fn authorized_methods(&self, did: &IotaDID) -> Vec<&VerificationMethod> {
+    let mut methods: Vec<&VerificationMethod> = Vec::new();
+    let Some(document) = self.documents.get(did) else {
+      return methods;
+    };
+
+    let mut controllers = document.controller().peekable();
+    let self_controlled: bool =
+      controllers.peek().is_none() || document.controller().any(|controller| controller == did);
+    if self_controlled {
+      methods.extend(capability_invocation_methods(document));
+    }
+
+    // Traverse the controllers, skipping documents that were already visited to break cycles.
+    let mut visited: HashSet<&IotaDID> = HashSet::new();
+    let mut stack: Vec<&IotaDID> = controllers.collect();
+    while let Some(controller) = stack.pop() {
+      if !visited.insert(controller) {
+        continue;
+      }
+      if let Some(controller_document) = self.documents.get(controller) {
+        methods.extend(capability_invocation_methods(controller_document));
+        stack.extend(controller_document.controller());
+      }
+    }
+
+    methods
+  }
+
+  /// Returns whether the verification method identified by `method_id` is authorized to update the
+  /// document of `did`.
+  ///
+  /// See [`Self::authorized_methods`].
+  pub fn may_update(&self, did: &IotaDID, method_id: &DIDUrl) -> bool {
+    self
+      .authorized_methods(did)
+      .into_iter()
+      .any(|method| method.id() == method_id)
+  }
+}
+
+fn capability_invocation_methods(document: &IotaDocument) -> Vec<&VerificationMethod> {
+  document
+    .core_document()
+    .methods(Some(MethodScope::capability_invocation()))
+}
+
+#[cfg(all(test, feature = "iota-client"))]
+mod tests {
+  use identity_did::DIDUrl;
+  use identity_verification::MethodScope;
+
+  use crate::block::address::Address;
+  use crate::block::address::AliasAddress;
+  use crate::block::address::Ed25519Address;
+  use crate::block::output::AliasId;
+  use crate::block::output::AliasOutput;
+  use crate::test_utils::generate_method;
+  use crate::Error;
+  use crate::IotaControllerGraph;
+  use crate::IotaDID;
+  use crate::IotaDocument;
+  use crate::IotaIdentityClientExt;
+  use crate::MemoryLedgerClient;
+
+  fn ed25519_address(byte: u8) -> Address {
+    Address::Ed25519(Ed25519Address::new([byte; 32]))
+  }
+
+  fn alias_address(did: &IotaDID) -> Address {
+    Address::Alias(AliasAddress::new(AliasId::from(did)))
+  }
+
+  /// Publishes a DID with a capability invocation method `#update`, returning the DID and the method id.
+  async fn create_did(client: &MemoryLedgerClient, controller: Address) -> (IotaDID, DIDUrl) {
+    let document: IotaDocument = IotaDocument::new(&client.network_name().await.unwrap());
+    let alias_output: AliasOutput = client.new_did_output(controller, document, None).await.unwrap();
+    let mut document: IotaDocument = client.publish_did_output(&controller, alias_output).unwrap();
+
+    let method = generate_method(document.id(), "update");
+    let method_id: DIDUrl = method.id().clone();
+    document
+      .insert_method(method, MethodScope::capability_invocation())
+      .unwrap();
+    let alias_output: AliasOutput = client
+      .update_did_output_with_storage_deposit(document.clone(), None, true)
+      .await
+      .unwrap()
+      .0;
+    client.publish_did_output(&controller, alias_output).unwrap();
+    (document.id().clone(), method_id)
+  }
+
+  async fn set_controller(client: &MemoryLedgerClient, signer: Address, did: &IotaDID, controllers: Vec<IotaDID>) {
+    let mut document: IotaDocument = client.resolve_did(did).await.unwrap();
+    document.set_controller(controllers);
+    let alias_output: AliasOutput = client
+      .update_did_output_with_storage_deposit(document, None, true)
+      .await
+      .unwrap()
+      .0;
+    client.publish_did_output(&signer, alias_output).unwrap();
+  }
+
+  #[tokio::test]
+  async fn alias_controlled_did() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let (controller_did, controller_method) = create_did(&client, ed25519_address(1)).await;
+    let (did, own_method) = create_did(&client, alias_address(&controller_did)).await;
+
+    let graph: IotaControllerGraph = client.resolve_did_with_controllers(&did, 1).await.unwrap();
+    assert_eq!(graph.document().id(), &did);
+    assert_eq!(graph.controller_documents().count(), 1);
+    assert!(graph.unresolved_controllers().is_empty());
+
+    // The controller's methods may update the DID, the DID's own methods may not.
+    assert!(graph.may_update(&did, &controller_method));
+    assert!(!graph.may_update(&did, &own_method));
+    assert!(graph.may_update(&controller_did, &controller_method));
+    assert_eq!(graph.authorized_methods(&did).len(), 1);
+
+    // The DID itself may be a controller.
+    set_controller(
+      &client,
+      alias_address(&controller_did),
+      &did,
+      vec![did.clone(), controller_did.clone()],
+    )
+    .await;
+    let graph: IotaControllerGraph = client.resolve_did_with_controllers(&did, 1).await.unwrap();
+    assert!(graph.may_update(&did, &controller_method));
+    assert!(graph.may_update(&did, &own_method));
+  }
+
+  #[tokio::test]
+  async fn depth_limit() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let (root_did, root_method) = create_did(&client, ed25519_address(1)).await;
+    let (middle_did, _) = create_did(&client, alias_address(&root_did)).await;
+    let (did, _) = create_did(&client, alias_address(&middle_did)).await;
+
+    let graph: IotaControllerGraph = client.resolve_did_with_controllers(&did, 2).await.unwrap();
+    assert_eq!(graph.controller_documents().count(), 2);
+    assert!(graph.may_update(&did, &root_method));
+
+    let graph: IotaControllerGraph = client.resolve_did_with_controllers(&did, 1).await.unwrap();
+    assert_eq!(graph.controller_documents().count(), 1);
+    assert_eq!(graph.unresolved_controllers(), &[root_did]);
+    assert!(!graph.may_update(&did, &root_method));
+
+    let graph: IotaControllerGraph = client.resolve_did_with_controllers(&did, 0).await.unwrap();
+    assert_eq!(graph.controller_documents().count(), 0);
+    assert_eq!(graph.unresolved_controllers(), &[middle_did]);
+    assert!(graph.authorized_methods(&did).is_empty());
+  }
+
+  #[tokio::test]
+  async fn controller_cycle() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let (did_a, method_a) = create_did(&client, ed25519_address(1)).await;
+    let (did_b, method_b) = create_did(&client, ed25519_address(2)).await;
+    set_controller(&client, ed25519_address(1), &did_a, vec![did_b.clone()]).await;
+    set_controller(&client, ed25519_address(2), &did_b, vec![did_a.clone()]).await;
+
+    let graph: IotaControllerGraph = client.resolve_did_with_controllers(&did_a, 10).await.unwrap();
+    assert_eq!(graph.controller_documents().count(), 1);
+    assert!(graph.unresolved_controllers().is_empty());
+
+    // Each DID is controlled by the other one, including transitively by itself.
+    assert!(graph.may_update(&did_a, &method_b));
+    assert!(graph.may_update(&did_a, &method_a));
+    assert!(graph.may_update(&did_b, &method_a));
+    assert!(graph.may_update(&did_b, &method_b));
+  }
+
+  #[tokio::test]
+  async fn missing_controller() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let (did, _) = create_did(&client, ed25519_address(1)).await;
+    let missing: IotaDID = IotaDID::new(&[7; 32], &client.network_name().await.unwrap());
+    set_controller(&client, ed25519_address(1), &did, vec![missing]).await;
+
+    assert!(matches!(
+      client.resolve_did_with_controllers(&did, 1).await.unwrap_err(),
+      Error::DIDResolutionError(_)
+    ));
+  }
+}
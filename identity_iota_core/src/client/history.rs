@@ -0,0 +1,460 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+use identity_did::DIDUrl;
+
+use crate::block::output::AliasId;
+use crate::block::output::AliasOutput;
+use crate::block::output::OutputId;
+use crate::client::identity_client::validate_network;
+use crate::Error;
+use crate::IotaDID;
+use crate::IotaDocument;
+use crate::IotaIdentityClient;
+use crate::Result;
+
+/// The DID URL query parameter selecting a DID Document version by its state index.
+const VERSION_ID_QUERY: &str = "versionId";
+/// The DID URL query parameter selecting the DID Document version that was valid at a point in time.
+const VERSION_TIME_QUERY: &str = "versionTime";
+
+/// A state of an Alias Output, as recorded by an [`IotaIdentityIndexer`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AliasOutputRecord {
+  /// The id of the output.
+  pub output_id: OutputId,
+  /// The Alias Output.
+  pub alias_output: AliasOutput,
+  /// The timestamp of the milestone that booked the output.
+  pub booked: Timestamp,
+}
+
+/// Helper functions necessary for the [`IotaIdentityHistoryExt`] trait.
+///
+/// Nodes only keep the unspent outputs of the ledger, so the previous states of an Alias Output have to be
+/// retrieved from an indexer recording the output history, e.g. a permanode.
+#[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
+#[cfg_attr(not(feature = "send-sync-client-ext"), async_trait::async_trait(?Send))]
+pub trait IotaIdentityIndexer {
+  /// Returns the state of the Alias Output with the given `alias_id` and `state_index`.
+  ///
+  /// Returns `Ok(None)` if the Alias Output never had the given `state_index`.
+  async fn get_alias_output_state(&self, alias_id: AliasId, state_index: u32) -> Result<Option<AliasOutputRecord>>;
+
+  /// Returns the latest recorded state of the Alias Output with the given `alias_id`, including Alias Outputs that
+  /// have since been destroyed.
+  ///
+  /// Returns `Ok(None)` if the Alias Output was never recorded.
+  async fn get_latest_alias_output_state(&self, alias_id: AliasId) -> Result<Option<AliasOutputRecord>>;
+}
+
+/// A version of an IOTA DID Document, as it was contained in one state of its Alias Output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IotaDocumentVersion {
+  /// The state index of the Alias Output containing this version.
+  pub state_index: u32,
+  /// The id of the output containing this version.
+  pub output_id: OutputId,
+  /// The timestamp of the milestone that booked this version.
+  pub booked: Timestamp,
+  /// The DID Document.
+  pub document: IotaDocument,
+}
+
+/// An extension trait that provides helper functions for the resolution of previous versions of DID documents
+/// in Alias Outputs.
+///
+/// This trait is not intended to be implemented directly, a blanket implementation is
+/// provided for implementers of both [`IotaIdentityClient`] and [`IotaIdentityIndexer`].
+#[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
+#[cfg_attr(not(feature = "send-sync-client-ext"), async_trait::async_trait(?Send))]
+pub trait IotaIdentityHistoryExt: IotaIdentityClient + IotaIdentityIndexer {
+  /// Resolves all versions of the DID Document of `did`, ordered by state index.
+  ///
+  /// # Errors
+  ///
+  /// - [`NetworkMismatch`](Error::NetworkMismatch) if the network of the DID and client differ.
+  /// - [`DIDVersionError`](Error::DIDVersionError) if the indexer is missing a state of the Alias Output.
+  async fn resolve_did_history(&self, did: &IotaDID) -> Result<Vec<IotaDocumentVersion>> {
+    let latest_state_index: u32 = latest_state_index(self, did).await?;
+
+    let mut versions: Vec<IotaDocumentVersion> = Vec::with_capacity(latest_state_index as usize + 1);
+    for state_index in 0..=latest_state_index {
+      versions.push(document_version(self, did, state_index).await?);
+    }
+    Ok(versions)
+  }
+
+  /// Resolves the version of the DID Document of `did` contained in the state of its Alias Output with the given
+  /// `state_index`.
+  ///
+  /// # Errors
+  ///
+  /// - [`NetworkMismatch`](Error::NetworkMismatch) if the network of the DID and client differ.
+  /// - [`DIDVersionError`](Error::DIDVersionError) if the Alias Output has no state with the given `state_index`.
+  async fn resolve_did_version(&self, did: &IotaDID, state_index: u32) -> Result<IotaDocumentVersion> {
+    validate_network(self, did).await?;
+    document_version(self, did, state_index).await
+  }
+
+  /// Resolves the version of the DID Document of `did` that was valid at the given `time`, i.e. the latest version
+  /// booked at or before `time`.
+  ///
+  /// # Errors
+  ///
+  /// - [`NetworkMismatch`](Error::NetworkMismatch) if the network of the DID and client differ.
+  /// - [`DIDVersionError`](Error::DIDVersionError) if the DID did not exist at `time`.
+  async fn resolve_did_at(&self, did: &IotaDID, time: Timestamp) -> Result<IotaDocumentVersion> {
+    let latest_state_index: u32 = latest_state_index(self, did).await?;
+
+    // Walk the history backwards, recent versions are requested more often.
+    for state_index in (0..=latest_state_index).rev() {
+      let version: IotaDocumentVersion = document_version(self, did, state_index).await?;
+      if version.booked <= time {
+        return Ok(version);
+      }
+    }
+    Err(Error::DIDVersionError(
+      "the DID did not exist at the given `versionTime`",
+    ))
+  }
+
+  /// Resolves the version of the DID Document selected by the `versionId` or `versionTime` query of `did_url`.
+  ///
+  /// The `versionId` is the state index of the Alias Output containing the version, the `versionTime` is a
+  /// [`Timestamp`] as described in [`resolve_did_at`](IotaIdentityHistoryExt::resolve_did_at). If neither is given,
+  /// the latest version is resolved.
+  ///
+  /// # Errors
+  ///
+  /// - [`DIDSyntaxError`](Error::DIDSyntaxError) if the DID of `did_url` is not a valid IOTA DID.
+  /// - [`DIDVersionError`](Error::DIDVersionError) if the query is invalid, or the version could not be resolved.
+  async fn resolve_did_url_version(&self, did_url: &DIDUrl) -> Result<IotaDocumentVersion> {
+    let did: IotaDID = IotaDID::try_from(did_url.did().clone()).map_err(Error::DIDSyntaxError)?;
+
+    let mut version_id: Option<String> = None;
+    let mut version_time: Option<String> = None;
+    for (key, value) in did_url.query_pairs() {
+      let parameter: &mut Option<String> = match key.as_ref() {
+        VERSION_ID_QUERY => &mut version_id,
+        VERSION_TIME_QUERY => &mut version_time,
+        _ => continue,
+      };
+      if parameter.replace(value.into_owned()).is_some() {
+        return Err(Error::DIDVersionError("duplicate version query parameter"));
+      }
+    }
+
+    match (version_id, version_time) {
+      (Some(_), Some(_)) => Err(Error::DIDVersionError(
+        "`versionId` and `versionTime` cannot be used together",
+      )),
+      (Some(version_id), None) => {
+        let state_index: u32 = version_id
+          .parse()
+          .map_err(|_| Error::DIDVersionError("`versionId` is not a state index"))?;
+        self.resolve_did_version(&did, state_index).await
+      }
+      (None, Some(version_time)) => {
+        let time: Timestamp =
+          Timestamp::parse(&version_time).map_err(|_| Error::DIDVersionError("`versionTime` is not a timestamp"))?;
+        self.resolve_did_at(&did, time).await
+      }
+      (None, None) => {
+        let latest_state_index: u32 = latest_state_index(self, &did).await?;
+        document_version(self, &did, latest_state_index).await
+      }
+    }
+  }
+}
+
+impl<T> IotaIdentityHistoryExt for T where T: IotaIdentityClient + IotaIdentityIndexer {}
+
+/// Returns the state index of the latest Alias Output of `did`.
+///
+/// The live output is read from the ledger. If the Alias Output was destroyed, its latest state is retrieved from
+/// the indexer instead.
+async fn latest_state_index<T>(client: &T, did: &IotaDID) -> Result<u32>
+where
+  T: IotaIdentityClient + IotaIdentityIndexer + ?Sized,
+{
+  validate_network(client, did).await?;
+  let alias_id: AliasId = AliasId::from(did);
+  match client.get_alias_output(alias_id).await {
+    Ok((_, alias_output)) => Ok(alias_output.state_index()),
+    Err(error @ Error::DIDResolutionError(iota_sdk::client::Error::NoOutput(_))) => {
+      match client.get_latest_alias_output_state(alias_id).await? {
+        Some(record) => Ok(record.alias_output.state_index()),
+        None => Err(error),
+      }
+    }
+    Err(error) => Err(error),
+  }
+}
+
+async fn document_version<T>(indexer: &T, did: &IotaDID, state_index: u32) -> Result<IotaDocumentVersion>
+where
+  T: IotaIdentityIndexer + ?Sized,
+{
+  let AliasOutputRecord {
+    output_id,
+    alias_output,
+    booked,
+  } = indexer
+    .get_alias_output_state(AliasId::from(did), state_index)
+    .await?
+    .ok_or(Error::DIDVersionError(
+      "no Alias Output state with the given state index",
+    ))?;
+  let document: IotaDocument = IotaDocument::unpack_from_output(did, &alias_output, true)?;
+
+  Ok(IotaDocumentVersion {
+    state_index,
+    output_id,
+    booked,
+    document,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use identity_core::common::Timestamp;
+  use identity_did::DIDUrl;
+  use identity_did::DID;
+
+  use crate::block::address::Address;
+  use crate::block::address::Ed25519Address;
+  use crate::block::output::unlock_condition::GovernorAddressUnlockCondition;
+  use crate::block::output::unlock_condition::StateControllerAddressUnlockCondition;
+  use crate::block::output::AliasId;
+  use crate::block::output::AliasOutput;
+  use crate::block::output::AliasOutputBuilder;
+  use crate::block::output::OutputId;
+  use crate::block::protocol::ProtocolParameters;
+  use crate::Error;
+  use crate::IotaDID;
+  use crate::IotaDocument;
+  use crate::IotaIdentityClient;
+  use crate::Result;
+
+  use super::AliasOutputRecord;
+  use super::IotaDocumentVersion;
+  use super::IotaIdentityHistoryExt;
+  use super::IotaIdentityIndexer;
+
+  const DID: &str = "did:iota:smr:0x0101010101010101010101010101010101010101010101010101010101010101";
+
+  /// A ledger and indexer that knows the full history of a single Alias Output.
+  struct MockIndexer {
+    history: Vec<AliasOutputRecord>,
+    /// Whether the Alias Output was destroyed, i.e. is no longer part of the ledger.
+    destroyed: bool,
+  }
+
+  #[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
+  #[cfg_attr(not(feature = "send-sync-client-ext"), async_trait::async_trait(?Send))]
+  impl IotaIdentityClient for MockIndexer {
+    async fn get_alias_output(&self, alias_id: AliasId) -> Result<(OutputId, AliasOutput)> {
+      if self.destroyed {
+        return Err(Error::DIDResolutionError(iota_sdk::client::Error::NoOutput(
+          alias_id.to_string(),
+        )));
+      }
+      let latest: &AliasOutputRecord = self.history.last().unwrap();
+      Ok((latest.output_id, latest.alias_output.clone()))
+    }
+
+    async fn get_protocol_parameters(&self) -> Result<ProtocolParameters> {
+      Ok(ProtocolParameters::default())
+    }
+  }
+
+  #[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
+  #[cfg_attr(not(feature = "send-sync-client-ext"), async_trait::async_trait(?Send))]
+  impl IotaIdentityIndexer for MockIndexer {
+    async fn get_alias_output_state(&self, _alias_id: AliasId, state_index: u32) -> Result<Option<AliasOutputRecord>> {
+      Ok(self.history.get(state_index as usize).cloned())
+    }
+
+    async fn get_latest_alias_output_state(&self, _alias_id: AliasId) -> Result<Option<AliasOutputRecord>> {
+      Ok(self.history.last().cloned())
+    }
+  }
+
+  fn booked(state_index: u32) -> Timestamp {
+    Timestamp::from_unix(1_700_000_000 + 3600 * state_index as i64).unwrap()
+  }
+
+  /// Creates the history of an Alias Output with three versions of a DID Document, each adding a property, and
+  /// a final deactivation.
+  fn mock_indexer() -> MockIndexer {
+    let did: IotaDID = IotaDID::parse(DID).unwrap();
+    let address: Address = Address::Ed25519(Ed25519Address::new([0; 32]));
+    let mut document: IotaDocument = IotaDocument::new_with_id(did.clone());
+
+    let history: Vec<AliasOutputRecord> = (0..4)
+      .map(|state_index: u32| {
+        let state_metadata: Vec<u8> = if state_index < 3 {
+          document
+            .properties_mut_unchecked()
+            .insert(format!("version-{state_index}"), state_index.into());
+          document.clone().pack().unwrap()
+        } else {
+          Vec::new()
+        };
+        let alias_id: AliasId = if state_index == 0 {
+          AliasId::null()
+        } else {
+          AliasId::from(&did)
+        };
+        let alias_output: AliasOutput = AliasOutputBuilder::new_with_amount(1_000_000, alias_id)
+          .with_state_index(state_index)
+          .with_state_metadata(state_metadata)
+          .add_unlock_condition(StateControllerAddressUnlockCondition::new(address))
+          .add_unlock_condition(GovernorAddressUnlockCondition::new(address))
+          .finish()
+          .unwrap();
+
+        AliasOutputRecord {
+          output_id: OutputId::null(),
+          alias_output,
+          booked: booked(state_index),
+        }
+      })
+      .collect();
+
+    MockIndexer {
+      history,
+      destroyed: false,
+    }
+  }
+
+  fn version_properties(version: &IotaDocumentVersion) -> Vec<String> {
+    let mut properties: Vec<String> = version.document.properties().keys().cloned().collect();
+    properties.sort();
+    properties
+  }
+
+  #[tokio::test]
+  async fn resolve_history() {
+    let indexer: MockIndexer = mock_indexer();
+    let did: IotaDID = IotaDID::parse(DID).unwrap();
+
+    let history: Vec<IotaDocumentVersion> = indexer.resolve_did_history(&did).await.unwrap();
+    assert_eq!(history.len(), 4);
+    for (state_index, version) in history.iter().enumerate() {
+      assert_eq!(version.state_index, state_index as u32);
+      assert_eq!(version.booked, booked(state_index as u32));
+      assert_eq!(version.document.id(), &did);
+    }
+    assert_eq!(version_properties(&history[1]), ["version-0", "version-1"]);
+    assert_eq!(history[2].document.metadata.deactivated, None);
+    assert_eq!(history[3].document.metadata.deactivated, Some(true));
+
+    let version: IotaDocumentVersion = indexer.resolve_did_version(&did, 1).await.unwrap();
+    assert_eq!(version, history[1]);
+
+    // INVALID: the state index does not exist.
+    let err: Error = indexer.resolve_did_version(&did, 4).await.unwrap_err();
+    assert!(matches!(err, Error::DIDVersionError(_)));
+
+    // INVALID: the DID is on another network.
+    let did: IotaDID =
+      IotaDID::parse("did:iota:0x0101010101010101010101010101010101010101010101010101010101010101").unwrap();
+    let err: Error = indexer.resolve_did_history(&did).await.unwrap_err();
+    assert!(matches!(err, Error::NetworkMismatch { .. }));
+  }
+
+  #[tokio::test]
+  async fn resolve_history_of_destroyed_alias_output() {
+    let mut indexer: MockIndexer = mock_indexer();
+    indexer.destroyed = true;
+    let did: IotaDID = IotaDID::parse(DID).unwrap();
+
+    let history: Vec<IotaDocumentVersion> = indexer.resolve_did_history(&did).await.unwrap();
+    assert_eq!(history.len(), 4);
+    let version: IotaDocumentVersion = indexer.resolve_did_at(&did, Timestamp::now_utc()).await.unwrap();
+    assert_eq!(version.state_index, 3);
+    let version: IotaDocumentVersion = indexer
+      .resolve_did_url_version(&DIDUrl::parse(DID).unwrap())
+      .await
+      .unwrap();
+    assert_eq!(version.state_index, 3);
+
+    // INVALID: the Alias Output was never recorded.
+    indexer.history.clear();
+    let err: Error = indexer.resolve_did_history(&did).await.unwrap_err();
+    assert!(matches!(
+      err,
+      Error::DIDResolutionError(iota_sdk::client::Error::NoOutput(_))
+    ));
+  }
+
+  #[tokio::test]
+  async fn resolve_at_time() {
+    let indexer: MockIndexer = mock_indexer();
+    let did: IotaDID = IotaDID::parse(DID).unwrap();
+
+    let version: IotaDocumentVersion = indexer.resolve_did_at(&did, booked(0)).await.unwrap();
+    assert_eq!(version.state_index, 0);
+
+    // Between two updates the earlier version is valid.
+    let time: Timestamp = booked(1)
+      .checked_add(identity_core::common::Duration::minutes(30))
+      .unwrap();
+    let version: IotaDocumentVersion = indexer.resolve_did_at(&did, time).await.unwrap();
+    assert_eq!(version.state_index, 1);
+
+    let version: IotaDocumentVersion = indexer.resolve_did_at(&did, Timestamp::now_utc()).await.unwrap();
+    assert_eq!(version.state_index, 3);
+
+    // INVALID: the DID did not exist yet.
+    let time: Timestamp = booked(0)
+      .checked_sub(identity_core::common::Duration::seconds(1))
+      .unwrap();
+    let err: Error = indexer.resolve_did_at(&did, time).await.unwrap_err();
+    assert!(matches!(err, Error::DIDVersionError(_)));
+  }
+
+  #[tokio::test]
+  async fn resolve_did_url_queries() {
+    let indexer: MockIndexer = mock_indexer();
+
+    let cases: HashMap<String, u32> = [
+      (DID.to_owned(), 3),
+      (format!("{DID}?versionId=1"), 1),
+      (format!("{DID}?versionId=2#key-1"), 2),
+      (format!("{DID}?versionTime={}", booked(2)), 2),
+      (format!("{DID}?service=files&versionTime=2023-11-14T22:13:20Z"), 0),
+    ]
+    .into_iter()
+    .collect();
+    for (did_url, state_index) in cases {
+      let did_url: DIDUrl = DIDUrl::parse(&did_url).unwrap();
+      let version: IotaDocumentVersion = indexer.resolve_did_url_version(&did_url).await.unwrap();
+      assert_eq!(version.state_index, state_index, "{did_url}");
+      assert_eq!(version.document.id().as_str(), did_url.did().as_str());
+    }
+
+    for invalid in [
+      format!("{DID}?versionId=latest"),
+      format!("{DID}?versionId=-1"),
+      format!("{DID}?versionId=1&versionId=2"),
+      format!("{DID}?versionTime=yesterday"),
+      format!("{DID}?versionId=1&versionTime={}", booked(1)),
+    ] {
+      let did_url: DIDUrl = DIDUrl::parse(&invalid).unwrap();
+      let err: Error = indexer.resolve_did_url_version(&did_url).await.unwrap_err();
+      assert!(matches!(err, Error::DIDVersionError(_)), "{invalid}");
+    }
+
+    // INVALID: not an IOTA DID.
+    let did_url: DIDUrl = DIDUrl::parse("did:foo:bar?versionId=1").unwrap();
+    let err: Error = indexer.resolve_did_url_version(&did_url).await.unwrap_err();
+    assert!(matches!(err, Error::DIDSyntaxError(_)));
+  }
+}
@@ -1,10 +1,13 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::VecDeque;
+
 #[cfg(feature = "test")]
 use iota_sdk::client::Client;
 
 use crate::block::address::Address;
+use crate::block::address::AliasAddress;
 use crate::block::output::feature::SenderFeature;
 use crate::block::output::unlock_condition::GovernorAddressUnlockCondition;
 use crate::block::output::unlock_condition::StateControllerAddressUnlockCondition;
@@ -17,10 +20,12 @@ use crate::block::output::RentStructure;
 use crate::block::output::UnlockCondition;
 use crate::block::protocol::ProtocolParameters;
 use crate::Error;
+use crate::IotaControllerGraph;
 use crate::IotaDID;
 use crate::IotaDocument;
 use crate::NetworkName;
 use crate::Result;
+use crate::StorageDeposit;
 
 /// Helper functions necessary for the [`IotaIdentityClientExt`] trait.
 #[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
@@ -80,9 +85,35 @@ pub trait IotaIdentityClientExt: IotaIdentityClient {
       .map_err(Error::AliasOutputBuildError)
   }
 
+  /// Create a DID controlled by the DID `controller` with a new Alias Output containing the given `document`.
+  ///
+  /// The Alias Address of `controller` will be set as the state controller and governor unlock conditions,
+  /// like the `address` in [`new_did_output`](IotaIdentityClientExt::new_did_output). The new Alias Output
+  /// therefore has to be published in a transaction that also transitions the Alias Output of `controller`.
+  ///
+  /// NOTE: This does *not* publish the Alias Output.
+  ///
+  /// # Errors
+  ///
+  /// - Returns `Err` when failing to resolve the Alias Output of `controller`.
+  /// - [`Error::AliasOutputBuildError`] when building the Alias Output fails.
+  async fn new_controlled_did_output(
+    &self,
+    controller: &IotaDID,
+    document: IotaDocument,
+    rent_structure: Option<RentStructure>,
+  ) -> Result<AliasOutput> {
+    // Tokens locked to an Alias Address that does not exist could never be unlocked again.
+    self.resolve_did_output(controller).await?;
+
+    let address: Address = Address::Alias(AliasAddress::new(AliasId::from(controller)));
+    self.new_did_output(address, document, rent_structure).await
+  }
+
   /// Fetches the associated Alias Output and updates it with `document` in its state metadata.
   /// The storage deposit on the output is left unchanged. If the size of the document increased,
-  /// the amount should be increased manually.
+  /// the amount should be increased manually, see
+  /// [`update_did_output_with_storage_deposit`](IotaIdentityClientExt::update_did_output_with_storage_deposit).
   ///
   /// NOTE: This does *not* publish the updated Alias Output.
   ///
@@ -104,6 +135,81 @@ pub trait IotaIdentityClientExt: IotaIdentityClient {
     alias_output_builder.finish().map_err(Error::AliasOutputBuildError)
   }
 
+  /// Fetches the associated Alias Output and updates it with `document` in its state metadata, like
+  /// [`update_did_output`](IotaIdentityClientExt::update_did_output).
+  ///
+  /// Returns the updated Alias Output together with the [`StorageDeposit`] it requires according to the given
+  /// `rent_structure`, which will be fetched from the node if not provided. If `top_up` is true and the deposited
+  /// amount does not cover the required storage deposit, the amount of the returned output is increased to the
+  /// required storage deposit, the returned [`StorageDeposit`] still describes the output before the increase.
+  /// An amount exceeding the required storage deposit is never decreased.
+  ///
+  /// NOTE: This does *not* publish the updated Alias Output.
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` when failing to resolve the DID contained in `document` or to retrieve the `RentStructure`.
+  async fn update_did_output_with_storage_deposit(
+    &self,
+    document: IotaDocument,
+    rent_structure: Option<RentStructure>,
+    top_up: bool,
+  ) -> Result<(AliasOutput, StorageDeposit)> {
+    let rent_structure: RentStructure = if let Some(rent) = rent_structure {
+      rent
+    } else {
+      self.get_rent_structure().await?
+    };
+
+    let alias_output: AliasOutput = self.update_did_output(document).await?;
+    let storage_deposit: StorageDeposit = StorageDeposit::new(&alias_output, rent_structure)?;
+    if !top_up || storage_deposit.is_sufficient() {
+      return Ok((alias_output, storage_deposit));
+    }
+
+    let alias_output: AliasOutput = AliasOutputBuilder::from(&alias_output)
+      .with_amount(storage_deposit.required_amount)
+      .finish()
+      .map_err(Error::AliasOutputBuildError)?;
+    Ok((alias_output, storage_deposit))
+  }
+
+  /// Updates the Alias Output of a DID controlled by the DID `controller` with `document`, like
+  /// [`update_did_output`](IotaIdentityClientExt::update_did_output).
+  ///
+  /// An Alias Address can only unlock an output in a transaction that also transitions the Alias Output it
+  /// belongs to. Hence, the next state of the Alias Output of `controller` is returned first, followed by the
+  /// updated Alias Output of the controlled DID. Both have to be published in the same transaction.
+  ///
+  /// NOTE: This does *not* publish the updated Alias Outputs.
+  ///
+  /// # Errors
+  ///
+  /// - [`Error::NotControlledBy`] if the state controller of the Alias Output is not the Alias Address of
+  ///   `controller`.
+  /// - Returns `Err` when failing to resolve the DID contained in `document` or `controller`.
+  async fn update_controlled_did_output(
+    &self,
+    controller: &IotaDID,
+    document: IotaDocument,
+  ) -> Result<(AliasOutput, AliasOutput)> {
+    let controller_alias_id: AliasId = AliasId::from(controller);
+    let alias_output: AliasOutput = self.resolve_did_output(document.id()).await?;
+    check_controlled_by(document.id(), &alias_output, controller)?;
+
+    let controller_output: AliasOutput = self.resolve_did_output(controller).await?;
+    let mut controller_output_builder: AliasOutputBuilder =
+      AliasOutputBuilder::from(&controller_output).with_state_index(controller_output.state_index() + 1);
+    if controller_output.alias_id().is_null() {
+      controller_output_builder = controller_output_builder.with_alias_id(controller_alias_id);
+    }
+    let controller_output: AliasOutput = controller_output_builder
+      .finish()
+      .map_err(Error::AliasOutputBuildError)?;
+
+    Ok((controller_output, self.update_did_output(document).await?))
+  }
+
   /// Removes the DID document from the state metadata of its Alias Output,
   /// effectively deactivating it. The storage deposit on the output is left unchanged,
   /// and should be reallocated manually.
@@ -146,6 +252,56 @@ pub trait IotaIdentityClientExt: IotaIdentityClient {
     IotaDocument::unpack_from_output(did, &alias_output, true)
   }
 
+  /// Resolve a [`IotaDocument`] together with the documents of its controllers, recursively up to
+  /// `max_depth` levels of controllers, e.g. [`DEFAULT_MAX_CONTROLLER_DEPTH`](crate::DEFAULT_MAX_CONTROLLER_DEPTH).
+  ///
+  /// Controllers are resolved at most once, so cycles in the controller relationships are not followed.
+  /// Controllers exceeding `max_depth` are not resolved, but listed in
+  /// [`IotaControllerGraph::unresolved_controllers`].
+  ///
+  /// # Errors
+  ///
+  /// Returns `Err` when failing to resolve the `did` or one of its controllers up to `max_depth`.
+  async fn resolve_did_with_controllers(&self, did: &IotaDID, max_depth: usize) -> Result<IotaControllerGraph> {
+    let document: IotaDocument = self.resolve_did(did).await?;
+    let mut pending: VecDeque<(IotaDID, usize)> = document
+      .controller()
+      .map(|controller| (controller.clone(), 1))
+      .collect();
+    let mut graph: IotaControllerGraph = IotaControllerGraph::new(document);
+
+    while let Some((controller, depth)) = pending.pop_front() {
+      if graph.contains(&controller) {
+        continue;
+      }
+      if depth > max_depth {
+        graph.insert_unresolved(controller);
+        continue;
+      }
+      let document: IotaDocument = self.resolve_did(&controller).await?;
+      pending.extend(document.controller().map(|controller| (controller.clone(), depth + 1)));
+      graph.insert(document);
+    }
+
+    Ok(graph)
+  }
+
+  /// Resolve the [`IotaDocument`] of a DID controlled by the DID `controller`.
+  ///
+  /// The controller is also available from the resolved document as
+  /// [`controlling_did`](crate::IotaDocumentMetadata::controlling_did).
+  ///
+  /// # Errors
+  ///
+  /// - [`Error::NotControlledBy`] if the state controller of the Alias Output is not the Alias Address of
+  ///   `controller`.
+  /// - Returns `Err` when failing to resolve the `did`.
+  async fn resolve_controlled_did(&self, controller: &IotaDID, did: &IotaDID) -> Result<IotaDocument> {
+    let alias_output: AliasOutput = self.resolve_did_output(did).await?;
+    check_controlled_by(did, &alias_output, controller)?;
+    IotaDocument::unpack_from_output(did, &alias_output, true)
+  }
+
   /// Fetches the [`AliasOutput`] associated with the given DID.
   ///
   /// # Errors
@@ -198,6 +354,19 @@ pub trait IotaIdentityClientExt: IotaIdentityClient {
 impl<T> IotaIdentityClientExt for T where T: IotaIdentityClient {}
 #[cfg(feature = "test")]
 impl IotaIdentityClientExt for Client {}
+#[cfg(all(feature = "test", feature = "iota-client"))]
+impl IotaIdentityClientExt for crate::MemoryLedgerClient {}
+
+/// Checks that the state controller of the `alias_output` of `did` is the Alias Address of `controller`.
+fn check_controlled_by(did: &IotaDID, alias_output: &AliasOutput, controller: &IotaDID) -> Result<()> {
+  match alias_output.state_controller_address() {
+    Address::Alias(alias_address) if alias_address.alias_id() == &AliasId::from(controller) => Ok(()),
+    _ => Err(Error::NotControlledBy {
+      did: did.to_string(),
+      controller: controller.to_string(),
+    }),
+  }
+}
 
 pub(super) async fn validate_network<T>(client: &T, did: &IotaDID) -> Result<()>
 where
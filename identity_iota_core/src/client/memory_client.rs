@@ -0,0 +1,514 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use identity_core::common::Timestamp;
+use iota_sdk::client::error::Error as ClientError;
+
+use crate::block::address::Address;
+use crate::block::output::AliasId;
+use crate::block::output::AliasOutput;
+use crate::block::output::AliasOutputBuilder;
+use crate::block::output::Output;
+use crate::block::output::OutputId;
+use crate::block::output::RentStructure;
+use crate::block::payload::transaction::TransactionId;
+use crate::block::protocol::ProtocolParameters;
+use crate::AliasOutputRecord;
+use crate::Error;
+use crate::IotaDID;
+use crate::IotaDocument;
+use crate::IotaIdentityClient;
+use crate::IotaIdentityIndexer;
+use crate::NetworkName;
+use crate::Result;
+
+/// An [`IotaIdentityClient`] backed by a ledger that only exists in memory.
+///
+/// Alias Outputs created with [`IotaIdentityClientExt`](crate::IotaIdentityClientExt) can be published with
+/// [`MemoryLedgerClient::publish_did_output`] and destroyed with [`MemoryLedgerClient::delete_did_output`]. Instead of
+/// signing a transaction, the caller names the `signer` address that unlocks the Alias Output, for which the
+/// following rules of the ledger are enforced:
+///
+/// - A new Alias Output can be created by anyone.
+/// - A state transition increments the state index by one and must be signed by the state controller. The state
+///   controller and governor cannot be changed by a state transition.
+/// - A governance transition keeps the state index, state metadata and amount and must be signed by the governor.
+/// - An Alias Output can only be destroyed by its governor.
+/// - Every Alias Output must cover its storage deposit.
+///
+/// Every published state is recorded, so the client also implements [`IotaIdentityIndexer`].
+///
+/// Clones of the client share the same ledger, so a clone can be attached to a `Resolver` while the original is used
+/// to publish updates.
+#[derive(Clone)]
+pub struct MemoryLedgerClient {
+  protocol_parameters: ProtocolParameters,
+  ledger: Arc<Mutex<Ledger>>,
+}
+
+#[derive(Default)]
+struct Ledger {
+  /// The unspent Alias Outputs.
+  unspent: HashMap<AliasId, (OutputId, AliasOutput)>,
+  /// Every published state of the Alias Outputs, ordered by state index.
+  history: HashMap<AliasId, Vec<AliasOutputRecord>>,
+  /// Number of published transactions, used to derive unique output ids.
+  transactions: u64,
+}
+
+impl MemoryLedgerClient {
+  /// Creates a client with an empty ledger for the [`IotaDID::DEFAULT_NETWORK`].
+  pub fn new() -> Self {
+    let protocol_parameters: ProtocolParameters = ProtocolParameters::new(
+      2,
+      "memory".to_owned(),
+      IotaDID::DEFAULT_NETWORK,
+      0,
+      15,
+      RentStructure::default(),
+      4_600_000_000_000_000,
+    )
+    .expect("the protocol parameters should be valid");
+
+    Self::with_protocol_parameters(protocol_parameters)
+  }
+
+  /// Creates a client with an empty ledger using the given `protocol_parameters`.
+  ///
+  /// The Bech32 human-readable part of the parameters determines the network of the DIDs.
+  pub fn with_protocol_parameters(protocol_parameters: ProtocolParameters) -> Self {
+    Self {
+      protocol_parameters,
+      ledger: Arc::new(Mutex::new(Ledger::default())),
+    }
+  }
+
+  /// Publishes the given `alias_output` signed by `signer`, and returns the DID document it contains.
+  ///
+  /// # Errors
+  ///
+  /// [`Error::DIDUpdateError`] if the output violates one of the rules of the ledger.
+  pub fn publish_did_output(&self, signer: &Address, alias_output: AliasOutput) -> Result<IotaDocument> {
+    Output::Alias(alias_output.clone())
+      .verify_storage_deposit(
+        *self.protocol_parameters.rent_structure(),
+        self.protocol_parameters.token_supply(),
+      )
+      .map_err(|err| {
+        Error::DIDUpdateError(
+          "publish_did_output: insufficient storage deposit",
+          Some(Box::new(ClientError::Block(err))),
+        )
+      })?;
+
+    let mut ledger = self.lock();
+    let output_id: OutputId = ledger.next_output_id();
+
+    // The state index of new Alias Outputs is already checked when they are built.
+    let alias_id: AliasId = if alias_output.alias_id().is_null() {
+      AliasId::from(&output_id)
+    } else {
+      let alias_id: AliasId = *alias_output.alias_id();
+      let (_, current) = ledger.unspent.get(&alias_id).ok_or_else(|| {
+        Error::DIDUpdateError(
+          "publish_did_output: alias output not found",
+          Some(Box::new(ClientError::NoOutput(alias_id.to_string()))),
+        )
+      })?;
+      check_transition(current, &alias_output, signer)?;
+      alias_id
+    };
+
+    let alias_output: AliasOutput = AliasOutputBuilder::from(&alias_output)
+      .with_alias_id(alias_id)
+      .finish()
+      .map_err(Error::AliasOutputBuildError)?;
+    ledger.unspent.insert(alias_id, (output_id, alias_output.clone()));
+    ledger.history.entry(alias_id).or_default().push(AliasOutputRecord {
+      output_id,
+      alias_output: alias_output.clone(),
+      booked: Timestamp::now_utc(),
+    });
+    drop(ledger);
+
+    let did: IotaDID = IotaDID::new(&alias_id, &self.network_name()?);
+    IotaDocument::unpack_from_output(&did, &alias_output, true)
+  }
+
+  /// Destroys the Alias Output containing the given `did`, signed by `signer`.
+  ///
+  /// The recorded history of the Alias Output is retained.
+  ///
+  /// # Errors
+  ///
+  /// [`Error::DIDUpdateError`] if the Alias Output does not exist or `signer` is not its governor.
+  pub fn delete_did_output(&self, signer: &Address, did: &IotaDID) -> Result<()> {
+    let alias_id: AliasId = AliasId::from(did);
+    let mut ledger = self.lock();
+    let (_, current) = ledger.unspent.get(&alias_id).ok_or_else(|| {
+      Error::DIDUpdateError(
+        "delete_did_output: alias output not found",
+        Some(Box::new(ClientError::NoOutput(alias_id.to_string()))),
+      )
+    })?;
+    if current.governor_address() != signer {
+      return Err(Error::DIDUpdateError(
+        "delete_did_output: only the governor can destroy an alias output",
+        None,
+      ));
+    }
+
+    ledger.unspent.remove(&alias_id);
+    Ok(())
+  }
+
+  fn network_name(&self) -> Result<NetworkName> {
+    NetworkName::try_from(self.protocol_parameters.bech32_hrp().to_string())
+  }
+
+  fn lock(&self) -> MutexGuard<'_, Ledger> {
+    // The ledger is consistent after every operation, so a poisoned lock can be recovered.
+    self.ledger.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+impl Default for MemoryLedgerClient {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl std::fmt::Debug for MemoryLedgerClient {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("MemoryLedgerClient")
+      .field("network", &self.protocol_parameters.bech32_hrp())
+      .field("alias_outputs", &self.lock().unspent.len())
+      .finish()
+  }
+}
+
+impl Ledger {
+  fn next_output_id(&mut self) -> OutputId {
+    self.transactions += 1;
+    let mut transaction_id: [u8; 32] = [0; 32];
+    transaction_id[..8].copy_from_slice(&self.transactions.to_le_bytes());
+    OutputId::new(TransactionId::new(transaction_id), 0).expect("output index 0 should be valid")
+  }
+}
+
+/// Checks that `next` is a valid transition of the `current` Alias Output, signed by `signer`.
+fn check_transition(current: &AliasOutput, next: &AliasOutput, signer: &Address) -> Result<()> {
+  let error = |message: &'static str| Err(Error::DIDUpdateError(message, None));
+
+  if next.state_index() == current.state_index() {
+    if current.governor_address() != signer {
+      return error("publish_did_output: only the governor can perform a governance transition");
+    }
+    if next.state_metadata() != current.state_metadata() || next.amount() != current.amount() {
+      return error("publish_did_output: a governance transition cannot change the state");
+    }
+  } else if Some(next.state_index()) == current.state_index().checked_add(1) {
+    if current.state_controller_address() != signer {
+      return error("publish_did_output: only the state controller can perform a state transition");
+    }
+    if next.state_controller_address() != current.state_controller_address()
+      || next.governor_address() != current.governor_address()
+    {
+      return error("publish_did_output: a state transition cannot change the controllers");
+    }
+  } else {
+    return error("publish_did_output: invalid state index");
+  }
+
+  Ok(())
+}
+
+#[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
+#[cfg_attr(not(feature = "send-sync-client-ext"), async_trait::async_trait(?Send))]
+impl IotaIdentityClient for MemoryLedgerClient {
+  async fn get_alias_output(&self, alias_id: AliasId) -> Result<(OutputId, AliasOutput)> {
+    self
+      .lock()
+      .unspent
+      .get(&alias_id)
+      .cloned()
+      .ok_or_else(|| Error::DIDResolutionError(ClientError::NoOutput(alias_id.to_string())))
+  }
+
+  async fn get_protocol_parameters(&self) -> Result<ProtocolParameters> {
+    Ok(self.protocol_parameters.clone())
+  }
+}
+
+#[cfg_attr(feature = "send-sync-client-ext", async_trait::async_trait)]
+#[cfg_attr(not(feature = "send-sync-client-ext"), async_trait::async_trait(?Send))]
+impl IotaIdentityIndexer for MemoryLedgerClient {
+  async fn get_alias_output_state(&self, alias_id: AliasId, state_index: u32) -> Result<Option<AliasOutputRecord>> {
+    Ok(
+      self
+        .lock()
+        .history
+        .get(&alias_id)
+        .and_then(|history| history.get(state_index as usize))
+        .cloned(),
+    )
+  }
+
+  async fn get_latest_alias_output_state(&self, alias_id: AliasId) -> Result<Option<AliasOutputRecord>> {
+    Ok(
+      self
+        .lock()
+        .history
+        .get(&alias_id)
+        .and_then(|history| history.last())
+        .cloned(),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::block::address::Address;
+  use crate::block::address::AliasAddress;
+  use crate::block::address::Ed25519Address;
+  use crate::block::output::unlock_condition::StateControllerAddressUnlockCondition;
+  use crate::block::output::AliasId;
+  use crate::block::output::AliasOutput;
+  use crate::block::output::AliasOutputBuilder;
+  use crate::block::output::RentStructure;
+  use crate::block::output::UnlockCondition;
+  use crate::Error;
+  use crate::IotaDID;
+  use crate::IotaDocument;
+  use crate::IotaDocumentVersion;
+  use crate::IotaIdentityClientExt;
+  use crate::IotaIdentityHistoryExt;
+
+  use super::MemoryLedgerClient;
+
+  fn address(byte: u8) -> Address {
+    Address::Ed25519(Ed25519Address::new([byte; 32]))
+  }
+
+  async fn create_did(client: &MemoryLedgerClient, controller: Address) -> IotaDocument {
+    let document: IotaDocument = IotaDocument::new(&client.network_name().unwrap());
+    let alias_output: AliasOutput = client.new_did_output(controller, document, None).await.unwrap();
+    client.publish_did_output(&controller, alias_output).unwrap()
+  }
+
+  #[tokio::test]
+  async fn create_update_deactivate_and_delete() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let controller: Address = address(1);
+
+    let mut document: IotaDocument = create_did(&client, controller).await;
+    assert!(!document.id().is_placeholder());
+    assert_eq!(client.resolve_did(document.id()).await.unwrap(), document);
+
+    // Publish an update.
+    document.properties_mut_unchecked().insert("foo".to_owned(), 1.into());
+    let alias_output: AliasOutput = client.update_did_output(document.clone()).await.unwrap();
+    assert_eq!(alias_output.state_index(), 1);
+    // INVALID: the larger document is not covered by the storage deposit.
+    assert!(matches!(
+      client
+        .publish_did_output(&controller, alias_output.clone())
+        .unwrap_err(),
+      Error::DIDUpdateError(..)
+    ));
+    let rent_structure: RentStructure = client.get_rent_structure().await.unwrap();
+    let alias_output: AliasOutput = AliasOutputBuilder::from(&alias_output)
+      .with_minimum_storage_deposit(rent_structure)
+      .finish()
+      .unwrap();
+    client.publish_did_output(&controller, alias_output).unwrap();
+    let resolved: IotaDocument = client.resolve_did(document.id()).await.unwrap();
+    assert_eq!(resolved.properties().get("foo"), Some(&1.into()));
+
+    // Deactivate the DID.
+    let alias_output: AliasOutput = client.deactivate_did_output(document.id()).await.unwrap();
+    client.publish_did_output(&controller, alias_output).unwrap();
+    let resolved: IotaDocument = client.resolve_did(document.id()).await.unwrap();
+    assert_eq!(resolved.metadata.deactivated, Some(true));
+
+    // Every state is recorded.
+    let history: Vec<IotaDocumentVersion> = client.resolve_did_history(document.id()).await.unwrap();
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[1].document.properties().get("foo"), Some(&1.into()));
+
+    // INVALID: only the governor can destroy the alias output.
+    assert!(matches!(
+      client.delete_did_output(&address(2), document.id()).unwrap_err(),
+      Error::DIDUpdateError(..)
+    ));
+    client.delete_did_output(&controller, document.id()).unwrap();
+    assert!(matches!(
+      client.resolve_did(document.id()).await.unwrap_err(),
+      Error::DIDResolutionError(iota_sdk::client::Error::NoOutput(_))
+    ));
+    // The history of the destroyed alias output can still be resolved.
+    assert_eq!(client.resolve_did_history(document.id()).await.unwrap(), history);
+    assert!(client.delete_did_output(&controller, document.id()).is_err());
+  }
+
+  #[tokio::test]
+  async fn dids_are_unique() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let document_1: IotaDocument = create_did(&client, address(1)).await;
+    let document_2: IotaDocument = create_did(&client, address(1)).await;
+    assert_ne!(document_1.id(), document_2.id());
+
+    // Clones share the ledger.
+    let clone: MemoryLedgerClient = client.clone();
+    assert_eq!(clone.resolve_did(document_1.id()).await.unwrap(), document_1);
+  }
+
+  #[tokio::test]
+  async fn state_transitions_are_signed_by_the_state_controller() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let controller: Address = address(1);
+    let document: IotaDocument = create_did(&client, controller).await;
+
+    // INVALID: signed by another address.
+    let alias_output: AliasOutput = client.update_did_output(document.clone()).await.unwrap();
+    assert!(client.publish_did_output(&address(2), alias_output.clone()).is_err());
+
+    // INVALID: the state index is skipped.
+    let skipped: AliasOutput = AliasOutputBuilder::from(&alias_output)
+      .with_state_index(2)
+      .finish()
+      .unwrap();
+    assert!(client.publish_did_output(&controller, skipped).is_err());
+
+    // INVALID: a state transition cannot change the state controller.
+    let new_controller: AliasOutput = AliasOutputBuilder::from(&alias_output)
+      .replace_unlock_condition(UnlockCondition::StateControllerAddress(
+        StateControllerAddressUnlockCondition::new(address(2)),
+      ))
+      .finish()
+      .unwrap();
+    assert!(client.publish_did_output(&controller, new_controller).is_err());
+
+    // INVALID: the storage deposit is not covered.
+    let insufficient: AliasOutput = AliasOutputBuilder::from(&alias_output).with_amount(1).finish().unwrap();
+    assert!(client.publish_did_output(&controller, insufficient).is_err());
+
+    client.publish_did_output(&controller, alias_output).unwrap();
+  }
+
+  #[tokio::test]
+  async fn governance_transitions_are_signed_by_the_governor() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let governor: Address = address(1);
+    let document: IotaDocument = create_did(&client, governor).await;
+    let (_, alias_output) = client.get_alias_output_of(&document);
+
+    // The governor hands over the state control.
+    let governance: AliasOutput = AliasOutputBuilder::from(&alias_output)
+      .replace_unlock_condition(UnlockCondition::StateControllerAddress(
+        StateControllerAddressUnlockCondition::new(address(2)),
+      ))
+      .finish()
+      .unwrap();
+    // INVALID: the state controller is not the governor.
+    assert!(client.publish_did_output(&address(2), governance.clone()).is_err());
+    client.publish_did_output(&governor, governance).unwrap();
+
+    // INVALID: the previous state controller can no longer update the DID.
+    let update: AliasOutput = client.update_did_output(document.clone()).await.unwrap();
+    assert!(client.publish_did_output(&governor, update.clone()).is_err());
+    client.publish_did_output(&address(2), update).unwrap();
+
+    // INVALID: a governance transition cannot change the state metadata.
+    let (_, alias_output) = client.get_alias_output_of(&document);
+    let governance: AliasOutput = AliasOutputBuilder::from(&alias_output)
+      .with_state_metadata(Vec::new())
+      .finish()
+      .unwrap();
+    assert!(client.publish_did_output(&governor, governance).is_err());
+  }
+
+  #[tokio::test]
+  async fn alias_owned_dids() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let parent: IotaDocument = create_did(&client, address(1)).await;
+    let parent_address: Address = Address::Alias(AliasAddress::new(AliasId::from(parent.id())));
+
+    let document: IotaDocument = IotaDocument::new(&client.network_name().unwrap());
+    let alias_output: AliasOutput = client
+      .new_controlled_did_output(parent.id(), document, None)
+      .await
+      .unwrap();
+    assert_eq!(alias_output.state_controller_address(), &parent_address);
+    assert_eq!(alias_output.governor_address(), &parent_address);
+    let mut document: IotaDocument = client.publish_did_output(&parent_address, alias_output).unwrap();
+    assert_eq!(document.metadata.controlling_did.as_ref(), Some(parent.id()));
+    assert_eq!(document.controller().collect::<Vec<_>>(), vec![parent.id()]);
+
+    // The parent is transitioned together with the update.
+    document
+      .metadata
+      .properties_mut()
+      .insert("label".to_owned(), "child".into());
+    let (parent_output, alias_output) = client
+      .update_controlled_did_output(parent.id(), document.clone())
+      .await
+      .unwrap();
+    assert_eq!(parent_output.state_index(), 1);
+    assert_eq!(
+      parent_output.state_metadata(),
+      client.get_alias_output_of(&parent).1.state_metadata()
+    );
+    let alias_output: AliasOutput = AliasOutputBuilder::from(&alias_output)
+      .with_minimum_storage_deposit(RentStructure::default())
+      .finish()
+      .unwrap();
+    client.publish_did_output(&address(1), parent_output).unwrap();
+    client.publish_did_output(&parent_address, alias_output).unwrap();
+
+    let resolved: IotaDocument = client.resolve_controlled_did(parent.id(), document.id()).await.unwrap();
+    assert_eq!(resolved.metadata.properties().get("label").unwrap(), "child");
+    assert_eq!(resolved.metadata.controlling_did.as_ref(), Some(parent.id()));
+
+    // INVALID: the DIDs are not controlled by the given DID, which has to exist.
+    let other: IotaDocument = create_did(&client, address(2)).await;
+    assert!(matches!(
+      client
+        .resolve_controlled_did(other.id(), document.id())
+        .await
+        .unwrap_err(),
+      Error::NotControlledBy { .. }
+    ));
+    assert!(matches!(
+      client
+        .update_controlled_did_output(parent.id(), other.clone())
+        .await
+        .unwrap_err(),
+      Error::NotControlledBy { .. }
+    ));
+    assert!(other.metadata.controlling_did.is_none());
+    let missing: IotaDID = IotaDID::new(&[7; 32], &client.network_name().unwrap());
+    let document: IotaDocument = IotaDocument::new(&client.network_name().unwrap());
+    assert!(client
+      .new_controlled_did_output(&missing, document, None)
+      .await
+      .is_err());
+  }
+
+  impl MemoryLedgerClient {
+    fn get_alias_output_of(&self, document: &IotaDocument) -> (crate::block::output::OutputId, AliasOutput) {
+      self
+        .lock()
+        .unspent
+        .get(&crate::block::output::AliasId::from(document.id()))
+        .cloned()
+        .unwrap()
+    }
+  }
+}
@@ -1,12 +1,27 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub use controllers::IotaControllerGraph;
+pub use controllers::DEFAULT_MAX_CONTROLLER_DEPTH;
+pub use history::AliasOutputRecord;
+pub use history::IotaDocumentVersion;
+pub use history::IotaIdentityHistoryExt;
+pub use history::IotaIdentityIndexer;
 pub use identity_client::IotaIdentityClient;
 pub use identity_client::IotaIdentityClientExt;
+pub use storage_deposit::DocumentFieldCost;
+pub use storage_deposit::StorageDeposit;
 
 #[cfg(feature = "iota-client")]
 pub use self::iota_client::IotaClientExt;
+#[cfg(feature = "iota-client")]
+pub use self::memory_client::MemoryLedgerClient;
 
+mod controllers;
+mod history;
 mod identity_client;
 #[cfg(feature = "iota-client")]
 mod iota_client;
+#[cfg(feature = "iota-client")]
+mod memory_client;
+mod storage_deposit;
@@ -0,0 +1,255 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Value;
+use identity_core::convert::ToJson;
+
+use crate::block::output::AliasOutput;
+use crate::block::output::Output;
+use crate::block::output::Rent;
+use crate::block::output::RentStructure;
+use crate::Error;
+use crate::Result;
+use crate::StateMetadataDocument;
+
+/// The storage deposit required by an Alias Output containing a DID document.
+///
+/// See [`IotaIdentityClientExt::update_did_output_with_storage_deposit`](crate::IotaIdentityClientExt::update_did_output_with_storage_deposit).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StorageDeposit {
+  /// The amount of tokens deposited on the Alias Output.
+  pub amount: u64,
+  /// The minimum storage deposit required by the Alias Output.
+  pub required_amount: u64,
+  /// The part of `required_amount` caused by the packed DID document in the state metadata.
+  pub state_metadata_cost: u64,
+  /// The length of the packed DID document in bytes.
+  pub state_metadata_len: usize,
+  /// The share of each field of the DID document in `state_metadata_cost`, ordered by descending cost.
+  pub fields: Vec<DocumentFieldCost>,
+}
+
+/// The share of a field of a DID document in the storage deposit of its Alias Output.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DocumentFieldCost {
+  /// The name of the field, e.g. `verificationMethod` or `service`. The metadata of the document is reported as
+  /// `meta`.
+  pub field: String,
+  /// The length of the value of the field in the JSON representation of the document.
+  pub json_len: usize,
+  /// The share of the field in the storage deposit, proportional to its length in the JSON representation.
+  pub cost: u64,
+}
+
+impl StorageDeposit {
+  /// Computes the storage deposit required by `alias_output` according to `rent_structure`.
+  ///
+  /// # Errors
+  ///
+  /// Fails if the state metadata of the Alias Output does not contain a valid DID document.
+  pub fn new(alias_output: &AliasOutput, rent_structure: RentStructure) -> Result<Self> {
+    let state_metadata: &[u8] = alias_output.state_metadata();
+    let required_amount: u64 = Output::Alias(alias_output.clone()).rent_cost(&rent_structure);
+    let state_metadata_cost: u64 =
+      state_metadata.len() as u64 * rent_structure.byte_cost() as u64 * rent_structure.byte_factor_data() as u64;
+
+    let fields: Vec<DocumentFieldCost> = if state_metadata.is_empty() {
+      Vec::new()
+    } else {
+      field_costs(&StateMetadataDocument::unpack(state_metadata)?, state_metadata_cost)?
+    };
+
+    Ok(Self {
+      amount: alias_output.amount(),
+      required_amount,
+      state_metadata_cost,
+      state_metadata_len: state_metadata.len(),
+      fields,
+    })
+  }
+
+  /// Returns the difference between the required and the deposited amount.
+  ///
+  /// A positive delta has to be added to the Alias Output before it can be published, a negative one could be
+  /// released.
+  pub fn delta(&self) -> i128 {
+    self.required_amount as i128 - self.amount as i128
+  }
+
+  /// Returns whether the deposited amount covers the required storage deposit.
+  pub fn is_sufficient(&self) -> bool {
+    self.amount >= self.required_amount
+  }
+}
+
+/// Distributes `state_metadata_cost` over the fields of `document` in proportion to their length in JSON.
+fn field_costs(document: &StateMetadataDocument, state_metadata_cost: u64) -> Result<Vec<DocumentFieldCost>> {
+  let mut fields: Vec<(String, usize)> = Vec::new();
+  for (field, value) in to_object(&document.document)? {
+    fields.push((field, json_len(&value)?));
+  }
+  fields.push((
+    "meta".to_owned(),
+    json_len(&document.metadata.to_json_value().map_err(serialization_error)?)?,
+  ));
+
+  let total_len: u128 = fields.iter().map(|(_, len)| *len as u128).sum::<u128>().max(1);
+  let mut costs: Vec<DocumentFieldCost> = fields
+    .into_iter()
+    .map(|(field, json_len)| DocumentFieldCost {
+      cost: (state_metadata_cost as u128 * json_len as u128 / total_len) as u64,
+      field,
+      json_len,
+    })
+    .collect();
+  costs.sort_by(|a, b| b.cost.cmp(&a.cost).then_with(|| a.field.cmp(&b.field)));
+  Ok(costs)
+}
+
+fn to_object(value: &impl ToJson) -> Result<Object> {
+  match value.to_json_value().map_err(serialization_error)? {
+    Value::Object(object) => Ok(object.into_iter().collect()),
+    _ => Err(Error::SerializationError(
+      "expected the document to serialize to a JSON object",
+      None,
+    )),
+  }
+}
+
+fn json_len(value: &Value) -> Result<usize> {
+  value.to_json_vec().map(|json| json.len()).map_err(serialization_error)
+}
+
+fn serialization_error(err: identity_core::Error) -> Error {
+  Error::SerializationError("failed to serialize document to JSON", Some(err))
+}
+
+#[cfg(all(test, feature = "iota-client"))]
+mod tests {
+  use identity_core::common::Object;
+  use identity_core::common::Url;
+  use identity_did::DID;
+  use identity_document::service::Service;
+  use identity_verification::MethodScope;
+
+  use crate::block::address::Address;
+  use crate::block::address::Ed25519Address;
+  use crate::block::output::AliasOutput;
+  use crate::block::output::AliasOutputBuilder;
+  use crate::block::output::Output;
+  use crate::block::output::Rent;
+  use crate::block::output::RentStructure;
+  use crate::test_utils::generate_method;
+  use crate::IotaDocument;
+  use crate::IotaIdentityClientExt;
+  use crate::MemoryLedgerClient;
+
+  use super::StorageDeposit;
+
+  fn controller() -> Address {
+    Address::Ed25519(Ed25519Address::new([1; 32]))
+  }
+
+  async fn create_did(client: &MemoryLedgerClient) -> IotaDocument {
+    let document: IotaDocument = IotaDocument::new(&client.network_name().await.unwrap());
+    let alias_output: AliasOutput = client.new_did_output(controller(), document, None).await.unwrap();
+    client.publish_did_output(&controller(), alias_output).unwrap()
+  }
+
+  fn grow(document: &mut IotaDocument) {
+    for index in 0..3 {
+      let method = generate_method(document.id(), &format!("key-{index}"));
+      document.insert_method(method, MethodScope::VerificationMethod).unwrap();
+    }
+    let service: Service = Service::builder(Object::new())
+      .id(document.id().to_url().join("#linked-domain").unwrap())
+      .type_("LinkedDomains")
+      .service_endpoint(Url::parse("https://example.com").unwrap())
+      .build()
+      .unwrap();
+    document.insert_service(service).unwrap();
+  }
+
+  #[tokio::test]
+  async fn storage_deposit_of_grown_document() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let rent_structure: RentStructure = client.get_rent_structure().await.unwrap();
+    let mut document: IotaDocument = create_did(&client).await;
+    grow(&mut document);
+
+    let (alias_output, storage_deposit) = client
+      .update_did_output_with_storage_deposit(document.clone(), None, false)
+      .await
+      .unwrap();
+    assert_eq!(alias_output.amount(), storage_deposit.amount);
+    assert!(!storage_deposit.is_sufficient());
+    assert!(storage_deposit.delta() > 0);
+    assert_eq!(
+      storage_deposit.required_amount,
+      Output::Alias(alias_output.clone()).rent_cost(&rent_structure)
+    );
+    assert_eq!(storage_deposit.state_metadata_len, alias_output.state_metadata().len());
+
+    // The state metadata cost is the difference to an output without a document.
+    let empty: AliasOutput = AliasOutputBuilder::from(&alias_output)
+      .with_state_metadata(Vec::new())
+      .finish()
+      .unwrap();
+    assert_eq!(
+      storage_deposit.state_metadata_cost,
+      storage_deposit.required_amount - Output::Alias(empty).rent_cost(&rent_structure)
+    );
+
+    // The verification methods are the most expensive field.
+    assert_eq!(storage_deposit.fields[0].field, "verificationMethod");
+    assert!(storage_deposit.fields.iter().any(|field| field.field == "service"));
+    assert!(storage_deposit.fields.iter().any(|field| field.field == "meta"));
+    let fields_cost: u64 = storage_deposit.fields.iter().map(|field| field.cost).sum();
+    assert!(fields_cost <= storage_deposit.state_metadata_cost);
+    assert!(fields_cost + storage_deposit.fields.len() as u64 > storage_deposit.state_metadata_cost);
+
+    // INVALID: the storage deposit is not covered.
+    assert!(client.publish_did_output(&controller(), alias_output).is_err());
+
+    let (alias_output, topped_up) = client
+      .update_did_output_with_storage_deposit(document, Some(rent_structure), true)
+      .await
+      .unwrap();
+    assert_eq!(topped_up, storage_deposit);
+    assert_eq!(alias_output.amount(), storage_deposit.required_amount);
+    client.publish_did_output(&controller(), alias_output).unwrap();
+  }
+
+  #[tokio::test]
+  async fn excess_amount_is_kept() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let mut document: IotaDocument = create_did(&client).await;
+    grow(&mut document);
+    let (alias_output, _) = client
+      .update_did_output_with_storage_deposit(document.clone(), None, true)
+      .await
+      .unwrap();
+    client.publish_did_output(&controller(), alias_output).unwrap();
+
+    // Shrinking the document releases part of the deposit, but the amount is not decreased.
+    let did = document.id().clone();
+    let document: IotaDocument = IotaDocument::new_with_id(did.clone());
+    let (alias_output, storage_deposit) = client
+      .update_did_output_with_storage_deposit(document, None, true)
+      .await
+      .unwrap();
+    assert!(storage_deposit.is_sufficient());
+    assert!(storage_deposit.delta() < 0);
+    assert_eq!(alias_output.amount(), storage_deposit.amount);
+
+    // A deactivated DID has no document fields.
+    let alias_output: AliasOutput = client.deactivate_did_output(&did).await.unwrap();
+    let storage_deposit: StorageDeposit =
+      StorageDeposit::new(&alias_output, client.get_rent_structure().await.unwrap()).unwrap();
+    assert_eq!(storage_deposit.state_metadata_cost, 0);
+    assert!(storage_deposit.fields.is_empty());
+  }
+}
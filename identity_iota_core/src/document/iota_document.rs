@@ -19,6 +19,7 @@ use identity_core::common::OrderedSet;
 use identity_core::common::Url;
 use identity_core::convert::FmtJson;
 use identity_document::document::CoreDocument;
+use identity_document::document::CoreDocumentDiff;
 use identity_document::service::Service;
 use identity_document::utils::DIDUrlQuery;
 use identity_verification::MethodRelationship;
@@ -193,6 +194,26 @@ impl IotaDocument {
     self.document.properties_mut_unchecked()
   }
 
+  /// Returns the changes from this document to `other`, see [`CoreDocument::diff`].
+  ///
+  /// The metadata of the documents is not part of the diff.
+  pub fn diff(&self, other: &IotaDocument) -> CoreDocumentDiff {
+    self.document.diff(&other.document)
+  }
+
+  /// Applies the changes of `diff` to this document, see [`CoreDocument::patch`].
+  ///
+  /// # Errors
+  ///
+  /// Fails without modifying the document if the diff can not be applied or adds a controller that does not conform
+  /// to the IOTA method specification.
+  pub fn patch(&mut self, diff: &CoreDocumentDiff) -> Result<()> {
+    for controller in diff.controller().added() {
+      IotaDID::check_validity(controller).map_err(Error::DIDSyntaxError)?;
+    }
+    self.document.patch(diff).map_err(Error::InvalidDoc)
+  }
+
   // ===========================================================================
   // Services
   // ===========================================================================
@@ -332,7 +353,7 @@ impl IotaDocument {
   /// Returns the first [`Service`] with an `id` property matching the provided `service_query`, if present.
   // NOTE: This method demonstrates unexpected behaviour in the edge cases where the document contains
   // services whose ids are of the form <did different from this document's>#<fragment>.
-  pub fn resolve_service<'query, 'me, Q>(&'me self, service_query: Q) -> Option<&Service>
+  pub fn resolve_service<'query, 'me, Q>(&'me self, service_query: Q) -> Option<&'me Service>
   where
     Q: Into<DIDUrlQuery<'query>>,
   {
@@ -347,7 +368,7 @@ impl IotaDocument {
     &'me self,
     method_query: Q,
     scope: Option<MethodScope>,
-  ) -> Option<&VerificationMethod>
+  ) -> Option<&'me VerificationMethod>
   where
     Q: Into<DIDUrlQuery<'query>>,
   {
@@ -457,6 +478,7 @@ mod client_document {
         _ => None,
       };
 
+      self.metadata.controlling_did = controller_did.clone();
       if let Some(controller_did) = controller_did {
         match self.core_document_mut().controller_mut() {
           Some(controllers) => {
@@ -852,6 +874,7 @@ mod tests {
     assert_eq!(controllers.first().unwrap(), &external_controller_did);
     assert_eq!(controllers.get(1).unwrap(), &alias_controller);
     assert_eq!(controllers.len(), 2);
+    assert_eq!(document.metadata.controlling_did.as_ref(), Some(&alias_controller));
   }
 
   #[test]
@@ -877,14 +900,14 @@ mod tests {
 
     // Ensure no other fields are injected.
     let json: String = format!(
-      r#"{{"doc":{{"id":"{did}","controller":"{controller_did}"}},"meta":{{"deactivated":true,"governorAddress":"iota1pz424242424242424242424242424242424242424242424242425ryaqzy","stateControllerAddress":"iota1pz424242424242424242424242424242424242424242424242425ryaqzy"}}}}"#
+      r#"{{"doc":{{"id":"{did}","controller":"{controller_did}"}},"meta":{{"deactivated":true,"governorAddress":"iota1pz424242424242424242424242424242424242424242424242425ryaqzy","stateControllerAddress":"iota1pz424242424242424242424242424242424242424242424242425ryaqzy","controllingDid":"{controller_did}"}}}}"#
     );
     assert_eq!(document.to_json().unwrap(), json);
 
     // INVALID: reject empty document.
     assert!(IotaDocument::unpack_from_output(&did, &alias_output, false).is_err());
 
-    // Ensure re-packing removes the controller, state controller address, and governor address.
+    // Ensure re-packing removes the controller, state controller address, governor address, and controlling DID.
     let packed: Vec<u8> = document.pack_with_encoding(StateMetadataEncoding::Json).unwrap();
     let state_metadata_document: StateMetadataDocument = StateMetadataDocument::unpack(&packed).unwrap();
     let unpacked_document: IotaDocument = state_metadata_document.into_iota_document(&did).unwrap();
@@ -894,6 +917,7 @@ mod tests {
     );
     assert!(unpacked_document.metadata.state_controller_address.is_none());
     assert!(unpacked_document.metadata.governor_address.is_none());
+    assert!(unpacked_document.metadata.controlling_did.is_none());
   }
 
   #[test]
@@ -1128,4 +1152,28 @@ mod tests {
 
     assert!(IotaDocument::try_from((doc_with_iota_id_and_controller, metadata)).is_ok());
   }
+
+  #[test]
+  fn test_diff_patch() {
+    let document: IotaDocument = generate_document(&valid_did());
+    let mut updated: IotaDocument = document.clone();
+    updated
+      .set_controller([
+        IotaDID::parse("did:iota:0xfbaaa919b51112d51a8f18b1500d98f0b2e91d793bc5b27fd5ab04cb1b806343").unwrap(),
+      ]);
+    updated
+      .remove_method(&document.id().to_url().join("#key-3").unwrap())
+      .unwrap();
+
+    let mut patched: IotaDocument = document.clone();
+    patched.patch(&document.diff(&updated)).unwrap();
+    assert_eq!(patched.core_document(), updated.core_document());
+
+    // Controllers of other methods are rejected.
+    let mut foreign: CoreDocument = document.core_document().clone();
+    *foreign.controller_mut() = Some(OneOrSet::new_one(CoreDID::parse("did:example:controller").unwrap()));
+    let mut patched: IotaDocument = document.clone();
+    assert!(patched.patch(&document.core_document().diff(&foreign)).is_err());
+    assert_eq!(patched, document);
+  }
 }
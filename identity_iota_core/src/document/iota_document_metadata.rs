@@ -11,6 +11,8 @@ use identity_core::convert::FmtJson;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::IotaDID;
+
 /// Additional attributes related to a [`IotaDocument`][crate::IotaDocument].
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct IotaDocumentMetadata {
@@ -29,6 +31,9 @@ pub struct IotaDocumentMetadata {
   /// Bech32-encoded address of the state controller unlock condition.
   #[serde(rename = "stateControllerAddress", skip_serializing_if = "Option::is_none")]
   pub state_controller_address: Option<String>,
+  /// The DID whose Alias Address is the state controller of the Alias Output, if any.
+  #[serde(rename = "controllingDid", skip_serializing_if = "Option::is_none")]
+  pub controlling_did: Option<IotaDID>,
   #[serde(flatten)]
   properties: Object,
 }
@@ -44,6 +49,7 @@ impl IotaDocumentMetadata {
       deactivated: None,
       governor_address: None,
       state_controller_address: None,
+      controlling_did: None,
       properties: Object::default(),
     }
   }
@@ -70,6 +70,19 @@ pub enum Error {
   /// Caused by an error in the Wasm bindings.
   #[error("JavaScript function threw an exception: {0}")]
   JsError(String),
+  #[cfg(feature = "client")]
+  /// Caused by a failure to resolve a specific version of a DID document.
+  #[error("DID version resolution failed: {0}")]
+  DIDVersionError(&'static str),
+  #[cfg(feature = "client")]
+  /// Caused by a DID whose Alias Output is not controlled by the Alias Output of the expected DID.
+  #[error("`{did}` is not controlled by `{controller}`")]
+  NotControlledBy {
+    /// The controlled DID.
+    did: String,
+    /// The expected controller DID.
+    controller: String,
+  },
   /// Caused by an error during JSON Web Signature verification.
   #[error("jws signature verification failed")]
   JwsVerificationError(#[source] identity_document::Error),
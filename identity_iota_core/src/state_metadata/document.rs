@@ -1,6 +1,11 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "state-metadata-cbor")]
+use std::io::Read;
+#[cfg(feature = "state-metadata-cbor")]
+use std::io::Write;
+
 use identity_core::convert::FromJson;
 use identity_core::convert::ToJson;
 use identity_did::CoreDID;
@@ -23,6 +28,10 @@ pub(crate) static PLACEHOLDER_DID: Lazy<CoreDID> = Lazy::new(|| CoreDID::parse("
 /// Magic bytes used to mark DID documents.
 const DID_MARKER: &[u8] = b"DID";
 
+/// The maximum length of a decompressed document, which is also the maximum length of an uncompressed one.
+#[cfg(feature = "state-metadata-cbor")]
+const MAX_DECOMPRESSED_LEN: u64 = u16::MAX as u64;
+
 /// Intermediate representation of the DID document as it is contained in the state metadata of
 /// an Alias Output.
 ///
@@ -76,14 +85,34 @@ impl StateMetadataDocument {
   /// Pack a [`StateMetadataDocument`] into bytes, suitable for inclusion in
   /// an Alias Output's state metadata, according to the given `encoding`.
   pub fn pack(mut self, encoding: StateMetadataEncoding) -> Result<Vec<u8>> {
-    // Unset Governor and State Controller Addresses to avoid bloating the payload
+    // Unset Governor and State Controller Addresses and the controlling DID to avoid bloating the payload
     self.metadata.governor_address = None;
     self.metadata.state_controller_address = None;
+    self.metadata.controlling_did = None;
 
     let encoded_message_data: Vec<u8> = match encoding {
       StateMetadataEncoding::Json => self
         .to_json_vec()
         .map_err(|err| Error::SerializationError("failed to serialize document to JSON", Some(err)))?,
+      #[cfg(feature = "state-metadata-cbor")]
+      StateMetadataEncoding::CborZlib => {
+        let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder
+          .write_all(&self.to_cbor_vec()?)
+          .and_then(|_| encoder.finish())
+          .map_err(|_| Error::SerializationError("failed to compress document with zlib", None))?
+      }
+      #[cfg(feature = "state-metadata-cbor")]
+      StateMetadataEncoding::CborBrotli => {
+        let mut compressed: Vec<u8> = Vec::new();
+        brotli::BrotliCompress(
+          &mut self.to_cbor_vec()?.as_slice(),
+          &mut compressed,
+          &brotli::enc::BrotliEncoderParams::default(),
+        )
+        .map_err(|_| Error::SerializationError("failed to compress document with brotli", None))?;
+        compressed
+      }
     };
 
     // Prepend flags and length.
@@ -160,8 +189,42 @@ impl StateMetadataDocument {
           Some(err),
         )
       }),
+      #[cfg(feature = "state-metadata-cbor")]
+      StateMetadataEncoding::CborZlib => Self::from_cbor_slice(&decompress(flate2::read::ZlibDecoder::new(data))?),
+      #[cfg(feature = "state-metadata-cbor")]
+      StateMetadataEncoding::CborBrotli => Self::from_cbor_slice(&decompress(brotli::Decompressor::new(data, 4096))?),
     }
   }
+
+  #[cfg(feature = "state-metadata-cbor")]
+  fn to_cbor_vec(&self) -> Result<Vec<u8>> {
+    let mut buffer: Vec<u8> = Vec::new();
+    ciborium::into_writer(self, &mut buffer)
+      .map_err(|_| Error::SerializationError("failed to serialize document to CBOR", None))?;
+    Ok(buffer)
+  }
+
+  #[cfg(feature = "state-metadata-cbor")]
+  fn from_cbor_slice(data: &[u8]) -> Result<Self> {
+    ciborium::from_reader(data)
+      .map_err(|_| Error::SerializationError("state metadata decoding: failed to deserialize CBOR document", None))
+  }
+}
+
+/// Reads the decompressed document from `decoder`, failing if it exceeds [`MAX_DECOMPRESSED_LEN`].
+#[cfg(feature = "state-metadata-cbor")]
+fn decompress(decoder: impl Read) -> Result<Vec<u8>> {
+  let mut decompressed: Vec<u8> = Vec::new();
+  decoder
+    .take(MAX_DECOMPRESSED_LEN + 1)
+    .read_to_end(&mut decompressed)
+    .map_err(|_| Error::InvalidStateMetadata("failed to decompress document"))?;
+  if decompressed.len() as u64 > MAX_DECOMPRESSED_LEN {
+    return Err(Error::InvalidStateMetadata(
+      "decompressed document exceeds the maximum length",
+    ));
+  }
+  Ok(decompressed)
 }
 
 /// Prepends the message flags and marker magic bytes to the data in the following order:
@@ -217,7 +280,11 @@ mod tests {
   use identity_did::DID;
   use identity_verification::MethodScope;
 
+  #[cfg(feature = "state-metadata-cbor")]
+  use crate::state_metadata::document::add_flags_to_message;
   use crate::state_metadata::document::DID_MARKER;
+  #[cfg(feature = "state-metadata-cbor")]
+  use crate::state_metadata::document::MAX_DECOMPRESSED_LEN;
   use crate::state_metadata::PLACEHOLDER_DID;
   use crate::test_utils::generate_method;
   use crate::IotaDID;
@@ -426,7 +493,7 @@ mod tests {
     // JSON length.
     assert_eq!(
       &packed[5..=6],
-      (expected_payload.as_bytes().len() as u16).to_le_bytes().as_ref()
+      (expected_payload.len() as u16).to_le_bytes().as_ref()
     );
     // JSON payload.
     assert_eq!(&packed[7..], expected_payload.as_bytes());
@@ -534,4 +601,142 @@ mod tests {
       unpacked_doc.document.properties()
     );
   }
+
+  #[cfg(feature = "state-metadata-cbor")]
+  #[test]
+  fn test_compressed_packing_roundtrip() {
+    let TestSetup { document, did_self, .. } = test_document();
+    let mut state_metadata_doc: StateMetadataDocument = StateMetadataDocument::from(document.clone());
+    // Governor and State Controller are set to None when packing
+    state_metadata_doc.metadata.governor_address = None;
+    state_metadata_doc.metadata.state_controller_address = None;
+
+    for encoding in [StateMetadataEncoding::CborZlib, StateMetadataEncoding::CborBrotli] {
+      let packed: Vec<u8> = state_metadata_doc.clone().pack(encoding).unwrap();
+      assert_eq!(&packed[0..3], DID_MARKER);
+      assert_eq!(packed[3], StateMetadataVersion::V1 as u8);
+      assert_eq!(packed[4], encoding as u8);
+      assert_eq!(&packed[5..=6], ((packed.len() - 7) as u16).to_le_bytes().as_ref());
+
+      let unpacked_doc: StateMetadataDocument = StateMetadataDocument::unpack(&packed).unwrap();
+      assert_eq!(unpacked_doc, state_metadata_doc);
+      assert_eq!(unpacked_doc.into_iota_document(&did_self).unwrap(), document);
+    }
+  }
+
+  #[cfg(feature = "state-metadata-cbor")]
+  #[test]
+  fn test_compressed_encoding_size() {
+    let TestSetup {
+      mut document, did_self, ..
+    } = test_document();
+    for index in 0..5 {
+      document
+        .insert_method(
+          generate_method(&did_self, &format!("key-{index}")),
+          MethodScope::assertion_method(),
+        )
+        .unwrap();
+      document
+        .insert_service(
+          Service::builder(Object::new())
+            .id(document.id().to_url().join(format!("#linked-domain-{index}")).unwrap())
+            .type_("LinkedDomains")
+            .service_endpoint(Url::parse(format!("https://domain-{index}.example.com")).unwrap())
+            .build()
+            .unwrap(),
+        )
+        .unwrap();
+    }
+
+    let json: Vec<u8> = document
+      .clone()
+      .pack_with_encoding(StateMetadataEncoding::Json)
+      .unwrap();
+    let zlib: Vec<u8> = document
+      .clone()
+      .pack_with_encoding(StateMetadataEncoding::CborZlib)
+      .unwrap();
+    let brotli: Vec<u8> = document
+      .clone()
+      .pack_with_encoding(StateMetadataEncoding::CborBrotli)
+      .unwrap();
+
+    assert!(zlib.len() < json.len(), "zlib: {}, json: {}", zlib.len(), json.len());
+    assert!(
+      brotli.len() < json.len(),
+      "brotli: {}, json: {}",
+      brotli.len(),
+      json.len()
+    );
+
+    for packed in [json, zlib, brotli] {
+      let unpacked: IotaDocument = StateMetadataDocument::unpack(&packed)
+        .unwrap()
+        .into_iota_document(&did_self)
+        .unwrap();
+      assert_eq!(unpacked.core_document(), document.core_document());
+    }
+  }
+
+  #[cfg(feature = "state-metadata-cbor")]
+  #[test]
+  fn test_unpack_invalid_compressed_data() {
+    let TestSetup { document, .. } = test_document();
+    let state_metadata_doc: StateMetadataDocument = StateMetadataDocument::from(document);
+
+    for encoding in [StateMetadataEncoding::CborZlib, StateMetadataEncoding::CborBrotli] {
+      // INVALID: the compressed document is corrupted.
+      let mut packed: Vec<u8> = state_metadata_doc.clone().pack(encoding).unwrap();
+      let middle: usize = 7 + (packed.len() - 7) / 2;
+      packed.truncate(middle);
+      packed[5..=6].copy_from_slice(&((middle - 7) as u16).to_le_bytes());
+      assert!(StateMetadataDocument::unpack(&packed).is_err());
+
+      // INVALID: the decompressed data is not a CBOR document.
+      let packed: Vec<u8> = add_flags_to_message(
+        compress(encoding, b"not a document"),
+        StateMetadataVersion::V1,
+        encoding,
+      )
+      .unwrap();
+      assert!(StateMetadataDocument::unpack(&packed).is_err());
+
+      // INVALID: the decompressed data exceeds the maximum length.
+      let packed: Vec<u8> = add_flags_to_message(
+        compress(encoding, &vec![0; MAX_DECOMPRESSED_LEN as usize + 1]),
+        StateMetadataVersion::V1,
+        encoding,
+      )
+      .unwrap();
+      assert!(matches!(
+        StateMetadataDocument::unpack(&packed).unwrap_err(),
+        crate::Error::InvalidStateMetadata(_)
+      ));
+    }
+  }
+
+  #[cfg(feature = "state-metadata-cbor")]
+  fn compress(encoding: StateMetadataEncoding, data: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+
+    match encoding {
+      StateMetadataEncoding::CborZlib => {
+        let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+      }
+      StateMetadataEncoding::CborBrotli => {
+        let mut compressed: Vec<u8> = Vec::new();
+        brotli::BrotliCompress(
+          &mut &data[..],
+          &mut compressed,
+          &brotli::enc::BrotliEncoderParams::default(),
+        )
+        .unwrap();
+        compressed
+      }
+      StateMetadataEncoding::Json => unreachable!(),
+    }
+  }
 }
@@ -12,6 +12,14 @@ pub enum StateMetadataEncoding {
   /// State Metadata encoded as JSON.
   #[default]
   Json = 0,
+  /// State Metadata encoded as CBOR and compressed with zlib.
+  #[cfg(feature = "state-metadata-cbor")]
+  CborZlib = 1,
+  /// State Metadata encoded as CBOR and compressed with Brotli.
+  ///
+  /// Usually yields the smallest state metadata, and therefore the lowest storage deposit.
+  #[cfg(feature = "state-metadata-cbor")]
+  CborBrotli = 2,
 }
 
 impl TryFrom<u8> for StateMetadataEncoding {
@@ -43,6 +43,18 @@ pub enum Error {
   /// Caused by an error during signature verification.
   #[error("signature verification error")]
   SignatureVerificationError(#[source] crate::jws::SignatureVerificationError),
+  /// Caused by a string that does not correspond to a supported [`JweAlgorithm`](crate::jwe::JweAlgorithm).
+  #[error("attempt to parse an unregistered jwe algorithm")]
+  JweAlgorithmParsingError,
+  /// Caused by a string that does not correspond to a supported [`JweEncryption`](crate::jwe::JweEncryption).
+  #[error("attempt to parse an unregistered jwe encryption algorithm")]
+  JweEncryptionParsingError,
+  /// Caused by a failure to encrypt a JSON Web Encryption.
+  #[error("encryption error: {0}")]
+  EncryptionError(&'static str),
+  /// Caused by a failure to decrypt a JSON Web Encryption.
+  #[error("decryption error: {0}")]
+  DecryptionError(&'static str),
   /// Caused by a mising header.
   #[error("missing header")]
   MissingHeader(&'static str),
@@ -0,0 +1,80 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result;
+use std::str::FromStr;
+
+/// Supported algorithms for the JSON Web Encryption `alg` claim.
+///
+/// Only the Elliptic Curve Diffie-Hellman Ephemeral Static key agreement family is supported.
+///
+/// [More Info](https://www.rfc-editor.org/rfc/rfc7518#section-4.6)
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, serde::Deserialize, serde::Serialize)]
+#[allow(non_camel_case_types)]
+pub enum JweAlgorithm {
+  /// Elliptic Curve Diffie-Hellman Ephemeral Static key agreement using Concat KDF
+  #[serde(rename = "ECDH-ES")]
+  ECDH_ES,
+  /// ECDH-ES using Concat KDF and CEK wrapped with "A128KW"
+  #[serde(rename = "ECDH-ES+A128KW")]
+  ECDH_ES_A128KW,
+  /// ECDH-ES using Concat KDF and CEK wrapped with "A192KW"
+  #[serde(rename = "ECDH-ES+A192KW")]
+  ECDH_ES_A192KW,
+  /// ECDH-ES using Concat KDF and CEK wrapped with "A256KW"
+  #[serde(rename = "ECDH-ES+A256KW")]
+  ECDH_ES_A256KW,
+}
+
+impl JweAlgorithm {
+  /// A slice of all supported [`JweAlgorithm`]s.
+  pub const ALL: &'static [Self] = &[
+    Self::ECDH_ES,
+    Self::ECDH_ES_A128KW,
+    Self::ECDH_ES_A192KW,
+    Self::ECDH_ES_A256KW,
+  ];
+
+  /// Returns the JWE algorithm as a `str` slice.
+  pub const fn name(self) -> &'static str {
+    match self {
+      Self::ECDH_ES => "ECDH-ES",
+      Self::ECDH_ES_A128KW => "ECDH-ES+A128KW",
+      Self::ECDH_ES_A192KW => "ECDH-ES+A192KW",
+      Self::ECDH_ES_A256KW => "ECDH-ES+A256KW",
+    }
+  }
+
+  /// Returns the length in bytes of the AES key wrapping key, or `None` if the
+  /// agreed upon key is used directly as the content encryption key.
+  pub const fn key_wrap_len(self) -> Option<usize> {
+    match self {
+      Self::ECDH_ES => None,
+      Self::ECDH_ES_A128KW => Some(16),
+      Self::ECDH_ES_A192KW => Some(24),
+      Self::ECDH_ES_A256KW => Some(32),
+    }
+  }
+}
+
+impl FromStr for JweAlgorithm {
+  type Err = crate::error::Error;
+
+  fn from_str(string: &str) -> std::result::Result<Self, Self::Err> {
+    match string {
+      "ECDH-ES" => Ok(Self::ECDH_ES),
+      "ECDH-ES+A128KW" => Ok(Self::ECDH_ES_A128KW),
+      "ECDH-ES+A192KW" => Ok(Self::ECDH_ES_A192KW),
+      "ECDH-ES+A256KW" => Ok(Self::ECDH_ES_A256KW),
+      _ => Err(crate::error::Error::JweAlgorithmParsingError),
+    }
+  }
+}
+
+impl Display for JweAlgorithm {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    f.write_str(self.name())
+  }
+}
@@ -0,0 +1,123 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use aes_gcm::aead::AeadInPlace;
+use aes_gcm::aead::KeyInit;
+use aes_gcm::Aes256Gcm;
+use chacha20poly1305::ChaCha20Poly1305;
+use crypto::ciphers::aes_kw::Aes128Kw;
+use crypto::ciphers::aes_kw::Aes192Kw;
+use crypto::ciphers::aes_kw::Aes256Kw;
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+
+/// The result of encrypting a plaintext with a [`JweEncryption`].
+pub(crate) struct EncryptedContent {
+  pub(crate) iv: Vec<u8>,
+  pub(crate) ciphertext: Vec<u8>,
+  pub(crate) tag: Vec<u8>,
+}
+
+/// Generates `len` random bytes, e.g. to be used as a content encryption key.
+pub(crate) fn random_bytes(len: usize) -> Result<Zeroizing<Vec<u8>>> {
+  let mut bytes: Zeroizing<Vec<u8>> = Zeroizing::new(vec![0; len]);
+  crypto::utils::rand::fill(&mut bytes).map_err(|_| Error::EncryptionError("random number generation failed"))?;
+  Ok(bytes)
+}
+
+/// Encrypts `plaintext` under `cek` with a freshly generated IV and authenticates `aad` along with it.
+pub(crate) fn encrypt_content(
+  enc: JweEncryption,
+  cek: &[u8],
+  aad: &[u8],
+  plaintext: &[u8],
+) -> Result<EncryptedContent> {
+  if cek.len() != enc.key_len() {
+    return Err(Error::EncryptionError("invalid content encryption key length"));
+  }
+  let iv: Vec<u8> = random_bytes(enc.iv_len())?.to_vec();
+  let mut ciphertext: Vec<u8> = plaintext.to_vec();
+
+  let tag: Vec<u8> = match enc {
+    JweEncryption::A256GCM => Aes256Gcm::new(cek.into())
+      .encrypt_in_place_detached(iv.as_slice().into(), aad, &mut ciphertext)
+      .map(|tag| tag.to_vec()),
+    JweEncryption::C20P => ChaCha20Poly1305::new(cek.into())
+      .encrypt_in_place_detached(iv.as_slice().into(), aad, &mut ciphertext)
+      .map(|tag| tag.to_vec()),
+  }
+  .map_err(|_| Error::EncryptionError("content encryption failed"))?;
+
+  Ok(EncryptedContent { iv, ciphertext, tag })
+}
+
+/// Decrypts and authenticates the given content with `cek`.
+pub(crate) fn decrypt_content(
+  enc: JweEncryption,
+  cek: &[u8],
+  aad: &[u8],
+  content: &EncryptedContent,
+) -> Result<Zeroizing<Vec<u8>>> {
+  if cek.len() != enc.key_len() {
+    return Err(Error::DecryptionError("invalid content encryption key length"));
+  }
+  if content.iv.len() != enc.iv_len() {
+    return Err(Error::DecryptionError("invalid initialization vector length"));
+  }
+  if content.tag.len() != enc.tag_len() {
+    return Err(Error::DecryptionError("invalid authentication tag length"));
+  }
+  let mut plaintext: Zeroizing<Vec<u8>> = Zeroizing::new(content.ciphertext.clone());
+  let iv = content.iv.as_slice().into();
+  let tag = content.tag.as_slice().into();
+
+  match enc {
+    JweEncryption::A256GCM => Aes256Gcm::new(cek.into()).decrypt_in_place_detached(iv, aad, &mut plaintext, tag),
+    JweEncryption::C20P => ChaCha20Poly1305::new(cek.into()).decrypt_in_place_detached(iv, aad, &mut plaintext, tag),
+  }
+  .map_err(|_| Error::DecryptionError("content decryption failed"))?;
+
+  Ok(plaintext)
+}
+
+/// Wraps `cek` with the key encryption key `kek` using the AES key wrap variant of `alg`.
+pub(crate) fn wrap_key(alg: JweAlgorithm, kek: &[u8], cek: &[u8]) -> Result<Vec<u8>> {
+  let mut wrapped: Vec<u8> = vec![0; cek.len() + Aes256Kw::BLOCK];
+  match alg {
+    JweAlgorithm::ECDH_ES_A128KW => Aes128Kw::new(kek).wrap_key(cek, &mut wrapped),
+    JweAlgorithm::ECDH_ES_A192KW => Aes192Kw::new(kek).wrap_key(cek, &mut wrapped),
+    JweAlgorithm::ECDH_ES_A256KW => Aes256Kw::new(kek).wrap_key(cek, &mut wrapped),
+    JweAlgorithm::ECDH_ES => {
+      return Err(Error::EncryptionError(
+        "`ECDH-ES` does not wrap the content encryption key",
+      ))
+    }
+  }
+  .map_err(|_| Error::EncryptionError("key wrapping failed"))?;
+  Ok(wrapped)
+}
+
+/// Unwraps the content encryption key from `wrapped` with the key encryption key `kek`.
+pub(crate) fn unwrap_key(alg: JweAlgorithm, kek: &[u8], wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>> {
+  let cek_len: usize = wrapped
+    .len()
+    .checked_sub(Aes256Kw::BLOCK)
+    .ok_or(Error::DecryptionError("invalid encrypted key length"))?;
+  let mut cek: Zeroizing<Vec<u8>> = Zeroizing::new(vec![0; cek_len]);
+  match alg {
+    JweAlgorithm::ECDH_ES_A128KW => Aes128Kw::new(kek).unwrap_key(wrapped, &mut cek),
+    JweAlgorithm::ECDH_ES_A192KW => Aes192Kw::new(kek).unwrap_key(wrapped, &mut cek),
+    JweAlgorithm::ECDH_ES_A256KW => Aes256Kw::new(kek).unwrap_key(wrapped, &mut cek),
+    JweAlgorithm::ECDH_ES => {
+      return Err(Error::DecryptionError(
+        "`ECDH-ES` does not wrap the content encryption key",
+      ))
+    }
+  }
+  .map_err(|_| Error::DecryptionError("key unwrapping failed"))?;
+  Ok(cek)
+}
@@ -0,0 +1,297 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwe::cipher;
+use crate::jwe::cipher::EncryptedContent;
+use crate::jwe::encoder::validate_jwe_headers;
+use crate::jwe::key_agreement;
+use crate::jwe::serialization::create_aad;
+use crate::jwe::serialization::Flatten;
+use crate::jwe::serialization::General;
+use crate::jwe::serialization::JweRecipientJson;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwe::JweHeader;
+use crate::jwk::Jwk;
+use crate::jwu;
+use crate::jwu::parse_utf8;
+
+/// A decrypted JWE.
+///
+/// Contains the decoded headers, the additional authenticated data and the plaintext.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DecodedJwe {
+  /// The decoded protected header.
+  pub protected: JweHeader,
+  /// The decoded shared unprotected header.
+  pub unprotected: Option<JweHeader>,
+  /// The decoded per-recipient unprotected header of the recipient the JWE was decrypted for.
+  pub recipient_header: Option<JweHeader>,
+  /// The decoded additional authenticated data.
+  pub aad: Option<Vec<u8>>,
+  /// The decrypted plaintext.
+  pub plaintext: Vec<u8>,
+}
+
+struct DecodedRecipient {
+  header: Option<JweHeader>,
+  encrypted_key: Vec<u8>,
+}
+
+/// A decoded, but still encrypted JWE. The plaintext can be obtained for one of the recipients with
+/// [`Self::decrypt`](Self::decrypt()) or [`Self::decrypt_with_shared_secret`](Self::decrypt_with_shared_secret()).
+pub struct JweDecryptionItem {
+  protected: JweHeader,
+  unprotected: Option<JweHeader>,
+  recipients: Vec<DecodedRecipient>,
+  aad: Option<Vec<u8>>,
+  authenticated_data: Vec<u8>,
+  content: EncryptedContent,
+}
+
+impl JweDecryptionItem {
+  /// Returns the decoded protected header.
+  pub fn protected_header(&self) -> &JweHeader {
+    &self.protected
+  }
+
+  /// Returns the decoded shared unprotected header if it exists.
+  pub fn unprotected_header(&self) -> Option<&JweHeader> {
+    self.unprotected.as_ref()
+  }
+
+  /// Returns the number of recipients of the JWE.
+  pub fn recipient_count(&self) -> usize {
+    self.recipients.len()
+  }
+
+  /// Returns the per-recipient header of the recipient at `index` if it exists.
+  pub fn recipient_header(&self, index: usize) -> Option<&JweHeader> {
+    self
+      .recipients
+      .get(index)
+      .and_then(|recipient| recipient.header.as_ref())
+  }
+
+  /// Returns the content encryption algorithm, which is only taken from the protected header.
+  pub fn enc(&self) -> Option<JweEncryption> {
+    self.protected.enc()
+  }
+
+  /// Returns the key agreement algorithm used for the recipient at `index`.
+  pub fn alg(&self, index: usize) -> Option<JweAlgorithm> {
+    self.find_param(index, JweHeader::alg)
+  }
+
+  /// Returns the `kid` of the recipient at `index` if it is set.
+  pub fn kid(&self, index: usize) -> Option<&str> {
+    self.find_param(index, |header| header.kid())
+  }
+
+  /// Returns the ephemeral public key the sender used for the key agreement with the recipient at `index`.
+  pub fn epk(&self, index: usize) -> Option<&Jwk> {
+    self.find_param(index, JweHeader::epk)
+  }
+
+  /// Returns the index of the first recipient whose `kid` is equal to the given `kid`.
+  pub fn find_recipient(&self, kid: &str) -> Option<usize> {
+    (0..self.recipient_count()).find(|index| self.kid(*index) == Some(kid))
+  }
+
+  /// Decrypts the JWE for the recipient at `index` with the recipient's `private_key`.
+  pub fn decrypt(&self, index: usize, private_key: &Jwk) -> Result<DecodedJwe> {
+    let epk: &Jwk = self.epk(index).ok_or(Error::MissingParam("epk"))?;
+    let z: Zeroizing<Vec<u8>> = key_agreement::diffie_hellman(private_key, epk)?;
+    self.decrypt_with_shared_secret(index, &z)
+  }
+
+  /// Decrypts the JWE for the recipient at `index` given the Diffie-Hellman shared secret `z` between
+  /// the recipient's private key and the ephemeral public key (see [`Self::epk`](Self::epk())).
+  ///
+  /// This allows decrypting with private keys that cannot be exported, see
+  /// [`diffie_hellman`](crate::jwe::diffie_hellman()).
+  pub fn decrypt_with_shared_secret(&self, index: usize, z: &[u8]) -> Result<DecodedJwe> {
+    let recipient: &DecodedRecipient = self
+      .recipients
+      .get(index)
+      .ok_or(Error::InvalidContent("recipient index out of bounds"))?;
+    let alg: JweAlgorithm = self.alg(index).ok_or(Error::MissingParam("alg"))?;
+    let enc: JweEncryption = self.enc().ok_or(Error::MissingParam("enc"))?;
+    let apu: Vec<u8> = self
+      .find_param(index, JweHeader::apu)
+      .map(jwu::decode_b64)
+      .transpose()?
+      .unwrap_or_default();
+    let apv: Vec<u8> = self
+      .find_param(index, JweHeader::apv)
+      .map(jwu::decode_b64)
+      .transpose()?
+      .unwrap_or_default();
+
+    let cek: Zeroizing<Vec<u8>> = match alg.key_wrap_len() {
+      None => {
+        if !recipient.encrypted_key.is_empty() {
+          return Err(Error::InvalidContent("`ECDH-ES` requires an empty encrypted key"));
+        }
+        key_agreement::concat_kdf(z, enc.name(), enc.key_len(), &apu, &apv)
+      }
+      Some(key_wrap_len) => {
+        let kek: Zeroizing<Vec<u8>> = key_agreement::concat_kdf(z, alg.name(), key_wrap_len, &apu, &apv);
+        cipher::unwrap_key(alg, &kek, &recipient.encrypted_key)?
+      }
+    };
+
+    let plaintext: Zeroizing<Vec<u8>> = cipher::decrypt_content(enc, &cek, &self.authenticated_data, &self.content)?;
+
+    Ok(DecodedJwe {
+      protected: self.protected.clone(),
+      unprotected: self.unprotected.clone(),
+      recipient_header: recipient.header.clone(),
+      aad: self.aad.clone(),
+      plaintext: plaintext.to_vec(),
+    })
+  }
+
+  fn find_param<'a, T: 'a>(&'a self, index: usize, f: impl Fn(&'a JweHeader) -> Option<T>) -> Option<T> {
+    self
+      .recipient_header(index)
+      .and_then(&f)
+      .or_else(|| self.unprotected.as_ref().and_then(&f))
+      .or_else(|| f(&self.protected))
+  }
+}
+
+// =============================================================================
+// Decoder
+// =============================================================================
+
+/// The [`JweDecoder`] is responsible for decoding a JWE into a [`JweDecryptionItem`].
+#[derive(Debug, Clone, Default)]
+pub struct JweDecoder;
+
+impl JweDecoder {
+  /// Constructs a new [`JweDecoder`].
+  pub fn new() -> JweDecoder {
+    Self
+  }
+
+  /// Decode a JWE encoded with the [JWE compact serialization format](https://www.rfc-editor.org/rfc/rfc7516#section-3.1).
+  pub fn decode_compact_serialization(&self, jwe_bytes: &[u8]) -> Result<JweDecryptionItem> {
+    let mut segments = jwe_bytes.split(|byte| *byte == b'.');
+
+    let (Some(protected), Some(encrypted_key), Some(iv), Some(ciphertext), Some(tag), None) = (
+      segments.next(),
+      segments.next(),
+      segments.next(),
+      segments.next(),
+      segments.next(),
+      segments.next(),
+    ) else {
+      return Err(Error::InvalidContent("invalid segments count"));
+    };
+
+    self.decode(
+      Some(parse_utf8(protected)?),
+      None,
+      vec![JweRecipientJson {
+        header: None,
+        encrypted_key: Some(parse_utf8(encrypted_key)?.to_owned()),
+      }],
+      None,
+      parse_utf8(iv)?,
+      parse_utf8(ciphertext)?,
+      parse_utf8(tag)?,
+    )
+  }
+
+  /// Decode a JWE encoded with the [flattened JWE JSON serialization format](https://www.rfc-editor.org/rfc/rfc7516#section-7.2.2).
+  pub fn decode_flattened_serialization(&self, jwe_bytes: &[u8]) -> Result<JweDecryptionItem> {
+    let data: Flatten<'_> = serde_json::from_slice(jwe_bytes).map_err(Error::InvalidJson)?;
+    self.decode(
+      data.protected.as_deref(),
+      data.unprotected.map(Cow::into_owned),
+      vec![data.recipient],
+      data.aad.as_deref(),
+      &data.iv,
+      &data.ciphertext,
+      &data.tag,
+    )
+  }
+
+  /// Decode a JWE encoded with the [general JWE JSON serialization format](https://www.rfc-editor.org/rfc/rfc7516#section-7.2.1).
+  pub fn decode_general_serialization(&self, jwe_bytes: &[u8]) -> Result<JweDecryptionItem> {
+    let data: General<'_> = serde_json::from_slice(jwe_bytes).map_err(Error::InvalidJson)?;
+    self.decode(
+      data.protected.as_deref(),
+      data.unprotected.map(Cow::into_owned),
+      data.recipients,
+      data.aad.as_deref(),
+      &data.iv,
+      &data.ciphertext,
+      &data.tag,
+    )
+  }
+
+  #[allow(clippy::too_many_arguments)]
+  fn decode(
+    &self,
+    protected: Option<&str>,
+    unprotected: Option<JweHeader>,
+    recipients: Vec<JweRecipientJson<'_>>,
+    aad: Option<&str>,
+    iv: &str,
+    ciphertext: &str,
+    tag: &str,
+  ) -> Result<JweDecryptionItem> {
+    // The "enc" parameter must be integrity protected, hence a protected header is always required.
+    let encoded_protected: &str = protected
+      .filter(|protected| !protected.is_empty())
+      .ok_or(Error::MissingHeader("missing protected header"))?;
+    let protected: JweHeader = jwu::decode_b64_json(encoded_protected)?;
+    if unprotected.as_ref().and_then(JweHeader::enc).is_some() {
+      return Err(Error::InvalidParam("`enc` must only be set in the protected header"));
+    }
+
+    if recipients.is_empty() {
+      return Err(Error::InvalidContent("missing recipients"));
+    }
+    let recipients: Vec<DecodedRecipient> = recipients
+      .into_iter()
+      .map(|recipient| {
+        let header: Option<JweHeader> = recipient.header.map(Cow::into_owned);
+        if header.as_ref().and_then(JweHeader::enc).is_some() {
+          return Err(Error::InvalidParam("`enc` must only be set in the protected header"));
+        }
+        validate_jwe_headers(Some(&protected), unprotected.as_ref(), header.as_ref())?;
+        Ok(DecodedRecipient {
+          header,
+          encrypted_key: recipient
+            .encrypted_key
+            .map(jwu::decode_b64)
+            .transpose()?
+            .unwrap_or_default(),
+        })
+      })
+      .collect::<Result<_>>()?;
+
+    Ok(JweDecryptionItem {
+      protected,
+      unprotected,
+      recipients,
+      aad: aad.map(jwu::decode_b64).transpose()?,
+      authenticated_data: create_aad(encoded_protected, aad),
+      content: EncryptedContent {
+        iv: jwu::decode_b64(iv)?,
+        ciphertext: jwu::decode_b64(ciphertext)?,
+        tag: jwu::decode_b64(tag)?,
+      },
+    })
+  }
+}
@@ -0,0 +1,338 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::borrow::Cow;
+
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwe::cipher;
+use crate::jwe::cipher::EncryptedContent;
+use crate::jwe::key_agreement;
+use crate::jwe::serialization::create_aad;
+use crate::jwe::serialization::Flatten;
+use crate::jwe::serialization::General;
+use crate::jwe::serialization::JweRecipientJson;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweEncryption;
+use crate::jwe::JweFormat;
+use crate::jwe::JweHeader;
+use crate::jwe::JweRecipient;
+use crate::jwk::Jwk;
+use crate::jwu;
+
+/// An encoder producing JWEs whose content encryption key is established through ECDH-ES key agreement with
+/// each recipient's public key.
+///
+/// The `enc` parameter must be set in the protected header, since it has to be integrity protected, while the `alg`
+/// parameter may be set in any of the protected, shared unprotected or per-recipient headers.
+///
+/// See `identity_jose/src/tests/jwe.rs` for examples.
+#[derive(Clone, Copy)]
+pub struct JweEncoder<'a> {
+  protected: &'a JweHeader,
+  unprotected: Option<&'a JweHeader>,
+  aad: Option<&'a [u8]>,
+}
+
+/// The outcome of the key agreement with a single recipient.
+struct RecipientKey<'a> {
+  header: Option<Cow<'a, JweHeader>>,
+  epk: Jwk,
+  encrypted_key: Option<Vec<u8>>,
+}
+
+impl<'a> JweEncoder<'a> {
+  /// Creates a new encoder with the given integrity-protected header.
+  pub fn new(protected_header: &'a JweHeader) -> Self {
+    Self {
+      protected: protected_header,
+      unprotected: None,
+      aad: None,
+    }
+  }
+
+  /// Set the shared non integrity-protected JOSE header.
+  ///
+  /// Only supported by the JSON serialization formats.
+  pub fn unprotected(mut self, value: &'a JweHeader) -> Self {
+    self.unprotected = Some(value);
+    self
+  }
+
+  /// Set additional authenticated data which is integrity-protected but not encrypted.
+  ///
+  /// Only supported by the JSON serialization formats.
+  pub fn aad(mut self, value: &'a [u8]) -> Self {
+    self.aad = Some(value);
+    self
+  }
+
+  /// Encrypts `plaintext` for the given `recipients` and serializes the result in the given `format`.
+  ///
+  /// # Errors
+  /// Fails if the headers are invalid, the `format` does not support the given number of recipients or
+  /// configuration, or a key agreement with one of the recipient keys fails.
+  pub fn encrypt(&self, plaintext: &[u8], recipients: &[JweRecipient<'_>], format: JweFormat) -> Result<String> {
+    match format {
+      JweFormat::Compact | JweFormat::Flatten if recipients.len() != 1 => {
+        return Err(Error::InvalidContent("the format requires exactly one recipient"));
+      }
+      JweFormat::Compact if self.unprotected.is_some() || recipients[0].header.is_some() => {
+        return Err(Error::InvalidContent(
+          "the compact serialization does not support unprotected headers",
+        ));
+      }
+      JweFormat::Compact if self.aad.is_some() => {
+        return Err(Error::InvalidContent(
+          "the compact serialization does not support additional authenticated data",
+        ));
+      }
+      _ if recipients.is_empty() => return Err(Error::InvalidContent("missing recipients")),
+      _ => {}
+    }
+
+    self.validate_headers(recipients)?;
+    let enc: JweEncryption = self.protected.enc().ok_or(Error::MissingParam("enc"))?;
+
+    // With direct key agreement the content encryption key is derived from the key agreement
+    // itself and can therefore only be shared with a single recipient.
+    let algorithms: Vec<JweAlgorithm> = recipients
+      .iter()
+      .map(|recipient| self.recipient_alg(recipient))
+      .collect::<Result<_>>()?;
+    if recipients.len() > 1 && algorithms.contains(&JweAlgorithm::ECDH_ES) {
+      return Err(Error::InvalidParam("`ECDH-ES` only supports a single recipient"));
+    }
+
+    let mut cek: Option<Zeroizing<Vec<u8>>> = None;
+    let mut recipient_keys: Vec<RecipientKey<'_>> = Vec::with_capacity(recipients.len());
+    for (recipient, alg) in recipients.iter().zip(algorithms) {
+      let (ephemeral_secret, epk) = key_agreement::generate_ephemeral_key(recipient.public_key)?;
+      let z: Zeroizing<Vec<u8>> = key_agreement::diffie_hellman(&ephemeral_secret, recipient.public_key)?;
+      let (apu, apv) = self.party_info(recipient.header)?;
+
+      let encrypted_key: Option<Vec<u8>> = match alg.key_wrap_len() {
+        None => {
+          cek = Some(key_agreement::concat_kdf(&z, enc.name(), enc.key_len(), &apu, &apv));
+          None
+        }
+        Some(key_wrap_len) => {
+          let kek: Zeroizing<Vec<u8>> = key_agreement::concat_kdf(&z, alg.name(), key_wrap_len, &apu, &apv);
+          let cek: &[u8] = match cek {
+            Some(ref cek) => cek,
+            None => cek.insert(cipher::random_bytes(enc.key_len())?),
+          };
+          Some(cipher::wrap_key(alg, &kek, cek)?)
+        }
+      };
+
+      recipient_keys.push(RecipientKey {
+        header: recipient.header.map(Cow::Borrowed),
+        epk,
+        encrypted_key,
+      });
+    }
+    let cek: Zeroizing<Vec<u8>> = cek.ok_or(Error::EncryptionError("missing content encryption key"))?;
+
+    match format {
+      JweFormat::Compact => self.encode_compact(plaintext, enc, &cek, recipient_keys),
+      JweFormat::Flatten => self.encode_flattened(plaintext, enc, &cek, recipient_keys),
+      JweFormat::General => self.encode_general(plaintext, enc, &cek, recipient_keys),
+    }
+  }
+
+  fn encode_compact(
+    &self,
+    plaintext: &[u8],
+    enc: JweEncryption,
+    cek: &[u8],
+    recipient_keys: Vec<RecipientKey<'_>>,
+  ) -> Result<String> {
+    let RecipientKey { epk, encrypted_key, .. } = single(recipient_keys)?;
+    let protected: String = self.encode_protected_header(Some(epk))?;
+    let EncryptedContent { iv, ciphertext, tag } =
+      cipher::encrypt_content(enc, cek, &create_aad(&protected, None), plaintext)?;
+
+    Ok(format!(
+      "{}.{}.{}.{}.{}",
+      protected,
+      encrypted_key.map(jwu::encode_b64).unwrap_or_default(),
+      jwu::encode_b64(iv),
+      jwu::encode_b64(ciphertext),
+      jwu::encode_b64(tag)
+    ))
+  }
+
+  fn encode_flattened(
+    &self,
+    plaintext: &[u8],
+    enc: JweEncryption,
+    cek: &[u8],
+    recipient_keys: Vec<RecipientKey<'_>>,
+  ) -> Result<String> {
+    let RecipientKey {
+      header,
+      epk,
+      encrypted_key,
+    } = single(recipient_keys)?;
+    let protected: String = self.encode_protected_header(Some(epk))?;
+    let aad: Option<String> = self.aad.map(jwu::encode_b64);
+    let EncryptedContent { iv, ciphertext, tag } =
+      cipher::encrypt_content(enc, cek, &create_aad(&protected, aad.as_deref()), plaintext)?;
+
+    Flatten {
+      protected: Some(protected),
+      unprotected: self.unprotected.map(Cow::Borrowed),
+      recipient: JweRecipientJson {
+        header,
+        encrypted_key: encrypted_key.map(jwu::encode_b64),
+      },
+      aad,
+      iv: jwu::encode_b64(iv),
+      ciphertext: jwu::encode_b64(ciphertext),
+      tag: jwu::encode_b64(tag),
+    }
+    .to_json()
+  }
+
+  fn encode_general(
+    &self,
+    plaintext: &[u8],
+    enc: JweEncryption,
+    cek: &[u8],
+    recipient_keys: Vec<RecipientKey<'_>>,
+  ) -> Result<String> {
+    let protected: String = self.encode_protected_header(None)?;
+    let aad: Option<String> = self.aad.map(jwu::encode_b64);
+    let EncryptedContent { iv, ciphertext, tag } =
+      cipher::encrypt_content(enc, cek, &create_aad(&protected, aad.as_deref()), plaintext)?;
+
+    // Every recipient has its own ephemeral key which is therefore set in the per-recipient header.
+    let recipients: Vec<JweRecipientJson<'_>> = recipient_keys
+      .into_iter()
+      .map(
+        |RecipientKey {
+           header,
+           epk,
+           encrypted_key,
+         }| {
+          let mut header: JweHeader = header.map(Cow::into_owned).unwrap_or_default();
+          header.set_epk(epk);
+          JweRecipientJson {
+            header: Some(Cow::Owned(header)),
+            encrypted_key: encrypted_key.map(jwu::encode_b64),
+          }
+        },
+      )
+      .collect();
+
+    General {
+      protected: Some(protected),
+      unprotected: self.unprotected.map(Cow::Borrowed),
+      recipients,
+      aad,
+      iv: jwu::encode_b64(iv),
+      ciphertext: jwu::encode_b64(ciphertext),
+      tag: jwu::encode_b64(tag),
+    }
+    .to_json()
+  }
+
+  fn encode_protected_header(&self, epk: Option<Jwk>) -> Result<String> {
+    match epk {
+      Some(epk) => {
+        let mut protected: JweHeader = self.protected.clone();
+        protected.set_epk(epk);
+        jwu::encode_b64_json(&protected)
+      }
+      None => jwu::encode_b64_json(self.protected),
+    }
+  }
+
+  fn recipient_alg(&self, recipient: &JweRecipient<'_>) -> Result<JweAlgorithm> {
+    recipient
+      .header
+      .and_then(JweHeader::alg)
+      .or_else(|| self.unprotected.and_then(JweHeader::alg))
+      .or_else(|| self.protected.alg())
+      .ok_or(Error::MissingParam("alg"))
+  }
+
+  fn party_info(&self, recipient_header: Option<&JweHeader>) -> Result<(Vec<u8>, Vec<u8>)> {
+    let headers = [Some(self.protected), self.unprotected, recipient_header];
+    let apu: Option<&str> = headers.iter().flatten().find_map(|header| header.apu());
+    let apv: Option<&str> = headers.iter().flatten().find_map(|header| header.apv());
+
+    Ok((
+      apu.map(jwu::decode_b64).transpose()?.unwrap_or_default(),
+      apv.map(jwu::decode_b64).transpose()?.unwrap_or_default(),
+    ))
+  }
+
+  fn validate_headers(&self, recipients: &[JweRecipient<'_>]) -> Result<()> {
+    if self.protected.epk().is_some()
+      || self
+        .unprotected
+        .map(|header| header.epk().is_some())
+        .unwrap_or_default()
+      || recipients.iter().any(|recipient| {
+        recipient
+          .header
+          .map(|header| header.epk().is_some())
+          .unwrap_or_default()
+      })
+    {
+      return Err(Error::InvalidParam("`epk` is set by the encoder"));
+    }
+
+    let recipient_headers = recipients.iter().filter_map(|recipient| recipient.header);
+    if self
+      .unprotected
+      .into_iter()
+      .chain(recipient_headers)
+      .any(|header| header.enc().is_some())
+    {
+      return Err(Error::InvalidParam("`enc` must only be set in the protected header"));
+    }
+
+    validate_jwe_headers(Some(self.protected), self.unprotected, None)?;
+    for recipient in recipients {
+      validate_jwe_headers(Some(self.protected), self.unprotected, recipient.header)?;
+    }
+
+    Ok(())
+  }
+}
+
+fn single(mut recipient_keys: Vec<RecipientKey<'_>>) -> Result<RecipientKey<'_>> {
+  match (recipient_keys.pop(), recipient_keys.is_empty()) {
+    (Some(recipient_key), true) => Ok(recipient_key),
+    _ => Err(Error::InvalidContent("the format requires exactly one recipient")),
+  }
+}
+
+/// Validates that the protected, shared unprotected and per-recipient headers are pairwise disjoint
+/// and that the "crit" parameter is valid.
+///
+/// See (<https://www.rfc-editor.org/rfc/rfc7516#section-7.2.1>).
+pub(crate) fn validate_jwe_headers(
+  protected: Option<&JweHeader>,
+  unprotected: Option<&JweHeader>,
+  recipient: Option<&JweHeader>,
+) -> Result<()> {
+  let headers = [protected, unprotected, recipient];
+  for (index, header) in headers.iter().enumerate() {
+    for other in headers.iter().skip(index + 1) {
+      if let (Some(header), Some(other)) = (header, other) {
+        if !header.is_disjoint(other) {
+          return Err(Error::InvalidContent("jwe headers are not disjoint"));
+        }
+      }
+    }
+  }
+
+  jwu::validate_crit(protected, unprotected)?;
+  jwu::validate_crit(protected, recipient)
+}
@@ -0,0 +1,16 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+/// The serialization format used for the JWE.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum JweFormat {
+  /// JWE Compact Serialization (<https://www.rfc-editor.org/rfc/rfc7516#section-3.1>).
+  #[default]
+  Compact,
+  /// General JWE JSON Serialization (<https://www.rfc-editor.org/rfc/rfc7516#section-7.2.1>).
+  General,
+  /// Flattened JWE JSON Serialization (<https://www.rfc-editor.org/rfc/rfc7516#section-7.2.2>).
+  ///
+  /// Should be used for single recipient use cases.
+  Flatten,
+}
@@ -0,0 +1,199 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::hashes::sha::Sha256;
+use crypto::hashes::Digest;
+use crypto::keys::x25519;
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwk::EcCurve;
+use crate::jwk::EcxCurve;
+use crate::jwk::Jwk;
+use crate::jwk::JwkParams;
+use crate::jwk::JwkParamsEc;
+use crate::jwk::JwkParamsOkp;
+use crate::jwu;
+
+/// Computes the raw Diffie-Hellman shared secret `Z` between `secret_key` and `public_key`.
+///
+/// Both keys must either be `X25519` OKP keys or `P-256` EC keys.
+///
+/// This is the only operation requiring access to private key material when decrypting a JWE with one of the
+/// [`JweAlgorithm`](crate::jwe::JweAlgorithm)s. Key storages that do not expose their private keys may perform it
+/// internally and continue with
+/// [`JweDecryptionItem::decrypt_with_shared_secret`](crate::jwe::JweDecryptionItem::decrypt_with_shared_secret()).
+pub fn diffie_hellman(secret_key: &Jwk, public_key: &Jwk) -> Result<Zeroizing<Vec<u8>>> {
+  match (secret_key.params(), public_key.params()) {
+    (JwkParams::Okp(secret), JwkParams::Okp(public)) => {
+      if secret.try_ecx_curve()? != EcxCurve::X25519 || public.try_ecx_curve()? != EcxCurve::X25519 {
+        return Err(Error::KeyError("key agreement requires X25519 keys"));
+      }
+      let secret: x25519::SecretKey = expand_x25519_secret(secret)?;
+      let public: x25519::PublicKey = expand_x25519_public(public)?;
+      let shared_secret = secret.diffie_hellman(&public);
+      // Reject low order points which would result in an all-zero shared secret.
+      if !shared_secret.was_contributory() {
+        return Err(Error::KeyError("X25519 shared secret is not contributory"));
+      }
+      Ok(Zeroizing::new(shared_secret.as_bytes().to_vec()))
+    }
+    (JwkParams::Ec(secret), JwkParams::Ec(public)) => {
+      if secret.try_ec_curve()? != EcCurve::P256 || public.try_ec_curve()? != EcCurve::P256 {
+        return Err(Error::KeyError("key agreement requires P-256 keys"));
+      }
+      let secret: p256::SecretKey = expand_p256_secret(secret)?;
+      let public: p256::PublicKey = expand_p256_public(public)?;
+      let shared_secret = p256::ecdh::diffie_hellman(secret.to_nonzero_scalar(), public.as_affine());
+      Ok(Zeroizing::new(shared_secret.raw_secret_bytes().to_vec()))
+    }
+    _ => Err(Error::KeyError("key agreement requires matching X25519 or P-256 keys")),
+  }
+}
+
+/// Generates an ephemeral key pair on the same curve as `recipient_key`.
+///
+/// Returns the private key and its public counterpart.
+pub(crate) fn generate_ephemeral_key(recipient_key: &Jwk) -> Result<(Jwk, Jwk)> {
+  let secret: Jwk = match recipient_key.params() {
+    JwkParams::Okp(params) if params.try_ecx_curve()? == EcxCurve::X25519 => {
+      let secret = x25519::SecretKey::generate().map_err(|_| Error::EncryptionError("key generation failed"))?;
+      let mut params = JwkParamsOkp::new();
+      params.crv = EcxCurve::X25519.name().to_owned();
+      params.x = jwu::encode_b64(secret.public_key().as_slice());
+      params.d = Some(jwu::encode_b64(secret.to_bytes()));
+      Jwk::from_params(params)
+    }
+    JwkParams::Ec(params) if params.try_ec_curve()? == EcCurve::P256 => {
+      let secret: p256::SecretKey = loop {
+        let mut bytes: Zeroizing<[u8; 32]> = Zeroizing::new([0; 32]);
+        crypto::utils::rand::fill(bytes.as_mut()).map_err(|_| Error::EncryptionError("key generation failed"))?;
+        // Retry in the negligible case that the bytes are not a valid scalar.
+        if let Ok(secret) = p256::SecretKey::from_slice(bytes.as_ref()) {
+          break secret;
+        }
+      };
+      let encoded_point = p256::elliptic_curve::sec1::ToEncodedPoint::to_encoded_point(&secret.public_key(), false);
+      let mut params = JwkParamsEc::new();
+      params.crv = EcCurve::P256.name().to_owned();
+      params.x = jwu::encode_b64(encoded_point.x().ok_or(Error::KeyError("P-256"))?);
+      params.y = jwu::encode_b64(encoded_point.y().ok_or(Error::KeyError("P-256"))?);
+      params.d = Some(jwu::encode_b64(secret.to_bytes()));
+      Jwk::from_params(params)
+    }
+    _ => return Err(Error::KeyError("key agreement requires X25519 or P-256 keys")),
+  };
+
+  let public: Jwk = secret.to_public().ok_or(Error::KeyError("ephemeral key"))?;
+  Ok((secret, public))
+}
+
+/// Derives a key of `key_len` bytes from the shared secret `z` with the Concat KDF
+/// as parameterized by [RFC 7518 section 4.6.2](https://www.rfc-editor.org/rfc/rfc7518#section-4.6.2).
+pub(crate) fn concat_kdf(z: &[u8], algorithm_id: &str, key_len: usize, apu: &[u8], apv: &[u8]) -> Zeroizing<Vec<u8>> {
+  fn length_prefixed(data: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    (data.len() as u32)
+      .to_be_bytes()
+      .into_iter()
+      .chain(data.iter().copied())
+  }
+
+  let other_info: Vec<u8> = length_prefixed(algorithm_id.as_bytes())
+    .chain(length_prefixed(apu))
+    .chain(length_prefixed(apv))
+    .chain(((key_len * 8) as u32).to_be_bytes())
+    .collect();
+
+  let mut output: Zeroizing<Vec<u8>> = Zeroizing::new(Vec::with_capacity(key_len));
+  let mut counter: u32 = 1;
+  while output.len() < key_len {
+    let mut hasher = Sha256::new();
+    hasher.update(counter.to_be_bytes());
+    hasher.update(z);
+    hasher.update(&other_info);
+    output.extend_from_slice(&hasher.finalize());
+    counter += 1;
+  }
+  output.truncate(key_len);
+  output
+}
+
+fn expand_x25519_secret(params: &JwkParamsOkp) -> Result<x25519::SecretKey> {
+  let bytes: Zeroizing<Vec<u8>> = Zeroizing::new(jwu::decode_b64(
+    params.d.as_deref().ok_or(Error::KeyError("expected a private key"))?,
+  )?);
+  x25519::SecretKey::try_from_slice(&bytes).map_err(|_| Error::KeyError("X25519"))
+}
+
+fn expand_x25519_public(params: &JwkParamsOkp) -> Result<x25519::PublicKey> {
+  let bytes: Vec<u8> = jwu::decode_b64(&params.x)?;
+  x25519::PublicKey::try_from_slice(&bytes).map_err(|_| Error::KeyError("X25519"))
+}
+
+fn expand_p256_secret(params: &JwkParamsEc) -> Result<p256::SecretKey> {
+  let bytes: Zeroizing<Vec<u8>> = Zeroizing::new(jwu::decode_b64(
+    params.d.as_deref().ok_or(Error::KeyError("expected a private key"))?,
+  )?);
+  p256::SecretKey::from_slice(&bytes).map_err(|_| Error::KeyError("P-256"))
+}
+
+fn expand_p256_public(params: &JwkParamsEc) -> Result<p256::PublicKey> {
+  // Transformation according to section 2.3.3 from http://www.secg.org/sec1-v2.pdf.
+  let pk_bytes: Vec<u8> = [0x04]
+    .into_iter()
+    .chain(jwu::decode_b64(&params.x)?)
+    .chain(jwu::decode_b64(&params.y)?)
+    .collect();
+  p256::PublicKey::from_sec1_bytes(&pk_bytes).map_err(|_| Error::KeyError("P-256"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // Test https://www.rfc-editor.org/rfc/rfc7518#appendix-C
+  #[test]
+  fn rfc7518_appendix_c() {
+    let bob: Jwk = serde_json::from_str(
+      r#"{"kty":"EC","crv":"P-256",
+        "x":"weNJy2HscCSM6AEDTDg04biOvhFhyyWvOHQfeF_PxMQ",
+        "y":"e8lnCO-AlStT-NJVX-crhB7QRYhiix03illJOVAOyck",
+        "d":"VEmDZpDXXK8p8N0Cndsxs924q6nS1RXFASRl6BfUqdw"}"#,
+    )
+    .unwrap();
+    let alice_ephemeral: Jwk = serde_json::from_str(
+      r#"{"kty":"EC","crv":"P-256",
+        "x":"gI0GAILBdu7T53akrFmMyGcsF3n5dO7MmwNBHKW5SV0",
+        "y":"SLW_xSffzlPWrHEVI30DHM_4egVwt3NQqeUD7nMFpps",
+        "d":"0_NxaRPUMQoAJt50Gz8YiTr8gRTwyEaCumd-MToTmIo"}"#,
+    )
+    .unwrap();
+
+    let z_sender = diffie_hellman(&alice_ephemeral, &bob.to_public().unwrap()).unwrap();
+    let z_recipient = diffie_hellman(&bob, &alice_ephemeral.to_public().unwrap()).unwrap();
+    assert_eq!(z_sender, z_recipient);
+
+    let key = concat_kdf(&z_recipient, "A128GCM", 16, b"Alice", b"Bob");
+    assert_eq!(jwu::encode_b64(key.as_slice()), "VqqN6vgjbSBcIijNcacQGg");
+  }
+
+  #[test]
+  fn x25519_key_agreement() {
+    let recipient: Jwk =
+      serde_json::from_str(r#"{"kty":"OKP","crv":"X25519","x":"hSDwCYkwp1R0i33ctD73Wg2_Og0mOBr066SpjqqbTmo"}"#)
+        .unwrap();
+    let (ephemeral_secret, ephemeral_public) = generate_ephemeral_key(&recipient).unwrap();
+    assert!(ephemeral_public.is_public());
+    assert_eq!(ephemeral_secret.try_okp_params().unwrap().crv, "X25519");
+
+    // Key agreement with a public key on a different curve fails.
+    let p256_key: Jwk = serde_json::from_str(
+      r#"{"kty":"EC","crv":"P-256",
+        "x":"weNJy2HscCSM6AEDTDg04biOvhFhyyWvOHQfeF_PxMQ",
+        "y":"e8lnCO-AlStT-NJVX-crhB7QRYhiix03illJOVAOyck"}"#,
+    )
+    .unwrap();
+    assert!(diffie_hellman(&ephemeral_secret, &p256_key).is_err());
+  }
+}
@@ -0,0 +1,29 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! JSON Web Encryption ([JWE](https://tools.ietf.org/html/rfc7516))
+//!
+//! Supports the ECDH-ES family of key agreement algorithms over `X25519` and `P-256` keys
+//! ([RFC 7518 section 4.6](https://www.rfc-editor.org/rfc/rfc7518#section-4.6)) in combination with the
+//! `A256GCM` and `C20P` content encryption algorithms. See `identity_jose/src/tests/jwe.rs` for complete examples
+//! of how to encrypt and then decrypt a JWE.
+
+mod algorithm;
+mod cipher;
+mod decoder;
+mod encoder;
+mod encryption;
+mod format;
+mod header;
+mod key_agreement;
+mod recipient;
+mod serialization;
+
+pub use self::algorithm::*;
+pub use self::decoder::*;
+pub use self::encoder::JweEncoder;
+pub use self::encryption::*;
+pub use self::format::*;
+pub use self::header::*;
+pub use self::key_agreement::diffie_hellman;
+pub use self::recipient::*;
@@ -0,0 +1,33 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jwe::JweHeader;
+use crate::jwk::Jwk;
+
+/// The recipient of a JWE.
+///
+/// The content encryption key is made available to the recipient through key agreement with `public_key`.
+/// The optional per-recipient header can be used to set recipient specific parameters such as the `alg` or `kid`.
+#[derive(Clone, Copy)]
+pub struct JweRecipient<'a> {
+  /// The public key of the recipient.
+  pub public_key: &'a Jwk,
+  /// The non integrity-protected per-recipient JOSE header.
+  pub header: Option<&'a JweHeader>,
+}
+
+impl<'a> JweRecipient<'a> {
+  /// Creates a new recipient identified by the given `public_key`.
+  pub fn new(public_key: &'a Jwk) -> Self {
+    Self {
+      public_key,
+      header: None,
+    }
+  }
+
+  /// Set the non integrity-protected per-recipient JOSE header.
+  pub fn header(mut self, value: &'a JweHeader) -> Self {
+    self.header = Some(value);
+    self
+  }
+}
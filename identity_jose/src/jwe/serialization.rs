@@ -0,0 +1,70 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! Format dependent (de)serializable helper structs used by the encoder and decoder.
+
+use std::borrow::Cow;
+
+use crate::error::Error;
+use crate::error::Result;
+use crate::jwe::JweHeader;
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct JweRecipientJson<'a> {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) header: Option<Cow<'a, JweHeader>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) encrypted_key: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct General<'a> {
+  pub(crate) protected: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) unprotected: Option<Cow<'a, JweHeader>>,
+  pub(crate) recipients: Vec<JweRecipientJson<'a>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) aad: Option<String>,
+  pub(crate) iv: String,
+  pub(crate) ciphertext: String,
+  pub(crate) tag: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Flatten<'a> {
+  pub(crate) protected: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) unprotected: Option<Cow<'a, JweHeader>>,
+  #[serde(flatten)]
+  pub(crate) recipient: JweRecipientJson<'a>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub(crate) aad: Option<String>,
+  pub(crate) iv: String,
+  pub(crate) ciphertext: String,
+  pub(crate) tag: String,
+}
+
+impl General<'_> {
+  pub(crate) fn to_json(&self) -> Result<String> {
+    serde_json::to_string(self).map_err(Error::InvalidJson)
+  }
+}
+
+impl Flatten<'_> {
+  pub(crate) fn to_json(&self) -> Result<String> {
+    serde_json::to_string(self).map_err(Error::InvalidJson)
+  }
+}
+
+/// Computes the additional authenticated data passed to the content encryption algorithm.
+///
+/// See [RFC 7516 section 5.1 step 14](https://www.rfc-editor.org/rfc/rfc7516#section-5.1).
+pub(crate) fn create_aad(encoded_protected_header: &str, encoded_aad: Option<&str>) -> Vec<u8> {
+  match encoded_aad {
+    Some(aad) => crate::jwu::create_message(encoded_protected_header.as_bytes(), aad.as_bytes()),
+    None => encoded_protected_header.as_bytes().to_vec(),
+  }
+}
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 /// The serialization format used for the JWS.
-#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum JwsFormat {
   /// JWS Compact Serialization (<https://www.rfc-editor.org/rfc/rfc7515#section-3.1>).
+  #[default]
   Compact,
   /// General JWS JSON Serialization (<https://www.rfc-editor.org/rfc/rfc7515#section-7.2.1>).
   General,
@@ -13,9 +14,3 @@ pub enum JwsFormat {
   /// Should be used for single signature or MAC use cases.
   Flatten,
 }
-
-impl Default for JwsFormat {
-  fn default() -> Self {
-    Self::Compact
-  }
-}
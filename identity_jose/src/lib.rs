@@ -17,6 +17,7 @@
 
 pub mod error;
 pub mod jose;
+pub mod jwe;
 pub mod jwk;
 pub mod jws;
 pub mod jwt;
@@ -0,0 +1,274 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use crate::jwe::DecodedJwe;
+use crate::jwe::JweAlgorithm;
+use crate::jwe::JweDecoder;
+use crate::jwe::JweDecryptionItem;
+use crate::jwe::JweEncoder;
+use crate::jwe::JweEncryption;
+use crate::jwe::JweFormat;
+use crate::jwe::JweHeader;
+use crate::jwe::JweRecipient;
+use crate::jwk::Jwk;
+
+const PLAINTEXT: &[u8] = br#"{"iss":"did:example:issuer","vc":{"type":["VerifiableCredential"]}}"#;
+
+// X25519 key pairs from https://www.rfc-editor.org/rfc/rfc7748#section-6.1.
+fn x25519_alice() -> Jwk {
+  serde_json::from_str(
+    r#"{"kty":"OKP","crv":"X25519","kid":"alice",
+      "x":"hSDwCYkwp1R0i33ctD73Wg2_Og0mOBr066SpjqqbTmo",
+      "d":"dwdtCnMYpX08FsFyUbJmRd9ML4frwJkqsXf7pR25LCo"}"#,
+  )
+  .unwrap()
+}
+
+fn x25519_bob() -> Jwk {
+  serde_json::from_str(
+    r#"{"kty":"OKP","crv":"X25519","kid":"bob",
+      "x":"3p7bfXt9wbTTW2HC7OQ1Nz-DQ8hbeGdNrfx-FG-IK08",
+      "d":"XasIfmJKikt54X-Lg4AO5m87sSkmGLb9HC-LJ_-I4Os"}"#,
+  )
+  .unwrap()
+}
+
+// P-256 key pair from https://www.rfc-editor.org/rfc/rfc7518#appendix-C.
+fn p256_bob() -> Jwk {
+  serde_json::from_str(
+    r#"{"kty":"EC","crv":"P-256","kid":"bob-p256",
+      "x":"weNJy2HscCSM6AEDTDg04biOvhFhyyWvOHQfeF_PxMQ",
+      "y":"e8lnCO-AlStT-NJVX-crhB7QRYhiix03illJOVAOyck",
+      "d":"VEmDZpDXXK8p8N0Cndsxs924q6nS1RXFASRl6BfUqdw"}"#,
+  )
+  .unwrap()
+}
+
+fn header(alg: JweAlgorithm, enc: JweEncryption) -> JweHeader {
+  let mut header: JweHeader = JweHeader::new();
+  header.set_alg(alg);
+  header.set_enc(enc);
+  header
+}
+
+#[test]
+fn test_compact_roundtrip() {
+  for private_key in [x25519_bob(), p256_bob()] {
+    let public_key: Jwk = private_key.to_public().unwrap();
+    for alg in JweAlgorithm::ALL.iter().copied() {
+      for enc in JweEncryption::ALL.iter().copied() {
+        let mut protected: JweHeader = header(alg, enc);
+        protected.set_apu(crate::jwu::encode_b64("Alice"));
+        protected.set_apv(crate::jwu::encode_b64("Bob"));
+
+        let jwe: String = JweEncoder::new(&protected)
+          .encrypt(PLAINTEXT, &[JweRecipient::new(&public_key)], JweFormat::Compact)
+          .unwrap();
+        assert_eq!(jwe.split('.').count(), 5);
+
+        let item: JweDecryptionItem = JweDecoder::new().decode_compact_serialization(jwe.as_bytes()).unwrap();
+        assert_eq!(item.alg(0), Some(alg));
+        assert_eq!(item.enc(), Some(enc));
+        assert!(item.epk(0).unwrap().is_public());
+
+        let decoded: DecodedJwe = item.decrypt(0, &private_key).unwrap();
+        assert_eq!(decoded.plaintext, PLAINTEXT);
+        assert_eq!(decoded.protected.apu(), protected.apu());
+      }
+    }
+  }
+}
+
+#[test]
+fn test_flattened_roundtrip_with_aad() {
+  let public_key: Jwk = x25519_bob().to_public().unwrap();
+  let protected: JweHeader = header(JweAlgorithm::ECDH_ES_A256KW, JweEncryption::C20P);
+  let mut unprotected: JweHeader = JweHeader::new();
+  unprotected.set_kid("bob");
+
+  let jwe: String = JweEncoder::new(&protected)
+    .unprotected(&unprotected)
+    .aad(b"additional data")
+    .encrypt(PLAINTEXT, &[JweRecipient::new(&public_key)], JweFormat::Flatten)
+    .unwrap();
+
+  let item: JweDecryptionItem = JweDecoder::new()
+    .decode_flattened_serialization(jwe.as_bytes())
+    .unwrap();
+  let index: usize = item.find_recipient("bob").unwrap();
+  let decoded: DecodedJwe = item.decrypt(index, &x25519_bob()).unwrap();
+  assert_eq!(decoded.plaintext, PLAINTEXT);
+  assert_eq!(decoded.aad.as_deref(), Some(b"additional data".as_slice()));
+  assert_eq!(decoded.unprotected.unwrap().kid(), Some("bob"));
+
+  // Tampering with the additional authenticated data is detected.
+  let mut json: serde_json::Value = serde_json::from_str(&jwe).unwrap();
+  json["aad"] = serde_json::Value::String(crate::jwu::encode_b64("other data"));
+  let item: JweDecryptionItem = JweDecoder::new()
+    .decode_flattened_serialization(json.to_string().as_bytes())
+    .unwrap();
+  assert!(matches!(
+    item.decrypt(index, &x25519_bob()).unwrap_err(),
+    Error::DecryptionError(_)
+  ));
+}
+
+#[test]
+fn test_general_roundtrip_multiple_recipients() {
+  let protected: JweHeader = header(JweAlgorithm::ECDH_ES_A128KW, JweEncryption::A256GCM);
+  let alice: Jwk = x25519_alice().to_public().unwrap();
+  let bob: Jwk = p256_bob().to_public().unwrap();
+  let mut alice_header: JweHeader = JweHeader::new();
+  alice_header.set_kid("alice");
+  let mut bob_header: JweHeader = JweHeader::new();
+  bob_header.set_kid("bob-p256");
+
+  let jwe: String = JweEncoder::new(&protected)
+    .encrypt(
+      PLAINTEXT,
+      &[
+        JweRecipient::new(&alice).header(&alice_header),
+        JweRecipient::new(&bob).header(&bob_header),
+      ],
+      JweFormat::General,
+    )
+    .unwrap();
+
+  let item: JweDecryptionItem = JweDecoder::new().decode_general_serialization(jwe.as_bytes()).unwrap();
+  assert_eq!(item.recipient_count(), 2);
+  // Every recipient has its own ephemeral key.
+  assert_ne!(item.epk(0), item.epk(1));
+
+  for private_key in [x25519_alice(), p256_bob()] {
+    let index: usize = item.find_recipient(private_key.kid().unwrap()).unwrap();
+    let decoded: DecodedJwe = item.decrypt(index, &private_key).unwrap();
+    assert_eq!(decoded.plaintext, PLAINTEXT);
+    assert_eq!(decoded.recipient_header.unwrap().kid(), private_key.kid());
+  }
+
+  // Decrypting with the wrong key fails.
+  assert!(item.decrypt(0, &x25519_bob()).is_err());
+}
+
+#[test]
+fn test_invalid_encoder_configurations() {
+  let public_key: Jwk = x25519_bob().to_public().unwrap();
+  let other_key: Jwk = x25519_alice().to_public().unwrap();
+
+  // Direct key agreement cannot be used with multiple recipients.
+  let protected: JweHeader = header(JweAlgorithm::ECDH_ES, JweEncryption::A256GCM);
+  assert!(JweEncoder::new(&protected)
+    .encrypt(
+      PLAINTEXT,
+      &[JweRecipient::new(&public_key), JweRecipient::new(&other_key)],
+      JweFormat::General
+    )
+    .is_err());
+
+  // The compact serialization does not support additional authenticated data.
+  assert!(JweEncoder::new(&protected)
+    .aad(b"aad")
+    .encrypt(PLAINTEXT, &[JweRecipient::new(&public_key)], JweFormat::Compact)
+    .is_err());
+
+  // Missing `enc`.
+  let mut protected: JweHeader = JweHeader::new();
+  protected.set_alg(JweAlgorithm::ECDH_ES);
+  assert!(matches!(
+    JweEncoder::new(&protected)
+      .encrypt(PLAINTEXT, &[JweRecipient::new(&public_key)], JweFormat::Compact)
+      .unwrap_err(),
+    Error::MissingParam("enc")
+  ));
+
+  // `enc` is only accepted in the protected header.
+  let mut unprotected: JweHeader = JweHeader::new();
+  unprotected.set_enc(JweEncryption::A256GCM);
+  assert!(matches!(
+    JweEncoder::new(&protected)
+      .unprotected(&unprotected)
+      .encrypt(PLAINTEXT, &[JweRecipient::new(&public_key)], JweFormat::Flatten)
+      .unwrap_err(),
+    Error::InvalidParam(_)
+  ));
+  assert!(matches!(
+    JweEncoder::new(&protected)
+      .encrypt(
+        PLAINTEXT,
+        &[JweRecipient::new(&public_key).header(&unprotected)],
+        JweFormat::General
+      )
+      .unwrap_err(),
+    Error::InvalidParam(_)
+  ));
+
+  // Key agreement with a non key agreement key fails.
+  let ed25519_key: Jwk =
+    serde_json::from_str(r#"{"kty":"OKP","crv":"Ed25519","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#).unwrap();
+  let protected: JweHeader = header(JweAlgorithm::ECDH_ES, JweEncryption::A256GCM);
+  assert!(JweEncoder::new(&protected)
+    .encrypt(PLAINTEXT, &[JweRecipient::new(&ed25519_key)], JweFormat::Compact)
+    .is_err());
+}
+
+#[test]
+fn test_compact_tampering_detected() {
+  let public_key: Jwk = p256_bob().to_public().unwrap();
+  let protected: JweHeader = header(JweAlgorithm::ECDH_ES, JweEncryption::A256GCM);
+  let jwe: String = JweEncoder::new(&protected)
+    .encrypt(PLAINTEXT, &[JweRecipient::new(&public_key)], JweFormat::Compact)
+    .unwrap();
+
+  let mut segments: Vec<String> = jwe.split('.').map(ToOwned::to_owned).collect();
+  let mut ciphertext: Vec<u8> = crate::jwu::decode_b64(&segments[3]).unwrap();
+  ciphertext[0] ^= 1;
+  segments[3] = crate::jwu::encode_b64(ciphertext);
+  let tampered: String = segments.join(".");
+
+  let item: JweDecryptionItem = JweDecoder::new()
+    .decode_compact_serialization(tampered.as_bytes())
+    .unwrap();
+  assert!(matches!(
+    item.decrypt(0, &p256_bob()).unwrap_err(),
+    Error::DecryptionError(_)
+  ));
+
+  assert!(JweDecoder::new().decode_compact_serialization(b"a.b.c.d").is_err());
+}
+
+#[test]
+fn test_unprotected_enc_rejected() {
+  let public_key: Jwk = x25519_bob().to_public().unwrap();
+  let protected: JweHeader = header(JweAlgorithm::ECDH_ES_A256KW, JweEncryption::A256GCM);
+  let jwe: String = JweEncoder::new(&protected)
+    .encrypt(PLAINTEXT, &[JweRecipient::new(&public_key)], JweFormat::General)
+    .unwrap();
+
+  // Move `enc` from the protected header to an unprotected one.
+  let mut without_enc: JweHeader = JweHeader::new();
+  without_enc.set_alg(JweAlgorithm::ECDH_ES_A256KW);
+  let mut json: serde_json::Value = serde_json::from_str(&jwe).unwrap();
+  json["protected"] = serde_json::Value::String(crate::jwu::encode_b64_json(&without_enc).unwrap());
+  let enc: serde_json::Value = serde_json::json!({ "enc": "A256GCM" });
+
+  let mut shared: serde_json::Value = json.clone();
+  shared["unprotected"] = enc.clone();
+  assert!(matches!(
+    JweDecoder::new()
+      .decode_general_serialization(shared.to_string().as_bytes())
+      .err()
+      .unwrap(),
+    Error::InvalidParam(_)
+  ));
+
+  let mut per_recipient: serde_json::Value = json;
+  per_recipient["recipients"][0]["header"]["enc"] = enc["enc"].clone();
+  assert!(matches!(
+    JweDecoder::new()
+      .decode_general_serialization(per_recipient.to_string().as_bytes())
+      .err()
+      .unwrap(),
+    Error::InvalidParam(_)
+  ));
+}
@@ -4,6 +4,7 @@
 mod ed25519;
 mod es256;
 mod hs256;
+mod jwe;
 mod rfc7515;
 mod rfc7517;
 mod rfc7638;
@@ -26,6 +26,59 @@ impl Error {
   pub fn into_error_cause(self) -> ErrorCause {
     self.error_cause
   }
+
+  /// Returns whether the error was caused by a handler reporting that the DID does not exist.
+  ///
+  /// Handlers report this by failing with a [`std::io::Error`] of kind [`std::io::ErrorKind::NotFound`] anywhere in
+  /// the chain of error sources. Failures of the IOTA handlers to find the Alias Output of a DID are recognized as
+  /// well.
+  pub(crate) fn is_not_found(&self) -> bool {
+    let ErrorCause::HandlerError { source } = &self.error_cause else {
+      return false;
+    };
+
+    let mut current: Option<&(dyn std::error::Error + 'static)> = Some(source.as_ref());
+    while let Some(err) = current {
+      if let Some(shared) = err.downcast_ref::<SharedError>() {
+        current = Some(shared.0.as_ref());
+        continue;
+      }
+      if matches!(err.downcast_ref::<std::io::Error>(), Some(io_err) if io_err.kind() == std::io::ErrorKind::NotFound) {
+        return true;
+      }
+      #[cfg(feature = "iota")]
+      if matches!(
+        err.downcast_ref::<iota_sdk::client::Error>(),
+        Some(
+          iota_sdk::client::Error::NoOutput(_)
+            | iota_sdk::client::Error::Node(iota_sdk::client::node_api::error::Error::NotFound(_))
+        )
+      ) {
+        return true;
+      }
+      current = err.source();
+    }
+
+    false
+  }
+}
+
+/// A handler error shared between the callers served from the resolution cache.
+///
+/// Displays and chains like the wrapped error.
+#[derive(Debug, Clone)]
+pub(crate) struct SharedError(pub(crate) std::sync::Arc<dyn std::error::Error + Send + Sync + 'static>);
+
+impl std::fmt::Display for SharedError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    std::fmt::Display::fmt(&self.0, f)
+  }
+}
+
+impl std::error::Error for SharedError {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    self.0.source()
+  }
 }
 
 impl std::fmt::Display for Error {
@@ -0,0 +1,310 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::future::Future;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use identity_core::common::Duration;
+use identity_core::common::Timestamp;
+
+use crate::error::SharedError;
+use crate::DIDDocumentMetadata;
+use crate::Error;
+use crate::ErrorCause;
+use crate::Result;
+
+use super::commands::Command;
+use super::commands::Resolution;
+
+/// Fetches the current state index of the Alias Output backing an IOTA DID.
+pub(super) type StateIndexFetcher =
+  Box<dyn for<'r> Fn(&'r str) -> Pin<Box<dyn Future<Output = Result<u32>> + 'r + Send>> + Send + Sync>;
+
+/// Options for caching the results of a [`Resolver`](crate::Resolver).
+///
+/// See [`Resolver::enable_cache`](crate::Resolver::enable_cache).
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq)]
+pub struct ResolverCacheOptions {
+  /// How long a resolved DID Document is served from the cache.
+  ///
+  /// Default: 5 minutes.
+  pub ttl: Duration,
+
+  /// The maximum number of cached entries.
+  ///
+  /// When the cache is full the oldest entry is evicted. Default: 1000.
+  pub max_entries: usize,
+
+  /// How long the failure to find a DID is served from the cache.
+  ///
+  /// A failure is only cached if the handler reported that the DID does not exist, by failing with a
+  /// [`std::io::Error`] of kind [`std::io::ErrorKind::NotFound`] or, for the IOTA handlers, because the Alias
+  /// Output of the DID was not found. Other failures are never cached.
+  ///
+  /// Default: `None`, failures are not cached.
+  pub negative_ttl: Option<Duration>,
+
+  /// Whether to check the state index of the Alias Output of IOTA DIDs before serving a cached DID Document.
+  ///
+  /// If the state index changed since the DID Document was cached, the DID is resolved again. This requires a
+  /// lookup of the Alias Output for every cache hit, but detects updated DID Documents before the `ttl` elapses.
+  /// Only applies to handlers attached with `attach_iota_handler` or `attach_multiple_iota_handlers`.
+  ///
+  /// Default: `false`.
+  pub check_iota_state_index: bool,
+}
+
+impl ResolverCacheOptions {
+  /// Creates a new [`ResolverCacheOptions`] with default values.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Replace the value of the `ttl` field.
+  pub fn ttl(mut self, value: Duration) -> Self {
+    self.ttl = value;
+    self
+  }
+
+  /// Replace the value of the `max_entries` field.
+  pub fn max_entries(mut self, value: usize) -> Self {
+    self.max_entries = value;
+    self
+  }
+
+  /// Replace the value of the `negative_ttl` field.
+  pub fn negative_ttl(mut self, value: Duration) -> Self {
+    self.negative_ttl = Some(value);
+    self
+  }
+
+  /// Replace the value of the `check_iota_state_index` field.
+  pub fn check_iota_state_index(mut self, value: bool) -> Self {
+    self.check_iota_state_index = value;
+    self
+  }
+}
+
+impl Default for ResolverCacheOptions {
+  fn default() -> Self {
+    Self {
+      ttl: Duration::minutes(5),
+      max_entries: 1000,
+      negative_ttl: None,
+      check_iota_state_index: false,
+    }
+  }
+}
+
+impl std::fmt::Debug for ResolverCacheOptions {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    // `Duration` does not implement `Debug`.
+    f.debug_struct("ResolverCacheOptions")
+      .field("max_entries", &self.max_entries)
+      .field("check_iota_state_index", &self.check_iota_state_index)
+      .finish_non_exhaustive()
+  }
+}
+
+/// The value cached for a DID.
+enum CachedValue<DOC> {
+  Document {
+    document: DOC,
+    metadata: DIDDocumentMetadata,
+    state_index: Option<u32>,
+  },
+  NotFound(SharedError),
+}
+
+struct CacheEntry<DOC> {
+  value: CachedValue<DOC>,
+  expires: Timestamp,
+  // Insertion order, used to evict the oldest entry.
+  sequence: u64,
+}
+
+struct CacheState<DOC> {
+  entries: HashMap<String, CacheEntry<DOC>>,
+  next_sequence: u64,
+}
+
+/// Cache of the DID Documents and failures returned by the handlers of a [`Resolver`](crate::Resolver).
+pub(super) struct ResolverCache<DOC> {
+  options: ResolverCacheOptions,
+  state: Mutex<CacheState<DOC>>,
+  // Cloning is only required when caching is enabled, so the bound is not put on the `Resolver`.
+  clone_document: fn(&DOC) -> DOC,
+}
+
+impl<DOC: Clone> ResolverCache<DOC> {
+  pub(super) fn new(options: ResolverCacheOptions) -> Self {
+    Self {
+      options,
+      state: Mutex::new(CacheState {
+        entries: HashMap::new(),
+        next_sequence: 0,
+      }),
+      clone_document: DOC::clone,
+    }
+  }
+}
+
+impl<DOC> ResolverCache<DOC> {
+  /// Resolves `did` from the cache, falling back to `command` if there is no valid entry.
+  pub(super) async fn resolve<'a, CMD>(
+    &self,
+    did: &'a str,
+    command: &CMD,
+    state_index_fetcher: Option<&StateIndexFetcher>,
+  ) -> Result<Resolution<DOC>>
+  where
+    CMD: Command<'a, Result<Resolution<DOC>>>,
+  {
+    let state_index_fetcher: Option<&StateIndexFetcher> =
+      state_index_fetcher.filter(|_| self.options.check_iota_state_index);
+
+    let current_state_index: Option<u32> = match (self.get(did), state_index_fetcher) {
+      (Some(CachedValue::NotFound(err)), _) => {
+        return Err(Error::new(ErrorCause::HandlerError { source: Box::new(err) }));
+      }
+      (Some(CachedValue::Document { document, metadata, .. }), None) => return Ok((document, metadata)),
+      (
+        Some(CachedValue::Document {
+          document,
+          metadata,
+          state_index,
+        }),
+        Some(fetch),
+      ) => {
+        // Only serve the cached document if the DID was not updated in the meantime.
+        let current_state_index: Option<u32> = fetch(did).await.ok();
+        if current_state_index.is_some() && current_state_index == state_index {
+          return Ok((document, metadata));
+        }
+        current_state_index
+      }
+      (None, Some(fetch)) => fetch(did).await.ok(),
+      (None, None) => None,
+    };
+
+    match command.apply(did).await {
+      Ok((document, metadata)) => {
+        let value: CachedValue<DOC> = CachedValue::Document {
+          document: (self.clone_document)(&document),
+          metadata: metadata.clone(),
+          state_index: current_state_index,
+        };
+        self.insert(did, value, self.options.ttl);
+        Ok((document, metadata))
+      }
+      Err(err) => {
+        self.invalidate(did);
+        match self.options.negative_ttl {
+          Some(negative_ttl) if err.is_not_found() => {
+            let ErrorCause::HandlerError { source } = err.into_error_cause() else {
+              unreachable!("only handler errors report missing DIDs");
+            };
+            let shared: SharedError = SharedError(Arc::from(source));
+            self.insert(did, CachedValue::NotFound(shared.clone()), negative_ttl);
+            Err(Error::new(ErrorCause::HandlerError {
+              source: Box::new(shared),
+            }))
+          }
+          _ => Err(err),
+        }
+      }
+    }
+  }
+
+  /// Removes the entry of `did`, returning whether there was one.
+  pub(super) fn invalidate(&self, did: &str) -> bool {
+    self.lock().entries.remove(did).is_some()
+  }
+
+  /// Removes all entries.
+  pub(super) fn clear(&self) {
+    self.lock().entries.clear();
+  }
+
+  /// Returns the number of entries, including expired ones that have not been evicted yet.
+  pub(super) fn len(&self) -> usize {
+    self.lock().entries.len()
+  }
+
+  fn get(&self, did: &str) -> Option<CachedValue<DOC>> {
+    let mut state = self.lock();
+    let entry: &CacheEntry<DOC> = state.entries.get(did)?;
+    if entry.expires <= Timestamp::now_utc() {
+      state.entries.remove(did);
+      return None;
+    }
+
+    Some(match &entry.value {
+      CachedValue::Document {
+        document,
+        metadata,
+        state_index,
+      } => CachedValue::Document {
+        document: (self.clone_document)(document),
+        metadata: metadata.clone(),
+        state_index: *state_index,
+      },
+      CachedValue::NotFound(err) => CachedValue::NotFound(err.clone()),
+    })
+  }
+
+  fn insert(&self, did: &str, value: CachedValue<DOC>, ttl: Duration) {
+    if self.options.max_entries == 0 {
+      return;
+    }
+    let now: Timestamp = Timestamp::now_utc();
+    let Some(expires) = now.checked_add(ttl) else {
+      return;
+    };
+
+    let mut state = self.lock();
+    if !state.entries.contains_key(did) && state.entries.len() >= self.options.max_entries {
+      state.entries.retain(|_, entry| entry.expires > now);
+    }
+    while !state.entries.contains_key(did) && state.entries.len() >= self.options.max_entries {
+      let oldest: String = state
+        .entries
+        .iter()
+        .min_by_key(|(_, entry)| entry.sequence)
+        .map(|(did, _)| did.clone())
+        .expect("the cache is not empty");
+      state.entries.remove(&oldest);
+    }
+
+    let sequence: u64 = state.next_sequence;
+    state.next_sequence += 1;
+    state.entries.insert(
+      did.to_owned(),
+      CacheEntry {
+        value,
+        expires,
+        sequence,
+      },
+    );
+  }
+
+  fn lock(&self) -> MutexGuard<'_, CacheState<DOC>> {
+    // The cache state is consistent after every operation, so a poisoned lock can be recovered.
+    self.state.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+}
+
+impl<DOC> std::fmt::Debug for ResolverCache<DOC> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ResolverCache")
+      .field("options", &self.options)
+      .field("entries", &self.len())
+      .finish()
+  }
+}
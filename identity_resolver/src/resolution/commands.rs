@@ -4,11 +4,15 @@
 use core::future::Future;
 use identity_did::DID;
 
+use crate::DIDDocumentMetadata;
 use crate::Error;
 use crate::ErrorCause;
 use crate::Result;
 use std::pin::Pin;
 
+/// A resolved DID Document together with the metadata supplied by the handler.
+pub(super) type Resolution<DOC> = (DOC, DIDDocumentMetadata);
+
 /// Internal trait used by the resolver to apply the command pattern.
 ///
 /// The resolver is generic over the type of command which enables  
@@ -41,15 +45,15 @@ impl<DOC: 'static> std::fmt::Debug for SingleThreadedCommand<DOC> {
 
 /// Internal representation of a thread safe handler.
 type SendSyncCallback<DOC> =
-  Box<dyn for<'r> Fn(&'r str) -> Pin<Box<dyn Future<Output = Result<DOC>> + 'r + Send>> + Send + Sync>;
+  Box<dyn for<'r> Fn(&'r str) -> Pin<Box<dyn Future<Output = Result<Resolution<DOC>>> + 'r + Send>> + Send + Sync>;
 
 /// Wrapper around a thread safe callback.
 pub struct SendSyncCommand<DOC: 'static> {
   fun: SendSyncCallback<DOC>,
 }
 
-impl<'a, DOC: 'static> Command<'a, Result<DOC>> for SendSyncCommand<DOC> {
-  type Output = Pin<Box<dyn Future<Output = Result<DOC>> + 'a + Send>>;
+impl<'a, DOC: 'static> Command<'a, Result<Resolution<DOC>>> for SendSyncCommand<DOC> {
+  type Output = Pin<Box<dyn Future<Output = Result<Resolution<DOC>>> + 'a + Send>>;
   fn apply(&self, input: &'a str) -> Self::Output {
     (self.fun)(input)
   }
@@ -70,6 +74,23 @@ impl<DOC: 'static> SendSyncCommand<DOC> {
     Fut: Future<Output = std::result::Result<DOCUMENT, E>> + Send,
     E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    Self::new_with_metadata(move |did: D| {
+      let future = handler(did);
+      async move { future.await.map(|document| (document, DIDDocumentMetadata::new())) }
+    })
+  }
+
+  /// Equivalent to [`SendSyncCommand::new`](SendSyncCommand::new()), but for handlers that also return the
+  /// [`DIDDocumentMetadata`] of the resolved document.
+  pub(super) fn new_with_metadata<D, F, Fut, DOCUMENT, E, DIDERR>(handler: F) -> Self
+  where
+    D: DID + Send + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D) -> Fut + 'static + Clone + Send + Sync,
+    Fut: Future<Output = std::result::Result<Resolution<DOCUMENT>, E>> + Send,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
   {
     let fun: SendSyncCallback<DOC> = Box::new(move |input: &str| {
       let handler_clone: F = handler.clone();
@@ -81,7 +102,7 @@ impl<DOC: 'static> SendSyncCommand<DOC> {
         let did: D = did_parse_attempt?;
         handler_clone(did)
           .await
-          .map(Into::into)
+          .map(|(document, metadata)| (document.into(), metadata))
           .map_err(|error| ErrorCause::HandlerError { source: error.into() })
           .map_err(Error::new)
       })
@@ -97,14 +118,14 @@ impl<DOC: 'static> SendSyncCommand<DOC> {
 
 /// Internal representation of a single threaded handler.
 pub(super) type SingleThreadedCallback<DOC> =
-  Box<dyn for<'r> Fn(&'r str) -> Pin<Box<dyn Future<Output = Result<DOC>> + 'r>>>;
+  Box<dyn for<'r> Fn(&'r str) -> Pin<Box<dyn Future<Output = Result<Resolution<DOC>>> + 'r>>>;
 
 /// Wrapper around a single threaded callback.
 pub struct SingleThreadedCommand<DOC> {
   fun: SingleThreadedCallback<DOC>,
 }
-impl<'a, DOC: 'static> Command<'a, Result<DOC>> for SingleThreadedCommand<DOC> {
-  type Output = Pin<Box<dyn Future<Output = Result<DOC>> + 'a>>;
+impl<'a, DOC: 'static> Command<'a, Result<Resolution<DOC>>> for SingleThreadedCommand<DOC> {
+  type Output = Pin<Box<dyn Future<Output = Result<Resolution<DOC>>> + 'a>>;
   fn apply(&self, input: &'a str) -> Self::Output {
     (self.fun)(input)
   }
@@ -120,6 +141,23 @@ impl<DOC: 'static> SingleThreadedCommand<DOC> {
     Fut: Future<Output = std::result::Result<DOCUMENT, E>>,
     E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
     DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    Self::new_with_metadata(move |did: D| {
+      let future = handler(did);
+      async move { future.await.map(|document| (document, DIDDocumentMetadata::new())) }
+    })
+  }
+
+  /// Equivalent to [`SendSyncCommand::new_with_metadata`](SendSyncCommand::new_with_metadata()), but with less
+  /// `Send` + `Sync` bounds.
+  pub(super) fn new_with_metadata<D, F, Fut, DOCUMENT, E, DIDERR>(handler: F) -> Self
+  where
+    D: DID + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D) -> Fut + 'static + Clone,
+    Fut: Future<Output = std::result::Result<Resolution<DOCUMENT>, E>>,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
   {
     let fun: SingleThreadedCallback<DOC> = Box::new(move |input: &str| {
       let handler_clone: F = handler.clone();
@@ -131,7 +169,7 @@ impl<DOC: 'static> SingleThreadedCommand<DOC> {
         let did: D = did_parse_attempt?;
         handler_clone(did)
           .await
-          .map(Into::into)
+          .map(|(document, metadata)| (document.into(), metadata))
           .map_err(|error| ErrorCause::HandlerError { source: error.into() })
           .map_err(Error::new)
       })
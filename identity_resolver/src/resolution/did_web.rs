@@ -0,0 +1,116 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Url;
+use identity_core::convert::FromJson;
+use identity_did::DIDWeb;
+use identity_document::document::CoreDocument;
+
+/// Fetches the DID documents of `did:web` DIDs.
+///
+/// A fetcher is passed to [`Resolver::attach_did_web_handler`](crate::Resolver::attach_did_web_handler), which
+/// allows replacing the HTTP client, e.g. with a local stub in tests. With the `did-web-fetch` feature,
+/// [`ReqwestFetcher`] is provided as an implementation backed by `reqwest`.
+///
+/// Fetchers should report a missing DID document by failing with a [`std::io::Error`] of kind
+/// [`std::io::ErrorKind::NotFound`], so resolution with metadata can report a `notFound` error.
+#[async_trait::async_trait]
+pub trait DIDWebFetcher {
+  /// Fetches the resource at `url`, which uses the `https` scheme.
+  async fn fetch(&self, url: &Url) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync + 'static>>;
+}
+
+/// Errors that can occur when resolving a `did:web` DID.
+#[derive(Debug, thiserror::Error, strum::IntoStaticStr)]
+#[non_exhaustive]
+pub enum DIDWebError {
+  /// Caused by a failure to fetch the DID document.
+  #[error("failed to fetch the did:web DID document")]
+  FetchError(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+  /// Caused by a fetched resource that is not a valid DID document.
+  #[error("the fetched did:web DID document is invalid")]
+  InvalidDocument(#[source] identity_core::Error),
+  /// Caused by a fetched DID document whose `id` differs from the resolved DID.
+  #[error("the id `{0}` of the fetched DID document does not match the resolved did:web")]
+  IdMismatch(String),
+}
+
+/// Fetches the DID document of `did` with `fetcher` and checks that it belongs to `did`.
+pub(super) async fn resolve_did_web<F>(fetcher: &F, did: DIDWeb) -> Result<CoreDocument, DIDWebError>
+where
+  F: DIDWebFetcher + ?Sized,
+{
+  let json: Vec<u8> = fetcher.fetch(&did.to_url()).await.map_err(DIDWebError::FetchError)?;
+  let document: CoreDocument = CoreDocument::from_json_slice(&json).map_err(DIDWebError::InvalidDocument)?;
+  if document.id() != did.as_ref() {
+    return Err(DIDWebError::IdMismatch(document.id().to_string()));
+  }
+
+  Ok(document)
+}
+
+#[cfg(feature = "did-web-fetch")]
+mod __reqwest_fetcher {
+  use futures::StreamExt;
+  use identity_core::common::Url;
+  use reqwest::redirect::Policy;
+  use reqwest::Client;
+  use reqwest::StatusCode;
+
+  use super::DIDWebFetcher;
+
+  /// The maximum size of a DID document fetched by [`ReqwestFetcher`].
+  const MAX_DOCUMENT_SIZE: usize = 1_048_576;
+
+  /// A [`DIDWebFetcher`] backed by a `reqwest` [`Client`].
+  ///
+  /// Only `https` is allowed, redirects are not followed and DID documents can not exceed 1 MiB.
+  #[derive(Debug, Clone)]
+  pub struct ReqwestFetcher {
+    client: Client,
+  }
+
+  impl ReqwestFetcher {
+    /// Creates a fetcher with a new [`Client`].
+    pub fn new() -> Result<Self, reqwest::Error> {
+      let client: Client = reqwest::ClientBuilder::new()
+        .https_only(true)
+        .redirect(Policy::none())
+        .build()?;
+      Ok(Self { client })
+    }
+
+    /// Creates a fetcher using the given `client`.
+    pub fn with_client(client: Client) -> Self {
+      Self { client }
+    }
+  }
+
+  #[async_trait::async_trait]
+  impl DIDWebFetcher for ReqwestFetcher {
+    async fn fetch(&self, url: &Url) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+      let response = self.client.get(url.as_str()).send().await?;
+      if response.status() == StatusCode::NOT_FOUND {
+        return Err(Box::new(std::io::Error::new(
+          std::io::ErrorKind::NotFound,
+          format!("no DID document found at {url}"),
+        )));
+      }
+
+      // We use a stream so we can limit the size of the response to 1 MiB.
+      let mut stream = response.error_for_status()?.bytes_stream();
+      let mut json: Vec<u8> = Vec::new();
+      while let Some(item) = stream.next().await {
+        json.extend(item?);
+        if json.len() > MAX_DOCUMENT_SIZE {
+          return Err("did:web DID document can not exceed 1 MiB".into());
+        }
+      }
+
+      Ok(json)
+    }
+  }
+}
+
+#[cfg(feature = "did-web-fetch")]
+pub use __reqwest_fetcher::ReqwestFetcher;
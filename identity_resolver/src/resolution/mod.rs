@@ -1,7 +1,10 @@
 // Copyright 2020-2022 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+mod cache;
 mod commands;
+mod did_web;
+mod resolution_result;
 mod resolver;
 #[cfg(test)]
 mod tests;
@@ -9,6 +12,9 @@ mod tests;
 use self::commands::SingleThreadedCommand;
 use identity_document::document::CoreDocument;
 
+pub use cache::ResolverCacheOptions;
+pub use did_web::*;
+pub use resolution_result::*;
 pub use resolver::Resolver;
 /// Alias for a [`Resolver`] that is not [`Send`] + [`Sync`].
 pub type SingleThreadedResolver<DOC = CoreDocument> = Resolver<DOC, SingleThreadedCommand<DOC>>;
@@ -0,0 +1,194 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Error;
+use crate::ErrorCause;
+
+/// The media type of the JSON representation of a DID Document.
+pub const DID_JSON_MEDIA_TYPE: &str = "application/did+json";
+/// The media type of the JSON-LD representation of a DID Document.
+pub const DID_JSON_LD_MEDIA_TYPE: &str = "application/did+ld+json";
+
+/// Options for [`Resolver::resolve_with_metadata`](crate::Resolver::resolve_with_metadata).
+///
+/// See the [DID resolution options](https://www.w3.org/TR/did-core/#did-resolution-options).
+#[non_exhaustive]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct ResolutionOptions {
+  /// The media type of the requested representation of the DID Document.
+  ///
+  /// Only [`DID_JSON_MEDIA_TYPE`] and [`DID_JSON_LD_MEDIA_TYPE`] are supported, other values result in the
+  /// [`ResolutionErrorCode::RepresentationNotSupported`] error. Default: [`DID_JSON_MEDIA_TYPE`].
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub accept: Option<String>,
+}
+
+impl ResolutionOptions {
+  /// Creates a new [`ResolutionOptions`] with default values.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Replace the value of the `accept` field.
+  pub fn accept(mut self, value: impl Into<String>) -> Self {
+    self.accept = Some(value.into());
+    self
+  }
+}
+
+/// The error codes of a failed DID resolution.
+///
+/// See the [DID resolution metadata](https://www.w3.org/TR/did-core/#did-resolution-metadata).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub enum ResolutionErrorCode {
+  /// The DID supplied to the resolution function does not conform to valid syntax.
+  InvalidDid,
+  /// The resolver was unable to find the DID Document resulting from the resolution request.
+  NotFound,
+  /// The representation requested via the `accept` option is not supported.
+  RepresentationNotSupported,
+  /// The resolver has no handler for the method of the DID.
+  MethodNotSupported,
+  /// The resolution failed for any other reason, e.g. a network failure of the handler.
+  InternalError,
+}
+
+impl ResolutionErrorCode {
+  /// Returns the error code as it appears in the DID resolution metadata.
+  pub const fn as_str(&self) -> &'static str {
+    match self {
+      Self::InvalidDid => "invalidDid",
+      Self::NotFound => "notFound",
+      Self::RepresentationNotSupported => "representationNotSupported",
+      Self::MethodNotSupported => "methodNotSupported",
+      Self::InternalError => "internalError",
+    }
+  }
+}
+
+impl std::fmt::Display for ResolutionErrorCode {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl From<&Error> for ResolutionErrorCode {
+  fn from(error: &Error) -> Self {
+    match error.error_cause() {
+      ErrorCause::DIDParsingError { .. } => Self::InvalidDid,
+      ErrorCause::UnsupportedMethodError { .. } => Self::MethodNotSupported,
+      ErrorCause::HandlerError { .. } if error.is_not_found() => Self::NotFound,
+      _ => Self::InternalError,
+    }
+  }
+}
+
+/// Metadata about the resolution process.
+///
+/// See the [DID resolution metadata](https://www.w3.org/TR/did-core/#did-resolution-metadata).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DIDResolutionMetadata {
+  /// The media type of the representation of the resolved DID Document.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub content_type: Option<String>,
+  /// The error code of a failed resolution.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error: Option<ResolutionErrorCode>,
+  /// A human-readable description of the error of a failed resolution.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub error_message: Option<String>,
+}
+
+/// Metadata about the resolved DID Document, supplied by the handler that resolved it.
+///
+/// See the [DID document metadata](https://www.w3.org/TR/did-core/#did-document-metadata).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct DIDDocumentMetadata {
+  /// The timestamp of the creation of the DID.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub created: Option<Timestamp>,
+  /// The timestamp of the last update of the DID Document.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub updated: Option<Timestamp>,
+  /// Whether the DID has been deactivated.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub deactivated: Option<bool>,
+  /// The timestamp of the next update of the DID Document, if the resolved document is not the latest version.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_update: Option<Timestamp>,
+  /// The version of the resolved DID Document.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub version_id: Option<String>,
+  /// The version of the next update of the DID Document, if the resolved document is not the latest version.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_version_id: Option<String>,
+  /// Additional method specific metadata.
+  #[serde(flatten)]
+  pub properties: Object,
+}
+
+impl DIDDocumentMetadata {
+  /// Creates a new, empty [`DIDDocumentMetadata`].
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+/// The result of [`Resolver::resolve_with_metadata`](crate::Resolver::resolve_with_metadata).
+///
+/// See the [DID resolution](https://www.w3.org/TR/did-core/#did-resolution) specification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ResolutionResult<DOC> {
+  /// Metadata about the resolution process.
+  pub did_resolution_metadata: DIDResolutionMetadata,
+  /// The resolved DID Document, `None` if the resolution failed.
+  pub did_document: Option<DOC>,
+  /// Metadata about the resolved DID Document, empty if the resolution failed.
+  pub did_document_metadata: DIDDocumentMetadata,
+}
+
+impl<DOC> ResolutionResult<DOC> {
+  /// Returns the error code of a failed resolution.
+  pub fn error(&self) -> Option<ResolutionErrorCode> {
+    self.did_resolution_metadata.error
+  }
+
+  pub(crate) fn success(document: DOC, did_document_metadata: DIDDocumentMetadata, content_type: String) -> Self {
+    Self {
+      did_resolution_metadata: DIDResolutionMetadata {
+        content_type: Some(content_type),
+        error: None,
+        error_message: None,
+      },
+      did_document: Some(document),
+      did_document_metadata,
+    }
+  }
+
+  pub(crate) fn failure(error: ResolutionErrorCode, error_message: String) -> Self {
+    Self {
+      did_resolution_metadata: DIDResolutionMetadata {
+        content_type: None,
+        error: Some(error),
+        error_message: Some(error_message),
+      },
+      did_document: None,
+      did_document_metadata: DIDDocumentMetadata::default(),
+    }
+  }
+}
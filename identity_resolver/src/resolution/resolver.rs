@@ -5,20 +5,38 @@ use core::future::Future;
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt;
 use identity_did::DIDJwk;
+#[cfg(feature = "did-key")]
+use identity_did::DIDKey;
+#[cfg(feature = "did-key")]
+use identity_did::DIDPeer;
+use identity_did::DIDWeb;
 use identity_did::DID;
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use identity_document::document::CoreDocument;
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
+use crate::DIDDocumentMetadata;
 use crate::Error;
 use crate::ErrorCause;
+use crate::ResolutionErrorCode;
+use crate::ResolutionOptions;
+use crate::ResolutionResult;
 use crate::Result;
+use crate::DID_JSON_LD_MEDIA_TYPE;
+use crate::DID_JSON_MEDIA_TYPE;
 
+use super::cache::ResolverCache;
+use super::cache::ResolverCacheOptions;
+use super::cache::StateIndexFetcher;
 use super::commands::Command;
+use super::commands::Resolution;
 use super::commands::SendSyncCommand;
 use super::commands::SingleThreadedCommand;
+use super::did_web::resolve_did_web;
+use super::did_web::DIDWebFetcher;
 
 /// Convenience type for resolving DID documents from different DID methods.   
 ///
@@ -26,17 +44,24 @@ use super::commands::SingleThreadedCommand;
 ///
 /// The resolver will only be able to resolve DID documents for methods it has been configured for. This is done by
 /// attaching method specific handlers with [`Self::attach_handler`](Self::attach_handler()).
+///
+/// # Caching
+///
+/// By default every resolution invokes the attached handler. Resolved DID Documents can be cached by calling
+/// [`Self::enable_cache`](Self::enable_cache()).
 pub struct Resolver<DOC = CoreDocument, CMD = SendSyncCommand<DOC>>
 where
-  CMD: for<'r> Command<'r, Result<DOC>>,
+  CMD: for<'r> Command<'r, Result<Resolution<DOC>>>,
 {
   command_map: HashMap<String, CMD>,
+  cache: Option<ResolverCache<DOC>>,
+  state_index_fetchers: HashMap<String, StateIndexFetcher>,
   _required: PhantomData<DOC>,
 }
 
 impl<M, DOC> Resolver<DOC, M>
 where
-  M: for<'r> Command<'r, Result<DOC>>,
+  M: for<'r> Command<'r, Result<Resolution<DOC>>>,
 {
   /// Constructs a new [`Resolver`].
   ///
@@ -54,6 +79,8 @@ where
   pub fn new() -> Self {
     Self {
       command_map: HashMap::new(),
+      cache: None,
+      state_index_fetchers: HashMap::new(),
       _required: PhantomData::<DOC>,
     }
   }
@@ -91,6 +118,56 @@ where
   /// }
   /// ```
   pub async fn resolve<D: DID>(&self, did: &D) -> Result<DOC> {
+    self.resolve_with_handler(did).await.map(|(document, _)| document)
+  }
+
+  /// Fetches the DID Document of the given DID together with the metadata defined by the
+  /// [DID resolution](https://www.w3.org/TR/did-core/#did-resolution) specification.
+  ///
+  /// Unlike [`Self::resolve`](Self::resolve()) this method does not fail, a failed resolution is described by the
+  /// [`ResolutionErrorCode`] in the resolution metadata of the returned [`ResolutionResult`]:
+  /// * [`ResolutionErrorCode::MethodNotSupported`] if the resolver has not been configured to handle the method of
+  ///   the given DID.
+  /// * [`ResolutionErrorCode::InvalidDid`] if the DID is not valid for its method.
+  /// * [`ResolutionErrorCode::NotFound`] if the handler reported that the DID does not exist, see
+  ///   [`ResolverCacheOptions::negative_ttl`](crate::ResolverCacheOptions::negative_ttl).
+  /// * [`ResolutionErrorCode::RepresentationNotSupported`] if the DID was resolved, but the representation requested
+  ///   by `options` is not supported.
+  /// * [`ResolutionErrorCode::InternalError`] if the resolution failed for any other reason.
+  ///
+  /// The DID document metadata is supplied by the handler, see
+  /// [`Self::attach_handler_with_metadata`](Self::attach_handler_with_metadata()). It is empty for handlers
+  /// attached with [`Self::attach_handler`](Self::attach_handler()). For IOTA DIDs the version id is set to the current
+  /// state index of the Alias Output backing the DID, which is fetched after the DID Document was resolved.
+  pub async fn resolve_with_metadata<D: DID>(&self, did: &D, options: &ResolutionOptions) -> ResolutionResult<DOC> {
+    // The handler parses and validates the DID, whose errors take precedence over an unsupported representation.
+    let (document, mut metadata): Resolution<DOC> = match self.resolve_with_handler(did).await {
+      Ok(resolution) => resolution,
+      Err(err) => return ResolutionResult::failure(ResolutionErrorCode::from(&err), err.to_string()),
+    };
+    // The version of an IOTA DID Document is the state index of its Alias Output, which is only read when the
+    // metadata is requested, so that plain resolutions only go through `IotaIdentityClientExt::resolve_did`.
+    if let (None, Some(fetcher)) = (&metadata.version_id, self.state_index_fetchers.get(did.method())) {
+      match fetcher(did.as_str()).await {
+        Ok(state_index) => metadata.version_id = Some(state_index.to_string()),
+        Err(err) => return ResolutionResult::failure(ResolutionErrorCode::from(&err), err.to_string()),
+      }
+    }
+
+    match options.accept.as_deref() {
+      None => ResolutionResult::success(document, metadata, DID_JSON_MEDIA_TYPE.to_owned()),
+      Some(accept @ (DID_JSON_MEDIA_TYPE | DID_JSON_LD_MEDIA_TYPE)) => {
+        ResolutionResult::success(document, metadata, accept.to_owned())
+      }
+      Some(accept) => ResolutionResult::failure(
+        ResolutionErrorCode::RepresentationNotSupported,
+        format!("the representation \"{accept}\" is not supported"),
+      ),
+    }
+  }
+
+  /// Resolves the given DID with the handler attached for its method, or from the cache if enabled.
+  async fn resolve_with_handler<D: DID>(&self, did: &D) -> Result<Resolution<DOC>> {
     let method: &str = did.method();
     let delegate: &M = self
       .command_map
@@ -100,7 +177,14 @@ where
       })
       .map_err(Error::new)?;
 
-    delegate.apply(did.as_str()).await
+    match self.cache {
+      Some(ref cache) => {
+        cache
+          .resolve(did.as_str(), delegate, self.state_index_fetchers.get(method))
+          .await
+      }
+      None => delegate.apply(did.as_str()).await,
+    }
   }
 
   /// Concurrently fetches the DID Documents of the multiple given DIDs.
@@ -127,6 +211,64 @@ where
 
     Ok(documents)
   }
+
+  /// Disables caching and drops all cached entries.
+  pub fn disable_cache(&mut self) {
+    self.cache = None;
+  }
+
+  /// Removes the cached entry of the given DID, if any, so that it is resolved again by the next call to
+  /// [`Self::resolve`](Self::resolve()).
+  ///
+  /// Returns `true` if there was a cached entry.
+  pub fn invalidate<D: DID>(&self, did: &D) -> bool {
+    self
+      .cache
+      .as_ref()
+      .map(|cache| cache.invalidate(did.as_str()))
+      .unwrap_or(false)
+  }
+
+  /// Removes all cached entries.
+  pub fn clear_cache(&self) {
+    if let Some(cache) = self.cache.as_ref() {
+      cache.clear();
+    }
+  }
+}
+
+impl<M, DOC> Resolver<DOC, M>
+where
+  M: for<'r> Command<'r, Result<Resolution<DOC>>>,
+  DOC: Clone,
+{
+  /// Enables caching of the DID Documents returned by the attached handlers, configured by `options`.
+  ///
+  /// Subsequent calls to [`Self::resolve`](Self::resolve()) and [`Self::resolve_multiple`](Self::resolve_multiple())
+  /// serve a DID Document from the cache for as long as its entry is valid. Entries can be removed explicitly with
+  /// [`Self::invalidate`](Self::invalidate()).
+  ///
+  /// Replaces any previously enabled cache, dropping its entries.
+  ///
+  /// # Example
+  ///
+  /// ```
+  /// # use identity_core::common::Duration;
+  /// # use identity_resolver::Resolver;
+  /// # use identity_resolver::ResolverCacheOptions;
+  /// # use identity_document::document::CoreDocument;
+  ///
+  /// let mut resolver = Resolver::<CoreDocument>::new();
+  /// resolver.enable_cache(
+  ///   ResolverCacheOptions::new()
+  ///     .ttl(Duration::minutes(10))
+  ///     .max_entries(10_000)
+  ///     .negative_ttl(Duration::seconds(30)),
+  /// );
+  /// ```
+  pub fn enable_cache(&mut self, options: ResolverCacheOptions) {
+    self.cache = Some(ResolverCache::new(options));
+  }
 }
 
 impl<DOC: 'static> Resolver<DOC, SendSyncCommand<DOC>> {
@@ -185,6 +327,28 @@ impl<DOC: 'static> Resolver<DOC, SendSyncCommand<DOC>> {
     DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
   {
     let command = SendSyncCommand::new(handler);
+    self.state_index_fetchers.remove(&method);
+    self.command_map.insert(method, command);
+  }
+
+  /// Attach a new handler responsible for resolving DIDs of the given DID method, that also supplies the metadata of
+  /// the resolved DID Document.
+  ///
+  /// The `handler` is expected to be a closure taking an owned DID and asynchronously returning a DID Document
+  /// together with its [`DIDDocumentMetadata`], which is returned by
+  /// [`Self::resolve_with_metadata`](Self::resolve_with_metadata()). Otherwise it behaves exactly like a handler
+  /// attached with [`Self::attach_handler`](Self::attach_handler()).
+  pub fn attach_handler_with_metadata<D, F, Fut, DOCUMENT, E, DIDERR>(&mut self, method: String, handler: F)
+  where
+    D: DID + Send + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D) -> Fut + 'static + Clone + Send + Sync,
+    Fut: Future<Output = std::result::Result<(DOCUMENT, DIDDocumentMetadata), E>> + Send,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    let command = SendSyncCommand::new_with_metadata(handler);
+    self.state_index_fetchers.remove(&method);
     self.command_map.insert(method, command);
   }
 }
@@ -244,6 +408,26 @@ impl<DOC: 'static> Resolver<DOC, SingleThreadedCommand<DOC>> {
     DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
   {
     let command = SingleThreadedCommand::new(handler);
+    self.state_index_fetchers.remove(&method);
+    self.command_map.insert(method, command);
+  }
+
+  /// Attach a new handler responsible for resolving DIDs of the given DID method, that also supplies the metadata of
+  /// the resolved DID Document.
+  ///
+  /// Equivalent to [`Resolver::attach_handler_with_metadata`](Resolver::attach_handler_with_metadata()), but with
+  /// less `Send` + `Sync` bounds.
+  pub fn attach_handler_with_metadata<D, F, Fut, DOCUMENT, E, DIDERR>(&mut self, method: String, handler: F)
+  where
+    D: DID + for<'r> TryFrom<&'r str, Error = DIDERR> + 'static,
+    DOCUMENT: 'static + Into<DOC>,
+    F: Fn(D) -> Fut + 'static + Clone,
+    Fut: Future<Output = std::result::Result<(DOCUMENT, DIDDocumentMetadata), E>>,
+    E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    DIDERR: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+  {
+    let command = SingleThreadedCommand::new_with_metadata(handler);
+    self.state_index_fetchers.remove(&method);
     self.command_map.insert(method, command);
   }
 }
@@ -254,6 +438,35 @@ impl<DOC: From<CoreDocument> + 'static> Resolver<DOC, SingleThreadedCommand<DOC>
     let handler = |did_jwk: DIDJwk| async move { CoreDocument::expand_did_jwk(did_jwk) };
     self.attach_handler(DIDJwk::METHOD.to_string(), handler)
   }
+
+  /// Attaches a handler capable of resolving `did:key` DIDs.
+  #[cfg(feature = "did-key")]
+  pub fn attach_did_key_handler(&mut self) {
+    let handler = |did_key: DIDKey| async move { CoreDocument::expand_did_key(did_key) };
+    self.attach_handler(DIDKey::METHOD.to_string(), handler)
+  }
+
+  /// Attaches a handler capable of resolving `did:peer` DIDs using numalgo 0 or 2.
+  #[cfg(feature = "did-key")]
+  pub fn attach_did_peer_handler(&mut self) {
+    let handler = |did_peer: DIDPeer| async move { CoreDocument::expand_did_peer(did_peer) };
+    self.attach_handler(DIDPeer::METHOD.to_string(), handler)
+  }
+
+  /// Attaches a handler capable of resolving `did:web` DIDs, whose DID documents are fetched with `fetcher`.
+  ///
+  /// The fetched DID documents are rejected if their `id` does not match the resolved DID.
+  pub fn attach_did_web_handler<F>(&mut self, fetcher: F)
+  where
+    F: DIDWebFetcher + 'static,
+  {
+    let fetcher: Arc<F> = Arc::new(fetcher);
+    let handler = move |did_web: DIDWeb| {
+      let fetcher: Arc<F> = Arc::clone(&fetcher);
+      async move { resolve_did_web(fetcher.as_ref(), did_web).await }
+    };
+    self.attach_handler(DIDWeb::METHOD.to_string(), handler)
+  }
 }
 
 impl<DOC: From<CoreDocument> + 'static> Resolver<DOC, SendSyncCommand<DOC>> {
@@ -262,39 +475,145 @@ impl<DOC: From<CoreDocument> + 'static> Resolver<DOC, SendSyncCommand<DOC>> {
     let handler = |did_jwk: DIDJwk| async move { CoreDocument::expand_did_jwk(did_jwk) };
     self.attach_handler(DIDJwk::METHOD.to_string(), handler)
   }
+
+  /// Attaches a handler capable of resolving `did:key` DIDs.
+  #[cfg(feature = "did-key")]
+  pub fn attach_did_key_handler(&mut self) {
+    let handler = |did_key: DIDKey| async move { CoreDocument::expand_did_key(did_key) };
+    self.attach_handler(DIDKey::METHOD.to_string(), handler)
+  }
+
+  /// Attaches a handler capable of resolving `did:peer` DIDs using numalgo 0 or 2.
+  #[cfg(feature = "did-key")]
+  pub fn attach_did_peer_handler(&mut self) {
+    let handler = |did_peer: DIDPeer| async move { CoreDocument::expand_did_peer(did_peer) };
+    self.attach_handler(DIDPeer::METHOD.to_string(), handler)
+  }
+
+  /// Attaches a handler capable of resolving `did:web` DIDs, whose DID documents are fetched with `fetcher`.
+  ///
+  /// The fetched DID documents are rejected if their `id` does not match the resolved DID.
+  pub fn attach_did_web_handler<F>(&mut self, fetcher: F)
+  where
+    F: DIDWebFetcher + Send + Sync + 'static,
+  {
+    let fetcher: Arc<F> = Arc::new(fetcher);
+    let handler = move |did_web: DIDWeb| {
+      let fetcher: Arc<F> = Arc::clone(&fetcher);
+      async move { resolve_did_web(fetcher.as_ref(), did_web).await }
+    };
+    self.attach_handler(DIDWeb::METHOD.to_string(), handler)
+  }
 }
 
 #[cfg(feature = "iota")]
 mod iota_handler {
+  use crate::DIDDocumentMetadata;
+  use crate::Error;
   use crate::ErrorCause;
 
   use super::Resolver;
+  use super::StateIndexFetcher;
   use identity_document::document::CoreDocument;
+  use identity_iota_core::block::output::AliasId;
   use identity_iota_core::IotaDID;
   use identity_iota_core::IotaDocument;
+  use identity_iota_core::IotaDocumentMetadata;
   use identity_iota_core::IotaIdentityClientExt;
   use std::collections::HashMap;
   use std::sync::Arc;
 
+  /// Fetches the state index of the Alias Output of an IOTA DID using the client selected by `select_client`.
+  fn state_index_fetcher<CLI, C>(
+    clients: Arc<C>,
+    select_client: for<'c> fn(&'c C, &IotaDID) -> Result<&'c CLI, Error>,
+  ) -> StateIndexFetcher
+  where
+    CLI: IotaIdentityClientExt + Send + Sync + 'static,
+    C: Send + Sync + 'static,
+  {
+    Box::new(move |did: &str| {
+      let clients: Arc<C> = clients.clone();
+      let did = IotaDID::parse(did).map_err(|err| Error::new(ErrorCause::DIDParsingError { source: err.into() }));
+      Box::pin(async move {
+        let did: IotaDID = did?;
+        let client: &CLI = select_client(&clients, &did)?;
+        // Only the state index is needed, so the network of the DID is not validated and the DID Document is not
+        // unpacked as during a resolution.
+        client
+          .get_alias_output(AliasId::from(&did))
+          .await
+          .map(|(_, alias_output)| alias_output.state_index())
+          .map_err(|err| Error::new(ErrorCause::HandlerError { source: Box::new(err) }))
+      })
+    })
+  }
+
+  /// Resolves the DID Document of `did` together with its [`DIDDocumentMetadata`] using `client`.
+  async fn resolve_with_metadata<CLI>(
+    client: &CLI,
+    did: &IotaDID,
+  ) -> identity_iota_core::Result<(IotaDocument, DIDDocumentMetadata)>
+  where
+    CLI: IotaIdentityClientExt + Send + Sync,
+  {
+    client.resolve_did(did).await.map(with_metadata)
+  }
+
+  /// Returns the resolved `document` together with its [`DIDDocumentMetadata`].
+  ///
+  /// The governor and state controller addresses and the controlling DID are added as method specific metadata
+  /// properties. The version id is the state index of the Alias Output containing the document, which is only fetched
+  /// by [`Resolver::resolve_with_metadata`].
+  fn with_metadata(document: IotaDocument) -> (IotaDocument, DIDDocumentMetadata) {
+    let iota_metadata: &IotaDocumentMetadata = &document.metadata;
+    let mut metadata: DIDDocumentMetadata = DIDDocumentMetadata::new();
+    metadata.created = iota_metadata.created;
+    metadata.updated = iota_metadata.updated;
+    metadata.deactivated = iota_metadata.deactivated;
+    metadata.properties = iota_metadata.properties().clone();
+    if let Some(ref address) = iota_metadata.governor_address {
+      metadata
+        .properties
+        .insert("governorAddress".to_owned(), address.clone().into());
+    }
+    if let Some(ref address) = iota_metadata.state_controller_address {
+      metadata
+        .properties
+        .insert("stateControllerAddress".to_owned(), address.clone().into());
+    }
+    if let Some(ref did) = iota_metadata.controlling_did {
+      metadata
+        .properties
+        .insert("controllingDid".to_owned(), did.to_string().into());
+    }
+    (document, metadata)
+  }
+
   impl<DOC> Resolver<DOC>
   where
     DOC: From<IotaDocument> + AsRef<CoreDocument> + 'static,
   {
     /// Convenience method for attaching a new handler responsible for resolving IOTA DIDs.
     ///
+    /// The handler supplies the [`DIDDocumentMetadata`] of the resolved documents, see
+    /// [`resolve_with_metadata`](Self::resolve_with_metadata).
+    ///
     /// See also [`attach_handler`](Self::attach_handler).
     pub fn attach_iota_handler<CLI>(&mut self, client: CLI)
     where
       CLI: IotaIdentityClientExt + Send + Sync + 'static,
     {
       let arc_client: Arc<CLI> = Arc::new(client);
+      let fetcher: StateIndexFetcher = state_index_fetcher(arc_client.clone(), |client: &CLI, _| Ok(client));
 
       let handler = move |did: IotaDID| {
         let future_client = arc_client.clone();
-        async move { future_client.resolve_did(&did).await }
+        async move { resolve_with_metadata(future_client.as_ref(), &did).await }
       };
 
-      self.attach_handler(IotaDID::METHOD.to_owned(), handler);
+      self.attach_handler_with_metadata(IotaDID::METHOD.to_owned(), handler);
+      self.state_index_fetchers.insert(IotaDID::METHOD.to_owned(), fetcher);
     }
 
     /// Convenience method for attaching multiple handlers responsible for resolving IOTA DIDs
@@ -328,6 +647,14 @@ mod iota_handler {
       I: IntoIterator<Item = (&'static str, CLI)>,
     {
       let arc_clients = Arc::new(clients.into_iter().collect::<HashMap<&'static str, CLI>>());
+      let fetcher: StateIndexFetcher = state_index_fetcher(
+        arc_clients.clone(),
+        |clients: &HashMap<&'static str, CLI>, did: &IotaDID| {
+          clients
+            .get(did.network_str())
+            .ok_or_else(|| Error::new(ErrorCause::UnsupportedNetwork(did.network_str().to_string())))
+        },
+      );
 
       let handler = move |did: IotaDID| {
         let future_client = arc_clients.clone();
@@ -339,21 +666,21 @@ mod iota_handler {
               .ok_or(crate::Error::new(ErrorCause::UnsupportedNetwork(
                 did_network.to_string(),
               )))?;
-          client
-            .resolve_did(&did)
+          resolve_with_metadata(client, &did)
             .await
             .map_err(|err| crate::Error::new(ErrorCause::HandlerError { source: Box::new(err) }))
         }
       };
 
-      self.attach_handler(IotaDID::METHOD.to_owned(), handler);
+      self.attach_handler_with_metadata(IotaDID::METHOD.to_owned(), handler);
+      self.state_index_fetchers.insert(IotaDID::METHOD.to_owned(), fetcher);
     }
   }
 }
 
 impl<CMD, DOC> Default for Resolver<DOC, CMD>
 where
-  CMD: for<'r> Command<'r, Result<DOC>>,
+  CMD: for<'r> Command<'r, Result<Resolution<DOC>>>,
   DOC: AsRef<CoreDocument>,
 {
   fn default() -> Self {
@@ -363,16 +690,33 @@ where
 
 impl<CMD, DOC> std::fmt::Debug for Resolver<DOC, CMD>
 where
-  CMD: for<'r> Command<'r, Result<DOC>>,
+  CMD: for<'r> Command<'r, Result<Resolution<DOC>>>,
   DOC: AsRef<CoreDocument>,
 {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     f.debug_struct("Resolver")
       .field("command_map", &self.command_map)
+      .field("cache", &self.cache)
       .finish()
   }
 }
 
+#[cfg(feature = "status-list-2021")]
+#[async_trait::async_trait]
+impl<DOC> identity_credential::validator::IssuerResolver for Resolver<DOC, SendSyncCommand<DOC>>
+where
+  DOC: AsRef<CoreDocument> + Clone + Send + Sync + 'static,
+{
+  type Document = DOC;
+
+  async fn resolve_issuer(
+    &self,
+    issuer: &identity_did::CoreDID,
+  ) -> std::result::Result<DOC, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    self.resolve(issuer).await.map_err(Into::into)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use identity_iota_core::block::output::AliasId;
@@ -442,4 +786,60 @@ mod tests {
     let doc = resolver.resolve(&did_jwk).await.unwrap();
     assert_eq!(doc.id(), did_jwk.as_ref());
   }
+
+  #[cfg(feature = "did-key")]
+  #[tokio::test]
+  async fn test_did_key_resolution() {
+    let mut resolver = Resolver::<CoreDocument>::new();
+    resolver.attach_did_key_handler();
+
+    let did_key = "did:key:zDnaerDaTF5BXEavCrfRZEk316dpbLsfPDZ3WJ5hRTPFU2169"
+      .parse::<DIDKey>()
+      .unwrap();
+
+    let doc = resolver.resolve(&did_key).await.unwrap();
+    assert_eq!(doc.id(), did_key.as_ref());
+    assert_eq!(doc.methods(None).len(), 1);
+    assert!(doc.key_agreement().is_empty());
+  }
+
+  #[cfg(feature = "did-key")]
+  #[tokio::test]
+  async fn test_did_peer_resolution() {
+    let mut resolver = Resolver::<CoreDocument>::new();
+    resolver.attach_did_peer_handler();
+
+    let did_peer = "did:peer:0z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+      .parse::<DIDPeer>()
+      .unwrap();
+    let doc = resolver.resolve(&did_peer).await.unwrap();
+    assert_eq!(doc.id(), did_peer.as_ref());
+    assert_eq!(doc.key_agreement().len(), 1);
+
+    let did_peer = "did:peer:2.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V"
+      .parse::<DIDPeer>()
+      .unwrap();
+    let doc = resolver.resolve(&did_peer).await.unwrap();
+    assert_eq!(doc.id(), did_peer.as_ref());
+    assert_eq!(doc.authentication().len(), 1);
+  }
+
+  #[cfg(all(feature = "status-list-2021", feature = "did-key"))]
+  #[tokio::test]
+  async fn test_issuer_resolution() {
+    use identity_credential::validator::IssuerResolver;
+    use identity_did::CoreDID;
+
+    let mut resolver = Resolver::<CoreDocument>::new();
+    resolver.attach_did_key_handler();
+
+    let issuer: CoreDID = "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"
+      .parse()
+      .unwrap();
+    let doc = resolver.resolve_issuer(&issuer).await.unwrap();
+    assert_eq!(doc.id(), &issuer);
+
+    let unsupported: CoreDID = "did:example:123".parse().unwrap();
+    assert!(resolver.resolve_issuer(&unsupported).await.is_err());
+  }
 }
@@ -0,0 +1,322 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use identity_core::common::Duration;
+use identity_did::CoreDID;
+use identity_did::DID;
+use identity_document::document::CoreDocument;
+use identity_document::document::DocumentBuilder;
+
+use crate::ErrorCause;
+use crate::Resolver;
+use crate::ResolverCacheOptions;
+use crate::SingleThreadedResolver;
+
+fn core_document(did: CoreDID) -> CoreDocument {
+  DocumentBuilder::default().id(did).build().unwrap()
+}
+
+fn did(method_id: &str) -> CoreDID {
+  CoreDID::parse(format!("did:foo:{method_id}")).unwrap()
+}
+
+/// Creates a resolver for the "foo" method whose handler counts its calls and fails to find DIDs with method id
+/// "missing" and "broken".
+fn counting_resolver(options: ResolverCacheOptions) -> (Resolver, Arc<AtomicUsize>) {
+  let counter: Arc<AtomicUsize> = Arc::new(AtomicUsize::new(0));
+  let handler_counter: Arc<AtomicUsize> = counter.clone();
+  let mut resolver: Resolver = Resolver::new();
+  resolver.attach_handler("foo".to_owned(), move |did: CoreDID| {
+    let counter: Arc<AtomicUsize> = handler_counter.clone();
+    async move {
+      counter.fetch_add(1, Ordering::SeqCst);
+      match did.method_id() {
+        "missing" => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "not found")),
+        "broken" => Err(std::io::Error::new(std::io::ErrorKind::Other, "connection failed")),
+        _ => Ok(core_document(did)),
+      }
+    }
+  });
+  resolver.enable_cache(options);
+
+  (resolver, counter)
+}
+
+#[tokio::test]
+async fn cached_documents_are_served_without_calling_the_handler() {
+  let (resolver, counter) = counting_resolver(ResolverCacheOptions::new());
+  let did_1: CoreDID = did("1234");
+  let did_2: CoreDID = did("5678");
+
+  assert_eq!(resolver.resolve(&did_1).await.unwrap().id(), &did_1);
+  assert_eq!(resolver.resolve(&did_1).await.unwrap().id(), &did_1);
+  assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+  let documents = resolver
+    .resolve_multiple(&[did_1.clone(), did_2.clone()])
+    .await
+    .unwrap();
+  assert_eq!(documents.len(), 2);
+  assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+  // Entries are removed explicitly by invalidation.
+  assert!(resolver.invalidate(&did_1));
+  assert!(!resolver.invalidate(&did_1));
+  resolver.resolve(&did_1).await.unwrap();
+  resolver.resolve(&did_2).await.unwrap();
+  assert_eq!(counter.load(Ordering::SeqCst), 3);
+
+  resolver.clear_cache();
+  resolver.resolve_multiple(&[did_1, did_2]).await.unwrap();
+  assert_eq!(counter.load(Ordering::SeqCst), 5);
+}
+
+#[tokio::test]
+async fn expired_entries_are_resolved_again() {
+  let (resolver, counter) = counting_resolver(ResolverCacheOptions::new().ttl(Duration::seconds(0)));
+  let did: CoreDID = did("1234");
+
+  resolver.resolve(&did).await.unwrap();
+  resolver.resolve(&did).await.unwrap();
+  assert_eq!(counter.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn oldest_entries_are_evicted() {
+  let (resolver, counter) = counting_resolver(ResolverCacheOptions::new().max_entries(2));
+  let dids: [CoreDID; 3] = [did("1"), did("2"), did("3")];
+
+  for did in dids.iter() {
+    resolver.resolve(did).await.unwrap();
+  }
+  assert_eq!(counter.load(Ordering::SeqCst), 3);
+
+  // The first entry was evicted to make room for the third one.
+  resolver.resolve(&dids[2]).await.unwrap();
+  resolver.resolve(&dids[1]).await.unwrap();
+  assert_eq!(counter.load(Ordering::SeqCst), 3);
+  resolver.resolve(&dids[0]).await.unwrap();
+  assert_eq!(counter.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn missing_dids_are_cached_if_enabled() {
+  let missing: CoreDID = did("missing");
+  let broken: CoreDID = did("broken");
+
+  let (resolver, counter) = counting_resolver(ResolverCacheOptions::new());
+  resolver.resolve(&missing).await.unwrap_err();
+  resolver.resolve(&missing).await.unwrap_err();
+  assert_eq!(counter.load(Ordering::SeqCst), 2);
+
+  let (resolver, counter) = counting_resolver(ResolverCacheOptions::new().negative_ttl(Duration::minutes(1)));
+  for _ in 0..2 {
+    let err = resolver.resolve(&missing).await.unwrap_err();
+    assert!(err.is_not_found());
+    assert!(matches!(err.error_cause(), ErrorCause::HandlerError { .. }));
+    assert_eq!(
+      std::error::Error::source(&err).unwrap().to_string(),
+      "not found".to_owned()
+    );
+  }
+  assert_eq!(counter.load(Ordering::SeqCst), 1);
+
+  // Other failures are never cached.
+  resolver.resolve(&broken).await.unwrap_err();
+  let err = resolver.resolve(&broken).await.unwrap_err();
+  assert!(!err.is_not_found());
+  assert_eq!(counter.load(Ordering::SeqCst), 3);
+
+  // Invalidation also applies to missing DIDs.
+  assert!(resolver.invalidate(&missing));
+  resolver.resolve(&missing).await.unwrap_err();
+  assert_eq!(counter.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn single_threaded_resolver_cache() {
+  let counter: Rc<Cell<usize>> = Rc::new(Cell::new(0));
+  let handler_counter: Rc<Cell<usize>> = counter.clone();
+  let mut resolver: SingleThreadedResolver = SingleThreadedResolver::new();
+  resolver.attach_handler("foo".to_owned(), move |did: CoreDID| {
+    let counter: Rc<Cell<usize>> = handler_counter.clone();
+    async move {
+      counter.set(counter.get() + 1);
+      Ok::<_, std::io::Error>(core_document(did))
+    }
+  });
+  resolver.enable_cache(ResolverCacheOptions::new());
+
+  let did: CoreDID = did("1234");
+  resolver.resolve(&did).await.unwrap();
+  resolver.resolve(&did).await.unwrap();
+  assert_eq!(counter.get(), 1);
+
+  // Disabling the cache drops all entries.
+  resolver.disable_cache();
+  resolver.resolve(&did).await.unwrap();
+  assert_eq!(counter.get(), 2);
+}
+
+#[cfg(feature = "iota")]
+mod iota {
+  use std::sync::atomic::AtomicU32;
+  use std::sync::atomic::AtomicUsize;
+  use std::sync::atomic::Ordering;
+  use std::sync::Arc;
+
+  use identity_iota_core::block::address::Address;
+  use identity_iota_core::block::address::Ed25519Address;
+  use identity_iota_core::block::output::unlock_condition::GovernorAddressUnlockCondition;
+  use identity_iota_core::block::output::unlock_condition::StateControllerAddressUnlockCondition;
+  use identity_iota_core::block::output::AliasId;
+  use identity_iota_core::block::output::AliasOutput;
+  use identity_iota_core::block::output::AliasOutputBuilder;
+  use identity_iota_core::block::output::OutputId;
+  use identity_iota_core::block::protocol::ProtocolParameters;
+  use identity_iota_core::IotaDID;
+  use identity_iota_core::IotaDocument;
+  use identity_iota_core::IotaIdentityClient;
+  use identity_iota_core::IotaIdentityClientExt;
+  use identity_iota_core::MemoryLedgerClient;
+
+  use crate::Resolver;
+  use crate::ResolverCacheOptions;
+
+  /// A client whose DID can be updated by incrementing the state index.
+  #[derive(Clone)]
+  struct MockClient {
+    document: IotaDocument,
+    state_index: Arc<AtomicU32>,
+    resolutions: Arc<AtomicUsize>,
+  }
+
+  impl MockClient {
+    fn alias_output(&self, id: AliasId) -> AliasOutput {
+      let address: Address = Address::Ed25519(Ed25519Address::new([0; 32]));
+      AliasOutputBuilder::new_with_amount(1_000_000, id)
+        .with_state_index(self.state_index.load(Ordering::SeqCst))
+        .add_unlock_condition(StateControllerAddressUnlockCondition::new(address))
+        .add_unlock_condition(GovernorAddressUnlockCondition::new(address))
+        .finish()
+        .unwrap()
+    }
+  }
+
+  #[async_trait::async_trait]
+  impl IotaIdentityClient for MockClient {
+    async fn get_alias_output(&self, id: AliasId) -> identity_iota_core::Result<(OutputId, AliasOutput)> {
+      Ok((OutputId::null(), self.alias_output(id)))
+    }
+    async fn get_protocol_parameters(&self) -> identity_iota_core::Result<ProtocolParameters> {
+      unreachable!()
+    }
+  }
+
+  #[async_trait::async_trait]
+  impl IotaIdentityClientExt for MockClient {
+    async fn resolve_did(&self, did: &IotaDID) -> identity_iota_core::Result<IotaDocument> {
+      if self.document.id() != did {
+        return Err(identity_iota_core::Error::DIDResolutionError(
+          iota_sdk::client::error::Error::NoOutput(did.to_string()),
+        ));
+      }
+      self.resolutions.fetch_add(1, Ordering::SeqCst);
+      Ok(self.document.clone())
+    }
+  }
+
+  fn mock_client() -> MockClient {
+    let did: IotaDID =
+      IotaDID::parse("did:iota:0x0101010101010101010101010101010101010101010101010101010101010101").unwrap();
+    MockClient {
+      document: IotaDocument::new_with_id(did),
+      state_index: Arc::new(AtomicU32::new(0)),
+      resolutions: Arc::new(AtomicUsize::new(0)),
+    }
+  }
+
+  #[tokio::test]
+  async fn updated_iota_documents_are_detected() {
+    let client: MockClient = mock_client();
+    let did: IotaDID = client.document.id().clone();
+    let mut resolver = Resolver::<IotaDocument>::new();
+    resolver.attach_iota_handler(client.clone());
+    resolver.enable_cache(ResolverCacheOptions::new().check_iota_state_index(true));
+
+    resolver.resolve(&did).await.unwrap();
+    resolver.resolve(&did).await.unwrap();
+    assert_eq!(client.resolutions.load(Ordering::SeqCst), 1);
+
+    // The DID was updated.
+    client.state_index.fetch_add(1, Ordering::SeqCst);
+    resolver.resolve(&did).await.unwrap();
+    resolver.resolve(&did).await.unwrap();
+    assert_eq!(client.resolutions.load(Ordering::SeqCst), 2);
+  }
+
+  #[tokio::test]
+  async fn state_index_is_only_checked_if_enabled() {
+    let client: MockClient = mock_client();
+    let did: IotaDID = client.document.id().clone();
+    let mut resolver = Resolver::<IotaDocument>::new();
+    resolver.attach_multiple_iota_handlers([("iota", client.clone())]);
+    resolver.enable_cache(ResolverCacheOptions::new());
+
+    resolver.resolve(&did).await.unwrap();
+    client.state_index.fetch_add(1, Ordering::SeqCst);
+    resolver.resolve(&did).await.unwrap();
+    assert_eq!(client.resolutions.load(Ordering::SeqCst), 1);
+  }
+
+  #[tokio::test]
+  async fn missing_iota_dids_are_cached() {
+    let client: MockClient = mock_client();
+    let did: IotaDID =
+      IotaDID::parse("did:iota:0x0202020202020202020202020202020202020202020202020202020202020202").unwrap();
+    let mut resolver = Resolver::<IotaDocument>::new();
+    resolver.attach_iota_handler(client);
+    resolver.enable_cache(ResolverCacheOptions::new().negative_ttl(identity_core::common::Duration::minutes(1)));
+
+    let err = resolver.resolve(&did).await.unwrap_err();
+    assert!(err.is_not_found());
+    assert!(resolver.invalidate(&did));
+  }
+
+  #[tokio::test]
+  async fn memory_ledger_updates_are_detected() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let controller: Address = Address::Ed25519(Ed25519Address::new([1; 32]));
+    let alias_output: AliasOutput = client
+      .new_did_output(
+        controller,
+        IotaDocument::new(&client.network_name().await.unwrap()),
+        None,
+      )
+      .await
+      .unwrap();
+    let mut document: IotaDocument = client.publish_did_output(&controller, alias_output).unwrap();
+
+    let mut resolver = Resolver::<IotaDocument>::new();
+    resolver.attach_iota_handler(client.clone());
+    resolver.enable_cache(ResolverCacheOptions::new().check_iota_state_index(true));
+    assert_eq!(resolver.resolve(document.id()).await.unwrap(), document);
+
+    document
+      .properties_mut_unchecked()
+      .insert("foo".to_owned(), "bar".into());
+    let alias_output: AliasOutput =
+      AliasOutputBuilder::from(&client.update_did_output(document.clone()).await.unwrap())
+        .with_minimum_storage_deposit(client.get_rent_structure().await.unwrap())
+        .finish()
+        .unwrap();
+    let updated: IotaDocument = client.publish_did_output(&controller, alias_output).unwrap();
+    assert_eq!(resolver.resolve(document.id()).await.unwrap(), updated);
+  }
+}
@@ -0,0 +1,115 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use identity_core::common::Url;
+use identity_core::convert::ToJson;
+use identity_did::CoreDID;
+use identity_did::DIDWeb;
+use identity_document::document::CoreDocument;
+use identity_document::document::DocumentBuilder;
+
+use crate::DIDWebError;
+use crate::DIDWebFetcher;
+use crate::ErrorCause;
+use crate::ResolutionErrorCode;
+use crate::ResolutionOptions;
+use crate::Resolver;
+use crate::SingleThreadedResolver;
+
+/// Serves the resources of a map, or fails with [`std::io::ErrorKind::NotFound`].
+#[derive(Default)]
+struct StubFetcher(HashMap<String, Vec<u8>>);
+
+impl StubFetcher {
+  fn with_document(mut self, url: &str, document: &CoreDocument) -> Self {
+    self.0.insert(url.to_owned(), document.to_json_vec().unwrap());
+    self
+  }
+}
+
+#[async_trait::async_trait]
+impl DIDWebFetcher for StubFetcher {
+  async fn fetch(&self, url: &Url) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    self
+      .0
+      .get(url.as_str())
+      .cloned()
+      .ok_or_else(|| Box::new(std::io::Error::new(std::io::ErrorKind::NotFound, url.to_string())) as _)
+  }
+}
+
+fn document(did: &str) -> CoreDocument {
+  DocumentBuilder::default()
+    .id(CoreDID::parse(did).unwrap())
+    .build()
+    .unwrap()
+}
+
+fn fetcher() -> StubFetcher {
+  let mut fetcher = StubFetcher::default()
+    .with_document(
+      "https://example.com/.well-known/did.json",
+      &document("did:web:example.com"),
+    )
+    .with_document(
+      "https://example.com:3000/user/alice/did.json",
+      &document("did:web:example.com%3A3000:user:alice"),
+    )
+    .with_document(
+      "https://example.com/user/mallory/did.json",
+      &document("did:web:example.com:user:alice"),
+    );
+  fetcher.0.insert(
+    "https://example.com/user/broken/did.json".to_owned(),
+    b"not a document".to_vec(),
+  );
+  fetcher
+}
+
+#[tokio::test]
+async fn resolves_did_web() {
+  let mut resolver: Resolver = Resolver::new();
+  resolver.attach_did_web_handler(fetcher());
+  let mut single_threaded_resolver: SingleThreadedResolver = SingleThreadedResolver::new();
+  single_threaded_resolver.attach_did_web_handler(fetcher());
+
+  for did in ["did:web:example.com", "did:web:example.com%3A3000:user:alice"] {
+    let did: DIDWeb = DIDWeb::parse(did).unwrap();
+    assert_eq!(resolver.resolve(&did).await.unwrap().id(), did.as_ref());
+    assert_eq!(single_threaded_resolver.resolve(&did).await.unwrap().id(), did.as_ref());
+  }
+}
+
+#[tokio::test]
+async fn rejects_invalid_documents() {
+  let mut resolver: Resolver = Resolver::new();
+  resolver.attach_did_web_handler(fetcher());
+
+  let handler_error = |error: crate::Error| match error.into_error_cause() {
+    ErrorCause::HandlerError { source } => *source.downcast::<DIDWebError>().unwrap(),
+    other => panic!("unexpected error {other}"),
+  };
+
+  // The document of another DID is served.
+  let did: DIDWeb = DIDWeb::parse("did:web:example.com:user:mallory").unwrap();
+  assert!(matches!(
+    handler_error(resolver.resolve(&did).await.unwrap_err()),
+    DIDWebError::IdMismatch(id) if id == "did:web:example.com:user:alice"
+  ));
+
+  let did: DIDWeb = DIDWeb::parse("did:web:example.com:user:broken").unwrap();
+  assert!(matches!(
+    handler_error(resolver.resolve(&did).await.unwrap_err()),
+    DIDWebError::InvalidDocument(_)
+  ));
+
+  let did: DIDWeb = DIDWeb::parse("did:web:example.com:user:bob").unwrap();
+  assert!(matches!(
+    handler_error(resolver.resolve(&did).await.unwrap_err()),
+    DIDWebError::FetchError(_)
+  ));
+  let result = resolver.resolve_with_metadata(&did, &ResolutionOptions::new()).await;
+  assert_eq!(result.error(), Some(ResolutionErrorCode::NotFound));
+}
@@ -0,0 +1,253 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+use identity_core::convert::ToJson;
+use identity_did::CoreDID;
+use identity_did::DID;
+use identity_document::document::CoreDocument;
+use identity_document::document::DocumentBuilder;
+
+use crate::DIDDocumentMetadata;
+use crate::ResolutionErrorCode;
+use crate::ResolutionOptions;
+use crate::ResolutionResult;
+use crate::Resolver;
+use crate::ResolverCacheOptions;
+use crate::SingleThreadedResolver;
+use crate::DID_JSON_LD_MEDIA_TYPE;
+use crate::DID_JSON_MEDIA_TYPE;
+
+fn core_document(did: CoreDID) -> CoreDocument {
+  DocumentBuilder::default().id(did).build().unwrap()
+}
+
+fn created() -> Timestamp {
+  Timestamp::parse("2024-01-01T00:00:00Z").unwrap()
+}
+
+/// Resolves DIDs of the "foo" method with metadata, failing to find DIDs with method id "missing" and "broken".
+async fn metadata_handler(did: CoreDID) -> Result<(CoreDocument, DIDDocumentMetadata), std::io::Error> {
+  match did.method_id() {
+    "missing" => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "not found")),
+    "broken" => Err(std::io::Error::new(std::io::ErrorKind::Other, "connection failed")),
+    _ => {
+      let mut metadata: DIDDocumentMetadata = DIDDocumentMetadata::new();
+      metadata.created = Some(created());
+      metadata.version_id = Some("1".to_owned());
+      metadata.properties.insert("foo".to_owned(), "bar".into());
+      Ok((core_document(did), metadata))
+    }
+  }
+}
+
+fn resolver() -> Resolver {
+  let mut resolver: Resolver = Resolver::new();
+  resolver.attach_handler_with_metadata("foo".to_owned(), metadata_handler);
+  resolver.attach_did_jwk_handler();
+  resolver
+}
+
+#[tokio::test]
+async fn handler_supplied_metadata_is_returned() {
+  let did: CoreDID = CoreDID::parse("did:foo:1234").unwrap();
+  let mut resolver: Resolver = resolver();
+
+  for _ in 0..2 {
+    let result: ResolutionResult<CoreDocument> = resolver.resolve_with_metadata(&did, &ResolutionOptions::new()).await;
+    assert_eq!(result.error(), None);
+    assert_eq!(result.did_document.as_ref().unwrap().id(), &did);
+    assert_eq!(
+      result.did_resolution_metadata.content_type.as_deref(),
+      Some(DID_JSON_MEDIA_TYPE)
+    );
+    assert_eq!(result.did_document_metadata.created, Some(created()));
+    assert_eq!(result.did_document_metadata.version_id.as_deref(), Some("1"));
+    assert_eq!(
+      result.did_document_metadata.properties.get("foo").unwrap().as_str(),
+      Some("bar")
+    );
+
+    // The metadata is also served from the cache.
+    resolver.enable_cache(ResolverCacheOptions::new());
+  }
+
+  // `resolve` only returns the document.
+  assert_eq!(resolver.resolve(&did).await.unwrap().id(), &did);
+
+  let result: ResolutionResult<CoreDocument> = resolver
+    .resolve_with_metadata(&did, &ResolutionOptions::new().accept(DID_JSON_LD_MEDIA_TYPE))
+    .await;
+  assert_eq!(
+    result.did_resolution_metadata.content_type.as_deref(),
+    Some(DID_JSON_LD_MEDIA_TYPE)
+  );
+}
+
+#[tokio::test]
+async fn handlers_without_metadata_return_empty_metadata() {
+  let did: CoreDID = CoreDID::parse("did:foo:1234").unwrap();
+  let mut resolver: SingleThreadedResolver = SingleThreadedResolver::new();
+  resolver.attach_handler("foo".to_owned(), |did: CoreDID| async move {
+    Ok::<_, std::io::Error>(core_document(did))
+  });
+
+  let result: ResolutionResult<CoreDocument> = resolver.resolve_with_metadata(&did, &ResolutionOptions::new()).await;
+  assert!(result.did_document.is_some());
+  assert_eq!(result.did_document_metadata, DIDDocumentMetadata::new());
+
+  resolver.attach_handler_with_metadata("foo".to_owned(), metadata_handler);
+  let result: ResolutionResult<CoreDocument> = resolver.resolve_with_metadata(&did, &ResolutionOptions::new()).await;
+  assert_eq!(result.did_document_metadata.created, Some(created()));
+}
+
+#[tokio::test]
+async fn failed_resolutions_report_error_codes() {
+  let resolver: Resolver = resolver();
+  let cases: [(&str, ResolutionOptions, ResolutionErrorCode); 7] = [
+    (
+      "did:foo:missing",
+      ResolutionOptions::new(),
+      ResolutionErrorCode::NotFound,
+    ),
+    (
+      "did:foo:broken",
+      ResolutionOptions::new(),
+      ResolutionErrorCode::InternalError,
+    ),
+    (
+      "did:bar:1234",
+      ResolutionOptions::new(),
+      ResolutionErrorCode::MethodNotSupported,
+    ),
+    (
+      "did:jwk:1234",
+      ResolutionOptions::new(),
+      ResolutionErrorCode::InvalidDid,
+    ),
+    (
+      "did:foo:1234",
+      ResolutionOptions::new().accept("application/did+cbor"),
+      ResolutionErrorCode::RepresentationNotSupported,
+    ),
+    // Errors of the DID take precedence over an unsupported representation.
+    (
+      "did:jwk:1234",
+      ResolutionOptions::new().accept("application/did+cbor"),
+      ResolutionErrorCode::InvalidDid,
+    ),
+    (
+      "did:foo:missing",
+      ResolutionOptions::new().accept("application/did+cbor"),
+      ResolutionErrorCode::NotFound,
+    ),
+  ];
+
+  for (did, options, code) in cases {
+    let did: CoreDID = CoreDID::parse(did).unwrap();
+    let result: ResolutionResult<CoreDocument> = resolver.resolve_with_metadata(&did, &options).await;
+    assert_eq!(result.error(), Some(code), "{did}");
+    assert!(result.did_document.is_none());
+    assert!(result.did_resolution_metadata.content_type.is_none());
+    assert!(result.did_resolution_metadata.error_message.is_some());
+    assert_eq!(result.did_document_metadata, DIDDocumentMetadata::new());
+  }
+}
+
+#[tokio::test]
+async fn resolution_result_serialization() {
+  let resolver: Resolver = resolver();
+
+  let did: CoreDID = CoreDID::parse("did:foo:1234").unwrap();
+  let json: serde_json::Value = resolver
+    .resolve_with_metadata(&did, &ResolutionOptions::new())
+    .await
+    .to_json_value()
+    .unwrap();
+  assert_eq!(
+    json["didResolutionMetadata"],
+    serde_json::json!({ "contentType": "application/did+json" })
+  );
+  assert_eq!(json["didDocument"]["id"], "did:foo:1234");
+  assert_eq!(
+    json["didDocumentMetadata"],
+    serde_json::json!({ "created": "2024-01-01T00:00:00Z", "versionId": "1", "foo": "bar" })
+  );
+
+  let did: CoreDID = CoreDID::parse("did:foo:missing").unwrap();
+  let json: serde_json::Value = resolver
+    .resolve_with_metadata(&did, &ResolutionOptions::new())
+    .await
+    .to_json_value()
+    .unwrap();
+  assert_eq!(json["didResolutionMetadata"]["error"], "notFound");
+  assert_eq!(json["didDocument"], serde_json::Value::Null);
+  assert_eq!(json["didDocumentMetadata"], serde_json::json!({}));
+}
+
+#[cfg(feature = "iota")]
+mod iota {
+  use identity_iota_core::block::address::Address;
+  use identity_iota_core::block::address::Ed25519Address;
+  use identity_iota_core::block::output::AliasOutput;
+  use identity_iota_core::block::output::AliasOutputBuilder;
+  use identity_iota_core::IotaDocument;
+  use identity_iota_core::IotaIdentityClientExt;
+  use identity_iota_core::MemoryLedgerClient;
+
+  use crate::ResolutionOptions;
+  use crate::ResolutionResult;
+  use crate::Resolver;
+
+  #[tokio::test]
+  async fn iota_handlers_supply_metadata() {
+    let client: MemoryLedgerClient = MemoryLedgerClient::new();
+    let controller: Address = Address::Ed25519(Ed25519Address::new([1; 32]));
+    let alias_output: AliasOutput = client
+      .new_did_output(
+        controller,
+        IotaDocument::new(&client.network_name().await.unwrap()),
+        None,
+      )
+      .await
+      .unwrap();
+    let mut document: IotaDocument = client.publish_did_output(&controller, alias_output).unwrap();
+
+    // Update the DID once, so its state index is 1.
+    document
+      .metadata
+      .properties_mut()
+      .insert("custom".to_owned(), 42.into());
+    let alias_output: AliasOutput =
+      AliasOutputBuilder::from(&client.update_did_output(document.clone()).await.unwrap())
+        .with_minimum_storage_deposit(client.get_rent_structure().await.unwrap())
+        .finish()
+        .unwrap();
+    let document: IotaDocument = client.publish_did_output(&controller, alias_output).unwrap();
+
+    let mut resolver: Resolver<IotaDocument> = Resolver::new();
+    resolver.attach_iota_handler(client.clone());
+    let mut multiple_resolver: Resolver<IotaDocument> = Resolver::new();
+    multiple_resolver.attach_multiple_iota_handlers([("iota", client)]);
+
+    for resolver in [resolver, multiple_resolver] {
+      let result: ResolutionResult<IotaDocument> = resolver
+        .resolve_with_metadata(document.id(), &ResolutionOptions::new())
+        .await;
+      let metadata = result.did_document_metadata;
+      assert_eq!(metadata.version_id.as_deref(), Some("1"));
+      assert_eq!(metadata.created, document.metadata.created);
+      assert_eq!(metadata.updated, document.metadata.updated);
+      assert_eq!(
+        metadata.properties.get("governorAddress").unwrap().as_str(),
+        document.metadata.governor_address.as_deref()
+      );
+      assert_eq!(
+        metadata.properties.get("stateControllerAddress").unwrap().as_str(),
+        document.metadata.state_controller_address.as_deref()
+      );
+      assert!(!metadata.properties.contains_key("controllingDid"));
+      assert_eq!(metadata.properties.get("custom").unwrap().as_u64(), Some(42));
+    }
+  }
+}
@@ -2,5 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::resolver::*;
+mod cache;
+mod did_web;
+mod metadata;
 mod resolution;
 mod send_sync;
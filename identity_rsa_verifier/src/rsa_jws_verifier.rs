@@ -0,0 +1,139 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::ops::Deref;
+
+use identity_verification::jwk::Jwk;
+use identity_verification::jwk::JwkParamsRsa;
+use identity_verification::jws::JwsAlgorithm;
+use identity_verification::jws::JwsVerifier;
+use identity_verification::jws::SignatureVerificationError;
+use identity_verification::jws::SignatureVerificationErrorKind;
+use identity_verification::jws::VerificationInput;
+use identity_verification::jwu;
+use rsa::traits::PublicKeyParts;
+use rsa::BigUint;
+use rsa::RsaPublicKey;
+use sha2::Sha256;
+use sha2::Sha384;
+use sha2::Sha512;
+
+/// An implementor of [`JwsVerifier`](identity_verification::jws::JwsVerifier)
+/// that can handle the RSA algorithms.
+///
+/// The following algorithms are supported:
+///
+/// - [`JwsAlgorithm::RS256`](identity_verification::jws::JwsAlgorithm::RS256).
+/// - [`JwsAlgorithm::RS384`](identity_verification::jws::JwsAlgorithm::RS384).
+/// - [`JwsAlgorithm::RS512`](identity_verification::jws::JwsAlgorithm::RS512).
+/// - [`JwsAlgorithm::PS256`](identity_verification::jws::JwsAlgorithm::PS256).
+/// - [`JwsAlgorithm::PS384`](identity_verification::jws::JwsAlgorithm::PS384).
+/// - [`JwsAlgorithm::PS512`](identity_verification::jws::JwsAlgorithm::PS512).
+///
+/// Keys with a modulus shorter than [`RsaJwsVerifier::MIN_KEY_SIZE`] bits are rejected.
+///
+/// Only public key operations are performed, which are not affected by the timing side channel
+/// of [RUSTSEC-2023-0071](https://rustsec.org/advisories/RUSTSEC-2023-0071) in the underlying `rsa` crate.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct RsaJwsVerifier {}
+
+impl RsaJwsVerifier {
+  /// The minimum size in bits of the RSA modulus, as mandated by
+  /// [RFC 7518](https://datatracker.ietf.org/doc/html/rfc7518#section-3.3).
+  pub const MIN_KEY_SIZE: usize = 2048;
+
+  /// Verify a JWS signature secured with one of the RSA algorithms, given by `input.alg`.
+  ///
+  /// This function is useful when one is building a
+  /// [`JwsVerifier`](identity_verification::jws::JwsVerifier) that
+  /// handles the RSA algorithms in the same manner as the [`RsaJwsVerifier`]
+  /// hence extending its capabilities.
+  pub fn verify(input: &VerificationInput, public_key: &Jwk) -> Result<(), SignatureVerificationError> {
+    let public_key: RsaPublicKey = expand_rsa_public_key(public_key)?;
+    let signing_input: &[u8] = &input.signing_input;
+    let signature: &[u8] = input.decoded_signature.deref();
+
+    match input.alg {
+      JwsAlgorithm::RS256 => verify_pkcs1v15::<Sha256>(public_key, signing_input, signature),
+      JwsAlgorithm::RS384 => verify_pkcs1v15::<Sha384>(public_key, signing_input, signature),
+      JwsAlgorithm::RS512 => verify_pkcs1v15::<Sha512>(public_key, signing_input, signature),
+      JwsAlgorithm::PS256 => verify_pss::<Sha256>(public_key, signing_input, signature),
+      JwsAlgorithm::PS384 => verify_pss::<Sha384>(public_key, signing_input, signature),
+      JwsAlgorithm::PS512 => verify_pss::<Sha512>(public_key, signing_input, signature),
+      _ => Err(SignatureVerificationErrorKind::UnsupportedAlg.into()),
+    }
+  }
+}
+
+impl JwsVerifier for RsaJwsVerifier {
+  fn verify(&self, input: VerificationInput, public_key: &Jwk) -> Result<(), SignatureVerificationError> {
+    Self::verify(&input, public_key)
+  }
+}
+
+/// Obtains an [`RsaPublicKey`] from the modulus and exponent of an RSA [`Jwk`].
+fn expand_rsa_public_key(jwk: &Jwk) -> Result<RsaPublicKey, SignatureVerificationError> {
+  let params: &JwkParamsRsa = jwk
+    .try_rsa_params()
+    .map_err(|_| SignatureVerificationErrorKind::UnsupportedKeyType)?;
+
+  let decode = |value: &str| -> Result<BigUint, SignatureVerificationError> {
+    jwu::decode_b64(value)
+      .map(|bytes| BigUint::from_bytes_be(&bytes))
+      .map_err(|err| {
+        SignatureVerificationError::new(SignatureVerificationErrorKind::KeyDecodingFailure).with_source(err)
+      })
+  };
+
+  let public_key: RsaPublicKey = RsaPublicKey::new(decode(&params.n)?, decode(&params.e)?).map_err(|err| {
+    SignatureVerificationError::new(SignatureVerificationErrorKind::KeyDecodingFailure).with_source(err)
+  })?;
+
+  if public_key.size() * 8 < RsaJwsVerifier::MIN_KEY_SIZE {
+    return Err(
+      SignatureVerificationError::new(SignatureVerificationErrorKind::UnsupportedKeyParams).with_custom_message(
+        format!("RSA keys must be at least {} bits long", RsaJwsVerifier::MIN_KEY_SIZE),
+      ),
+    );
+  }
+
+  Ok(public_key)
+}
+
+/// Verifies an RSASSA-PKCS1-v1_5 signature using the hash function `D`.
+fn verify_pkcs1v15<D>(
+  public_key: RsaPublicKey,
+  signing_input: &[u8],
+  signature: &[u8],
+) -> Result<(), SignatureVerificationError>
+where
+  D: sha2::Digest + rsa::pkcs8::AssociatedOid,
+{
+  let verifying_key: rsa::pkcs1v15::VerifyingKey<D> = rsa::pkcs1v15::VerifyingKey::new(public_key);
+  let signature: rsa::pkcs1v15::Signature = rsa::pkcs1v15::Signature::try_from(signature).map_err(|err| {
+    SignatureVerificationError::new(SignatureVerificationErrorKind::InvalidSignature).with_source(err)
+  })?;
+
+  signature::Verifier::verify(&verifying_key, signing_input, &signature)
+    .map_err(|err| SignatureVerificationError::new(SignatureVerificationErrorKind::InvalidSignature).with_source(err))
+}
+
+/// Verifies an RSASSA-PSS signature using the hash function `D` for both the message digest and MGF1,
+/// with a salt as long as the digest.
+fn verify_pss<D>(
+  public_key: RsaPublicKey,
+  signing_input: &[u8],
+  signature: &[u8],
+) -> Result<(), SignatureVerificationError>
+where
+  D: sha2::Digest + sha2::digest::FixedOutputReset,
+{
+  let verifying_key: rsa::pss::VerifyingKey<D> = rsa::pss::VerifyingKey::new(public_key);
+  let signature: rsa::pss::Signature = rsa::pss::Signature::try_from(signature).map_err(|err| {
+    SignatureVerificationError::new(SignatureVerificationErrorKind::InvalidSignature).with_source(err)
+  })?;
+
+  signature::Verifier::verify(&verifying_key, signing_input, &signature)
+    .map_err(|err| SignatureVerificationError::new(SignatureVerificationErrorKind::InvalidSignature).with_source(err))
+}
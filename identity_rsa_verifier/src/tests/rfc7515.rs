@@ -0,0 +1,41 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_verification::jwk::Jwk;
+use identity_verification::jws;
+use identity_verification::jws::JwsHeader;
+use rsa::pkcs1v15::SigningKey;
+use rsa::RsaPrivateKey;
+use sha2::Sha256;
+
+use super::utils::expand_rsa_jwk;
+use super::utils::RFC7515_TEST_VECTORS;
+use crate::RsaJwsVerifier;
+
+#[test]
+fn test_rs256_rfc7515() {
+  for tv in RFC7515_TEST_VECTORS {
+    let header: JwsHeader = serde_json::from_str(tv.header).unwrap();
+    let private_key: Jwk = serde_json::from_str(tv.private_key).unwrap();
+    let public_key: Jwk = private_key.to_public().unwrap();
+
+    // RSASSA-PKCS1-v1_5 signatures are deterministic.
+    if tv.deterministic {
+      let encoder: jws::CompactJwsEncoder<'_> = jws::CompactJwsEncoder::new(tv.claims, &header).unwrap();
+      let sk: RsaPrivateKey = expand_rsa_jwk(&private_key);
+      let signing_key: SigningKey<Sha256> = SigningKey::new(sk);
+      let signature =
+        signature::SignatureEncoding::to_vec(&signature::Signer::sign(&signing_key, encoder.signing_input()));
+      let encoded: String = encoder.into_jws(&signature);
+      assert_eq!(encoded.as_bytes(), tv.encoded);
+    }
+
+    let token = jws::Decoder::new()
+      .decode_compact_serialization(tv.encoded, None)
+      .and_then(|decoded| decoded.verify(&RsaJwsVerifier::default(), &public_key))
+      .unwrap();
+
+    assert_eq!(token.protected, header);
+    assert_eq!(token.claims, tv.claims);
+  }
+}
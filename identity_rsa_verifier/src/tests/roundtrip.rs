@@ -0,0 +1,155 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_verification::jwk::Jwk;
+use identity_verification::jws::JwsAlgorithm;
+use identity_verification::jws::JwsVerifier;
+use identity_verification::jws::SignatureVerificationErrorKind;
+use identity_verification::jws::VerificationInput;
+use identity_verification::jwu;
+use sha2::Sha256;
+use sha2::Sha384;
+use sha2::Sha512;
+use signature::RandomizedSigner;
+use signature::SignatureEncoding;
+use signature::Signer;
+
+use super::utils::expand_rsa_jwk;
+use super::utils::RFC7515_TEST_VECTORS;
+use crate::RsaJwsVerifier;
+
+const SIGNING_INPUT: &[u8] = b"eyJhbGciOiJQUzI1NiJ9.dGVzdA";
+
+fn rfc7515_private_key() -> Jwk {
+  let tv = &RFC7515_TEST_VECTORS[0];
+  serde_json::from_str(tv.private_key).unwrap()
+}
+
+/// Produces a signature over [`SIGNING_INPUT`] with the given private key.
+type SignFn = fn(&Jwk) -> Vec<u8>;
+
+/// The supported algorithms paired with the function producing a signature for them.
+fn signers() -> [(JwsAlgorithm, SignFn); 6] {
+  [
+    (JwsAlgorithm::RS256, sign_rs256),
+    (JwsAlgorithm::RS384, |jwk| {
+      rsa::pkcs1v15::SigningKey::<Sha384>::new(expand_rsa_jwk(jwk))
+        .sign(SIGNING_INPUT)
+        .to_vec()
+    }),
+    (JwsAlgorithm::RS512, |jwk| {
+      rsa::pkcs1v15::SigningKey::<Sha512>::new(expand_rsa_jwk(jwk))
+        .sign(SIGNING_INPUT)
+        .to_vec()
+    }),
+    (JwsAlgorithm::PS256, |jwk| {
+      rsa::pss::SigningKey::<Sha256>::new(expand_rsa_jwk(jwk))
+        .sign_with_rng(&mut rand::thread_rng(), SIGNING_INPUT)
+        .to_vec()
+    }),
+    (JwsAlgorithm::PS384, |jwk| {
+      rsa::pss::SigningKey::<Sha384>::new(expand_rsa_jwk(jwk))
+        .sign_with_rng(&mut rand::thread_rng(), SIGNING_INPUT)
+        .to_vec()
+    }),
+    (JwsAlgorithm::PS512, |jwk| {
+      rsa::pss::SigningKey::<Sha512>::new(expand_rsa_jwk(jwk))
+        .sign_with_rng(&mut rand::thread_rng(), SIGNING_INPUT)
+        .to_vec()
+    }),
+  ]
+}
+
+fn sign_rs256(private_key: &Jwk) -> Vec<u8> {
+  rsa::pkcs1v15::SigningKey::<Sha256>::new(expand_rsa_jwk(private_key))
+    .sign(SIGNING_INPUT)
+    .to_vec()
+}
+
+fn verification_input(alg: &JwsAlgorithm, signature: Vec<u8>) -> VerificationInput {
+  VerificationInput {
+    alg: alg.to_owned(),
+    signing_input: SIGNING_INPUT.into(),
+    decoded_signature: signature.into(),
+  }
+}
+
+#[test]
+fn sign_and_verify_all_algorithms() {
+  let private_key: Jwk = rfc7515_private_key();
+  let public_key: Jwk = private_key.to_public().unwrap();
+  let verifier = RsaJwsVerifier::default();
+
+  for (alg, sign) in signers() {
+    let signature: Vec<u8> = sign(&private_key);
+    verifier
+      .verify(verification_input(&alg, signature.clone()), &public_key)
+      .unwrap();
+
+    // INVALID: tampered signature.
+    let mut tampered: Vec<u8> = signature.clone();
+    tampered[0] ^= 1;
+    let err = verifier
+      .verify(verification_input(&alg, tampered), &public_key)
+      .unwrap_err();
+    assert!(matches!(err.kind(), SignatureVerificationErrorKind::InvalidSignature));
+
+    // INVALID: the signature was produced with a different algorithm.
+    let other_alg: JwsAlgorithm = if alg.name().starts_with("RS") {
+      JwsAlgorithm::PS256
+    } else {
+      JwsAlgorithm::RS256
+    };
+    let err = verifier
+      .verify(verification_input(&other_alg, signature), &public_key)
+      .unwrap_err();
+    assert!(matches!(err.kind(), SignatureVerificationErrorKind::InvalidSignature));
+  }
+}
+
+#[test]
+fn unsupported_alg_and_key_type_are_rejected() {
+  let private_key: Jwk = rfc7515_private_key();
+  let public_key: Jwk = private_key.to_public().unwrap();
+  let signature: Vec<u8> = sign_rs256(&private_key);
+  let verifier = RsaJwsVerifier::default();
+
+  let err = verifier
+    .verify(verification_input(&JwsAlgorithm::ES256, signature.clone()), &public_key)
+    .unwrap_err();
+  assert!(matches!(err.kind(), SignatureVerificationErrorKind::UnsupportedAlg));
+
+  let ec_key: Jwk = serde_json::from_str(
+    r#"{
+      "kty": "EC",
+      "crv": "P-256",
+      "x": "f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU",
+      "y": "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0"
+    }"#,
+  )
+  .unwrap();
+  let err = verifier
+    .verify(verification_input(&JwsAlgorithm::RS256, signature), &ec_key)
+    .unwrap_err();
+  assert!(matches!(err.kind(), SignatureVerificationErrorKind::UnsupportedKeyType));
+}
+
+#[test]
+fn short_keys_are_rejected() {
+  let mut public_key: Jwk = rfc7515_private_key().to_public().unwrap();
+  let params = public_key.try_rsa_params_mut().unwrap();
+  // Keep the upper 1024 bits of the modulus and make it odd again.
+  let mut modulus: Vec<u8> = jwu::decode_b64(&params.n).unwrap();
+  modulus.truncate(128);
+  modulus[127] |= 1;
+  params.n = jwu::encode_b64(&modulus);
+
+  let signature: Vec<u8> = vec![0; 128];
+  let err = RsaJwsVerifier::default()
+    .verify(verification_input(&JwsAlgorithm::RS256, signature), &public_key)
+    .unwrap_err();
+  assert!(matches!(
+    err.kind(),
+    SignatureVerificationErrorKind::UnsupportedKeyParams
+  ));
+}
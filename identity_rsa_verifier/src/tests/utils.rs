@@ -0,0 +1,38 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_verification::jwk::Jwk;
+use identity_verification::jwk::JwkParamsRsa;
+use identity_verification::jwu;
+use rsa::BigUint;
+use rsa::RsaPrivateKey;
+
+pub(crate) struct TestVector {
+  pub(crate) deterministic: bool,
+  pub(crate) header: &'static str,
+  pub(crate) claims: &'static [u8],
+  pub(crate) encoded: &'static [u8],
+  pub(crate) private_key: &'static str,
+}
+
+/// The RSA test vectors of https://datatracker.ietf.org/doc/html/rfc7515#appendix-A.
+pub(crate) static RFC7515_TEST_VECTORS: &[TestVector] = &include!("fixtures/rfc7515.rs");
+
+fn decode(value: &str) -> BigUint {
+  BigUint::from_bytes_be(&jwu::decode_b64(value).unwrap())
+}
+
+pub(crate) fn expand_rsa_jwk(jwk: &Jwk) -> RsaPrivateKey {
+  let params: &JwkParamsRsa = jwk.try_rsa_params().unwrap();
+  let primes: Vec<BigUint> = vec![
+    decode(params.p.as_deref().unwrap()),
+    decode(params.q.as_deref().unwrap()),
+  ];
+  RsaPrivateKey::from_components(
+    decode(&params.n),
+    decode(&params.e),
+    decode(params.d.as_deref().unwrap()),
+    primes,
+  )
+  .unwrap()
+}
@@ -0,0 +1,147 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use argon2::Algorithm;
+use argon2::Argon2;
+use argon2::Params;
+use argon2::Version;
+use chacha20poly1305::aead::Aead;
+use chacha20poly1305::KeyInit;
+use chacha20poly1305::XChaCha20Poly1305;
+use chacha20poly1305::XNonce;
+use identity_verification::jose::jwu;
+use rand::RngCore;
+use serde::Deserialize;
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use super::error::FileStorageError;
+
+const KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 24;
+
+/// The version of the format of the encrypted file.
+const FILE_FORMAT_VERSION: u8 = 1;
+
+/// Parameters of the Argon2id key derivation used to derive the encryption key from a passphrase.
+///
+/// The parameters are stored alongside the encrypted content, so they only apply to newly created files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KdfParams {
+  /// Memory size in KiB.
+  pub memory_cost: u32,
+  /// Number of iterations.
+  pub iterations: u32,
+  /// Degree of parallelism.
+  pub parallelism: u32,
+}
+
+impl KdfParams {
+  /// Creates new [`KdfParams`] from the given memory size in KiB, number of iterations and degree of parallelism.
+  pub const fn new(memory_cost: u32, iterations: u32, parallelism: u32) -> Self {
+    Self {
+      memory_cost,
+      iterations,
+      parallelism,
+    }
+  }
+}
+
+impl Default for KdfParams {
+  /// Returns the parameters recommended by the Argon2 reference implementation.
+  fn default() -> Self {
+    Self::new(Params::DEFAULT_M_COST, Params::DEFAULT_T_COST, Params::DEFAULT_P_COST)
+  }
+}
+
+/// An encryption key derived from a passphrase, together with the parameters used to derive it.
+pub(crate) struct DerivedKey {
+  key: Zeroizing<[u8; KEY_LENGTH]>,
+  salt: Vec<u8>,
+  params: KdfParams,
+}
+
+impl DerivedKey {
+  /// Derives a key from `passphrase` using a fresh random salt.
+  pub(crate) fn generate(passphrase: &[u8], params: KdfParams) -> Result<Self, FileStorageError> {
+    let mut salt: Vec<u8> = vec![0; SALT_LENGTH];
+    rand::rngs::OsRng.fill_bytes(&mut salt);
+    Self::derive(passphrase, salt, params)
+  }
+
+  /// Derives a key from `passphrase` using the salt and parameters of an existing file.
+  pub(crate) fn derive_for(passphrase: &[u8], file: &EncryptedFile) -> Result<Self, FileStorageError> {
+    let salt: Vec<u8> = jwu::decode_b64(&file.salt).map_err(|_| FileStorageError::MalformedFile)?;
+    Self::derive(passphrase, salt, file.kdf)
+  }
+
+  fn derive(passphrase: &[u8], salt: Vec<u8>, params: KdfParams) -> Result<Self, FileStorageError> {
+    let argon2_params: Params = Params::new(
+      params.memory_cost,
+      params.iterations,
+      params.parallelism,
+      Some(KEY_LENGTH),
+    )
+    .map_err(|err| FileStorageError::KeyDerivation(err.to_string()))?;
+
+    let mut key: Zeroizing<[u8; KEY_LENGTH]> = Zeroizing::new([0; KEY_LENGTH]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params)
+      .hash_password_into(passphrase, &salt, key.as_mut())
+      .map_err(|err| FileStorageError::KeyDerivation(err.to_string()))?;
+
+    Ok(Self { key, salt, params })
+  }
+
+  /// Encrypts `plaintext` with a fresh random nonce.
+  pub(crate) fn encrypt(&self, plaintext: &[u8]) -> Result<EncryptedFile, FileStorageError> {
+    let mut nonce: [u8; NONCE_LENGTH] = [0; NONCE_LENGTH];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+    let ciphertext: Vec<u8> = XChaCha20Poly1305::new(self.key.as_ref().into())
+      .encrypt(XNonce::from_slice(&nonce), plaintext)
+      .map_err(|_| FileStorageError::Encryption)?;
+
+    Ok(EncryptedFile {
+      version: FILE_FORMAT_VERSION,
+      kdf: self.params,
+      salt: jwu::encode_b64(&self.salt),
+      nonce: jwu::encode_b64(nonce),
+      ciphertext: jwu::encode_b64(ciphertext),
+    })
+  }
+
+  /// Decrypts the content of `file`.
+  ///
+  /// Fails with [`FileStorageError::Unauthenticated`] if `file` was not encrypted with this key.
+  pub(crate) fn decrypt(&self, file: &EncryptedFile) -> Result<Zeroizing<Vec<u8>>, FileStorageError> {
+    if file.version != FILE_FORMAT_VERSION {
+      return Err(FileStorageError::UnsupportedVersion(file.version));
+    }
+    if file.kdf != self.params || jwu::encode_b64(&self.salt) != file.salt {
+      return Err(FileStorageError::Unauthenticated);
+    }
+
+    let nonce: Vec<u8> = jwu::decode_b64(&file.nonce).map_err(|_| FileStorageError::MalformedFile)?;
+    if nonce.len() != NONCE_LENGTH {
+      return Err(FileStorageError::MalformedFile);
+    }
+    let ciphertext: Vec<u8> = jwu::decode_b64(&file.ciphertext).map_err(|_| FileStorageError::MalformedFile)?;
+
+    XChaCha20Poly1305::new(self.key.as_ref().into())
+      .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+      .map(Zeroizing::new)
+      .map_err(|_| FileStorageError::Unauthenticated)
+  }
+}
+
+/// The on-disk representation of a [`FileStorage`](super::FileStorage).
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct EncryptedFile {
+  version: u8,
+  kdf: KdfParams,
+  salt: String,
+  nonce: String,
+  ciphertext: String,
+}
@@ -0,0 +1,54 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::key_id_storage::KeyIdStorageError;
+use crate::key_id_storage::KeyIdStorageErrorKind;
+use crate::key_storage::KeyStorageError;
+use crate::key_storage::KeyStorageErrorKind;
+
+/// Failures of the file operations backing a [`FileStorage`](super::FileStorage).
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum FileStorageError {
+  #[error("file storage I/O operation failed")]
+  Io(#[from] std::io::Error),
+  #[error("unable to (de)serialize the file storage content")]
+  Serialization(#[from] serde_json::Error),
+  #[error("the file storage is malformed")]
+  MalformedFile,
+  #[error("unsupported file storage version {0}")]
+  UnsupportedVersion(u8),
+  #[error("key derivation failed: {0}")]
+  KeyDerivation(String),
+  #[error("encryption of the file storage content failed")]
+  Encryption,
+  #[error("unable to decrypt the file storage, the passphrase may be wrong")]
+  Unauthenticated,
+}
+
+impl From<FileStorageError> for KeyStorageError {
+  fn from(err: FileStorageError) -> Self {
+    let kind: KeyStorageErrorKind = match err {
+      FileStorageError::Io(_) => KeyStorageErrorKind::RetryableIOFailure,
+      FileStorageError::Serialization(_)
+      | FileStorageError::MalformedFile
+      | FileStorageError::UnsupportedVersion(_) => KeyStorageErrorKind::SerializationError,
+      FileStorageError::Unauthenticated => KeyStorageErrorKind::Unauthenticated,
+      FileStorageError::KeyDerivation(_) | FileStorageError::Encryption => KeyStorageErrorKind::Unspecified,
+    };
+    KeyStorageError::new(kind).with_source(err)
+  }
+}
+
+impl From<FileStorageError> for KeyIdStorageError {
+  fn from(err: FileStorageError) -> Self {
+    let kind: KeyIdStorageErrorKind = match err {
+      FileStorageError::Io(_) => KeyIdStorageErrorKind::RetryableIOFailure,
+      FileStorageError::Serialization(_)
+      | FileStorageError::MalformedFile
+      | FileStorageError::UnsupportedVersion(_) => KeyIdStorageErrorKind::SerializationError,
+      FileStorageError::Unauthenticated => KeyIdStorageErrorKind::Unauthenticated,
+      FileStorageError::KeyDerivation(_) | FileStorageError::Encryption => KeyIdStorageErrorKind::Unspecified,
+    };
+    KeyIdStorageError::new(kind).with_source(err)
+  }
+}
@@ -0,0 +1,384 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use core::fmt::Debug;
+use core::fmt::Formatter;
+use std::collections::HashMap;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use fs4::FileExt;
+use identity_verification::jose::jwe::JweAlgorithm;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jws::JwsAlgorithm;
+use identity_verification::jose::jwu;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tokio::sync::MutexGuard;
+use zeroize::Zeroizing;
+
+use super::encryption::DerivedKey;
+use super::encryption::EncryptedFile;
+use super::encryption::KdfParams;
+use super::error::FileStorageError;
+use super::keys;
+use crate::key_id_storage::KeyIdStorage;
+use crate::key_id_storage::KeyIdStorageError;
+use crate::key_id_storage::KeyIdStorageErrorKind;
+use crate::key_id_storage::KeyIdStorageResult;
+use crate::key_id_storage::MethodDigest;
+use crate::key_storage::JwkGenOutput;
+use crate::key_storage::JwkStorage;
+use crate::key_storage::JwkStorageKeyAgreementExt;
+use crate::key_storage::KeyId;
+use crate::key_storage::KeyStorageError;
+use crate::key_storage::KeyStorageErrorKind;
+use crate::key_storage::KeyStorageResult;
+use crate::key_storage::KeyType;
+
+/// The decrypted content of a [`FileStorage`].
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileStorageContent {
+  /// The map from key ids to private JWKs.
+  jwks: HashMap<KeyId, Jwk>,
+  /// The map from base64url encoded packed method digests to key ids.
+  key_ids: HashMap<String, KeyId>,
+}
+
+/// A [`JwkStorage`] and [`KeyIdStorage`] implementation that persists keys in a single file, encrypted with a key
+/// derived from a passphrase.
+///
+/// The file is encrypted with XChaCha20-Poly1305 using a key derived with Argon2id. Every modification rewrites the
+/// whole file through a temporary file that atomically replaces the previous version, so the file is never left in
+/// a partially written state. Operations are serialized through an advisory lock on a `.lock` file next to the
+/// storage file, which makes it safe to use the same file from multiple processes at once.
+///
+/// Supports Ed25519 keys for EdDSA, P-256, secp256k1 and P-384 keys for ECDSA, as well as X25519 and P-256 keys for
+/// ECDH-ES key agreement, see the key type constants of [`FileStorage`]. Waiting for the lock does not block the
+/// executor, but requires the storage to be used within a Tokio runtime.
+///
+/// Cloning a [`FileStorage`] is cheap and the clones share the same underlying file, which allows using one
+/// instance as both the key and the key id storage of a [`Storage`](crate::storage::Storage).
+#[derive(Clone)]
+pub struct FileStorage {
+  inner: Arc<FileStorageInner>,
+}
+
+struct FileStorageInner {
+  path: PathBuf,
+  lock_path: PathBuf,
+  key: DerivedKey,
+  /// Serializes the operations of this process, the file lock serializes the operations across processes.
+  mutex: Mutex<()>,
+}
+
+impl FileStorage {
+  const ED25519_KEY_TYPE_STR: &'static str = "Ed25519";
+  /// The Ed25519 key type.
+  pub const ED25519_KEY_TYPE: KeyType = KeyType::from_static_str(Self::ED25519_KEY_TYPE_STR);
+
+  const X25519_KEY_TYPE_STR: &'static str = "X25519";
+  /// The X25519 key type, used for key agreement.
+  pub const X25519_KEY_TYPE: KeyType = KeyType::from_static_str(Self::X25519_KEY_TYPE_STR);
+
+  const P256_KEY_TYPE_STR: &'static str = "P-256";
+  /// The P-256 key type, used for signing and key agreement.
+  pub const P256_KEY_TYPE: KeyType = KeyType::from_static_str(Self::P256_KEY_TYPE_STR);
+
+  const SECP256K1_KEY_TYPE_STR: &'static str = "secp256k1";
+  /// The secp256k1 key type.
+  pub const SECP256K1_KEY_TYPE: KeyType = KeyType::from_static_str(Self::SECP256K1_KEY_TYPE_STR);
+
+  const P384_KEY_TYPE_STR: &'static str = "P-384";
+  /// The P-384 key type.
+  pub const P384_KEY_TYPE: KeyType = KeyType::from_static_str(Self::P384_KEY_TYPE_STR);
+
+  /// Opens the storage file at `path`, creating it if it does not exist yet.
+  ///
+  /// A newly created file is encrypted with a key derived from `passphrase` with the default [`KdfParams`].
+  /// Opening an existing file fails with
+  /// [`KeyStorageErrorKind::Unauthenticated`](crate::key_storage::KeyStorageErrorKind::Unauthenticated)
+  /// if it was encrypted with a different passphrase.
+  pub fn open(path: impl AsRef<Path>, passphrase: &str) -> KeyStorageResult<Self> {
+    Self::open_with_kdf_params(path, passphrase, KdfParams::default())
+  }
+
+  /// Opens the storage file at `path` like [`FileStorage::open`], deriving the key of a newly created file with the
+  /// given `kdf_params`.
+  ///
+  /// The parameters are stored in the file, existing files are always opened with the parameters they were
+  /// created with.
+  pub fn open_with_kdf_params(
+    path: impl AsRef<Path>,
+    passphrase: &str,
+    kdf_params: KdfParams,
+  ) -> KeyStorageResult<Self> {
+    let path: PathBuf = path.as_ref().to_path_buf();
+    let lock_path: PathBuf = sibling_path(&path, "lock");
+
+    let _lock: FileLock = FileLock::acquire(&lock_path, true).map_err(FileStorageError::from)?;
+    let key: DerivedKey = if path.exists() {
+      let file: EncryptedFile = read_encrypted_file(&path)?;
+      let key: DerivedKey = DerivedKey::derive_for(passphrase.as_bytes(), &file)?;
+      // Ensure the passphrase is correct before handing out the storage.
+      key.decrypt(&file)?;
+      key
+    } else {
+      let key: DerivedKey = DerivedKey::generate(passphrase.as_bytes(), kdf_params)?;
+      write_encrypted_file(&path, &key, &FileStorageContent::default())?;
+      key
+    };
+
+    Ok(Self {
+      inner: Arc::new(FileStorageInner {
+        path,
+        lock_path,
+        key,
+        mutex: Mutex::new(()),
+      }),
+    })
+  }
+
+  /// Returns the path of the storage file.
+  pub fn path(&self) -> &Path {
+    &self.inner.path
+  }
+
+  /// Acquires the locks required to operate on the file and returns its decrypted content.
+  ///
+  /// An `exclusive` lock must be acquired for operations that modify the content.
+  async fn lock(&self, exclusive: bool) -> Result<LockedContent<'_>, FileStorageError> {
+    let guard: MutexGuard<'_, ()> = self.inner.mutex.lock().await;
+    let lock: FileLock = FileLock::acquire_async(self.inner.lock_path.clone(), exclusive).await?;
+
+    let file: EncryptedFile = read_encrypted_file(&self.inner.path)?;
+    let plaintext: Zeroizing<Vec<u8>> = self.inner.key.decrypt(&file)?;
+    let content: FileStorageContent = serde_json::from_slice(&plaintext)?;
+
+    Ok(LockedContent {
+      content,
+      _lock: lock,
+      _guard: guard,
+    })
+  }
+
+  /// Atomically replaces the content of the storage file.
+  ///
+  /// Must only be called while holding an exclusive lock.
+  fn write(&self, content: &FileStorageContent) -> Result<(), FileStorageError> {
+    write_encrypted_file(&self.inner.path, &self.inner.key, content)
+  }
+}
+
+impl Debug for FileStorage {
+  fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+    f.debug_struct("FileStorage").field("path", &self.inner.path).finish()
+  }
+}
+
+/// The decrypted content of the storage file, valid as long as the locks are held.
+struct LockedContent<'a> {
+  content: FileStorageContent,
+  _lock: FileLock,
+  _guard: MutexGuard<'a, ()>,
+}
+
+/// An advisory lock on a file that is released when dropped.
+struct FileLock(File);
+
+impl FileLock {
+  /// Acquires the lock on a blocking thread, so that waiting for other processes does not block the executor.
+  async fn acquire_async(path: PathBuf, exclusive: bool) -> std::io::Result<Self> {
+    tokio::task::spawn_blocking(move || Self::acquire(&path, exclusive))
+      .await
+      .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?
+  }
+
+  fn acquire(path: &Path, exclusive: bool) -> std::io::Result<Self> {
+    let file: File = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .create(true)
+      .truncate(false)
+      .open(path)?;
+    if exclusive {
+      FileExt::lock_exclusive(&file)?;
+    } else {
+      FileExt::lock_shared(&file)?;
+    }
+    Ok(Self(file))
+  }
+}
+
+impl Drop for FileLock {
+  fn drop(&mut self) {
+    let _ = FileExt::unlock(&self.0);
+  }
+}
+
+/// Appends `extension` to the file name of `path`.
+fn sibling_path(path: &Path, extension: &str) -> PathBuf {
+  let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+  file_name.push(".");
+  file_name.push(extension);
+  path.with_file_name(file_name)
+}
+
+fn read_encrypted_file(path: &Path) -> Result<EncryptedFile, FileStorageError> {
+  let bytes: Vec<u8> = std::fs::read(path)?;
+  serde_json::from_slice(&bytes).map_err(|_| FileStorageError::MalformedFile)
+}
+
+/// Encrypts `content` and writes it to a temporary file, which then atomically replaces the file at `path`.
+fn write_encrypted_file(path: &Path, key: &DerivedKey, content: &FileStorageContent) -> Result<(), FileStorageError> {
+  let plaintext: Zeroizing<Vec<u8>> = Zeroizing::new(serde_json::to_vec(content)?);
+  let file: EncryptedFile = key.encrypt(&plaintext)?;
+  let bytes: Vec<u8> = serde_json::to_vec_pretty(&file)?;
+
+  let tmp_path: PathBuf = sibling_path(path, "tmp");
+  let mut options: OpenOptions = OpenOptions::new();
+  options.write(true).create(true).truncate(true);
+  #[cfg(unix)]
+  std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+  let mut tmp_file: File = options.open(&tmp_path)?;
+  tmp_file.write_all(&bytes)?;
+  tmp_file.sync_all()?;
+  drop(tmp_file);
+
+  std::fs::rename(&tmp_path, path)?;
+  // Persist the rename itself.
+  #[cfg(unix)]
+  if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+    File::open(parent)?.sync_all()?;
+  }
+
+  Ok(())
+}
+
+fn digest_key(method_digest: &MethodDigest) -> String {
+  jwu::encode_b64(method_digest.pack())
+}
+
+/// Returns the private JWK stored under `key_id`.
+fn get_jwk<'a>(content: &'a FileStorageContent, key_id: &KeyId) -> KeyStorageResult<&'a Jwk> {
+  content
+    .jwks
+    .get(key_id)
+    .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))
+}
+
+// Refer to the `JwkStorage` interface docs for high-level documentation of the individual methods.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl JwkStorage for FileStorage {
+  async fn generate(&self, key_type: KeyType, alg: JwsAlgorithm) -> KeyStorageResult<JwkGenOutput> {
+    let jwk: Jwk = keys::generate_jwk(&key_type, &alg)?;
+    let public_jwk: Jwk = jwk.to_public().expect("should only panic if kty == oct");
+    let key_id: KeyId = keys::random_key_id();
+
+    let mut locked: LockedContent<'_> = self.lock(true).await?;
+    locked.content.jwks.insert(key_id.clone(), jwk);
+    self.write(&locked.content)?;
+    Ok(JwkGenOutput::new(key_id, public_jwk))
+  }
+
+  async fn insert(&self, jwk: Jwk) -> KeyStorageResult<KeyId> {
+    keys::check_insertion(&jwk)?;
+    let key_id: KeyId = keys::random_key_id();
+
+    let mut locked: LockedContent<'_> = self.lock(true).await?;
+    locked.content.jwks.insert(key_id.clone(), jwk);
+    self.write(&locked.content)?;
+    Ok(key_id)
+  }
+
+  async fn sign(&self, key_id: &KeyId, data: &[u8], public_key: &Jwk) -> KeyStorageResult<Vec<u8>> {
+    let locked: LockedContent<'_> = self.lock(false).await?;
+    keys::sign(get_jwk(&locked.content, key_id)?, data, public_key)
+  }
+
+  async fn delete(&self, key_id: &KeyId) -> KeyStorageResult<()> {
+    let mut locked: LockedContent<'_> = self.lock(true).await?;
+    locked
+      .content
+      .jwks
+      .remove(key_id)
+      .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))?;
+    self.write(&locked.content)?;
+    Ok(())
+  }
+
+  async fn exists(&self, key_id: &KeyId) -> KeyStorageResult<bool> {
+    let locked: LockedContent<'_> = self.lock(false).await?;
+    Ok(locked.content.jwks.contains_key(key_id))
+  }
+}
+
+// Refer to the `JwkStorageKeyAgreementExt` interface docs for high-level documentation of the individual methods.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl JwkStorageKeyAgreementExt for FileStorage {
+  async fn generate_key_agreement(&self, key_type: KeyType, alg: JweAlgorithm) -> KeyStorageResult<JwkGenOutput> {
+    let jwk: Jwk = keys::generate_key_agreement_jwk(&key_type, &alg)?;
+    let public_jwk: Jwk = jwk.to_public().expect("should only panic if kty == oct");
+    let key_id: KeyId = keys::random_key_id();
+
+    let mut locked: LockedContent<'_> = self.lock(true).await?;
+    locked.content.jwks.insert(key_id.clone(), jwk);
+    self.write(&locked.content)?;
+    Ok(JwkGenOutput::new(key_id, public_jwk))
+  }
+
+  async fn key_agreement(
+    &self,
+    key_id: &KeyId,
+    public_key: &Jwk,
+    peer_public_key: &Jwk,
+  ) -> KeyStorageResult<Zeroizing<Vec<u8>>> {
+    let locked: LockedContent<'_> = self.lock(false).await?;
+    keys::key_agreement(get_jwk(&locked.content, key_id)?, public_key, peer_public_key)
+  }
+}
+
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl KeyIdStorage for FileStorage {
+  async fn insert_key_id(&self, method_digest: MethodDigest, key_id: KeyId) -> KeyIdStorageResult<()> {
+    let mut locked: LockedContent<'_> = self.lock(true).await?;
+    let key: String = digest_key(&method_digest);
+    if locked.content.key_ids.contains_key(&key) {
+      return Err(KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdAlreadyExists));
+    }
+    locked.content.key_ids.insert(key, key_id);
+    self.write(&locked.content)?;
+    Ok(())
+  }
+
+  async fn get_key_id(&self, method_digest: &MethodDigest) -> KeyIdStorageResult<KeyId> {
+    let mut locked: LockedContent<'_> = self.lock(false).await?;
+    locked
+      .content
+      .key_ids
+      .remove(&digest_key(method_digest))
+      .ok_or_else(|| KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdNotFound))
+  }
+
+  async fn delete_key_id(&self, method_digest: &MethodDigest) -> KeyIdStorageResult<()> {
+    let mut locked: LockedContent<'_> = self.lock(true).await?;
+    locked
+      .content
+      .key_ids
+      .remove(&digest_key(method_digest))
+      .ok_or_else(|| KeyIdStorageError::new(KeyIdStorageErrorKind::KeyIdNotFound))?;
+    self.write(&locked.content)?;
+    Ok(())
+  }
+}
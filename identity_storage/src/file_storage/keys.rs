@@ -0,0 +1,268 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! The key operations of a [`FileStorage`], performed on the decrypted private JWKs.
+
+use std::str::FromStr;
+
+use crypto::keys::x25519;
+use crypto::signatures::ed25519::SecretKey;
+use identity_verification::jose::jwe::diffie_hellman;
+use identity_verification::jose::jwe::JweAlgorithm;
+use identity_verification::jose::jwk::EcCurve;
+use identity_verification::jose::jwk::EcxCurve;
+use identity_verification::jose::jwk::EdCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParams;
+use identity_verification::jose::jws::JwsAlgorithm;
+use rand::distributions::DistString;
+use zeroize::Zeroizing;
+
+use super::FileStorage;
+use crate::key_storage::ecdsa::check_ec_public_key;
+use crate::key_storage::ecdsa::ecdsa_curve;
+use crate::key_storage::ecdsa::encode_ec_jwk;
+use crate::key_storage::ecdsa::sign_ecdsa;
+use crate::key_storage::ed25519::encode_jwk;
+use crate::key_storage::ed25519::expand_secret_jwk;
+use crate::key_storage::x25519::encode_x25519_jwk;
+use crate::key_storage::KeyId;
+use crate::key_storage::KeyStorageError;
+use crate::key_storage::KeyStorageErrorKind;
+use crate::key_storage::KeyStorageResult;
+use crate::key_storage::KeyType;
+
+/// The key types supported by a [`FileStorage`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum FileKeyType {
+  Ed25519,
+  X25519,
+  P256,
+  Secp256K1,
+  P384,
+}
+
+impl FileKeyType {
+  /// Returns the key type of the given `key_type`.
+  fn from_key_type(key_type: &KeyType) -> KeyStorageResult<Self> {
+    [
+      (FileStorage::ED25519_KEY_TYPE, Self::Ed25519),
+      (FileStorage::X25519_KEY_TYPE, Self::X25519),
+      (FileStorage::P256_KEY_TYPE, Self::P256),
+      (FileStorage::SECP256K1_KEY_TYPE, Self::Secp256K1),
+      (FileStorage::P384_KEY_TYPE, Self::P384),
+    ]
+    .into_iter()
+    .find_map(|(supported, file_key_type)| (supported == *key_type).then_some(file_key_type))
+    .ok_or_else(|| {
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("{key_type} is not supported"))
+    })
+  }
+
+  /// Returns the key type of the given `jwk`, derived from its curve.
+  fn from_jwk(jwk: &Jwk) -> KeyStorageResult<Self> {
+    let crv: &str = match jwk.params() {
+      JwkParams::Okp(params) => &params.crv,
+      JwkParams::Ec(params) => {
+        // Keys with invalid coordinates are not keys of the supported type.
+        if let Ok(curve @ (EcCurve::P256 | EcCurve::Secp256K1 | EcCurve::P384)) = params.try_ec_curve() {
+          check_ec_public_key(params, curve)?;
+        }
+        &params.crv
+      }
+      _ => {
+        return Err(
+          KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+            .with_custom_message(format!("Jwk `kty` {} not supported", jwk.kty())),
+        )
+      }
+    };
+    [
+      (EdCurve::Ed25519.name(), Self::Ed25519),
+      (EcxCurve::X25519.name(), Self::X25519),
+      (EcCurve::P256.name(), Self::P256),
+      (EcCurve::Secp256K1.name(), Self::Secp256K1),
+      (EcCurve::P384.name(), Self::P384),
+    ]
+    .into_iter()
+    .find_map(|(name, file_key_type)| (name == crv).then_some(file_key_type))
+    .ok_or_else(|| {
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType).with_custom_message(format!("{crv} not supported"))
+    })
+  }
+
+  /// Returns the key type whose keys produce signatures with the given `alg`.
+  fn from_jws_algorithm(alg: &JwsAlgorithm) -> Option<Self> {
+    match alg {
+      JwsAlgorithm::EdDSA => Some(Self::Ed25519),
+      _ => match ecdsa_curve(alg)? {
+        EcCurve::P256 => Some(Self::P256),
+        EcCurve::Secp256K1 => Some(Self::Secp256K1),
+        EcCurve::P384 => Some(Self::P384),
+        _ => None,
+      },
+    }
+  }
+
+  fn is_key_agreement(self) -> bool {
+    matches!(self, Self::X25519 | Self::P256)
+  }
+
+  /// Generates a new private key of this type.
+  fn generate(self) -> KeyStorageResult<Jwk> {
+    let jwk: Jwk = match self {
+      Self::Ed25519 => {
+        let private_key = SecretKey::generate()
+          .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure).with_source(err))?;
+        encode_jwk(&private_key, &private_key.public_key())
+      }
+      Self::X25519 => {
+        let private_key = x25519::SecretKey::generate()
+          .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure).with_source(err))?;
+        encode_x25519_jwk(&private_key)
+      }
+      Self::P256 => encode_ec_jwk(&p256::SecretKey::random(&mut rand::rngs::OsRng), EcCurve::P256),
+      Self::Secp256K1 => encode_ec_jwk(&k256::SecretKey::random(&mut rand::rngs::OsRng), EcCurve::Secp256K1),
+      Self::P384 => encode_ec_jwk(&p384::SecretKey::random(&mut rand::rngs::OsRng), EcCurve::P384),
+    };
+    Ok(jwk)
+  }
+}
+
+/// Generates a random alphanumeric key id of len 32.
+pub(super) fn random_key_id() -> KeyId {
+  KeyId::new(rand::distributions::Alphanumeric.sample_string(&mut rand::thread_rng(), 32))
+}
+
+/// Generates a new private JWK of `key_type` for signing with `alg`.
+pub(super) fn generate_jwk(key_type: &KeyType, alg: &JwsAlgorithm) -> KeyStorageResult<Jwk> {
+  let key_type: FileKeyType = FileKeyType::from_key_type(key_type)?;
+  check_key_alg_compatibility(key_type, alg)?;
+
+  let mut jwk: Jwk = key_type.generate()?;
+  jwk.set_alg(alg.name());
+  jwk.set_kid(jwk.thumbprint_sha256_b64());
+  Ok(jwk)
+}
+
+/// Generates a new private JWK of `key_type` for key agreement with `alg`.
+pub(super) fn generate_key_agreement_jwk(key_type: &KeyType, alg: &JweAlgorithm) -> KeyStorageResult<Jwk> {
+  let key_type: FileKeyType = FileKeyType::from_key_type(key_type)?;
+  check_key_agreement_alg_compatibility(key_type, alg)?;
+
+  let mut jwk: Jwk = key_type.generate()?;
+  jwk.set_alg(alg.name());
+  jwk.set_kid(jwk.thumbprint_sha256_b64());
+  Ok(jwk)
+}
+
+/// Checks that `jwk` is a private key of a supported type with a compatible `alg`.
+pub(super) fn check_insertion(jwk: &Jwk) -> KeyStorageResult<()> {
+  let key_type: FileKeyType = FileKeyType::from_jwk(jwk)?;
+
+  if !jwk.is_private() {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("expected a Jwk with all private key components set"),
+    );
+  }
+
+  let alg: &str = jwk.alg().ok_or_else(|| {
+    KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
+      .with_custom_message("expected a Jwk with an `alg` parameter")
+  })?;
+  // Key agreement keys carry a JWE algorithm, all other keys a JWS algorithm.
+  if let Ok(alg) = JweAlgorithm::from_str(alg) {
+    check_key_agreement_alg_compatibility(key_type, &alg)
+  } else {
+    let alg: JwsAlgorithm = JwsAlgorithm::from_str(alg)
+      .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm).with_source(err))?;
+    check_key_alg_compatibility(key_type, &alg)
+  }
+}
+
+/// Signs `data` with the private `jwk`, according to the `alg` of its `public_key`.
+pub(super) fn sign(jwk: &Jwk, data: &[u8], public_key: &Jwk) -> KeyStorageResult<Vec<u8>> {
+  let alg: JwsAlgorithm = public_key
+    .alg()
+    .and_then(|alg| JwsAlgorithm::from_str(alg).ok())
+    .ok_or(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)?;
+  let key_type: FileKeyType = FileKeyType::from_jws_algorithm(&alg).ok_or_else(|| {
+    KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
+      .with_custom_message(format!("{alg} is not supported"))
+  })?;
+
+  if FileKeyType::from_jwk(public_key).ok() != Some(key_type) {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message(format!(
+        "expected a Jwk with a {key_type:?} key in order to sign with {alg}"
+      )),
+    );
+  }
+  check_private_key_type(jwk, key_type)?;
+
+  match key_type {
+    FileKeyType::Ed25519 => Ok(expand_secret_jwk(jwk)?.sign(data).to_bytes().to_vec()),
+    _ => sign_ecdsa(&alg, jwk, data),
+  }
+}
+
+/// Computes the shared secret of the private `jwk` matching `public_key` and the `peer_public_key`.
+pub(super) fn key_agreement(
+  jwk: &Jwk,
+  public_key: &Jwk,
+  peer_public_key: &Jwk,
+) -> KeyStorageResult<Zeroizing<Vec<u8>>> {
+  let key_type: FileKeyType = FileKeyType::from_jwk(public_key)?;
+  if !key_type.is_key_agreement() {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("{key_type:?} cannot be used for key agreement")),
+    );
+  }
+  check_private_key_type(jwk, key_type)?;
+
+  diffie_hellman(jwk, peer_public_key).map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::KeyAlgorithmMismatch)
+      .with_custom_message("key agreement failed")
+      .with_source(err)
+  })
+}
+
+/// Checks that the stored private `jwk` is of the `expected` key type.
+fn check_private_key_type(jwk: &Jwk, expected: FileKeyType) -> KeyStorageResult<()> {
+  if FileKeyType::from_jwk(jwk).ok() != Some(expected) {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::KeyAlgorithmMismatch)
+        .with_custom_message(format!("the stored key is not a {expected:?} key")),
+    );
+  }
+  Ok(())
+}
+
+/// Check that the key type can be used with the algorithm.
+fn check_key_alg_compatibility(key_type: FileKeyType, alg: &JwsAlgorithm) -> KeyStorageResult<()> {
+  if FileKeyType::from_jws_algorithm(alg) != Some(key_type) {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::KeyAlgorithmMismatch)
+        .with_custom_message(format!("cannot use key type `{key_type:?}` with algorithm `{alg}`")),
+    );
+  }
+  Ok(())
+}
+
+/// Check that the key type can be used with the key agreement algorithm.
+fn check_key_agreement_alg_compatibility(key_type: FileKeyType, alg: &JweAlgorithm) -> KeyStorageResult<()> {
+  let is_ecdh: bool = matches!(
+    alg,
+    JweAlgorithm::ECDH_ES | JweAlgorithm::ECDH_ES_A128KW | JweAlgorithm::ECDH_ES_A192KW | JweAlgorithm::ECDH_ES_A256KW
+  );
+  if !is_ecdh || !key_type.is_key_agreement() {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::KeyAlgorithmMismatch)
+        .with_custom_message(format!("cannot use key type `{key_type:?}` with algorithm `{alg}`")),
+    );
+  }
+  Ok(())
+}
@@ -0,0 +1,20 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+//! An encrypted, file-backed storage for keys and key ids.
+//!
+//! This module provides [`FileStorage`], which implements both
+//! [`JwkStorage`](crate::key_storage::JwkStorage) and [`KeyIdStorage`](crate::key_id_storage::KeyIdStorage)
+//! by persisting their content in a single file, encrypted with a key derived from a passphrase.
+
+mod encryption;
+mod error;
+#[allow(clippy::module_inception)]
+mod file_storage;
+mod keys;
+
+#[cfg(test)]
+mod tests;
+
+pub use encryption::KdfParams;
+pub use file_storage::*;
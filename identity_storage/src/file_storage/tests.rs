@@ -0,0 +1,221 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::path::PathBuf;
+
+use identity_verification::jose::jwe::JweAlgorithm;
+use identity_verification::jose::jws::JwsAlgorithm;
+use rand::distributions::DistString;
+
+use super::FileStorage;
+use super::KdfParams;
+use crate::key_id_storage::KeyIdStorage;
+use crate::key_id_storage::KeyIdStorageErrorKind;
+use crate::key_id_storage::MethodDigest;
+use crate::key_storage::tests::utils::test_generate_and_sign;
+use crate::key_storage::tests::utils::test_incompatible_key_alg;
+use crate::key_storage::tests::utils::test_incompatible_key_type;
+use crate::key_storage::tests::utils::test_insertion;
+use crate::key_storage::tests::utils::test_key_exists;
+use crate::key_storage::JwkGenOutput;
+use crate::key_storage::JwkStorage;
+use crate::key_storage::JwkStorageKeyAgreementExt;
+use crate::key_storage::KeyId;
+use crate::key_storage::KeyStorageErrorKind;
+use crate::storage::tests::test_utils::create_verification_method;
+
+const PASSPHRASE: &str = "secure_passphrase";
+// Keep the key derivation cheap in tests.
+const KDF_PARAMS: KdfParams = KdfParams::new(8, 1, 1);
+
+fn create_temp_file() -> PathBuf {
+  let mut file = std::env::temp_dir();
+  file.push("test_file_storage");
+  std::fs::create_dir_all(&file).unwrap();
+  file.push(rand::distributions::Alphanumeric.sample_string(&mut rand::thread_rng(), 32));
+  file.set_extension("json");
+  file
+}
+
+fn open_storage(path: &PathBuf) -> FileStorage {
+  FileStorage::open_with_kdf_params(path, PASSPHRASE, KDF_PARAMS).unwrap()
+}
+
+#[tokio::test]
+async fn jwk_storage_operations() {
+  test_insertion(open_storage(&create_temp_file())).await;
+  test_incompatible_key_alg(open_storage(&create_temp_file())).await;
+  test_incompatible_key_type(open_storage(&create_temp_file())).await;
+  test_generate_and_sign(open_storage(&create_temp_file())).await;
+  test_key_exists(open_storage(&create_temp_file())).await;
+}
+
+#[tokio::test]
+async fn key_id_storage_operations() {
+  let storage: FileStorage = open_storage(&create_temp_file());
+  let method_digest: MethodDigest = MethodDigest::new(&create_verification_method()).unwrap();
+  let key_id: KeyId = KeyId::new("keyid");
+
+  storage
+    .insert_key_id(method_digest.clone(), key_id.clone())
+    .await
+    .unwrap();
+  let err = storage
+    .insert_key_id(method_digest.clone(), key_id.clone())
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyIdStorageErrorKind::KeyIdAlreadyExists));
+  assert_eq!(storage.get_key_id(&method_digest).await.unwrap(), key_id);
+
+  storage.delete_key_id(&method_digest).await.unwrap();
+  let err = storage.get_key_id(&method_digest).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyIdStorageErrorKind::KeyIdNotFound));
+  let err = storage.delete_key_id(&method_digest).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyIdStorageErrorKind::KeyIdNotFound));
+}
+
+#[tokio::test]
+async fn key_agreement() {
+  let storage: FileStorage = open_storage(&create_temp_file());
+
+  let JwkGenOutput { key_id, jwk } = storage
+    .generate_key_agreement(FileStorage::X25519_KEY_TYPE, JweAlgorithm::ECDH_ES)
+    .await
+    .unwrap();
+  let peer: JwkGenOutput = storage
+    .generate_key_agreement(FileStorage::X25519_KEY_TYPE, JweAlgorithm::ECDH_ES)
+    .await
+    .unwrap();
+  let shared_secret = storage.key_agreement(&key_id, &jwk, &peer.jwk).await.unwrap();
+  let peer_shared_secret = storage.key_agreement(&peer.key_id, &peer.jwk, &jwk).await.unwrap();
+  assert_eq!(shared_secret, peer_shared_secret);
+
+  // INVALID: the stored key does not match the curve of the given public key.
+  let p256: JwkGenOutput = storage
+    .generate_key_agreement(FileStorage::P256_KEY_TYPE, JweAlgorithm::ECDH_ES)
+    .await
+    .unwrap();
+  let err = storage.key_agreement(&key_id, &p256.jwk, &p256.jwk).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+  // INVALID: signing keys cannot be used for key agreement.
+  let err = storage
+    .generate_key_agreement(FileStorage::ED25519_KEY_TYPE, JweAlgorithm::ECDH_ES)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+}
+
+#[tokio::test]
+async fn waiting_for_the_lock_does_not_block_the_executor() {
+  let path: PathBuf = create_temp_file();
+  let storage: FileStorage = open_storage(&path);
+
+  // Hold the lock like another process would.
+  let lock_path: PathBuf = path.with_file_name(format!("{}.lock", path.file_name().unwrap().to_string_lossy()));
+  let lock_file = std::fs::File::open(&lock_path).unwrap();
+  fs4::FileExt::lock_exclusive(&lock_file).unwrap();
+
+  // The current thread runtime keeps making progress while the storage waits for the lock.
+  let key_id: KeyId = KeyId::new("key");
+  let exists = storage.exists(&key_id);
+  let unlock = async {
+    tokio::task::yield_now().await;
+    fs4::FileExt::unlock(&lock_file).unwrap();
+  };
+  let (exists, ()) = tokio::join!(exists, unlock);
+  assert!(!exists.unwrap());
+}
+
+#[tokio::test]
+async fn content_is_persisted_encrypted() {
+  let path: PathBuf = create_temp_file();
+  let storage: FileStorage = open_storage(&path);
+
+  let JwkGenOutput { key_id, jwk, .. } = storage
+    .generate(FileStorage::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+    .await
+    .unwrap();
+  let method_digest: MethodDigest = MethodDigest::new(&create_verification_method()).unwrap();
+  storage
+    .insert_key_id(method_digest.clone(), key_id.clone())
+    .await
+    .unwrap();
+  drop(storage);
+
+  // Neither the key id nor the public key are stored in plaintext.
+  let file_content: String = std::fs::read_to_string(&path).unwrap();
+  assert!(!file_content.contains(key_id.as_str()));
+  assert!(!file_content.contains(&jwk.try_okp_params().unwrap().x));
+
+  let storage: FileStorage = open_storage(&path);
+  assert!(storage.exists(&key_id).await.unwrap());
+  assert_eq!(storage.get_key_id(&method_digest).await.unwrap(), key_id);
+  storage.sign(&key_id, b"test", &jwk).await.unwrap();
+}
+
+#[tokio::test]
+async fn wrong_passphrase_is_rejected() {
+  let path: PathBuf = create_temp_file();
+  drop(open_storage(&path));
+
+  let err = FileStorage::open_with_kdf_params(&path, "wrong_passphrase", KDF_PARAMS).unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::Unauthenticated));
+
+  // The parameters of an existing file take precedence over the given ones.
+  FileStorage::open(&path, PASSPHRASE).unwrap();
+}
+
+#[tokio::test]
+async fn failed_operations_do_not_modify_the_file() {
+  let path: PathBuf = create_temp_file();
+  let storage: FileStorage = open_storage(&path);
+  let file_content: Vec<u8> = std::fs::read(&path).unwrap();
+
+  storage
+    .generate(FileStorage::ED25519_KEY_TYPE, JwsAlgorithm::ES256)
+    .await
+    .unwrap_err();
+  storage.delete(&KeyId::new("non-existent-id")).await.unwrap_err();
+
+  assert_eq!(std::fs::read(&path).unwrap(), file_content);
+}
+
+#[test]
+fn concurrent_writers() {
+  const WRITERS: usize = 4;
+  const KEYS_PER_WRITER: usize = 5;
+
+  let path: PathBuf = create_temp_file();
+  drop(open_storage(&path));
+
+  // Every thread uses its own storage instance, like separate processes would.
+  let handles: Vec<_> = (0..WRITERS)
+    .map(|_| {
+      let path: PathBuf = path.clone();
+      std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        runtime.block_on(async move {
+          let storage: FileStorage = open_storage(&path);
+          let mut key_ids: Vec<KeyId> = Vec::new();
+          for _ in 0..KEYS_PER_WRITER {
+            let output: JwkGenOutput = storage
+              .generate(FileStorage::ED25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+              .await
+              .unwrap();
+            key_ids.push(output.key_id);
+          }
+          key_ids
+        })
+      })
+    })
+    .collect();
+  let key_ids: Vec<KeyId> = handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect();
+  assert_eq!(key_ids.len(), WRITERS * KEYS_PER_WRITER);
+
+  let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+  let storage: FileStorage = open_storage(&path);
+  for key_id in key_ids.iter() {
+    assert!(runtime.block_on(storage.exists(key_id)).unwrap());
+  }
+}
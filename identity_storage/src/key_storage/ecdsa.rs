@@ -0,0 +1,137 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_verification::jose::jwk::EcCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParamsEc;
+use identity_verification::jose::jws::JwsAlgorithm;
+use identity_verification::jose::jwu;
+use p256::elliptic_curve::sec1::FromEncodedPoint;
+use p256::elliptic_curve::sec1::ModulusSize;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::elliptic_curve::AffinePoint;
+use p256::elliptic_curve::CurveArithmetic;
+use p256::elliptic_curve::FieldBytesSize;
+use p256::elliptic_curve::PublicKey;
+use p256::elliptic_curve::SecretKey;
+use signature::Signer;
+use zeroize::Zeroizing;
+
+use crate::key_storage::KeyStorageError;
+use crate::key_storage::KeyStorageErrorKind;
+use crate::key_storage::KeyStorageResult;
+
+/// Encodes an elliptic curve private key on the given `curve` as a private JWK.
+pub(crate) fn encode_ec_jwk<C>(private_key: &SecretKey<C>, curve: EcCurve) -> Jwk
+where
+  C: CurveArithmetic,
+  AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+  FieldBytesSize<C>: ModulusSize,
+{
+  let encoded_point = private_key.public_key().to_encoded_point(false);
+  let mut params = JwkParamsEc::new();
+  params.x = jwu::encode_b64(encoded_point.x().expect("uncompressed point has an x coordinate"));
+  params.y = jwu::encode_b64(encoded_point.y().expect("uncompressed point has a y coordinate"));
+  params.d = Some(jwu::encode_b64(private_key.to_bytes()));
+  params.crv = curve.name().to_string();
+  Jwk::from_params(params)
+}
+
+/// Checks that the `x` and `y` coordinates of `params` encode a point on the given `curve`.
+pub(crate) fn check_ec_public_key(params: &JwkParamsEc, curve: EcCurve) -> KeyStorageResult<()> {
+  let is_valid: bool = match curve {
+    EcCurve::P256 => is_on_curve::<p256::NistP256>(params),
+    EcCurve::Secp256K1 => is_on_curve::<k256::Secp256k1>(params),
+    EcCurve::P384 => is_on_curve::<p384::NistP384>(params),
+    _ => false,
+  };
+  if !is_valid {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("expected a Jwk with a point on the {curve} curve")),
+    );
+  }
+  Ok(())
+}
+
+fn is_on_curve<C>(params: &JwkParamsEc) -> bool
+where
+  C: CurveArithmetic,
+  AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+  FieldBytesSize<C>: ModulusSize,
+{
+  let (Ok(x), Ok(y)) = (jwu::decode_b64(&params.x), jwu::decode_b64(&params.y)) else {
+    return false;
+  };
+  let encoded_point: Vec<u8> = [0x04].into_iter().chain(x).chain(y).collect();
+  PublicKey::<C>::from_sec1_bytes(&encoded_point).is_ok()
+}
+
+/// Decodes the private key of an elliptic curve JWK.
+pub(crate) fn expand_secret_ec_jwk<C>(jwk: &Jwk) -> KeyStorageResult<SecretKey<C>>
+where
+  C: CurveArithmetic,
+{
+  let params: &JwkParamsEc = jwk.try_ec_params().map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+      .with_custom_message("expected a Jwk with EC params")
+      .with_source(err)
+  })?;
+
+  let sk: Zeroizing<Vec<u8>> = params
+    .d
+    .as_deref()
+    .map(jwu::decode_b64)
+    .ok_or_else(|| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message("expected Jwk `d` param to be present")
+    })?
+    .map(Zeroizing::new)
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("unable to decode `d` param")
+        .with_source(err)
+    })?;
+
+  SecretKey::from_slice(&sk).map_err(|_| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+      .with_custom_message(format!("invalid private key for the {} curve", params.crv))
+  })
+}
+
+/// Returns the curve whose keys must be used to produce signatures with the given ECDSA `alg`.
+pub(crate) fn ecdsa_curve(alg: &JwsAlgorithm) -> Option<EcCurve> {
+  match alg {
+    JwsAlgorithm::ES256 => Some(EcCurve::P256),
+    JwsAlgorithm::ES256K => Some(EcCurve::Secp256K1),
+    JwsAlgorithm::ES384 => Some(EcCurve::P384),
+    _ => None,
+  }
+}
+
+/// Signs `data` with the private key contained in `jwk` according to the ECDSA `alg`.
+///
+/// The signature is encoded as the concatenation of `r` and `s` as required by
+/// [RFC 7518](https://www.rfc-editor.org/rfc/rfc7518#section-3.4).
+pub(crate) fn sign_ecdsa(alg: &JwsAlgorithm, jwk: &Jwk, data: &[u8]) -> KeyStorageResult<Vec<u8>> {
+  match alg {
+    JwsAlgorithm::ES256 => {
+      let signing_key = p256::ecdsa::SigningKey::from(expand_secret_ec_jwk::<p256::NistP256>(jwk)?);
+      let signature: p256::ecdsa::Signature = signing_key.sign(data);
+      Ok(signature.to_bytes().to_vec())
+    }
+    JwsAlgorithm::ES256K => {
+      let signing_key = k256::ecdsa::SigningKey::from(expand_secret_ec_jwk::<k256::Secp256k1>(jwk)?);
+      let signature: k256::ecdsa::Signature = signing_key.sign(data);
+      Ok(signature.to_bytes().to_vec())
+    }
+    JwsAlgorithm::ES384 => {
+      let signing_key = p384::ecdsa::SigningKey::from(expand_secret_ec_jwk::<p384::NistP384>(jwk)?);
+      let signature: p384::ecdsa::Signature = signing_key.sign(data);
+      Ok(signature.to_bytes().to_vec())
+    }
+    other => Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
+        .with_custom_message(format!("{other} is not an ECDSA algorithm")),
+    ),
+  }
+}
@@ -46,7 +46,7 @@ pub(crate) fn expand_secret_jwk(jwk: &Jwk) -> KeyStorageResult<SecretKey> {
   Ok(SecretKey::from_bytes(&sk))
 }
 
-#[cfg(any(test, feature = "memstore"))]
+#[cfg(any(test, feature = "memstore", feature = "file-storage"))]
 pub(crate) fn encode_jwk(private_key: &SecretKey, public_key: &crypto::signatures::ed25519::PublicKey) -> Jwk {
   let x = jwu::encode_b64(public_key.as_ref());
   let d = jwu::encode_b64(private_key.to_bytes().as_ref());
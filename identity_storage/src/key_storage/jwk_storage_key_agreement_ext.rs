@@ -0,0 +1,68 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use identity_verification::jose::jwe::DecodedJwe;
+use identity_verification::jose::jwe::JweAlgorithm;
+use identity_verification::jose::jwe::JweDecryptionItem;
+use identity_verification::jose::jwk::Jwk;
+use zeroize::Zeroizing;
+
+use crate::JwkGenOutput;
+use crate::JwkStorage;
+use crate::KeyId;
+use crate::KeyStorageError;
+use crate::KeyStorageErrorKind;
+use crate::KeyStorageResult;
+use crate::KeyType;
+
+/// Extension to the [`JwkStorage`] to handle key agreement keys, such as `X25519` or `P-256` keys used to decrypt
+/// JWEs.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+pub trait JwkStorageKeyAgreementExt: JwkStorage {
+  /// Generate a new key agreement key represented as a JSON Web Key that can be used with the given key agreement
+  /// algorithm.
+  ///
+  /// It is recommended that the implementer exposes constants for the supported [`KeyType`].
+  async fn generate_key_agreement(&self, key_type: KeyType, alg: JweAlgorithm) -> KeyStorageResult<JwkGenOutput>;
+
+  /// Compute the raw Diffie-Hellman shared secret between the private key identified by `key_id` and the
+  /// `peer_public_key`.
+  ///
+  /// `public_key` is the public key corresponding to `key_id` and must be on the same curve as `peer_public_key`.
+  async fn key_agreement(
+    &self,
+    key_id: &KeyId,
+    public_key: &Jwk,
+    peer_public_key: &Jwk,
+  ) -> KeyStorageResult<Zeroizing<Vec<u8>>>;
+
+  /// Unwrap the content encryption key of the recipient at index `recipient` of the given JWE with the private key
+  /// identified by `key_id` and decrypt its content.
+  ///
+  /// The default implementation performs the key agreement with the ephemeral public key of the recipient through
+  /// [`JwkStorageKeyAgreementExt::key_agreement`] and derives the content encryption key outside of the storage.
+  /// Implementations that are able to derive and unwrap keys internally may override this method.
+  async fn decrypt_jwe(
+    &self,
+    key_id: &KeyId,
+    public_key: &Jwk,
+    jwe: &JweDecryptionItem,
+    recipient: usize,
+  ) -> KeyStorageResult<DecodedJwe> {
+    let epk: &Jwk = jwe.epk(recipient).ok_or_else(|| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("missing ephemeral public key of the JWE recipient")
+    })?;
+    let shared_secret: Zeroizing<Vec<u8>> = self.key_agreement(key_id, public_key, epk).await?;
+
+    jwe
+      .decrypt_with_shared_secret(recipient, &shared_secret)
+      .map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("JWE decryption failed")
+          .with_source(err)
+      })
+  }
+}
@@ -7,7 +7,12 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 use async_trait::async_trait;
+use crypto::keys::x25519;
 use crypto::signatures::ed25519::SecretKey;
+use identity_verification::jose::jwe::diffie_hellman;
+use identity_verification::jose::jwe::JweAlgorithm;
+use identity_verification::jose::jwk::EcCurve;
+use identity_verification::jose::jwk::EcxCurve;
 use identity_verification::jose::jwk::EdCurve;
 use identity_verification::jose::jwk::Jwk;
 use identity_verification::jose::jwk::JwkType;
@@ -17,16 +22,23 @@ use rand::distributions::DistString;
 use shared::Shared;
 use tokio::sync::RwLockReadGuard;
 use tokio::sync::RwLockWriteGuard;
+use zeroize::Zeroizing;
 
+use super::ecdsa::check_ec_public_key;
+use super::ecdsa::ecdsa_curve;
+use super::ecdsa::encode_ec_jwk;
+use super::ecdsa::sign_ecdsa;
 use super::ed25519::encode_jwk;
 use super::ed25519::expand_secret_jwk;
 use super::jwk_gen_output::JwkGenOutput;
+use super::x25519::encode_x25519_jwk;
 use super::KeyId;
 use super::KeyStorageError;
 use super::KeyStorageErrorKind;
 use super::KeyStorageResult;
 use super::KeyType;
 use crate::key_storage::JwkStorage;
+use crate::key_storage::JwkStorageKeyAgreementExt;
 
 /// The map from key ids to JWKs.
 type JwkKeyStore = HashMap<KeyId, Jwk>;
@@ -60,13 +72,19 @@ impl JwkStorage for JwkMemStore {
 
     check_key_alg_compatibility(key_type, &alg)?;
 
-    let (private_key, public_key) = match key_type {
+    let mut jwk: Jwk = match key_type {
       MemStoreKeyType::Ed25519 => {
         let private_key = SecretKey::generate()
           .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure).with_source(err))?;
         let public_key = private_key.public_key();
-        (private_key, public_key)
+        encode_jwk(&private_key, &public_key)
       }
+      MemStoreKeyType::P256 => encode_ec_jwk(&p256::SecretKey::random(&mut rand::rngs::OsRng), EcCurve::P256),
+      MemStoreKeyType::Secp256K1 => encode_ec_jwk(
+        &k256::SecretKey::random(&mut rand::rngs::OsRng),
+        EcCurve::Secp256K1,
+      ),
+      MemStoreKeyType::P384 => encode_ec_jwk(&p384::SecretKey::random(&mut rand::rngs::OsRng), EcCurve::P384),
       other => {
         return Err(
           KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
@@ -77,7 +95,6 @@ impl JwkStorage for JwkMemStore {
 
     let kid: KeyId = random_key_id();
 
-    let mut jwk: Jwk = encode_jwk(&private_key, &public_key);
     jwk.set_alg(alg.name());
     jwk.set_kid(jwk.thumbprint_sha256_b64());
     let public_jwk: Jwk = jwk.to_public().expect("should only panic if kty == oct");
@@ -100,9 +117,14 @@ impl JwkStorage for JwkMemStore {
 
     match jwk.alg() {
       Some(alg) => {
-        let alg: JwsAlgorithm = JwsAlgorithm::from_str(alg)
-          .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm).with_source(err))?;
-        check_key_alg_compatibility(key_type, &alg)?;
+        // Key agreement keys carry a JWE algorithm, all other keys a JWS algorithm.
+        if let Ok(alg) = JweAlgorithm::from_str(alg) {
+          check_key_agreement_alg_compatibility(key_type, &alg)?;
+        } else {
+          let alg: JwsAlgorithm = JwsAlgorithm::from_str(alg)
+            .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm).with_source(err))?;
+          check_key_alg_compatibility(key_type, &alg)?;
+        }
       }
       None => {
         return Err(
@@ -139,7 +161,7 @@ impl JwkStorage for JwkMemStore {
         JwsAlgorithm::from_str(alg_str).map_err(|_| KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
       })?;
 
-    // Check that `kty` is `Okp` and `crv = Ed25519`.
+    // Check that `kty` is `Okp` and `crv = Ed25519` for EdDSA, or that `kty` is `Ec` with the matching `crv` for ECDSA.
     match alg {
       JwsAlgorithm::EdDSA => {
         let okp_params = public_key.try_okp_params().map_err(|err| {
@@ -156,6 +178,21 @@ impl JwkStorage for JwkMemStore {
           );
         }
       }
+      JwsAlgorithm::ES256 | JwsAlgorithm::ES256K | JwsAlgorithm::ES384 => {
+        let ec_params = public_key.try_ec_params().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message(format!("expected a Jwk with EC params in order to sign with {alg}"))
+            .with_source(err)
+        })?;
+        let curve: EcCurve = ecdsa_curve(&alg).expect("alg is an ECDSA algorithm");
+        if ec_params.crv != curve.name() {
+          return Err(
+            KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message(format!(
+              "expected Jwk with EC {curve} crv in order to sign with {alg}"
+            )),
+          );
+        }
+      }
       other => {
         return Err(
           KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
@@ -168,8 +205,13 @@ impl JwkStorage for JwkMemStore {
     let jwk: &Jwk = jwk_store
       .get(key_id)
       .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))?;
-    let secret_key = expand_secret_jwk(jwk)?;
-    Ok(secret_key.sign(data).to_bytes().to_vec())
+    match alg {
+      JwsAlgorithm::EdDSA => {
+        let secret_key = expand_secret_jwk(jwk)?;
+        Ok(secret_key.sign(data).to_bytes().to_vec())
+      }
+      _ => sign_ecdsa(&alg, jwk, data),
+    }
   }
 
   async fn delete(&self, key_id: &KeyId) -> KeyStorageResult<()> {
@@ -187,10 +229,86 @@ impl JwkStorage for JwkMemStore {
   }
 }
 
-#[derive(Debug, Copy, Clone)]
+// Refer to the `JwkStorageKeyAgreementExt` interface docs for high-level documentation of the individual methods.
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl JwkStorageKeyAgreementExt for JwkMemStore {
+  async fn generate_key_agreement(&self, key_type: KeyType, alg: JweAlgorithm) -> KeyStorageResult<JwkGenOutput> {
+    let key_type: MemStoreKeyType = MemStoreKeyType::try_from(&key_type)?;
+
+    check_key_agreement_alg_compatibility(key_type, &alg)?;
+
+    let mut jwk: Jwk = match key_type {
+      MemStoreKeyType::X25519 => {
+        let private_key = x25519::SecretKey::generate()
+          .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure).with_source(err))?;
+        encode_x25519_jwk(&private_key)
+      }
+      MemStoreKeyType::P256 => encode_ec_jwk(&p256::SecretKey::random(&mut rand::rngs::OsRng), EcCurve::P256),
+      other => {
+        return Err(
+          KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+            .with_custom_message(format!("{other} is not supported")),
+        );
+      }
+    };
+
+    let kid: KeyId = random_key_id();
+
+    jwk.set_alg(alg.name());
+    jwk.set_kid(jwk.thumbprint_sha256_b64());
+    let public_jwk: Jwk = jwk.to_public().expect("should only panic if kty == oct");
+
+    let mut jwk_store: RwLockWriteGuard<'_, JwkKeyStore> = self.jwk_store.write().await;
+    jwk_store.insert(kid.clone(), jwk);
+
+    Ok(JwkGenOutput::new(kid, public_jwk))
+  }
+
+  async fn key_agreement(
+    &self,
+    key_id: &KeyId,
+    public_key: &Jwk,
+    peer_public_key: &Jwk,
+  ) -> KeyStorageResult<Zeroizing<Vec<u8>>> {
+    let jwk_store: RwLockReadGuard<'_, JwkKeyStore> = self.jwk_store.read().await;
+
+    // Check that the given public key is a key agreement key.
+    let key_type: MemStoreKeyType = MemStoreKeyType::try_from(public_key)?;
+    if !matches!(key_type, MemStoreKeyType::X25519 | MemStoreKeyType::P256) {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+          .with_custom_message(format!("{key_type} cannot be used for key agreement")),
+      );
+    }
+
+    // Obtain the corresponding private key and compute the shared secret.
+    let jwk: &Jwk = jwk_store
+      .get(key_id)
+      .ok_or_else(|| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound))?;
+    // Check that the stored key is on the same curve as the given public key.
+    if MemStoreKeyType::try_from(jwk).ok() != Some(key_type) {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::KeyAlgorithmMismatch)
+          .with_custom_message(format!("the stored key is not a {key_type} key")),
+      );
+    }
+    diffie_hellman(jwk, peer_public_key).map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::KeyAlgorithmMismatch)
+        .with_custom_message("key agreement failed")
+        .with_source(err)
+    })
+  }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 enum MemStoreKeyType {
   Ed25519,
   BLS12381G2,
+  X25519,
+  P256,
+  Secp256K1,
+  P384,
 }
 
 impl JwkMemStore {
@@ -201,6 +319,22 @@ impl JwkMemStore {
   const BLS12381G2_KEY_TYPE_STR: &'static str = "BLS12381G2";
   /// The BLS12381G2 key type
   pub const BLS12381G2_KEY_TYPE: KeyType = KeyType::from_static_str(Self::BLS12381G2_KEY_TYPE_STR);
+
+  const X25519_KEY_TYPE_STR: &'static str = "X25519";
+  /// The X25519 key type, used for key agreement.
+  pub const X25519_KEY_TYPE: KeyType = KeyType::from_static_str(Self::X25519_KEY_TYPE_STR);
+
+  const P256_KEY_TYPE_STR: &'static str = "P-256";
+  /// The P-256 key type.
+  pub const P256_KEY_TYPE: KeyType = KeyType::from_static_str(Self::P256_KEY_TYPE_STR);
+
+  const SECP256K1_KEY_TYPE_STR: &'static str = "secp256k1";
+  /// The secp256k1 key type.
+  pub const SECP256K1_KEY_TYPE: KeyType = KeyType::from_static_str(Self::SECP256K1_KEY_TYPE_STR);
+
+  const P384_KEY_TYPE_STR: &'static str = "P-384";
+  /// The P-384 key type.
+  pub const P384_KEY_TYPE: KeyType = KeyType::from_static_str(Self::P384_KEY_TYPE_STR);
 }
 
 impl MemStoreKeyType {
@@ -208,6 +342,10 @@ impl MemStoreKeyType {
     match self {
       MemStoreKeyType::Ed25519 => JwkMemStore::ED25519_KEY_TYPE_STR,
       MemStoreKeyType::BLS12381G2 => JwkMemStore::BLS12381G2_KEY_TYPE_STR,
+      MemStoreKeyType::X25519 => JwkMemStore::X25519_KEY_TYPE_STR,
+      MemStoreKeyType::P256 => JwkMemStore::P256_KEY_TYPE_STR,
+      MemStoreKeyType::Secp256K1 => JwkMemStore::SECP256K1_KEY_TYPE_STR,
+      MemStoreKeyType::P384 => JwkMemStore::P384_KEY_TYPE_STR,
     }
   }
 }
@@ -225,6 +363,10 @@ impl TryFrom<&KeyType> for MemStoreKeyType {
     match value.as_str() {
       JwkMemStore::ED25519_KEY_TYPE_STR => Ok(MemStoreKeyType::Ed25519),
       JwkMemStore::BLS12381G2_KEY_TYPE_STR => Ok(MemStoreKeyType::BLS12381G2),
+      JwkMemStore::X25519_KEY_TYPE_STR => Ok(MemStoreKeyType::X25519),
+      JwkMemStore::P256_KEY_TYPE_STR => Ok(MemStoreKeyType::P256),
+      JwkMemStore::SECP256K1_KEY_TYPE_STR => Ok(MemStoreKeyType::Secp256K1),
+      JwkMemStore::P384_KEY_TYPE_STR => Ok(MemStoreKeyType::P384),
       _ => Err(KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)),
     }
   }
@@ -241,6 +383,15 @@ impl TryFrom<&Jwk> for MemStoreKeyType {
             .with_custom_message("expected Okp parameters for a JWK with `kty` Okp")
             .with_source(err)
         })?;
+        if let Ok(curve) = okp_params.try_ecx_curve() {
+          return match curve {
+            EcxCurve::X25519 => Ok(MemStoreKeyType::X25519),
+            curve => Err(
+              KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+                .with_custom_message(format!("{curve} not supported")),
+            ),
+          };
+        }
         match okp_params.try_ed_curve().map_err(|err| {
           KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
             .with_custom_message("only Ed curves are supported for signing")
@@ -259,6 +410,19 @@ impl TryFrom<&Jwk> for MemStoreKeyType {
             .with_custom_message("expected EC parameters for a JWK with `kty` Ec")
             .with_source(err)
         })?;
+        if let Ok(curve) = ec_params.try_ec_curve() {
+          let key_type: Option<MemStoreKeyType> = match curve {
+            EcCurve::P256 => Some(MemStoreKeyType::P256),
+            EcCurve::Secp256K1 => Some(MemStoreKeyType::Secp256K1),
+            EcCurve::P384 => Some(MemStoreKeyType::P384),
+            _ => None,
+          };
+          if let Some(key_type) = key_type {
+            // Keys with invalid coordinates are not keys of the supported type.
+            check_ec_public_key(ec_params, curve)?;
+            return Ok(key_type);
+          }
+        }
         match ec_params.try_bls_curve().map_err(|err| {
           KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
             .with_custom_message("only Ed curves are supported for signing")
@@ -294,6 +458,23 @@ fn random_key_id() -> KeyId {
 fn check_key_alg_compatibility(key_type: MemStoreKeyType, alg: &JwsAlgorithm) -> KeyStorageResult<()> {
   match (key_type, alg) {
     (MemStoreKeyType::Ed25519, JwsAlgorithm::EdDSA) => Ok(()),
+    (MemStoreKeyType::P256, JwsAlgorithm::ES256) => Ok(()),
+    (MemStoreKeyType::Secp256K1, JwsAlgorithm::ES256K) => Ok(()),
+    (MemStoreKeyType::P384, JwsAlgorithm::ES384) => Ok(()),
+    (key_type, alg) => Err(
+      KeyStorageError::new(crate::key_storage::KeyStorageErrorKind::KeyAlgorithmMismatch)
+        .with_custom_message(format!("`cannot use key type `{key_type}` with algorithm `{alg}`")),
+    ),
+  }
+}
+
+/// Check that the key type can be used with the key agreement algorithm.
+fn check_key_agreement_alg_compatibility(key_type: MemStoreKeyType, alg: &JweAlgorithm) -> KeyStorageResult<()> {
+  match (key_type, alg) {
+    (
+      MemStoreKeyType::X25519 | MemStoreKeyType::P256,
+      JweAlgorithm::ECDH_ES | JweAlgorithm::ECDH_ES_A128KW | JweAlgorithm::ECDH_ES_A192KW | JweAlgorithm::ECDH_ES_A256KW,
+    ) => Ok(()),
     (key_type, alg) => Err(
       KeyStorageError::new(crate::key_storage::KeyStorageErrorKind::KeyAlgorithmMismatch)
         .with_custom_message(format!("`cannot use key type `{key_type}` with algorithm `{alg}`")),
@@ -457,8 +638,11 @@ mod tests {
   use crate::key_storage::tests::utils::generate_ed25519;
   use crypto::signatures::ed25519::PublicKey;
   use crypto::signatures::ed25519::Signature;
-  use identity_verification::jose::jwk::EcCurve;
+  use identity_ecdsa_verifier::EcDSAJwsVerifier;
   use identity_verification::jose::jwk::JwkParamsEc;
+  use identity_verification::jose::jws::JwsVerifier;
+  use identity_verification::jose::jws::VerificationInput;
+  use identity_verification::jose::jwu;
 
   use super::*;
 
@@ -521,6 +705,169 @@ mod tests {
 
     let err = store.insert(jwk_ec).await.unwrap_err();
     assert!(matches!(err.kind(), KeyStorageErrorKind::UnsupportedKeyType));
+
+    // INVALID: P-521 keys are not supported.
+    let mut ec_params = JwkParamsEc::new();
+    ec_params.crv = EcCurve::P521.name().to_string();
+    ec_params.x = String::new();
+    ec_params.y = String::new();
+    ec_params.d = Some(String::new());
+    let jwk_ec = Jwk::from_params(ec_params);
+
+    let err = store.insert(jwk_ec).await.unwrap_err();
+    assert!(matches!(err.kind(), KeyStorageErrorKind::UnsupportedKeyType));
+  }
+
+  #[tokio::test]
+  async fn generate_and_sign_ecdsa() {
+    let test_msg: &[u8] = b"test";
+    let store: JwkMemStore = JwkMemStore::new();
+
+    for (key_type, alg) in [
+      (JwkMemStore::P256_KEY_TYPE, JwsAlgorithm::ES256),
+      (JwkMemStore::SECP256K1_KEY_TYPE, JwsAlgorithm::ES256K),
+    ] {
+      let JwkGenOutput { key_id, jwk } = store.generate(key_type, alg).await.unwrap();
+      assert!(jwk.is_public());
+
+      let signature = store.sign(&key_id, test_msg, &jwk).await.unwrap();
+      // `alg` has been moved into the key, which is not `Copy` with the `custom_alg` feature.
+      let input = VerificationInput {
+        alg: jwk.alg().unwrap().parse().unwrap(),
+        signing_input: test_msg.into(),
+        decoded_signature: signature.into(),
+      };
+      EcDSAJwsVerifier::default().verify(input, &jwk).unwrap();
+    }
+
+    // ES384 is not supported by the `EcDSAJwsVerifier`, hence verify the signature directly.
+    let JwkGenOutput { key_id, jwk } = store
+      .generate(JwkMemStore::P384_KEY_TYPE, JwsAlgorithm::ES384)
+      .await
+      .unwrap();
+    let signature = store.sign(&key_id, test_msg, &jwk).await.unwrap();
+    assert_eq!(signature.len(), 96);
+
+    let params = jwk.try_ec_params().unwrap();
+    let public_key_bytes: Vec<u8> = [0x04]
+      .into_iter()
+      .chain(jwu::decode_b64(&params.x).unwrap())
+      .chain(jwu::decode_b64(&params.y).unwrap())
+      .collect();
+    let verifying_key = p384::ecdsa::VerifyingKey::from_sec1_bytes(&public_key_bytes).unwrap();
+    let signature = p384::ecdsa::Signature::from_slice(&signature).unwrap();
+    signature::Verifier::verify(&verifying_key, test_msg, &signature).unwrap();
+  }
+
+  #[tokio::test]
+  async fn insert_and_sign_ecdsa() {
+    let test_msg: &[u8] = b"test";
+    let store: JwkMemStore = JwkMemStore::new();
+
+    let mut jwk: Jwk = encode_ec_jwk(&p256::SecretKey::random(&mut rand::rngs::OsRng), EcCurve::P256);
+
+    // INVALID: A P-256 key cannot be used with ES256K.
+    jwk.set_alg(JwsAlgorithm::ES256K.name());
+    let err = store.insert(jwk.clone()).await.unwrap_err();
+    assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+    // VALID: A P-256 key can be used with ES256.
+    jwk.set_alg(JwsAlgorithm::ES256.name());
+    let key_id: KeyId = store.insert(jwk.clone()).await.unwrap();
+    let public_jwk: Jwk = jwk.to_public().unwrap();
+    let signature = store.sign(&key_id, test_msg, &public_jwk).await.unwrap();
+    let input = VerificationInput {
+      alg: JwsAlgorithm::ES256,
+      signing_input: test_msg.into(),
+      decoded_signature: signature.into(),
+    };
+    EcDSAJwsVerifier::default().verify(input, &public_jwk).unwrap();
+
+    // INVALID: Signing requires the public key to match the curve of the algorithm.
+    let mut secp256k1_jwk: Jwk = encode_ec_jwk(&k256::SecretKey::random(&mut rand::rngs::OsRng), EcCurve::Secp256K1)
+      .to_public()
+      .unwrap();
+    secp256k1_jwk.set_alg(JwsAlgorithm::ES256.name());
+    let err = store.sign(&key_id, test_msg, &secp256k1_jwk).await.unwrap_err();
+    assert!(matches!(err.kind(), KeyStorageErrorKind::Unspecified));
+  }
+
+  #[tokio::test]
+  async fn generate_and_key_agreement() {
+    let store: JwkMemStore = JwkMemStore::new();
+
+    for key_type in [JwkMemStore::X25519_KEY_TYPE, JwkMemStore::P256_KEY_TYPE] {
+      let JwkGenOutput {
+        key_id: alice_key_id,
+        jwk: alice_jwk,
+      } = store
+        .generate_key_agreement(key_type.clone(), JweAlgorithm::ECDH_ES)
+        .await
+        .unwrap();
+      let JwkGenOutput {
+        key_id: bob_key_id,
+        jwk: bob_jwk,
+      } = store
+        .generate_key_agreement(key_type, JweAlgorithm::ECDH_ES_A256KW)
+        .await
+        .unwrap();
+      assert!(alice_jwk.is_public());
+
+      let alice_secret = store.key_agreement(&alice_key_id, &alice_jwk, &bob_jwk).await.unwrap();
+      let bob_secret = store.key_agreement(&bob_key_id, &bob_jwk, &alice_jwk).await.unwrap();
+      assert_eq!(alice_secret, bob_secret);
+    }
+
+    // INVALID: Key agreement between keys on different curves fails.
+    let x25519 = store
+      .generate_key_agreement(JwkMemStore::X25519_KEY_TYPE, JweAlgorithm::ECDH_ES)
+      .await
+      .unwrap();
+    let p256 = store
+      .generate_key_agreement(JwkMemStore::P256_KEY_TYPE, JweAlgorithm::ECDH_ES)
+      .await
+      .unwrap();
+    let err = store
+      .key_agreement(&x25519.key_id, &x25519.jwk, &p256.jwk)
+      .await
+      .unwrap_err();
+    assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+    // INVALID: The stored key must be on the curve of the given public key.
+    let err = store
+      .key_agreement(&x25519.key_id, &p256.jwk, &p256.jwk)
+      .await
+      .unwrap_err();
+    assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+  }
+
+  #[tokio::test]
+  async fn incompatible_key_agreement_alg() {
+    let store: JwkMemStore = JwkMemStore::new();
+
+    // INVALID: Ed25519 keys cannot be used for key agreement.
+    let err = store
+      .generate_key_agreement(JwkMemStore::ED25519_KEY_TYPE, JweAlgorithm::ECDH_ES)
+      .await
+      .unwrap_err();
+    assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+    // INVALID: X25519 keys cannot be used for signing.
+    let err = store
+      .generate(JwkMemStore::X25519_KEY_TYPE, JwsAlgorithm::EdDSA)
+      .await
+      .unwrap_err();
+    assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+    // VALID: A private X25519 key with a key agreement algorithm can be inserted.
+    let mut jwk: Jwk = super::encode_x25519_jwk(&x25519::SecretKey::generate().unwrap());
+    jwk.set_alg(JweAlgorithm::ECDH_ES.name());
+    store.insert(jwk.clone()).await.unwrap();
+
+    // INVALID: An X25519 key with a signature algorithm cannot be inserted.
+    jwk.set_alg(JwsAlgorithm::EdDSA.name());
+    let err = store.insert(jwk).await.unwrap_err();
+    assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
   }
 
   #[tokio::test]
@@ -9,17 +9,22 @@
 #[cfg(feature = "jpt-bbs-plus")]
 /// BLS12381 utils.
 pub mod bls;
-#[cfg(feature = "memstore")]
-mod ed25519;
+#[cfg(any(feature = "memstore", feature = "file-storage"))]
+pub(crate) mod ecdsa;
+#[cfg(any(feature = "memstore", feature = "file-storage"))]
+pub(crate) mod ed25519;
 mod jwk_gen_output;
 mod jwk_storage;
 #[cfg(feature = "jpt-bbs-plus")]
 mod jwk_storage_bbs_plus_ext;
+mod jwk_storage_key_agreement_ext;
 mod key_id;
 mod key_storage_error;
 mod key_type;
 #[cfg(feature = "memstore")]
 mod memstore;
+#[cfg(any(feature = "memstore", feature = "file-storage"))]
+pub(crate) mod x25519;
 
 #[cfg(test)]
 pub(crate) mod tests;
@@ -30,6 +35,7 @@ pub mod public_modules {
   pub use super::jwk_storage::*;
   #[cfg(feature = "jpt-bbs-plus")]
   pub use super::jwk_storage_bbs_plus_ext::*;
+  pub use super::jwk_storage_key_agreement_ext::*;
   pub use super::key_id::*;
   pub use super::key_storage_error::*;
   pub use super::key_type::*;
@@ -53,6 +53,16 @@ pub(crate) async fn test_incompatible_key_type(store: impl JwkStorage) {
 
   let err = store.insert(jwk_ec).await.unwrap_err();
   assert!(matches!(err.kind(), KeyStorageErrorKind::UnsupportedKeyType));
+
+  let mut ec_params = JwkParamsEc::new();
+  ec_params.crv = EcCurve::P521.name().to_string();
+  ec_params.x = String::new();
+  ec_params.y = String::new();
+  ec_params.d = Some(String::new());
+  let jwk_ec = Jwk::from_params(ec_params);
+
+  let err = store.insert(jwk_ec).await.unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::UnsupportedKeyType));
 }
 pub(crate) async fn test_generate_and_sign(store: impl JwkStorage) {
   let test_msg: &[u8] = b"test";
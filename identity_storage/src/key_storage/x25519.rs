@@ -0,0 +1,16 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::keys::x25519;
+use identity_verification::jose::jwk::EcxCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParamsOkp;
+use identity_verification::jose::jwu;
+
+pub(crate) fn encode_x25519_jwk(private_key: &x25519::SecretKey) -> Jwk {
+  let mut params = JwkParamsOkp::new();
+  params.x = jwu::encode_b64(private_key.public_key().as_slice());
+  params.d = Some(jwu::encode_b64(private_key.to_bytes()));
+  params.crv = EcxCurve::X25519.name().to_string();
+  Jwk::from_params(params)
+}
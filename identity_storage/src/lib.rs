@@ -14,10 +14,14 @@
   clippy::missing_safety_doc
 )]
 
+#[cfg(feature = "file-storage")]
+pub mod file_storage;
 pub mod key_id_storage;
 pub mod key_storage;
 pub mod storage;
 
+#[cfg(feature = "file-storage")]
+pub use file_storage::*;
 pub use key_id_storage::*;
 pub use key_storage::public_modules::*;
 pub use storage::*;
@@ -36,6 +36,15 @@ pub enum JwkStorageDocumentError {
   /// Credential's proof update internal error
   #[error("Credential's proof internal error")]
   ProofUpdateError(String),
+  /// Caused by a JWE that could not be decoded or decrypted.
+  #[error("JWE decryption failed")]
+  DecryptionError(#[source] identity_verification::jose::error::Error),
+  /// Caused by a JWE that has no recipient corresponding to the given method.
+  #[error("JWE decryption failed: no recipient corresponds to the method")]
+  JweRecipientNotFound,
+  /// Caused by an SD-JWT that cannot be used to create a presentation.
+  #[error("invalid SD-JWT")]
+  InvalidSdJwt(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 
   /// Caused by a failure to construct a verification method.
   #[error("method generation failed: unable to create a valid verification method")]
@@ -3,6 +3,11 @@
 
 use super::JwkStorageDocumentError as Error;
 use super::JwsSignatureOptions;
+#[cfg(feature = "sd-jwt")]
+use super::KeyBindingJwtOptions;
+use super::MethodRotationOptions;
+#[cfg(feature = "sd-jwt")]
+use super::SdJwtCredentialOptions;
 use super::Storage;
 
 use crate::key_id_storage::KeyIdStorage;
@@ -10,25 +15,46 @@ use crate::key_id_storage::KeyIdStorageResult;
 use crate::key_id_storage::MethodDigest;
 use crate::key_storage::JwkGenOutput;
 use crate::key_storage::JwkStorage;
+use crate::key_storage::JwkStorageKeyAgreementExt;
 use crate::key_storage::KeyId;
 use crate::key_storage::KeyStorageResult;
 use crate::key_storage::KeyType;
 
 use async_trait::async_trait;
 use identity_core::common::Object;
+#[cfg(feature = "sd-jwt")]
+use identity_core::common::Timestamp;
 use identity_credential::credential::Credential;
 use identity_credential::credential::Jws;
 use identity_credential::credential::Jwt;
 use identity_credential::presentation::JwtPresentationOptions;
 use identity_credential::presentation::Presentation;
+#[cfg(feature = "sd-jwt")]
+use identity_credential::sd_jwt_payload::KeyBindingJwtClaims;
+#[cfg(feature = "sd-jwt")]
+use identity_credential::sd_jwt_payload::SdJwt;
+#[cfg(feature = "sd-jwt")]
+use identity_credential::sd_jwt_payload::SdObjectDecoder;
+#[cfg(feature = "sd-jwt")]
+use identity_credential::sd_jwt_payload::SdObjectEncoder;
+#[cfg(feature = "sd-jwt")]
+use identity_credential::sd_jwt_vc::SdJwtVcClaims;
 use identity_did::DIDUrl;
 use identity_document::document::CoreDocument;
+use identity_verification::jose::jwe::DecodedJwe;
+use identity_verification::jose::jwe::JweAlgorithm;
+use identity_verification::jose::jwe::JweDecoder;
+use identity_verification::jose::jwe::JweDecryptionItem;
+use identity_verification::jose::jwe::JweFormat;
 use identity_verification::jose::jws::CompactJwsEncoder;
 use identity_verification::jose::jws::CompactJwsEncodingOptions;
 use identity_verification::jose::jws::JwsAlgorithm;
 use identity_verification::jose::jws::JwsHeader;
 use identity_verification::jws::CharSet;
+#[cfg(feature = "sd-jwt")]
+use identity_verification::jws::Decoder;
 use identity_verification::MethodData;
+use identity_verification::MethodRelationship;
 use identity_verification::MethodScope;
 use identity_verification::VerificationMethod;
 use serde::de::DeserializeOwned;
@@ -79,6 +105,33 @@ pub trait JwkDocumentExt: private::Sealed {
     K: JwkStorage,
     I: KeyIdStorage;
 
+  /// Replace the key material of the method identified by the given `id` with newly generated key material in the
+  /// given `storage`.
+  ///
+  /// The replacement method keeps all verification relationships of the rotated method and is inserted under the
+  /// fragment given in `options`, or the fragment of the rotated method if unset. Unless `options` specify that the
+  /// rotated method should be retained for a grace period, it is removed from the document and its key material is
+  /// deleted from the `storage`. A retained method loses all of its verification relationships.
+  ///
+  /// If any step fails, the document and the `storage` are reverted to their previous state.
+  ///
+  /// The fragment of the replacement method is returned.
+  ///
+  /// ## Warning
+  ///
+  /// Unless the rotated method is retained, its key material is deleted permanently and irrecoverably.
+  async fn rotate_method<K, I>(
+    &mut self,
+    storage: &Storage<K, I>,
+    id: &DIDUrl,
+    key_type: KeyType,
+    alg: JwsAlgorithm,
+    options: &MethodRotationOptions,
+  ) -> StorageResult<String>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage;
+
   /// Sign the arbitrary `payload` according to `options` with the storage backed private key corresponding to the
   /// public key material in the verification method identified by the given `fragment.
   ///
@@ -135,6 +188,135 @@ pub trait JwkDocumentExt: private::Sealed {
     I: KeyIdStorage,
     T: ToOwned<Owned = T> + Serialize + DeserializeOwned + Sync,
     CRED: ToOwned<Owned = CRED> + Serialize + DeserializeOwned + Clone + Sync;
+
+  /// Produces an SD-JWT where the payload is produced from the given `credential` in accordance with
+  /// [VC Data Model v1.1](https://www.w3.org/TR/vc-data-model/#json-web-token) and the claims identified by the
+  /// JSON pointers in `disclosable_paths` are made selectively disclosable according to
+  /// [SD-JWT](https://www.ietf.org/archive/id/draft-ietf-oauth-selective-disclosure-jwt-07.html).
+  ///
+  /// The pointers are evaluated against the JWT claims set, hence the credential's properties are found under `/vc`,
+  /// e.g. `/vc/credentialSubject/address`. Claims are concealed in the given order. The digests are computed with
+  /// SHA-256.
+  ///
+  /// The issuer-signed JWT is produced like in [`JwkDocumentExt::create_credential_jwt`] and is returned together with
+  /// all disclosures, without a key binding JWT.
+  #[cfg(feature = "sd-jwt")]
+  async fn create_sd_jwt_credential<K, I, T>(
+    &self,
+    credential: &Credential<T>,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    disclosable_paths: &[&str],
+    sd_jwt_options: &SdJwtCredentialOptions,
+    jws_options: &JwsSignatureOptions,
+  ) -> StorageResult<SdJwt>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+    T: ToOwned<Owned = T> + Serialize + DeserializeOwned + Sync;
+
+  /// Produces an SD-JWT to be presented to a verifier from the given `sd_jwt` issued to the holder of this document.
+  ///
+  /// Only the given `disclosures`, each of which must be one of the disclosures of `sd_jwt`, are included. A key
+  /// binding JWT with the claims set according to `kb_options` is appended to the result. It is signed with the
+  /// storage backed private key corresponding to the method identified by `fragment`, while the `typ` header
+  /// parameter is always set as required for key binding JWTs.
+  #[cfg(feature = "sd-jwt")]
+  async fn create_sd_jwt_presentation<K, I>(
+    &self,
+    sd_jwt: &SdJwt,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    disclosures: &[&str],
+    kb_options: &KeyBindingJwtOptions,
+    jws_options: &JwsSignatureOptions,
+  ) -> StorageResult<SdJwt>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage;
+
+  /// Produces an [SD-JWT VC](https://www.ietf.org/archive/id/draft-ietf-oauth-sd-jwt-vc-05.html) from the given
+  /// `claims`, where the claims identified by the JSON pointers in `disclosable_paths` are made selectively
+  /// disclosable.
+  ///
+  /// The claims listed in [`SdJwtVcClaims::NON_DISCLOSABLE_CLAIMS`] and their members cannot be made selectively
+  /// disclosable. The `custom_claims` of `sd_jwt_options` are added to the claims about the subject and must not
+  /// contain registered claims. Unless set in `jws_options`, the `typ` header parameter is set to
+  /// [`SdJwtVcClaims::HEADER_TYP`].
+  ///
+  /// The issuer-signed JWT is returned together with all disclosures, without a key binding JWT.
+  #[cfg(feature = "sd-jwt")]
+  async fn create_sd_jwt_vc<K, I>(
+    &self,
+    claims: &SdJwtVcClaims,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    disclosable_paths: &[&str],
+    sd_jwt_options: &SdJwtCredentialOptions,
+    jws_options: &JwsSignatureOptions,
+  ) -> StorageResult<SdJwt>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage;
+
+  /// Generate new key agreement key material in the given `storage` and insert a new verification method with the
+  /// corresponding public key material into the DID document.
+  ///
+  /// This behaves like [`JwkDocumentExt::generate_method`], except that the generated key is meant to be used with the
+  /// given JWE key agreement algorithm, typically under the
+  /// [`MethodScope::key_agreement`](MethodScope::key_agreement()) scope.
+  ///
+  /// The fragment of the generated method is returned.
+  async fn generate_key_agreement_method<K, I>(
+    &mut self,
+    storage: &Storage<K, I>,
+    key_type: KeyType,
+    alg: JweAlgorithm,
+    fragment: Option<&str>,
+    scope: MethodScope,
+  ) -> StorageResult<String>
+  where
+    K: JwkStorageKeyAgreementExt,
+    I: KeyIdStorage;
+
+  /// Replace the key material of the key agreement method identified by the given `id` with newly generated key
+  /// agreement key material in the given `storage`.
+  ///
+  /// This behaves like [`JwkDocumentExt::rotate_method`], except that the replacement key is generated for the given
+  /// JWE key agreement algorithm, as in [`JwkDocumentExt::generate_key_agreement_method`].
+  ///
+  /// The fragment of the replacement method is returned.
+  ///
+  /// ## Warning
+  ///
+  /// Unless the rotated method is retained, its key material is deleted permanently and irrecoverably.
+  async fn rotate_key_agreement_method<K, I>(
+    &mut self,
+    storage: &Storage<K, I>,
+    id: &DIDUrl,
+    key_type: KeyType,
+    alg: JweAlgorithm,
+    options: &MethodRotationOptions,
+  ) -> StorageResult<String>
+  where
+    K: JwkStorageKeyAgreementExt,
+    I: KeyIdStorage;
+
+  /// Decrypt the given `jwe` encoded according to `format` with the storage backed private key corresponding to the
+  /// public key material in the verification method identified by the given `fragment`.
+  ///
+  /// The recipient whose `kid` equals the `id` of the method is decrypted for. A JWE with a single recipient without
+  /// a `kid` is decrypted as well.
+  async fn decrypt_jwe<K, I>(
+    &self,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    jwe: &[u8],
+    format: JweFormat,
+  ) -> StorageResult<DecodedJwe>
+  where
+    K: JwkStorageKeyAgreementExt,
+    I: KeyIdStorage;
 }
 
 mod private {
@@ -300,6 +482,176 @@ macro_rules! purge_method_for_document_type {
   };
 }
 
+macro_rules! rotate_method_for_document_type {
+  ($t:ty, $a:ty, $k:path, $f:path, $name:ident) => {
+    async fn $name<K, I>(
+      document: &mut $t,
+      storage: &Storage<K, I>,
+      id: &DIDUrl,
+      key_type: KeyType,
+      alg: $a,
+      options: &MethodRotationOptions,
+    ) -> StorageResult<String>
+    where
+      K: $k,
+      I: KeyIdStorage,
+    {
+      let previous_method: VerificationMethod = document.resolve_method(id, None).ok_or(Error::MethodNotFound)?.clone();
+      let previous_id: DIDUrl = previous_method.id().clone();
+      let fragment: String = match options.fragment.as_deref() {
+        Some(fragment) => fragment.trim_start_matches('#').to_owned(),
+        None => previous_id
+          .fragment()
+          .ok_or(identity_verification::Error::MissingIdFragment)
+          .map_err(Error::VerificationMethodConstructionError)?
+          .to_owned(),
+      };
+      // A retained method cannot share its fragment with the replacement method.
+      if options.retain_previous_method && previous_id.fragment() == Some(fragment.as_str()) {
+        return Err(Error::FragmentAlreadyExists);
+      }
+
+      // Record how the rotated method is used in the document, so that the replacement method can take its place.
+      let is_embedded: bool = !document
+        .methods(Some(MethodScope::VerificationMethod))
+        .into_iter()
+        .any(|method| method.id() == &previous_id);
+      let relationships: Vec<MethodRelationship> = [
+        MethodRelationship::Authentication,
+        MethodRelationship::AssertionMethod,
+        MethodRelationship::KeyAgreement,
+        MethodRelationship::CapabilityDelegation,
+        MethodRelationship::CapabilityInvocation,
+      ]
+      .into_iter()
+      .filter(|relationship| {
+        document
+          .methods(Some(MethodScope::from(*relationship)))
+          .into_iter()
+          .any(|method| method.id() == &previous_id)
+      })
+      .collect();
+
+      let previous_method_digest: MethodDigest =
+        MethodDigest::new(&previous_method).map_err(Error::MethodDigestConstructionError)?;
+      let previous_key_id: KeyId = <I as KeyIdStorage>::get_key_id(storage.key_id_storage(), &previous_method_digest)
+        .await
+        .map_err(Error::KeyIdStorageError)?;
+
+      let JwkGenOutput { key_id, jwk } = $f(storage.key_storage(), key_type, alg)
+        .await
+        .map_err(Error::KeyStorageError)?;
+
+      // Produce the replacement method containing the generated JWK. If this operation fails we handle the error
+      // by attempting to revert key generation before returning an error.
+      let method: VerificationMethod = {
+        match VerificationMethod::new_from_jwk(document.id().clone(), jwk, Some(&fragment))
+          .map_err(Error::VerificationMethodConstructionError)
+        {
+          Ok(method) => method,
+          Err(source) => {
+            return Err(try_undo_key_generation(storage, &key_id, source).await);
+          }
+        }
+      };
+      let method_digest: MethodDigest = match MethodDigest::new(&method).map_err(Error::MethodDigestConstructionError)
+      {
+        Ok(digest) => digest,
+        Err(source) => {
+          return Err(try_undo_key_generation(storage, &key_id, source).await);
+        }
+      };
+      let method_id: DIDUrl = method.id().clone();
+
+      // Replace the method in the document, keeping a copy of the previous state to revert to upon failure.
+      let document_backup: $t = document.clone();
+      let replace_result: StorageResult<()> = (|| {
+        document.remove_method(&previous_id);
+        if options.retain_previous_method {
+          let mut retained_method: VerificationMethod = previous_method;
+          if let Some(expires) = options.expires {
+            retained_method
+              .properties_mut()
+              .insert("expires".to_owned(), expires.to_string().into());
+          }
+          document
+            .insert_method(retained_method, MethodScope::VerificationMethod)
+            .map_err(|_| Error::FragmentAlreadyExists)?;
+        }
+
+        if is_embedded {
+          // An embedded method is part of exactly one verification relationship.
+          let scope: MethodScope = relationships
+            .first()
+            .copied()
+            .map(MethodScope::from)
+            .unwrap_or(MethodScope::VerificationMethod);
+          document
+            .insert_method(method, scope)
+            .map_err(|_| Error::FragmentAlreadyExists)
+        } else {
+          document
+            .insert_method(method, MethodScope::VerificationMethod)
+            .map_err(|_| Error::FragmentAlreadyExists)?;
+          for relationship in relationships {
+            // The method was just inserted as a generic verification method, so this cannot fail.
+            let _ = document.attach_method_relationship(&method_id, relationship);
+          }
+          Ok(())
+        }
+      })();
+      if let Err(error) = replace_result {
+        *document = document_backup;
+        return Err(try_undo_key_generation(storage, &key_id, error).await);
+      }
+
+      // Insert the generated `KeyId` into storage under the computed method digest and handle the error if the
+      // operation fails.
+      if let Err(error) = <I as KeyIdStorage>::insert_key_id(storage.key_id_storage(), method_digest.clone(), key_id.clone())
+        .await
+        .map_err(Error::KeyIdStorageError)
+      {
+        *document = document_backup;
+        return Err(try_undo_key_generation(storage, &key_id, error).await);
+      }
+
+      if options.retain_previous_method {
+        return Ok(fragment);
+      }
+
+      // Delete the key material of the rotated method. The key id is deleted first since, unlike the key, it can be
+      // reinserted if deleting the key fails.
+      if let Err(error) = <I as KeyIdStorage>::delete_key_id(storage.key_id_storage(), &previous_method_digest)
+        .await
+        .map_err(Error::KeyIdStorageError)
+      {
+        *document = document_backup;
+        return Err(try_undo_method_key_generation(storage, &method_digest, &key_id, error).await);
+      }
+      if let Err(error) = <K as JwkStorage>::delete(storage.key_storage(), &previous_key_id)
+        .await
+        .map_err(Error::KeyStorageError)
+      {
+        if let Err(key_id_insertion_error) =
+          <I as KeyIdStorage>::insert_key_id(storage.key_id_storage(), previous_method_digest, previous_key_id.clone())
+            .await
+            .map_err(Error::KeyIdStorageError)
+        {
+          return Err(Error::UndoOperationFailed {
+            message: format!("cannot revert key id deletion: this results in stray key with key id: {previous_key_id}"),
+            source: Box::new(error),
+            undo_error: Some(Box::new(key_id_insertion_error)),
+          });
+        }
+        *document = document_backup;
+        return Err(try_undo_method_key_generation(storage, &method_digest, &key_id, error).await);
+      }
+
+      Ok(fragment)
+    }
+  };
+}
+
 // ====================================================================================================================
 // CoreDocument
 // ====================================================================================================================
@@ -311,7 +663,28 @@ generate_method_for_document_type!(
   JwkStorage::generate,
   generate_method_core_document
 );
+generate_method_for_document_type!(
+  CoreDocument,
+  JweAlgorithm,
+  JwkStorageKeyAgreementExt,
+  JwkStorageKeyAgreementExt::generate_key_agreement,
+  generate_key_agreement_method_core_document
+);
 purge_method_for_document_type!(CoreDocument, purge_method_core_document);
+rotate_method_for_document_type!(
+  CoreDocument,
+  JwsAlgorithm,
+  JwkStorage,
+  JwkStorage::generate,
+  rotate_method_core_document
+);
+rotate_method_for_document_type!(
+  CoreDocument,
+  JweAlgorithm,
+  JwkStorageKeyAgreementExt,
+  JwkStorageKeyAgreementExt::generate_key_agreement,
+  rotate_key_agreement_method_core_document
+);
 
 #[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
 #[cfg_attr(feature = "send-sync-storage", async_trait)]
@@ -339,6 +712,21 @@ impl JwkDocumentExt for CoreDocument {
     purge_method_core_document(self, storage, id).await
   }
 
+  async fn rotate_method<K, I>(
+    &mut self,
+    storage: &Storage<K, I>,
+    id: &DIDUrl,
+    key_type: KeyType,
+    alg: JwsAlgorithm,
+    options: &MethodRotationOptions,
+  ) -> StorageResult<String>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+  {
+    rotate_method_core_document(self, storage, id, key_type, alg, options).await
+  }
+
   async fn create_jws<K, I>(
     &self,
     storage: &Storage<K, I>,
@@ -450,18 +838,7 @@ impl JwkDocumentExt for CoreDocument {
     I: KeyIdStorage,
     T: ToOwned<Owned = T> + Serialize + DeserializeOwned + Sync,
   {
-    if options.detached_payload {
-      return Err(Error::EncodingError(Box::<dyn std::error::Error + Send + Sync>::from(
-        "cannot use detached payload for credential signing",
-      )));
-    }
-
-    if !options.b64.unwrap_or(true) {
-      // JWTs should not have `b64` set per https://datatracker.ietf.org/doc/html/rfc7797#section-7.
-      return Err(Error::EncodingError(Box::<dyn std::error::Error + Send + Sync>::from(
-        "cannot use `b64 = false` with JWTs",
-      )));
-    }
+    check_credential_jws_options(options)?;
 
     let payload = credential
       .serialize_jwt(custom_claims)
@@ -506,6 +883,259 @@ impl JwkDocumentExt for CoreDocument {
       .await
       .map(|jws| Jwt::new(jws.into()))
   }
+
+  #[cfg(feature = "sd-jwt")]
+  async fn create_sd_jwt_credential<K, I, T>(
+    &self,
+    credential: &Credential<T>,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    disclosable_paths: &[&str],
+    sd_jwt_options: &SdJwtCredentialOptions,
+    jws_options: &JwsSignatureOptions,
+  ) -> StorageResult<SdJwt>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+    T: ToOwned<Owned = T> + Serialize + DeserializeOwned + Sync,
+  {
+    check_credential_jws_options(jws_options)?;
+
+    let payload = credential
+      .serialize_jwt(sd_jwt_options.custom_claims.clone())
+      .map_err(Error::ClaimsSerializationError)?;
+
+    let (encoded_payload, disclosures): (String, Vec<String>) =
+      encode_sd_jwt_payload(&payload, disclosable_paths, sd_jwt_options)?;
+
+    let jws: Jws = self
+      .create_jws(storage, fragment, encoded_payload.as_bytes(), jws_options)
+      .await?;
+    Ok(SdJwt::new(jws.into(), disclosures, None))
+  }
+
+  #[cfg(feature = "sd-jwt")]
+  async fn create_sd_jwt_vc<K, I>(
+    &self,
+    claims: &SdJwtVcClaims,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    disclosable_paths: &[&str],
+    sd_jwt_options: &SdJwtCredentialOptions,
+    jws_options: &JwsSignatureOptions,
+  ) -> StorageResult<SdJwt>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+  {
+    check_credential_jws_options(jws_options)?;
+
+    if let Some(path) = disclosable_paths.iter().find(|path| {
+      let claim: &str = path.trim_start_matches('/').split('/').next().unwrap_or_default();
+      SdJwtVcClaims::NON_DISCLOSABLE_CLAIMS.contains(&claim)
+    }) {
+      return Err(Error::EncodingError(
+        format!("the claim identified by {path} cannot be selectively disclosable").into(),
+      ));
+    }
+
+    let mut claims: SdJwtVcClaims = claims.clone();
+    if let Some(ref custom_claims) = sd_jwt_options.custom_claims {
+      claims.properties.extend(custom_claims.clone());
+    }
+    claims.check_structure().map_err(Error::ClaimsSerializationError)?;
+    let payload: String = claims.serialize_jwt().map_err(Error::ClaimsSerializationError)?;
+
+    let (encoded_payload, disclosures): (String, Vec<String>) =
+      encode_sd_jwt_payload(&payload, disclosable_paths, sd_jwt_options)?;
+
+    let jws_options: JwsSignatureOptions = match jws_options.typ {
+      Some(_) => jws_options.clone(),
+      None => jws_options.clone().typ(SdJwtVcClaims::HEADER_TYP),
+    };
+    let jws: Jws = self
+      .create_jws(storage, fragment, encoded_payload.as_bytes(), &jws_options)
+      .await?;
+    Ok(SdJwt::new(jws.into(), disclosures, None))
+  }
+
+  #[cfg(feature = "sd-jwt")]
+  async fn create_sd_jwt_presentation<K, I>(
+    &self,
+    sd_jwt: &SdJwt,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    disclosures: &[&str],
+    kb_options: &KeyBindingJwtOptions,
+    jws_options: &JwsSignatureOptions,
+  ) -> StorageResult<SdJwt>
+  where
+    K: JwkStorage,
+    I: KeyIdStorage,
+  {
+    if let Some(disclosure) = disclosures
+      .iter()
+      .find(|disclosure| !sd_jwt.disclosures.iter().any(|known| known == *disclosure))
+    {
+      return Err(Error::InvalidSdJwt(
+        format!("disclosure {disclosure} is not part of the SD-JWT").into(),
+      ));
+    }
+    let disclosures: Vec<String> = disclosures.iter().map(|disclosure| disclosure.to_string()).collect();
+
+    // The `sd_hash` claim must be computed with the hash algorithm used by the issuer.
+    let binding_claims: String = {
+      let decoded = Decoder::new()
+        .decode_compact_serialization(sd_jwt.jwt.as_bytes(), None)
+        .map_err(|err| Error::InvalidSdJwt(err.into()))?;
+      let claims: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_slice(decoded.claims()).map_err(|err| Error::InvalidSdJwt(err.into()))?;
+      let decoder = SdObjectDecoder::new_with_sha256();
+      let hasher = decoder
+        .determine_hasher(&claims)
+        .map_err(|err| Error::InvalidSdJwt(err.into()))?;
+
+      let issued_at: i64 = kb_options.issued_at.unwrap_or_else(Timestamp::now_utc).to_unix();
+      let binding_claims = KeyBindingJwtClaims::new(
+        hasher,
+        sd_jwt.jwt.clone(),
+        disclosures.clone(),
+        kb_options.nonce.clone(),
+        kb_options.aud.clone(),
+        issued_at,
+      );
+      serde_json::to_string(&binding_claims).map_err(|err| Error::EncodingError(err.into()))?
+    };
+
+    // Setting the `typ` in the header is required.
+    // `KeyBindingJwtClaims::KB_JWT_HEADER_TYP` contains a leading whitespace, so the value is set explicitly.
+    let jws_options: JwsSignatureOptions = jws_options.clone().typ("kb+jwt");
+    let kb_jwt: Jws = self
+      .create_jws(storage, fragment, binding_claims.as_bytes(), &jws_options)
+      .await?;
+
+    Ok(SdJwt::new(sd_jwt.jwt.clone(), disclosures, Some(kb_jwt.into())))
+  }
+
+  async fn generate_key_agreement_method<K, I>(
+    &mut self,
+    storage: &Storage<K, I>,
+    key_type: KeyType,
+    alg: JweAlgorithm,
+    fragment: Option<&str>,
+    scope: MethodScope,
+  ) -> StorageResult<String>
+  where
+    K: JwkStorageKeyAgreementExt,
+    I: KeyIdStorage,
+  {
+    generate_key_agreement_method_core_document(self, storage, key_type, alg, fragment, scope).await
+  }
+
+  async fn rotate_key_agreement_method<K, I>(
+    &mut self,
+    storage: &Storage<K, I>,
+    id: &DIDUrl,
+    key_type: KeyType,
+    alg: JweAlgorithm,
+    options: &MethodRotationOptions,
+  ) -> StorageResult<String>
+  where
+    K: JwkStorageKeyAgreementExt,
+    I: KeyIdStorage,
+  {
+    rotate_key_agreement_method_core_document(self, storage, id, key_type, alg, options).await
+  }
+
+  async fn decrypt_jwe<K, I>(
+    &self,
+    storage: &Storage<K, I>,
+    fragment: &str,
+    jwe: &[u8],
+    format: JweFormat,
+  ) -> StorageResult<DecodedJwe>
+  where
+    K: JwkStorageKeyAgreementExt,
+    I: KeyIdStorage,
+  {
+    // Obtain the method corresponding to the given fragment.
+    let method: &VerificationMethod = self.resolve_method(fragment, None).ok_or(Error::MethodNotFound)?;
+    let MethodData::PublicKeyJwk(ref jwk) = method.data() else {
+      return Err(Error::NotPublicKeyJwk);
+    };
+
+    let decoder: JweDecoder = JweDecoder::new();
+    let decryption_item: JweDecryptionItem = match format {
+      JweFormat::Compact => decoder.decode_compact_serialization(jwe),
+      JweFormat::Flatten => decoder.decode_flattened_serialization(jwe),
+      JweFormat::General => decoder.decode_general_serialization(jwe),
+    }
+    .map_err(Error::DecryptionError)?;
+
+    // Find the recipient the JWE was encrypted for with the method's key.
+    let recipient: usize = decryption_item
+      .find_recipient(method.id().to_string().as_str())
+      .or_else(|| {
+        (decryption_item.recipient_count() == 1 && decryption_item.kid(0).is_none()).then_some(0)
+      })
+      .ok_or(Error::JweRecipientNotFound)?;
+
+    // Get the key identifier corresponding to the given method from the KeyId storage.
+    let method_digest: MethodDigest = MethodDigest::new(method).map_err(Error::MethodDigestConstructionError)?;
+    let key_id = <I as KeyIdStorage>::get_key_id(storage.key_id_storage(), &method_digest)
+      .await
+      .map_err(Error::KeyIdStorageError)?;
+
+    <K as JwkStorageKeyAgreementExt>::decrypt_jwe(storage.key_storage(), &key_id, jwk, &decryption_item, recipient)
+      .await
+      .map_err(Error::KeyStorageError)
+  }
+}
+
+/// Checks that `options` can be used to sign a credential JWT, which must neither use a detached payload
+/// nor set `b64` to `false`.
+fn check_credential_jws_options(options: &JwsSignatureOptions) -> StorageResult<()> {
+  if options.detached_payload {
+    return Err(Error::EncodingError(Box::<dyn std::error::Error + Send + Sync>::from(
+      "cannot use detached payload for credential signing",
+    )));
+  }
+
+  if !options.b64.unwrap_or(true) {
+    // JWTs should not have `b64` set per https://datatracker.ietf.org/doc/html/rfc7797#section-7.
+    return Err(Error::EncodingError(Box::<dyn std::error::Error + Send + Sync>::from(
+      "cannot use `b64 = false` with JWTs",
+    )));
+  }
+
+  Ok(())
+}
+
+/// Conceals the claims identified by `disclosable_paths` in the JWT claims set `payload` and adds the decoys
+/// configured in `sd_jwt_options`, returning the encoded claims set together with the disclosures.
+#[cfg(feature = "sd-jwt")]
+fn encode_sd_jwt_payload(
+  payload: &str,
+  disclosable_paths: &[&str],
+  sd_jwt_options: &SdJwtCredentialOptions,
+) -> StorageResult<(String, Vec<String>)> {
+  let mut encoder = SdObjectEncoder::new(payload).map_err(|err| Error::EncodingError(err.into()))?;
+  let disclosures: Vec<String> = disclosable_paths
+    .iter()
+    .map(|path| encoder.conceal(path, None).map(|disclosure| disclosure.into_string()))
+    .collect::<Result<_, _>>()
+    .map_err(|err| Error::EncodingError(err.into()))?;
+  for (path, number) in sd_jwt_options.decoys.iter() {
+    encoder
+      .add_decoys(path, *number)
+      .map_err(|err| Error::EncodingError(err.into()))?;
+  }
+  encoder.add_sd_alg_property();
+
+  let encoded_payload: String = encoder
+    .try_to_string()
+    .map_err(|err| Error::EncodingError(err.into()))?;
+  Ok((encoded_payload, disclosures))
 }
 
 /// Attempt to revert key generation. If this succeeds the original `source_error` is returned,
@@ -528,6 +1158,32 @@ where
   }
 }
 
+/// Attempt to revert the insertion of the key id stored under `method_digest` and the generation of the key with the
+/// given `key_id`. If this succeeds the original `source_error` is returned, otherwise
+/// [`JwkStorageDocumentError::UndoOperationFailed`] is returned with the `source_error` attached as `source`.
+async fn try_undo_method_key_generation<K, I>(
+  storage: &Storage<K, I>,
+  method_digest: &MethodDigest,
+  key_id: &KeyId,
+  source_error: Error,
+) -> Error
+where
+  K: JwkStorage,
+  I: KeyIdStorage,
+{
+  if let Err(err) = <I as KeyIdStorage>::delete_key_id(storage.key_id_storage(), method_digest).await {
+    return Error::UndoOperationFailed {
+      message: format!(
+        "unable to delete stray key id stored under packed method digest: {:?}",
+        method_digest.pack()
+      ),
+      source: Box::new(source_error),
+      undo_error: Some(Box::new(Error::KeyIdStorageError(err))),
+    };
+  }
+  try_undo_key_generation(storage, key_id, source_error).await
+}
+
 // ====================================================================================================================
 // IotaDocument
 // ====================================================================================================================
@@ -544,7 +1200,28 @@ mod iota_document {
     JwkStorage::generate,
     generate_method_iota_document
   );
+  generate_method_for_document_type!(
+    IotaDocument,
+    JweAlgorithm,
+    JwkStorageKeyAgreementExt,
+    JwkStorageKeyAgreementExt::generate_key_agreement,
+    generate_key_agreement_method_iota_document
+  );
   purge_method_for_document_type!(IotaDocument, purge_method_iota_document);
+  rotate_method_for_document_type!(
+    IotaDocument,
+    JwsAlgorithm,
+    JwkStorage,
+    JwkStorage::generate,
+    rotate_method_iota_document
+  );
+  rotate_method_for_document_type!(
+    IotaDocument,
+    JweAlgorithm,
+    JwkStorageKeyAgreementExt,
+    JwkStorageKeyAgreementExt::generate_key_agreement,
+    rotate_key_agreement_method_iota_document
+  );
 
   #[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
   #[cfg_attr(feature = "send-sync-storage", async_trait)]
@@ -572,6 +1249,21 @@ mod iota_document {
       purge_method_iota_document(self, storage, id).await
     }
 
+    async fn rotate_method<K, I>(
+      &mut self,
+      storage: &Storage<K, I>,
+      id: &DIDUrl,
+      key_type: KeyType,
+      alg: JwsAlgorithm,
+      options: &MethodRotationOptions,
+    ) -> StorageResult<String>
+    where
+      K: JwkStorage,
+      I: KeyIdStorage,
+    {
+      rotate_method_iota_document(self, storage, id, key_type, alg, options).await
+    }
+
     async fn create_jws<K, I>(
       &self,
       storage: &Storage<K, I>,
@@ -626,5 +1318,124 @@ mod iota_document {
         .create_presentation_jwt(presentation, storage, fragment, options, jwt_options)
         .await
     }
+
+    #[cfg(feature = "sd-jwt")]
+    async fn create_sd_jwt_credential<K, I, T>(
+      &self,
+      credential: &Credential<T>,
+      storage: &Storage<K, I>,
+      fragment: &str,
+      disclosable_paths: &[&str],
+      sd_jwt_options: &SdJwtCredentialOptions,
+      jws_options: &JwsSignatureOptions,
+    ) -> StorageResult<SdJwt>
+    where
+      K: JwkStorage,
+      I: KeyIdStorage,
+      T: ToOwned<Owned = T> + Serialize + DeserializeOwned + Sync,
+    {
+      self
+        .core_document()
+        .create_sd_jwt_credential(
+          credential,
+          storage,
+          fragment,
+          disclosable_paths,
+          sd_jwt_options,
+          jws_options,
+        )
+        .await
+    }
+
+    #[cfg(feature = "sd-jwt")]
+    async fn create_sd_jwt_presentation<K, I>(
+      &self,
+      sd_jwt: &SdJwt,
+      storage: &Storage<K, I>,
+      fragment: &str,
+      disclosures: &[&str],
+      kb_options: &KeyBindingJwtOptions,
+      jws_options: &JwsSignatureOptions,
+    ) -> StorageResult<SdJwt>
+    where
+      K: JwkStorage,
+      I: KeyIdStorage,
+    {
+      self
+        .core_document()
+        .create_sd_jwt_presentation(sd_jwt, storage, fragment, disclosures, kb_options, jws_options)
+        .await
+    }
+
+    #[cfg(feature = "sd-jwt")]
+    async fn create_sd_jwt_vc<K, I>(
+      &self,
+      claims: &SdJwtVcClaims,
+      storage: &Storage<K, I>,
+      fragment: &str,
+      disclosable_paths: &[&str],
+      sd_jwt_options: &SdJwtCredentialOptions,
+      jws_options: &JwsSignatureOptions,
+    ) -> StorageResult<SdJwt>
+    where
+      K: JwkStorage,
+      I: KeyIdStorage,
+    {
+      self
+        .core_document()
+        .create_sd_jwt_vc(
+          claims,
+          storage,
+          fragment,
+          disclosable_paths,
+          sd_jwt_options,
+          jws_options,
+        )
+        .await
+    }
+
+    async fn generate_key_agreement_method<K, I>(
+      &mut self,
+      storage: &Storage<K, I>,
+      key_type: KeyType,
+      alg: JweAlgorithm,
+      fragment: Option<&str>,
+      scope: MethodScope,
+    ) -> StorageResult<String>
+    where
+      K: JwkStorageKeyAgreementExt,
+      I: KeyIdStorage,
+    {
+      generate_key_agreement_method_iota_document(self, storage, key_type, alg, fragment, scope).await
+    }
+
+    async fn rotate_key_agreement_method<K, I>(
+      &mut self,
+      storage: &Storage<K, I>,
+      id: &DIDUrl,
+      key_type: KeyType,
+      alg: JweAlgorithm,
+      options: &MethodRotationOptions,
+    ) -> StorageResult<String>
+    where
+      K: JwkStorageKeyAgreementExt,
+      I: KeyIdStorage,
+    {
+      rotate_key_agreement_method_iota_document(self, storage, id, key_type, alg, options).await
+    }
+
+    async fn decrypt_jwe<K, I>(
+      &self,
+      storage: &Storage<K, I>,
+      fragment: &str,
+      jwe: &[u8],
+      format: JweFormat,
+    ) -> StorageResult<DecodedJwe>
+    where
+      K: JwkStorageKeyAgreementExt,
+      I: KeyIdStorage,
+    {
+      self.core_document().decrypt_jwe(storage, fragment, jwe, format).await
+    }
   }
 }
@@ -8,6 +8,9 @@ mod error;
 mod jwk_document_ext;
 #[cfg(feature = "jpt-bbs-plus")]
 mod jwp_document_ext;
+mod rotation_options;
+#[cfg(feature = "sd-jwt")]
+mod sd_jwt_options;
 mod signature_options;
 #[cfg(feature = "jpt-bbs-plus")]
 mod timeframe_revocation_ext;
@@ -20,6 +23,9 @@ pub use error::*;
 pub use jwk_document_ext::*;
 #[cfg(feature = "jpt-bbs-plus")]
 pub use jwp_document_ext::*;
+pub use rotation_options::*;
+#[cfg(feature = "sd-jwt")]
+pub use sd_jwt_options::*;
 pub use signature_options::*;
 #[cfg(feature = "jpt-bbs-plus")]
 pub use timeframe_revocation_ext::*;
@@ -0,0 +1,56 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+
+/// Options for rotating the key material of a verification method with
+/// [`JwkDocumentExt::rotate_method`](crate::storage::JwkDocumentExt::rotate_method).
+#[non_exhaustive]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct MethodRotationOptions {
+  /// The fragment of the replacement method.
+  ///
+  /// If unset, the replacement method takes over the fragment of the rotated method.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub fragment: Option<String>,
+
+  /// Whether to keep the rotated method and its key material for a grace period.
+  ///
+  /// The retained method loses all of its verification relationships, so it can still be used to verify existing
+  /// signatures but should no longer be used to produce new ones. Retaining the rotated method requires setting a
+  /// `fragment` different from the one of the rotated method.
+  pub retain_previous_method: bool,
+
+  /// The end of the grace period, set as the `expires` property of the retained method.
+  ///
+  /// Only used if `retain_previous_method` is set.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub expires: Option<Timestamp>,
+}
+
+impl MethodRotationOptions {
+  /// Creates a new [`MethodRotationOptions`].
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Replace the value of the `fragment` field.
+  pub fn fragment(mut self, value: impl Into<String>) -> Self {
+    self.fragment = Some(value.into());
+    self
+  }
+
+  /// Replace the value of the `retain_previous_method` field.
+  pub fn retain_previous_method(mut self, value: bool) -> Self {
+    self.retain_previous_method = value;
+    self
+  }
+
+  /// Replace the value of the `expires` field.
+  pub fn expires(mut self, value: Timestamp) -> Self {
+    self.expires = Some(value);
+    self
+  }
+}
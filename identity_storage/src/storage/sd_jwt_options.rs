@@ -0,0 +1,83 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+
+/// Options for issuing a selectively disclosable credential with
+/// [`JwkDocumentExt::create_sd_jwt_credential`](crate::storage::JwkDocumentExt::create_sd_jwt_credential).
+#[non_exhaustive]
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct SdJwtCredentialOptions {
+  /// The number of decoy digests to add to the objects or arrays identified by the given JSON pointers.
+  ///
+  /// Decoy digests hide the actual number of selectively disclosable claims. Use the empty pointer `""` to add
+  /// decoys to the top level of the claims set.
+  ///
+  /// [More Info](https://www.ietf.org/archive/id/draft-ietf-oauth-selective-disclosure-jwt-07.html#name-decoy-digests)
+  #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+  pub decoys: BTreeMap<String, usize>,
+
+  /// Additional claims to set on the JWT claims set of the credential.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub custom_claims: Option<Object>,
+}
+
+impl SdJwtCredentialOptions {
+  /// Creates a new [`SdJwtCredentialOptions`].
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds `number` decoy digests to the object or array identified by the JSON pointer `path`.
+  pub fn decoys(mut self, path: impl Into<String>, number: usize) -> Self {
+    self.decoys.insert(path.into(), number);
+    self
+  }
+
+  /// Replace the value of the `custom_claims` field.
+  pub fn custom_claims(mut self, value: Object) -> Self {
+    self.custom_claims = Some(value);
+    self
+  }
+}
+
+/// Options for creating the key binding JWT (KB-JWT) of an SD-JWT with
+/// [`JwkDocumentExt::create_sd_jwt_presentation`](crate::storage::JwkDocumentExt::create_sd_jwt_presentation).
+#[non_exhaustive]
+#[derive(Debug, serde::Serialize, serde::Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyBindingJwtOptions {
+  /// The `nonce` claim, typically a challenge provided by the verifier.
+  pub nonce: String,
+
+  /// The `aud` claim identifying the verifier.
+  pub aud: String,
+
+  /// The `iat` claim.
+  ///
+  /// If unset, the current time is used.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub issued_at: Option<Timestamp>,
+}
+
+impl KeyBindingJwtOptions {
+  /// Creates a new [`KeyBindingJwtOptions`] with the given `nonce` and `aud` claims.
+  pub fn new(nonce: impl Into<String>, aud: impl Into<String>) -> Self {
+    Self {
+      nonce: nonce.into(),
+      aud: aud.into(),
+      issued_at: None,
+    }
+  }
+
+  /// Replace the value of the `issued_at` field.
+  pub fn issued_at(mut self, value: Timestamp) -> Self {
+    self.issued_at = Some(value);
+    self
+  }
+}
@@ -11,6 +11,7 @@ use identity_did::DIDUrl;
 use identity_did::DID;
 use identity_document::document::CoreDocument;
 use identity_document::verifiable::JwsVerificationOptions;
+use identity_ecdsa_verifier::EcDSAJwsVerifier;
 use identity_eddsa_verifier::EdDSAJwsVerifier;
 use identity_verification::jose::jws::JwsAlgorithm;
 use identity_verification::jwk::Jwk;
@@ -123,6 +124,37 @@ async fn create_jws() {
     .is_ok());
 }
 
+#[tokio::test]
+async fn create_jws_ecdsa() {
+  let (mut document, storage) = setup();
+
+  for (key_type, alg) in [
+    (JwkMemStore::P256_KEY_TYPE, JwsAlgorithm::ES256),
+    (JwkMemStore::SECP256K1_KEY_TYPE, JwsAlgorithm::ES256K),
+  ] {
+    let alg_name: String = alg.name().to_string();
+    let fragment: String = document
+      .generate_method(&storage, key_type, alg, None, MethodScope::VerificationMethod)
+      .await
+      .unwrap();
+
+    let jws: Jws = document
+      .create_jws(&storage, &fragment, b"test", &JwsSignatureOptions::new())
+      .await
+      .unwrap();
+
+    let decoded_jws = document
+      .verify_jws(
+        jws.as_str(),
+        None,
+        &EcDSAJwsVerifier::default(),
+        &JwsVerificationOptions::new(),
+      )
+      .unwrap();
+    assert_eq!(decoded_jws.protected.alg().unwrap().name(), alg_name);
+  }
+}
+
 #[tokio::test]
 async fn create_jws_typ() {
   // Default `typ` is "JWT".
@@ -0,0 +1,233 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::convert::FromJson;
+use identity_document::document::CoreDocument;
+use identity_verification::jose::jwe::DecodedJwe;
+use identity_verification::jose::jwe::JweAlgorithm;
+use identity_verification::jose::jwe::JweEncoder;
+use identity_verification::jose::jwe::JweEncryption;
+use identity_verification::jose::jwe::JweFormat;
+use identity_verification::jose::jwe::JweHeader;
+use identity_verification::jose::jwe::JweRecipient;
+use identity_verification::jose::jws::JwsAlgorithm;
+use identity_verification::jwk::Jwk;
+use identity_verification::MethodData;
+use identity_verification::MethodScope;
+use identity_verification::VerificationMethod;
+
+use crate::key_id_storage::KeyIdMemstore;
+use crate::key_storage::JwkMemStore;
+use crate::storage::JwkDocumentExt;
+use crate::JwkStorageDocumentError;
+use crate::Storage;
+
+type MemStorage = Storage<JwkMemStore, KeyIdMemstore>;
+
+const MOCK_DOCUMENT_JSON: &str = r#"
+{
+    "id": "did:bar:Hyx62wPQGyvXCoihZq1BrbUjBRh2LuNxWiiqMkfAuSZr",
+    "verificationMethod": []
+}"#;
+
+const PLAINTEXT: &[u8] = b"the plaintext";
+
+fn setup() -> (CoreDocument, MemStorage) {
+  let mock_document = CoreDocument::from_json(MOCK_DOCUMENT_JSON).unwrap();
+  let storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
+  (mock_document, storage)
+}
+
+fn public_jwk<'a>(document: &'a CoreDocument, fragment: &str) -> (&'a VerificationMethod, &'a Jwk) {
+  let method: &VerificationMethod = document.resolve_method(fragment, None).unwrap();
+  let MethodData::PublicKeyJwk(ref jwk) = method.data() else {
+    panic!("expected a publicKeyJwk method");
+  };
+  (method, jwk)
+}
+
+#[tokio::test]
+async fn decrypt_compact_jwe() {
+  let (mut document, storage) = setup();
+
+  for (key_type, alg) in [
+    (JwkMemStore::X25519_KEY_TYPE, JweAlgorithm::ECDH_ES),
+    (JwkMemStore::X25519_KEY_TYPE, JweAlgorithm::ECDH_ES_A256KW),
+    (JwkMemStore::P256_KEY_TYPE, JweAlgorithm::ECDH_ES_A128KW),
+  ] {
+    let fragment: String = document
+      .generate_key_agreement_method(&storage, key_type, alg, None, MethodScope::key_agreement())
+      .await
+      .unwrap();
+
+    let (method, jwk) = public_jwk(&document, &fragment);
+    let mut header: JweHeader = JweHeader::new();
+    header.set_alg(alg);
+    header.set_enc(JweEncryption::A256GCM);
+    header.set_kid(method.id().to_string());
+
+    let jwe: String = JweEncoder::new(&header)
+      .encrypt(PLAINTEXT, &[JweRecipient::new(jwk)], JweFormat::Compact)
+      .unwrap();
+
+    let decoded: DecodedJwe = document
+      .decrypt_jwe(&storage, &fragment, jwe.as_bytes(), JweFormat::Compact)
+      .await
+      .unwrap();
+    assert_eq!(decoded.plaintext, PLAINTEXT);
+  }
+}
+
+#[tokio::test]
+async fn decrypt_general_jwe_for_each_recipient() {
+  let (mut document, storage) = setup();
+
+  let mut fragments: Vec<String> = Vec::new();
+  for key_type in [JwkMemStore::X25519_KEY_TYPE, JwkMemStore::P256_KEY_TYPE] {
+    let fragment: String = document
+      .generate_key_agreement_method(
+        &storage,
+        key_type,
+        JweAlgorithm::ECDH_ES_A256KW,
+        None,
+        MethodScope::key_agreement(),
+      )
+      .await
+      .unwrap();
+    fragments.push(fragment);
+  }
+
+  let mut protected: JweHeader = JweHeader::new();
+  protected.set_enc(JweEncryption::C20P);
+  let mut unprotected: JweHeader = JweHeader::new();
+  unprotected.set_alg(JweAlgorithm::ECDH_ES_A256KW);
+
+  let recipient_headers: Vec<(JweHeader, &Jwk)> = fragments
+    .iter()
+    .map(|fragment| {
+      let (method, jwk) = public_jwk(&document, fragment);
+      let mut header: JweHeader = JweHeader::new();
+      header.set_kid(method.id().to_string());
+      (header, jwk)
+    })
+    .collect();
+  let recipients: Vec<JweRecipient<'_>> = recipient_headers
+    .iter()
+    .map(|(header, jwk)| JweRecipient::new(jwk).header(header))
+    .collect();
+
+  let jwe: String = JweEncoder::new(&protected)
+    .unprotected(&unprotected)
+    .encrypt(PLAINTEXT, &recipients, JweFormat::General)
+    .unwrap();
+
+  for fragment in fragments.iter() {
+    let decoded: DecodedJwe = document
+      .decrypt_jwe(&storage, fragment, jwe.as_bytes(), JweFormat::General)
+      .await
+      .unwrap();
+    assert_eq!(decoded.plaintext, PLAINTEXT);
+    assert!(decoded.recipient_header.is_some());
+  }
+}
+
+#[tokio::test]
+async fn decrypt_jwe_fails_for_other_methods() {
+  let (mut document, storage) = setup();
+
+  let recipient_fragment: String = document
+    .generate_key_agreement_method(
+      &storage,
+      JwkMemStore::X25519_KEY_TYPE,
+      JweAlgorithm::ECDH_ES,
+      None,
+      MethodScope::key_agreement(),
+    )
+    .await
+    .unwrap();
+  let other_fragment: String = document
+    .generate_key_agreement_method(
+      &storage,
+      JwkMemStore::X25519_KEY_TYPE,
+      JweAlgorithm::ECDH_ES,
+      None,
+      MethodScope::key_agreement(),
+    )
+    .await
+    .unwrap();
+  let signing_fragment: String = document
+    .generate_method(
+      &storage,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      None,
+      MethodScope::VerificationMethod,
+    )
+    .await
+    .unwrap();
+
+  let (method, jwk) = public_jwk(&document, &recipient_fragment);
+  let mut header: JweHeader = JweHeader::new();
+  header.set_alg(JweAlgorithm::ECDH_ES);
+  header.set_enc(JweEncryption::A256GCM);
+  header.set_kid(method.id().to_string());
+  let jwe: String = JweEncoder::new(&header)
+    .encrypt(PLAINTEXT, &[JweRecipient::new(jwk)], JweFormat::Compact)
+    .unwrap();
+
+  // INVALID: The JWE was not encrypted for the other method.
+  let err = document
+    .decrypt_jwe(&storage, &other_fragment, jwe.as_bytes(), JweFormat::Compact)
+    .await
+    .unwrap_err();
+  assert!(matches!(err, JwkStorageDocumentError::JweRecipientNotFound));
+
+  // INVALID: The JWE is not encoded in the expected format.
+  let err = document
+    .decrypt_jwe(&storage, &recipient_fragment, jwe.as_bytes(), JweFormat::General)
+    .await
+    .unwrap_err();
+  assert!(matches!(err, JwkStorageDocumentError::DecryptionError(_)));
+
+  // INVALID: An unknown method cannot be used.
+  let err = document
+    .decrypt_jwe(&storage, "#unknown", jwe.as_bytes(), JweFormat::Compact)
+    .await
+    .unwrap_err();
+  assert!(matches!(err, JwkStorageDocumentError::MethodNotFound));
+
+  // INVALID: Signing keys cannot be used for key agreement.
+  let (method, _) = public_jwk(&document, &signing_fragment);
+  let mut header: JweHeader = JweHeader::new();
+  header.set_alg(JweAlgorithm::ECDH_ES);
+  header.set_enc(JweEncryption::A256GCM);
+  header.set_kid(method.id().to_string());
+  let (_, recipient_jwk) = public_jwk(&document, &recipient_fragment);
+  let jwe: String = JweEncoder::new(&header)
+    .encrypt(PLAINTEXT, &[JweRecipient::new(recipient_jwk)], JweFormat::Compact)
+    .unwrap();
+  let err = document
+    .decrypt_jwe(&storage, &signing_fragment, jwe.as_bytes(), JweFormat::Compact)
+    .await
+    .unwrap_err();
+  assert!(matches!(err, JwkStorageDocumentError::KeyStorageError(_)));
+}
+
+#[tokio::test]
+async fn key_agreement_method_incompatible_alg() {
+  let (mut document, storage) = setup();
+
+  // INVALID: Ed25519 keys cannot be used for key agreement.
+  let err = document
+    .generate_key_agreement_method(
+      &storage,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JweAlgorithm::ECDH_ES,
+      None,
+      MethodScope::key_agreement(),
+    )
+    .await
+    .unwrap_err();
+  assert!(matches!(err, JwkStorageDocumentError::KeyStorageError(_)));
+  assert_eq!(storage.key_storage().count().await, 0);
+}
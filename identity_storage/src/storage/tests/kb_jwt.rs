@@ -4,7 +4,10 @@
 use super::test_utils::setup_iotadocument;
 use super::test_utils::Setup;
 use crate::JwkDocumentExt;
+use crate::JwkStorageDocumentError;
 use crate::JwsSignatureOptions;
+use crate::KeyBindingJwtOptions;
+use crate::SdJwtCredentialOptions;
 use identity_core::common::Duration;
 use identity_core::common::Object;
 use identity_core::common::Timestamp;
@@ -13,6 +16,8 @@ use identity_core::convert::FromJson;
 use identity_core::convert::ToJson;
 use identity_credential::credential::Credential;
 use identity_credential::credential::CredentialBuilder;
+use identity_credential::credential::CredentialV2;
+use identity_credential::credential::CredentialV2Builder;
 use identity_credential::credential::Jws;
 use identity_credential::credential::Subject;
 use identity_credential::sd_jwt_payload::KeyBindingJwtClaims;
@@ -27,12 +32,18 @@ use identity_credential::validator::KeyBindingJwtError;
 use identity_credential::validator::SdJwtCredentialValidator;
 use identity_eddsa_verifier::EdDSAJwsVerifier;
 use identity_iota_core::IotaDocument;
+use identity_verification::jws::Decoder;
 use serde_json::json;
+use serde_json::Value;
 
 const NONCE: &str = "nonce-test";
 const VERIFIER_ID: &str = "did:test:verifier";
 
 async fn setup_test() -> (Setup<IotaDocument, IotaDocument>, Credential, SdJwt) {
+  setup_test_with_kb_jwt_typ("kb+jwt").await
+}
+
+async fn setup_test_with_kb_jwt_typ(typ: &str) -> (Setup<IotaDocument, IotaDocument>, Credential, SdJwt) {
   let setup: Setup<IotaDocument, IotaDocument> = setup_iotadocument(None, None).await;
 
   let subject: Subject = Subject::from_json_value(json!({
@@ -92,7 +103,7 @@ async fn setup_test() -> (Setup<IotaDocument, IotaDocument>, Credential, SdJwt)
   .unwrap();
 
   // Setting the `typ` in the header is required.
-  let options = JwsSignatureOptions::new().typ(KeyBindingJwtClaims::KB_JWT_HEADER_TYP);
+  let options = JwsSignatureOptions::new().typ(typ);
 
   // Create the KB-JWT.
   let kb_jwt: Jws = setup
@@ -125,6 +136,69 @@ async fn sd_jwt_validation() {
   assert_eq!(validation.credential, credential);
 }
 
+#[tokio::test]
+async fn sd_jwt_v2_validation() {
+  let setup: Setup<IotaDocument, IotaDocument> = setup_iotadocument(None, None).await;
+
+  let subject: Subject = Subject::from_json_value(json!({
+    "id": setup.subject_doc.id().to_string(),
+    "degree": {
+      "type": "BachelorDegree",
+      "name": "Bachelor of Science in Mechanical Engineering"
+    }
+  }))
+  .unwrap();
+
+  let credential: CredentialV2 = CredentialV2Builder::default()
+    .id(Url::parse("https://example.edu/credentials/3732").unwrap())
+    .issuer(Url::parse(setup.issuer_doc.id().to_string()).unwrap())
+    .type_("AddressCredential")
+    .valid_from(Timestamp::now_utc())
+    .subject(subject)
+    .build()
+    .unwrap();
+
+  // The claims set of a v2.0 credential is the credential itself, without a `vc` wrapper.
+  let payload = credential.serialize_jwt().unwrap();
+  let mut encoder = SdObjectEncoder::new(&payload).unwrap();
+  let disclosures: Vec<String> = vec![
+    encoder
+      .conceal("/credentialSubject/degree/type", None)
+      .unwrap()
+      .to_string(),
+    encoder
+      .conceal("/credentialSubject/degree/name", None)
+      .unwrap()
+      .to_string(),
+  ];
+  encoder.add_sd_alg_property();
+  let encoded_payload = encoder.try_to_string().unwrap();
+
+  let jwt: Jws = setup
+    .issuer_doc
+    .create_jws(
+      &setup.issuer_storage,
+      &setup.issuer_method_fragment,
+      encoded_payload.as_bytes(),
+      &JwsSignatureOptions::default(),
+    )
+    .await
+    .unwrap();
+  let sd_jwt = SdJwt::new(jwt.into(), disclosures, None);
+
+  let validator =
+    SdJwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default(), SdObjectDecoder::new_with_sha256());
+  let validation = validator
+    .validate_credential_v2::<_, Object>(
+      &sd_jwt,
+      &setup.issuer_doc,
+      &JwtCredentialValidationOptions::default(),
+      FailFast::FirstError,
+    )
+    .unwrap();
+  assert_eq!(validation.credential, credential);
+}
+
 #[tokio::test]
 async fn kb_validation() {
   let (setup, _credential, sd_jwt) = setup_test().await;
@@ -136,6 +210,26 @@ async fn kb_validation() {
     .expect("KB validation failed!");
 }
 
+#[tokio::test]
+async fn kb_validation_with_legacy_typ() {
+  // KB-JWTs created with the previously documented `typ`, which has a leading whitespace, are still accepted.
+  let (setup, _credential, sd_jwt) = setup_test_with_kb_jwt_typ(KeyBindingJwtClaims::KB_JWT_HEADER_TYP).await;
+  let decoder = SdObjectDecoder::new_with_sha256();
+  let validator = SdJwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default(), decoder);
+  let options = KeyBindingJWTValidationOptions::new().nonce(NONCE).aud(VERIFIER_ID);
+  validator
+    .validate_key_binding_jwt(&sd_jwt, &setup.subject_doc, &options)
+    .expect("KB validation failed!");
+
+  // Any other `typ` is rejected.
+  let (setup, _credential, sd_jwt) = setup_test_with_kb_jwt_typ("jwt").await;
+  let kb_validation = validator.validate_key_binding_jwt(&sd_jwt, &setup.subject_doc, &options);
+  assert!(matches!(
+    kb_validation.unwrap_err(),
+    KeyBindingJwtError::InvalidHeaderTypValue
+  ));
+}
+
 #[tokio::test]
 async fn kb_too_early() {
   let (setup, _credential, sd_jwt) = setup_test().await;
@@ -189,7 +283,7 @@ async fn kb_in_the_future() {
   .unwrap();
 
   // Setting the `typ` in the header is required.
-  let options = JwsSignatureOptions::new().typ(KeyBindingJwtClaims::KB_JWT_HEADER_TYP);
+  let options = JwsSignatureOptions::new().typ("kb+jwt");
 
   // Create the KB-JWT.
   let kb_jwt: Jws = setup
@@ -240,3 +334,188 @@ async fn kb_nonce() {
   let kb_validation = validator.validate_key_binding_jwt(&sd_jwt, &setup.subject_doc, &options);
   assert!(matches!(kb_validation.err().unwrap(), KeyBindingJwtError::InvalidNonce));
 }
+
+/// Issues an SD-JWT for the credential of [`setup_test`] with `create_sd_jwt_credential`.
+async fn setup_issued_sd_jwt() -> (Setup<IotaDocument, IotaDocument>, Credential, SdJwt) {
+  let (setup, credential, _sd_jwt) = setup_test().await;
+  let sd_jwt: SdJwt = setup
+    .issuer_doc
+    .create_sd_jwt_credential(
+      &credential,
+      &setup.issuer_storage,
+      &setup.issuer_method_fragment,
+      &["/vc/credentialSubject/degree/type", "/vc/credentialSubject/degree/name"],
+      &SdJwtCredentialOptions::default(),
+      &JwsSignatureOptions::default(),
+    )
+    .await
+    .unwrap();
+  assert_eq!(sd_jwt.disclosures.len(), 2);
+  (setup, credential, sd_jwt)
+}
+
+#[tokio::test]
+async fn sd_jwt_presentation_validation() {
+  let (setup, credential, sd_jwt) = setup_issued_sd_jwt().await;
+  let disclosures: Vec<&str> = sd_jwt.disclosures.iter().map(String::as_str).collect();
+  let sd_jwt: SdJwt = setup
+    .subject_doc
+    .create_sd_jwt_presentation(
+      &sd_jwt,
+      &setup.subject_storage,
+      &setup.subject_method_fragment,
+      &disclosures,
+      &KeyBindingJwtOptions::new(NONCE, VERIFIER_ID),
+      &JwsSignatureOptions::default(),
+    )
+    .await
+    .unwrap();
+
+  let kb_jwt = Decoder::new()
+    .decode_compact_serialization(sd_jwt.key_binding_jwt.as_deref().unwrap().as_bytes(), None)
+    .unwrap();
+  assert_eq!(kb_jwt.protected_header().unwrap().typ(), Some("kb+jwt"));
+
+  let validator =
+    SdJwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default(), SdObjectDecoder::new_with_sha256());
+  let validation = validator
+    .validate_credential::<_, Object>(
+      &sd_jwt,
+      &setup.issuer_doc,
+      &JwtCredentialValidationOptions::default(),
+      FailFast::FirstError,
+    )
+    .unwrap();
+  assert_eq!(validation.credential, credential);
+
+  let options = KeyBindingJWTValidationOptions::new().nonce(NONCE).aud(VERIFIER_ID);
+  validator
+    .validate_key_binding_jwt(&sd_jwt, &setup.subject_doc, &options)
+    .unwrap();
+}
+
+#[tokio::test]
+async fn sd_jwt_presentation_kb_in_the_future() {
+  let (setup, _credential, sd_jwt) = setup_issued_sd_jwt().await;
+  let disclosures: Vec<&str> = sd_jwt.disclosures.iter().map(String::as_str).collect();
+  let kb_options = KeyBindingJwtOptions::new(NONCE, VERIFIER_ID)
+    .issued_at(Timestamp::now_utc().checked_add(Duration::seconds(30)).unwrap());
+  let sd_jwt: SdJwt = setup
+    .subject_doc
+    .create_sd_jwt_presentation(
+      &sd_jwt,
+      &setup.subject_storage,
+      &setup.subject_method_fragment,
+      &disclosures,
+      &kb_options,
+      &JwsSignatureOptions::default(),
+    )
+    .await
+    .unwrap();
+
+  let decoder = SdObjectDecoder::new_with_sha256();
+  let validator = SdJwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default(), decoder);
+  let options = KeyBindingJWTValidationOptions::new().nonce(NONCE).aud(VERIFIER_ID);
+  let kb_validation = validator.validate_key_binding_jwt(&sd_jwt, &setup.subject_doc, &options);
+  assert!(matches!(
+    kb_validation.err().unwrap(),
+    KeyBindingJwtError::IssuanceDate(_)
+  ));
+}
+
+#[tokio::test]
+async fn sd_jwt_decoys() {
+  let (setup, credential, _sd_jwt) = setup_test().await;
+  let sd_jwt: SdJwt = setup
+    .issuer_doc
+    .create_sd_jwt_credential(
+      &credential,
+      &setup.issuer_storage,
+      &setup.issuer_method_fragment,
+      &["/vc/credentialSubject/degree/type"],
+      &SdJwtCredentialOptions::new().decoys("/vc/credentialSubject/degree", 3),
+      &JwsSignatureOptions::default(),
+    )
+    .await
+    .unwrap();
+  assert_eq!(sd_jwt.disclosures.len(), 1);
+
+  let decoded = Decoder::new()
+    .decode_compact_serialization(sd_jwt.jwt.as_bytes(), None)
+    .unwrap();
+  let claims: Value = serde_json::from_slice(decoded.claims()).unwrap();
+  assert_eq!(
+    claims["vc"]["credentialSubject"]["degree"]["_sd"]
+      .as_array()
+      .unwrap()
+      .len(),
+    4
+  );
+
+  // Decoys do not affect validation.
+  let validator =
+    SdJwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default(), SdObjectDecoder::new_with_sha256());
+  let validation = validator
+    .validate_credential::<_, Object>(
+      &sd_jwt,
+      &setup.issuer_doc,
+      &JwtCredentialValidationOptions::default(),
+      FailFast::FirstError,
+    )
+    .unwrap();
+  assert_eq!(validation.credential, credential);
+}
+
+#[tokio::test]
+async fn sd_jwt_partial_disclosure() {
+  let (setup, _credential, sd_jwt) = setup_issued_sd_jwt().await;
+  let sd_jwt: SdJwt = setup
+    .subject_doc
+    .create_sd_jwt_presentation(
+      &sd_jwt,
+      &setup.subject_storage,
+      &setup.subject_method_fragment,
+      &[sd_jwt.disclosures[0].as_str()],
+      &KeyBindingJwtOptions::new(NONCE, VERIFIER_ID),
+      &JwsSignatureOptions::default(),
+    )
+    .await
+    .unwrap();
+  assert_eq!(sd_jwt.disclosures.len(), 1);
+
+  let validator =
+    SdJwtCredentialValidator::with_signature_verifier(EdDSAJwsVerifier::default(), SdObjectDecoder::new_with_sha256());
+  let validation = validator
+    .validate_credential::<_, Object>(
+      &sd_jwt,
+      &setup.issuer_doc,
+      &JwtCredentialValidationOptions::default(),
+      FailFast::FirstError,
+    )
+    .unwrap();
+  let degree = validation.credential.credential_subject.first().unwrap().properties["degree"].clone();
+  assert_eq!(degree.get("type").unwrap(), "BachelorDegree");
+  assert!(degree.get("name").is_none());
+
+  let options = KeyBindingJWTValidationOptions::new().nonce(NONCE).aud(VERIFIER_ID);
+  validator
+    .validate_key_binding_jwt(&sd_jwt, &setup.subject_doc, &options)
+    .unwrap();
+}
+
+#[tokio::test]
+async fn sd_jwt_presentation_unknown_disclosure() {
+  let (setup, _credential, sd_jwt) = setup_issued_sd_jwt().await;
+  let result = setup
+    .subject_doc
+    .create_sd_jwt_presentation(
+      &sd_jwt,
+      &setup.subject_storage,
+      &setup.subject_method_fragment,
+      &["WyJzYWx0IiwgIm5hbWUiLCAidmFsdWUiXQ"],
+      &KeyBindingJwtOptions::new(NONCE, VERIFIER_ID),
+      &JwsSignatureOptions::default(),
+    )
+    .await;
+  assert!(matches!(result.unwrap_err(), JwkStorageDocumentError::InvalidSdJwt(_)));
+}
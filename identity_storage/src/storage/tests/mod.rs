@@ -4,6 +4,11 @@
 mod api;
 mod credential_jws;
 mod credential_validation;
+mod jwe;
+#[cfg(feature = "sd-jwt")]
 mod kb_jwt;
 mod presentation_validation;
+mod rotation;
+#[cfg(feature = "sd-jwt")]
+mod sd_jwt_vc;
 pub(crate) mod test_utils;
@@ -0,0 +1,324 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_core::common::Timestamp;
+use identity_core::convert::FromJson;
+use identity_credential::credential::Jws;
+use identity_did::DIDUrl;
+use identity_document::document::CoreDocument;
+use identity_document::verifiable::JwsVerificationOptions;
+use identity_eddsa_verifier::EdDSAJwsVerifier;
+use identity_verification::jose::jwe::JweAlgorithm;
+use identity_verification::jose::jwe::JweEncoder;
+use identity_verification::jose::jwe::JweEncryption;
+use identity_verification::jose::jwe::JweFormat;
+use identity_verification::jose::jwe::JweHeader;
+use identity_verification::jose::jwe::JweRecipient;
+use identity_verification::jose::jws::JwsAlgorithm;
+use identity_verification::MethodData;
+use identity_verification::MethodRelationship;
+use identity_verification::MethodScope;
+use identity_verification::VerificationMethod;
+
+use crate::key_id_storage::KeyIdMemstore;
+use crate::key_storage::JwkMemStore;
+use crate::storage::JwkDocumentExt;
+use crate::storage::JwsSignatureOptions;
+use crate::storage::MethodRotationOptions;
+use crate::JwkStorageDocumentError;
+use crate::Storage;
+
+type MemStorage = Storage<JwkMemStore, KeyIdMemstore>;
+
+const MOCK_DOCUMENT_JSON: &str = r#"
+{
+    "id": "did:bar:Hyx62wPQGyvXCoihZq1BrbUjBRh2LuNxWiiqMkfAuSZr",
+    "verificationMethod": [
+      {
+        "id": "did:bar:Hyx62wPQGyvXCoihZq1BrbUjBRh2LuNxWiiqMkfAuSZr#root",
+        "controller": "did:bar:Hyx62wPQGyvXCoihZq1BrbUjBRh2LuNxWiiqMkfAuSZr",
+        "type": "Ed25519VerificationKey2018",
+        "publicKeyMultibase": "zHyx62wPQGyvXCoihZq1BrbUjBRh2LuNxWiiqMkfAuSZr"
+      }
+    ]
+}"#;
+
+async fn setup(scope: MethodScope) -> (CoreDocument, MemStorage, DIDUrl) {
+  let mut document = CoreDocument::from_json(MOCK_DOCUMENT_JSON).unwrap();
+  let storage = Storage::new(JwkMemStore::new(), KeyIdMemstore::new());
+
+  let fragment: String = document
+    .generate_method(
+      &storage,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      Some("key-1"),
+      scope,
+    )
+    .await
+    .unwrap();
+  let method_id: DIDUrl = document.resolve_method(&fragment, None).unwrap().id().clone();
+
+  (document, storage, method_id)
+}
+
+fn verify(document: &CoreDocument, jws: &Jws) -> bool {
+  document
+    .verify_jws(
+      jws.as_str(),
+      None,
+      &EdDSAJwsVerifier::default(),
+      &JwsVerificationOptions::default(),
+    )
+    .is_ok()
+}
+
+#[tokio::test]
+async fn rotate_method_under_same_fragment() {
+  let (mut document, storage, method_id) = setup(MethodScope::VerificationMethod).await;
+  for relationship in [MethodRelationship::Authentication, MethodRelationship::AssertionMethod] {
+    document.attach_method_relationship(&method_id, relationship).unwrap();
+  }
+  let previous_method: VerificationMethod = document.resolve_method(&method_id, None).unwrap().clone();
+  let previous_jws: Jws = document
+    .create_jws(&storage, "key-1", b"test", &JwsSignatureOptions::new())
+    .await
+    .unwrap();
+
+  let fragment: String = document
+    .rotate_method(
+      &storage,
+      &method_id,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      &MethodRotationOptions::new(),
+    )
+    .await
+    .unwrap();
+  assert_eq!(fragment, "key-1");
+
+  // The replacement method keeps the id and relationships of the rotated method.
+  let method: &VerificationMethod = document.resolve_method(&method_id, None).unwrap();
+  assert_ne!(method.data(), previous_method.data());
+  assert!(document
+    .resolve_method(&method_id, Some(MethodScope::VerificationMethod))
+    .is_some());
+  assert!(document
+    .resolve_method(&method_id, Some(MethodScope::authentication()))
+    .is_some());
+  assert!(document
+    .resolve_method(&method_id, Some(MethodScope::assertion_method()))
+    .is_some());
+  assert!(document
+    .resolve_method(&method_id, Some(MethodScope::capability_invocation()))
+    .is_none());
+
+  // The previous key is deleted, the new key signs in its place.
+  assert_eq!(storage.key_storage().count().await, 1);
+  assert_eq!(storage.key_id_storage().count().await, 1);
+  assert!(!verify(&document, &previous_jws));
+  let jws: Jws = document
+    .create_jws(&storage, &fragment, b"test", &JwsSignatureOptions::new())
+    .await
+    .unwrap();
+  assert!(verify(&document, &jws));
+}
+
+#[tokio::test]
+async fn rotate_embedded_method_under_new_fragment() {
+  let (mut document, storage, method_id) = setup(MethodScope::assertion_method()).await;
+
+  let fragment: String = document
+    .rotate_method(
+      &storage,
+      &method_id,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      &MethodRotationOptions::new().fragment("#key-2"),
+    )
+    .await
+    .unwrap();
+  assert_eq!(fragment, "key-2");
+
+  assert!(document.resolve_method(&method_id, None).is_none());
+  assert!(document
+    .resolve_method("key-2", Some(MethodScope::assertion_method()))
+    .is_some());
+  assert_eq!(storage.key_storage().count().await, 1);
+  assert_eq!(storage.key_id_storage().count().await, 1);
+}
+
+#[tokio::test]
+async fn rotate_method_retaining_previous_method() {
+  let (mut document, storage, method_id) = setup(MethodScope::authentication()).await;
+  let previous_jws: Jws = document
+    .create_jws(&storage, "key-1", b"test", &JwsSignatureOptions::new())
+    .await
+    .unwrap();
+  let expires: Timestamp = Timestamp::parse("2030-01-01T00:00:00Z").unwrap();
+
+  // INVALID: the retained method cannot keep its fragment.
+  let err = document
+    .rotate_method(
+      &storage,
+      &method_id,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      &MethodRotationOptions::new().retain_previous_method(true),
+    )
+    .await
+    .unwrap_err();
+  assert!(matches!(err, JwkStorageDocumentError::FragmentAlreadyExists));
+  assert_eq!(storage.key_storage().count().await, 1);
+
+  let fragment: String = document
+    .rotate_method(
+      &storage,
+      &method_id,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      &MethodRotationOptions::new()
+        .fragment("key-2")
+        .retain_previous_method(true)
+        .expires(expires),
+    )
+    .await
+    .unwrap();
+
+  assert!(document
+    .resolve_method(&fragment, Some(MethodScope::authentication()))
+    .is_some());
+
+  // The retained method has no relationships left, but can still verify previous signatures.
+  let retained_method: &VerificationMethod = document
+    .resolve_method(&method_id, Some(MethodScope::VerificationMethod))
+    .unwrap();
+  assert_eq!(
+    retained_method.properties().get("expires").unwrap().as_str(),
+    Some(expires.to_string().as_str())
+  );
+  assert!(document
+    .resolve_method(&method_id, Some(MethodScope::authentication()))
+    .is_none());
+  assert!(verify(&document, &previous_jws));
+  assert_eq!(storage.key_storage().count().await, 2);
+  assert_eq!(storage.key_id_storage().count().await, 2);
+
+  // The retained method can be purged once the grace period is over.
+  document.purge_method(&storage, &method_id).await.unwrap();
+  assert_eq!(storage.key_storage().count().await, 1);
+  assert_eq!(storage.key_id_storage().count().await, 1);
+}
+
+#[tokio::test]
+async fn failed_rotation_is_reverted() {
+  let (mut document, storage, method_id) = setup(MethodScope::VerificationMethod).await;
+  document
+    .attach_method_relationship(&method_id, MethodRelationship::Authentication)
+    .unwrap();
+  let document_before: CoreDocument = document.clone();
+
+  // INVALID: key generation fails.
+  let err = document
+    .rotate_method(
+      &storage,
+      &method_id,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::ES256,
+      &MethodRotationOptions::new(),
+    )
+    .await
+    .unwrap_err();
+  assert!(matches!(err, JwkStorageDocumentError::KeyStorageError(_)));
+  assert_eq!(document, document_before);
+
+  // INVALID: the new fragment is already in use, the generated key is deleted.
+  let err = document
+    .rotate_method(
+      &storage,
+      &method_id,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      &MethodRotationOptions::new().fragment("root"),
+    )
+    .await
+    .unwrap_err();
+  assert!(matches!(err, JwkStorageDocumentError::FragmentAlreadyExists));
+  assert_eq!(document, document_before);
+  assert_eq!(storage.key_storage().count().await, 1);
+  assert_eq!(storage.key_id_storage().count().await, 1);
+
+  // INVALID: the key material of the method is not in the storage.
+  let root_id: DIDUrl = document.resolve_method("root", None).unwrap().id().clone();
+  let err = document
+    .rotate_method(
+      &storage,
+      &root_id,
+      JwkMemStore::ED25519_KEY_TYPE,
+      JwsAlgorithm::EdDSA,
+      &MethodRotationOptions::new(),
+    )
+    .await
+    .unwrap_err();
+  assert!(matches!(err, JwkStorageDocumentError::KeyIdStorageError(_)));
+  assert_eq!(document, document_before);
+  assert_eq!(storage.key_storage().count().await, 1);
+}
+
+#[tokio::test]
+async fn rotate_key_agreement_method() {
+  let (mut document, storage, _) = setup(MethodScope::VerificationMethod).await;
+  let encrypt = |method: &VerificationMethod| -> String {
+    let MethodData::PublicKeyJwk(ref jwk) = method.data() else {
+      panic!("expected a publicKeyJwk method");
+    };
+    let mut header: JweHeader = JweHeader::new();
+    header.set_alg(JweAlgorithm::ECDH_ES);
+    header.set_enc(JweEncryption::A256GCM);
+    JweEncoder::new(&header)
+      .encrypt(b"test", &[JweRecipient::new(jwk)], JweFormat::Compact)
+      .unwrap()
+  };
+
+  let fragment: String = document
+    .generate_key_agreement_method(
+      &storage,
+      JwkMemStore::X25519_KEY_TYPE,
+      JweAlgorithm::ECDH_ES,
+      Some("key-agreement-1"),
+      MethodScope::key_agreement(),
+    )
+    .await
+    .unwrap();
+  let method_id: DIDUrl = document.resolve_method(&fragment, None).unwrap().id().clone();
+  let previous_jwe: String = encrypt(document.resolve_method(&method_id, None).unwrap());
+
+  let fragment: String = document
+    .rotate_key_agreement_method(
+      &storage,
+      &method_id,
+      JwkMemStore::X25519_KEY_TYPE,
+      JweAlgorithm::ECDH_ES,
+      &MethodRotationOptions::new(),
+    )
+    .await
+    .unwrap();
+  assert_eq!(fragment, "key-agreement-1");
+  assert!(document
+    .resolve_method(&method_id, Some(MethodScope::key_agreement()))
+    .is_some());
+
+  // The previous key is deleted, the new key decrypts in its place.
+  assert_eq!(storage.key_storage().count().await, 2);
+  assert_eq!(storage.key_id_storage().count().await, 2);
+  assert!(document
+    .decrypt_jwe(&storage, &fragment, previous_jwe.as_bytes(), JweFormat::Compact)
+    .await
+    .is_err());
+  let jwe: String = encrypt(document.resolve_method(&method_id, None).unwrap());
+  let decrypted = document
+    .decrypt_jwe(&storage, &fragment, jwe.as_bytes(), JweFormat::Compact)
+    .await
+    .unwrap();
+  assert_eq!(decrypted.plaintext, b"test");
+}
@@ -0,0 +1,191 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use super::test_utils::setup_iotadocument;
+use super::test_utils::Setup;
+use crate::JwkDocumentExt;
+use crate::JwkStorageDocumentError;
+use crate::JwsSignatureOptions;
+use crate::KeyBindingJwtOptions;
+use crate::SdJwtCredentialOptions;
+use identity_core::common::Object;
+use identity_core::common::Timestamp;
+use identity_core::common::Url;
+use identity_credential::sd_jwt_payload::SdJwt;
+use identity_credential::sd_jwt_payload::SdObjectDecoder;
+use identity_credential::sd_jwt_vc::SdJwtVcClaims;
+use identity_credential::sd_jwt_vc::SdJwtVcValidationError;
+use identity_credential::sd_jwt_vc::SdJwtVcValidationOptions;
+use identity_credential::sd_jwt_vc::SdJwtVcValidator;
+use identity_credential::validator::KeyBindingJWTValidationOptions;
+use identity_eddsa_verifier::EdDSAJwsVerifier;
+use identity_iota_core::IotaDocument;
+use identity_verification::jwk::Jwk;
+use identity_verification::jws::Decoder;
+use identity_verification::MethodData;
+use serde_json::json;
+
+const NONCE: &str = "nonce-test";
+const VERIFIER_ID: &str = "did:test:verifier";
+
+fn holder_key(setup: &Setup<IotaDocument, IotaDocument>) -> Jwk {
+  let method = setup
+    .subject_doc
+    .resolve_method(&setup.subject_method_fragment, None)
+    .unwrap();
+  match method.data() {
+    MethodData::PublicKeyJwk(jwk) => jwk.clone(),
+    _ => panic!("not a jwk"),
+  }
+}
+
+async fn setup_test() -> (Setup<IotaDocument, IotaDocument>, SdJwt) {
+  let setup: Setup<IotaDocument, IotaDocument> = setup_iotadocument(None, None).await;
+
+  let claims: SdJwtVcClaims = SdJwtVcClaims::builder(
+    Url::parse(setup.issuer_doc.id().to_string()).unwrap(),
+    "https://credentials.example.com/identity_credential",
+  )
+  .issuance_date(Timestamp::now_utc())
+  .holder_key(holder_key(&setup))
+  .claim("given_name", "John")
+  .claim("family_name", "Doe")
+  .claim(
+    "address",
+    json!({ "street_address": "123 Main St", "locality": "Anytown" }),
+  )
+  .build()
+  .unwrap();
+
+  let sd_jwt: SdJwt = setup
+    .issuer_doc
+    .create_sd_jwt_vc(
+      &claims,
+      &setup.issuer_storage,
+      &setup.issuer_method_fragment,
+      &["/given_name", "/address/street_address"],
+      &SdJwtCredentialOptions::default(),
+      &JwsSignatureOptions::default(),
+    )
+    .await
+    .unwrap();
+
+  (setup, sd_jwt)
+}
+
+#[tokio::test]
+async fn sd_jwt_vc_header_typ() {
+  let (_setup, sd_jwt) = setup_test().await;
+  let decoded = Decoder::new()
+    .decode_compact_serialization(sd_jwt.jwt.as_bytes(), None)
+    .unwrap();
+  assert_eq!(
+    decoded.protected_header().and_then(|header| header.typ()),
+    Some(SdJwtVcClaims::HEADER_TYP)
+  );
+  assert_eq!(sd_jwt.disclosures.len(), 2);
+}
+
+#[tokio::test]
+async fn sd_jwt_vc_presentation_with_key_binding() {
+  let (setup, sd_jwt) = setup_test().await;
+
+  // Only disclose the street address.
+  let disclosures: Vec<&str> = vec![sd_jwt.disclosures[1].as_str()];
+  let presentation: SdJwt = setup
+    .subject_doc
+    .create_sd_jwt_presentation(
+      &sd_jwt,
+      &setup.subject_storage,
+      &setup.subject_method_fragment,
+      &disclosures,
+      &KeyBindingJwtOptions::new(NONCE, VERIFIER_ID),
+      &JwsSignatureOptions::default(),
+    )
+    .await
+    .unwrap();
+
+  let validator =
+    SdJwtVcValidator::with_signature_verifier(EdDSAJwsVerifier::default(), SdObjectDecoder::new_with_sha256());
+  let options =
+    SdJwtVcValidationOptions::new().key_binding(KeyBindingJWTValidationOptions::new().nonce(NONCE).aud(VERIFIER_ID));
+  let decoded = validator
+    .validate(&presentation, &[&setup.issuer_doc], &options)
+    .unwrap();
+
+  assert!(decoded.key_binding.is_some());
+  assert!(!decoded.claims.properties.contains_key("given_name"));
+  assert_eq!(decoded.claims.properties["family_name"], "Doe");
+  assert_eq!(decoded.claims.properties["address"]["street_address"], "123 Main St");
+
+  // The key binding JWT must be signed by the key in the `cnf` claim.
+  let options = SdJwtVcValidationOptions::new().key_binding(
+    KeyBindingJWTValidationOptions::new()
+      .nonce("other-nonce")
+      .aud(VERIFIER_ID),
+  );
+  let result = validator.validate(&presentation, &[&setup.issuer_doc], &options);
+  assert!(matches!(result, Err(SdJwtVcValidationError::KeyBindingJwtError(_))));
+
+  let forged: SdJwt = setup
+    .issuer_doc
+    .create_sd_jwt_presentation(
+      &sd_jwt,
+      &setup.issuer_storage,
+      &setup.issuer_method_fragment,
+      &disclosures,
+      &KeyBindingJwtOptions::new(NONCE, VERIFIER_ID),
+      &JwsSignatureOptions::default(),
+    )
+    .await
+    .unwrap();
+  let options =
+    SdJwtVcValidationOptions::new().key_binding(KeyBindingJWTValidationOptions::new().nonce(NONCE).aud(VERIFIER_ID));
+  let result = validator.validate(&forged, &[&setup.issuer_doc], &options);
+  assert!(matches!(result, Err(SdJwtVcValidationError::KeyBindingJwtError(_))));
+}
+
+#[tokio::test]
+async fn sd_jwt_vc_rejects_non_disclosable_claims() {
+  let (setup, _sd_jwt) = setup_test().await;
+  let claims: SdJwtVcClaims = SdJwtVcClaims::builder(
+    Url::parse(setup.issuer_doc.id().to_string()).unwrap(),
+    "IdentityCredential",
+  )
+  .holder_key(holder_key(&setup))
+  .build()
+  .unwrap();
+
+  for path in ["/vct", "/cnf/jwk"] {
+    let result = setup
+      .issuer_doc
+      .create_sd_jwt_vc(
+        &claims,
+        &setup.issuer_storage,
+        &setup.issuer_method_fragment,
+        &[path],
+        &SdJwtCredentialOptions::default(),
+        &JwsSignatureOptions::default(),
+      )
+      .await;
+    assert!(matches!(result, Err(JwkStorageDocumentError::EncodingError(_))));
+  }
+
+  let mut custom_claims: Object = Object::new();
+  custom_claims.insert("iss".to_owned(), "https://example.com".into());
+  let result = setup
+    .issuer_doc
+    .create_sd_jwt_vc(
+      &claims,
+      &setup.issuer_storage,
+      &setup.issuer_method_fragment,
+      &[],
+      &SdJwtCredentialOptions::new().custom_claims(custom_claims),
+      &JwsSignatureOptions::default(),
+    )
+    .await;
+  assert!(matches!(
+    result,
+    Err(JwkStorageDocumentError::ClaimsSerializationError(_))
+  ));
+}
@@ -0,0 +1,108 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_storage::key_storage::KeyStorageError;
+use identity_storage::key_storage::KeyStorageErrorKind;
+use identity_storage::key_storage::KeyStorageResult;
+use identity_verification::jose::jwk::EcCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParamsEc;
+use identity_verification::jose::jwu;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::EncodedPoint as P256EncodedPoint;
+use signature::Signer;
+use zeroize::Zeroizing;
+
+/// Encodes an uncompressed SEC1 encoded elliptic curve point as a public JWK on the given `curve`.
+fn encode_public_ec_jwk(x: &[u8], y: &[u8], curve: EcCurve) -> Jwk {
+  let mut params = JwkParamsEc::new();
+  params.x = jwu::encode_b64(x);
+  params.y = jwu::encode_b64(y);
+  params.crv = curve.name().to_string();
+  Jwk::from_params(params)
+}
+
+/// Returns the public P-256 JWK corresponding to the raw private key `secret_key`.
+pub(crate) fn p256_public_jwk(secret_key: &[u8]) -> KeyStorageResult<Jwk> {
+  let secret_key = p256::SecretKey::from_slice(secret_key).map_err(|_| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message("invalid P-256 private key")
+  })?;
+  let encoded_point: P256EncodedPoint = secret_key.public_key().to_encoded_point(false);
+  Ok(encode_public_ec_jwk(
+    encoded_point.x().expect("uncompressed point has an x coordinate"),
+    encoded_point.y().expect("uncompressed point has a y coordinate"),
+    EcCurve::P256,
+  ))
+}
+
+/// Returns the public secp256k1 JWK corresponding to the SEC1 encoded `public_key`.
+pub(crate) fn secp256k1_public_jwk(public_key: &[u8]) -> KeyStorageResult<Jwk> {
+  let public_key = k256::PublicKey::from_sec1_bytes(public_key).map_err(|_| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message("invalid secp256k1 public key")
+  })?;
+  let encoded_point: k256::EncodedPoint = public_key.to_encoded_point(false);
+  Ok(encode_public_ec_jwk(
+    encoded_point.x().expect("uncompressed point has an x coordinate"),
+    encoded_point.y().expect("uncompressed point has a y coordinate"),
+    EcCurve::Secp256K1,
+  ))
+}
+
+/// Generates a new raw P-256 private key.
+pub(crate) fn generate_p256_secret_key() -> Zeroizing<Vec<u8>> {
+  Zeroizing::new(p256::SecretKey::random(&mut rand::rngs::OsRng).to_bytes().to_vec())
+}
+
+/// Signs `msg` with the raw P-256 private key `secret_key` according to `ES256`.
+pub(crate) fn sign_p256(secret_key: &[u8], msg: &[u8]) -> KeyStorageResult<Vec<u8>> {
+  let signing_key = p256::ecdsa::SigningKey::from_slice(secret_key).map_err(|_| {
+    KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message("invalid P-256 private key")
+  })?;
+  let signature: p256::ecdsa::Signature = signing_key.sign(msg);
+  Ok(signature.to_bytes().to_vec())
+}
+
+/// Extracts the raw private key from an elliptic curve JWK on the given `curve`.
+pub(crate) fn expand_secret_ec_jwk(jwk: &Jwk, curve: EcCurve) -> KeyStorageResult<Zeroizing<Vec<u8>>> {
+  let params: &JwkParamsEc = jwk.try_ec_params().map_err(|err| {
+    KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+      .with_custom_message("expected a Jwk with EC params")
+      .with_source(err)
+  })?;
+
+  if params.crv != curve.name() {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("expected a {curve} key")),
+    );
+  }
+
+  let sk: Zeroizing<Vec<u8>> = params
+    .d
+    .as_deref()
+    .map(jwu::decode_b64)
+    .ok_or_else(|| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message("expected Jwk `d` param to be present")
+    })?
+    .map(Zeroizing::new)
+    .map_err(|err| {
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message("unable to decode `d` param")
+        .with_source(err)
+    })?;
+
+  // Ensure the private key is valid for the curve before it is persisted.
+  let valid: bool = match curve {
+    EcCurve::P256 => p256::SecretKey::from_slice(&sk).is_ok(),
+    EcCurve::Secp256K1 => k256::SecretKey::from_slice(&sk).is_ok(),
+    _ => false,
+  };
+  if !valid {
+    return Err(
+      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+        .with_custom_message(format!("invalid private key for the {curve} curve")),
+    );
+  }
+
+  Ok(sk)
+}
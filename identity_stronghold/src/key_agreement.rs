@@ -0,0 +1,67 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use crypto::keys::x25519;
+use identity_storage::key_storage::KeyStorageError;
+use identity_storage::key_storage::KeyStorageErrorKind;
+use identity_storage::key_storage::KeyStorageResult;
+use identity_verification::jose::jwk::EcCurve;
+use identity_verification::jose::jwk::EcxCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParamsEc;
+use identity_verification::jose::jwk::JwkParamsOkp;
+use identity_verification::jose::jwu;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use zeroize::Zeroizing;
+
+use crate::stronghold_key_type::StrongholdKeyType;
+
+/// Generates a new private key of the given key agreement `key_type` and returns its raw bytes.
+pub(crate) fn generate_secret_key(key_type: StrongholdKeyType) -> KeyStorageResult<Zeroizing<Vec<u8>>> {
+  match key_type {
+    StrongholdKeyType::X25519 => x25519::SecretKey::generate()
+      .map(|secret_key| Zeroizing::new(secret_key.to_bytes().to_vec()))
+      .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::RetryableIOFailure).with_source(err)),
+    StrongholdKeyType::P256 => Ok(Zeroizing::new(
+      p256::SecretKey::random(&mut rand::rngs::OsRng).to_bytes().to_vec(),
+    )),
+    other => Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("{other} cannot be used for key agreement")),
+    ),
+  }
+}
+
+/// Encodes the raw bytes of a private key of the given key agreement `key_type` as a private JWK.
+pub(crate) fn encode_secret_jwk(key_type: StrongholdKeyType, secret_key: &[u8]) -> KeyStorageResult<Jwk> {
+  match key_type {
+    StrongholdKeyType::X25519 => {
+      let secret_key = x25519::SecretKey::try_from_slice(secret_key).map_err(|err| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("invalid X25519 private key")
+          .with_source(err)
+      })?;
+      let mut params = JwkParamsOkp::new();
+      params.x = jwu::encode_b64(secret_key.public_key().as_slice());
+      params.d = Some(jwu::encode_b64(secret_key.to_bytes()));
+      params.crv = EcxCurve::X25519.name().to_string();
+      Ok(Jwk::from_params(params))
+    }
+    StrongholdKeyType::P256 => {
+      let secret_key = p256::SecretKey::from_slice(secret_key).map_err(|_| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message("invalid P-256 private key")
+      })?;
+      let encoded_point = secret_key.public_key().to_encoded_point(false);
+      let mut params = JwkParamsEc::new();
+      params.x = jwu::encode_b64(encoded_point.x().expect("uncompressed point has an x coordinate"));
+      params.y = jwu::encode_b64(encoded_point.y().expect("uncompressed point has a y coordinate"));
+      params.d = Some(jwu::encode_b64(secret_key.to_bytes()));
+      params.crv = EcCurve::P256.name().to_string();
+      Ok(Jwk::from_params(params))
+    }
+    other => Err(
+      KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+        .with_custom_message(format!("{other} cannot be used for key agreement")),
+    ),
+  }
+}
@@ -1,7 +1,9 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
 
+pub(crate) mod ecdsa;
 pub(crate) mod ed25519;
+pub(crate) mod key_agreement;
 mod storage;
 pub(crate) mod stronghold_key_type;
 #[cfg(test)]
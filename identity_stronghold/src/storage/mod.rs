@@ -4,6 +4,7 @@
 mod stronghold_jwk_storage;
 #[cfg(any(feature = "bbs-plus", test))]
 mod stronghold_jwk_storage_bbs_plus_ext;
+mod stronghold_jwk_storage_key_agreement_ext;
 mod stronghold_key_id;
 
 use std::sync::Arc;
@@ -21,10 +22,8 @@ use identity_verification::jws::JwsAlgorithm;
 use identity_verification::jwu;
 use iota_sdk::client::secret::stronghold::StrongholdSecretManager;
 use iota_sdk::client::secret::SecretManager;
-#[cfg(feature = "bbs-plus")]
 use iota_stronghold::procedures::FatalProcedureError;
 use iota_stronghold::procedures::KeyType as ProceduresKeyType;
-#[cfg(feature = "bbs-plus")]
 use iota_stronghold::procedures::Runner as _;
 use iota_stronghold::procedures::StrongholdProcedure;
 use iota_stronghold::Location;
@@ -37,6 +36,7 @@ use zeroize::Zeroizing;
 #[cfg(feature = "bbs-plus")]
 use zkryptium::bbsplus::keys::BBSplusSecretKey;
 
+use crate::ecdsa;
 use crate::stronghold_key_type::StrongholdKeyType;
 use crate::utils::get_client;
 use crate::utils::IDENTITY_VAULT_PATH;
@@ -95,6 +95,53 @@ impl StrongholdStorage {
     Ok(jwk)
   }
 
+  async fn get_secp256k1_public_key(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+    let stronghold = self.get_stronghold().await;
+    let client = get_client(&stronghold)?;
+
+    let location = Location::generic(
+      IDENTITY_VAULT_PATH.as_bytes().to_vec(),
+      key_id.to_string().as_bytes().to_vec(),
+    );
+
+    let public_key_procedure = iota_stronghold::procedures::PublicKey {
+      ty: ProceduresKeyType::Secp256k1Ecdsa,
+      private_key: location,
+    };
+
+    let procedure_result = client
+      .execute_procedure(StrongholdProcedure::PublicKey(public_key_procedure))
+      .map_err(|err| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound).with_source(err))?;
+
+    let public_key: Vec<u8> = procedure_result.into();
+
+    let mut jwk: Jwk = ecdsa::secp256k1_public_jwk(&public_key)?;
+    jwk.set_alg(JwsAlgorithm::ES256K.name());
+    jwk.set_kid(jwk.thumbprint_sha256_b64());
+
+    Ok(jwk)
+  }
+
+  async fn get_p256_public_key(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
+    let stronghold = self.get_stronghold().await;
+    let client = get_client(&stronghold)?;
+
+    let location = Location::generic(
+      IDENTITY_VAULT_PATH.as_bytes().to_vec(),
+      key_id.to_string().as_bytes().to_vec(),
+    );
+
+    let mut jwk: Jwk = client
+      .get_guards([location], |[sk]| {
+        ecdsa::p256_public_jwk(&sk.borrow()).map_err(|e| FatalProcedureError::from(e.to_string()))
+      })
+      .map_err(|e| KeyStorageError::new(KeyStorageErrorKind::KeyNotFound).with_source(e))?;
+    jwk.set_alg(JwsAlgorithm::ES256.name());
+    jwk.set_kid(jwk.thumbprint_sha256_b64());
+
+    Ok(jwk)
+  }
+
   #[cfg(feature = "bbs-plus")]
   async fn get_bls12381g2_public_key(&self, key_id: &KeyId) -> KeyStorageResult<Jwk> {
     let stronghold = self.get_stronghold().await;
@@ -122,6 +169,8 @@ impl StrongholdStorage {
   pub async fn get_public_key_with_type(&self, key_id: &KeyId, key_type: StrongholdKeyType) -> KeyStorageResult<Jwk> {
     match key_type {
       StrongholdKeyType::Ed25519 => self.get_ed25519_public_key(key_id).await,
+      StrongholdKeyType::P256 => self.get_p256_public_key(key_id).await,
+      StrongholdKeyType::Secp256K1 => self.get_secp256k1_public_key(key_id).await,
       #[cfg(feature = "bbs-plus")]
       StrongholdKeyType::Bls12381G2 => self.get_bls12381g2_public_key(key_id).await,
       #[allow(unreachable_patterns)]
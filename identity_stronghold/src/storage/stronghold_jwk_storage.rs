@@ -11,18 +11,26 @@ use identity_storage::KeyStorageError;
 use identity_storage::KeyStorageErrorKind;
 use identity_storage::KeyStorageResult;
 use identity_storage::KeyType;
+use identity_verification::jwk::EcCurve;
 use identity_verification::jwk::EdCurve;
 use identity_verification::jwk::Jwk;
 use identity_verification::jwk::JwkParamsOkp;
 use identity_verification::jws::JwsAlgorithm;
 use identity_verification::jwu;
 use iota_stronghold::procedures::Ed25519Sign;
+use iota_stronghold::procedures::FatalProcedureError;
 use iota_stronghold::procedures::GenerateKey;
 use iota_stronghold::procedures::KeyType as ProceduresKeyType;
+use iota_stronghold::procedures::Products;
+use iota_stronghold::procedures::Runner as _;
+use iota_stronghold::procedures::Secp256k1EcdsaFlavor;
+use iota_stronghold::procedures::Secp256k1EcdsaSign;
 use iota_stronghold::procedures::StrongholdProcedure;
 use iota_stronghold::Location;
 use std::str::FromStr;
+use zeroize::Zeroizing;
 
+use crate::ecdsa;
 use crate::ed25519;
 use crate::stronghold_key_type::StrongholdKeyType;
 use crate::utils::*;
@@ -38,8 +46,11 @@ impl JwkStorage for StrongholdStorage {
     let key_type = StrongholdKeyType::try_from(&key_type)?;
     check_key_alg_compatibility(key_type, &alg)?;
 
-    let keytype: ProceduresKeyType = match key_type {
-      StrongholdKeyType::Ed25519 => ProceduresKeyType::Ed25519,
+    let keytype: Option<ProceduresKeyType> = match key_type {
+      StrongholdKeyType::Ed25519 => Some(ProceduresKeyType::Ed25519),
+      StrongholdKeyType::Secp256K1 => Some(ProceduresKeyType::Secp256k1Ecdsa),
+      // Stronghold has no built-in support for P-256 keys.
+      StrongholdKeyType::P256 => None,
       StrongholdKeyType::Bls12381G2 => {
         return Err(
           KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message(format!(
@@ -47,6 +58,13 @@ impl JwkStorage for StrongholdStorage {
           )),
         )
       }
+      StrongholdKeyType::X25519 => {
+        return Err(
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message(format!(
+            "`{key_type}` is supported but `JwkStorageKeyAgreementExt::generate_key_agreement` should be called instead."
+          )),
+        )
+      }
     };
 
     let key_id: KeyId = random_key_id();
@@ -55,38 +73,60 @@ impl JwkStorage for StrongholdStorage {
       key_id.to_string().as_bytes().to_vec(),
     );
 
-    let generate_key_procedure = GenerateKey {
-      ty: keytype.clone(),
-      output: location.clone(),
-    };
+    let mut jwk: Jwk = if let Some(keytype) = keytype {
+      let generate_key_procedure = GenerateKey {
+        ty: keytype.clone(),
+        output: location.clone(),
+      };
 
-    client
-      .execute_procedure(StrongholdProcedure::GenerateKey(generate_key_procedure))
-      .map_err(|err| {
-        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
-          .with_custom_message("stronghold generate key procedure failed")
-          .with_source(err)
-      })?;
+      client
+        .execute_procedure(StrongholdProcedure::GenerateKey(generate_key_procedure))
+        .map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message("stronghold generate key procedure failed")
+            .with_source(err)
+        })?;
 
-    let public_key_procedure = iota_stronghold::procedures::PublicKey {
-      ty: keytype,
-      private_key: location,
-    };
+      let public_key_procedure = iota_stronghold::procedures::PublicKey {
+        ty: keytype,
+        private_key: location,
+      };
 
-    let procedure_result = client
-      .execute_procedure(StrongholdProcedure::PublicKey(public_key_procedure))
-      .map_err(|err| {
-        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
-          .with_custom_message("stronghold public key procedure failed")
-          .with_source(err)
-      })?;
-    let public_key: Vec<u8> = procedure_result.into();
+      let procedure_result = client
+        .execute_procedure(StrongholdProcedure::PublicKey(public_key_procedure))
+        .map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message("stronghold public key procedure failed")
+            .with_source(err)
+        })?;
+      let public_key: Vec<u8> = procedure_result.into();
+
+      if let StrongholdKeyType::Secp256K1 = key_type {
+        ecdsa::secp256k1_public_jwk(&public_key)?
+      } else {
+        let mut params = JwkParamsOkp::new();
+        params.x = jwu::encode_b64(public_key);
+        params.crv = EdCurve::Ed25519.name().to_string();
+        Jwk::from_params(params)
+      }
+    } else {
+      client
+        .exec_proc([], &location, |_| {
+          let secret = ecdsa::generate_p256_secret_key();
+          let public_jwk = ecdsa::p256_public_jwk(&secret).map_err(|e| FatalProcedureError::from(e.to_string()))?;
+          Ok(Products {
+            output: public_jwk,
+            secret,
+          })
+        })
+        .map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message("stronghold generate key procedure failed")
+            .with_source(err)
+        })?
+    };
     persist_changes(self.as_secret_manager(), stronghold).await?;
 
-    let mut params = JwkParamsOkp::new();
-    params.x = jwu::encode_b64(public_key);
-    params.crv = EdCurve::Ed25519.name().to_string();
-    let mut jwk: Jwk = Jwk::from_params(params);
     jwk.set_alg(alg.name());
     jwk.set_kid(jwk.thumbprint_sha256_b64());
 
@@ -115,7 +155,11 @@ impl JwkStorage for StrongholdStorage {
         );
       }
     }
-    let secret_key = ed25519::expand_secret_jwk(&jwk)?;
+    let secret_key: Zeroizing<Vec<u8>> = match key_type {
+      StrongholdKeyType::P256 => ecdsa::expand_secret_ec_jwk(&jwk, EcCurve::P256)?,
+      StrongholdKeyType::Secp256K1 => ecdsa::expand_secret_ec_jwk(&jwk, EcCurve::Secp256K1)?,
+      _ => Zeroizing::new(ed25519::expand_secret_jwk(&jwk)?.to_bytes().to_vec()),
+    };
     let key_id: KeyId = random_key_id();
 
     let location = Location::generic(
@@ -126,7 +170,7 @@ impl JwkStorage for StrongholdStorage {
     let client = get_client(&stronghold)?;
     client
       .vault(IDENTITY_VAULT_PATH.as_bytes())
-      .write_secret(location, zeroize::Zeroizing::from(secret_key.to_bytes().to_vec()))
+      .write_secret(location, secret_key)
       .map_err(|err| {
         KeyStorageError::new(KeyStorageErrorKind::Unspecified)
           .with_custom_message("stronghold write secret failed")
@@ -147,7 +191,7 @@ impl JwkStorage for StrongholdStorage {
         JwsAlgorithm::from_str(alg_str).map_err(|_| KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
       })?;
 
-    // Check that `kty` is `Okp` and `crv = Ed25519`.
+    // Check that `kty` is `Okp` and `crv = Ed25519` for EdDSA, or that `kty` is `Ec` with the matching `crv` for ECDSA.
     match alg {
       JwsAlgorithm::EdDSA => {
         let okp_params = public_key.try_okp_params().map_err(|err| {
@@ -164,6 +208,25 @@ impl JwkStorage for StrongholdStorage {
           );
         }
       }
+      JwsAlgorithm::ES256 | JwsAlgorithm::ES256K => {
+        let ec_params = public_key.try_ec_params().map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message(format!("expected a Jwk with EC params in order to sign with {alg}"))
+            .with_source(err)
+        })?;
+        let curve: EcCurve = if alg == JwsAlgorithm::ES256 {
+          EcCurve::P256
+        } else {
+          EcCurve::Secp256K1
+        };
+        if ec_params.crv != curve.name() {
+          return Err(
+            KeyStorageError::new(KeyStorageErrorKind::Unspecified).with_custom_message(format!(
+              "expected Jwk with EC {curve} crv in order to sign with {alg}"
+            )),
+          );
+        }
+      }
       other => {
         return Err(
           KeyStorageError::new(KeyStorageErrorKind::UnsupportedSignatureAlgorithm)
@@ -176,21 +239,49 @@ impl JwkStorage for StrongholdStorage {
       IDENTITY_VAULT_PATH.as_bytes().to_vec(),
       key_id.to_string().as_bytes().to_vec(),
     );
-    let procedure: Ed25519Sign = Ed25519Sign {
-      private_key: location,
-      msg: data.to_vec(),
-    };
 
     let stronghold = self.get_stronghold().await;
     let client = get_client(&stronghold)?;
 
-    let signature: [u8; 64] = client.execute_procedure(procedure).map_err(|err| {
-      KeyStorageError::new(KeyStorageErrorKind::Unspecified)
-        .with_custom_message("stronghold Ed25519Sign procedure failed")
-        .with_source(err)
-    })?;
+    match alg {
+      JwsAlgorithm::ES256 => client
+        .get_guards([location], |[sk]| {
+          ecdsa::sign_p256(&sk.borrow(), data).map_err(|e| FatalProcedureError::from(e.to_string()))
+        })
+        .map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message("stronghold P-256 signing failed")
+            .with_source(err)
+        }),
+      JwsAlgorithm::ES256K => {
+        let procedure: Secp256k1EcdsaSign = Secp256k1EcdsaSign {
+          flavor: Secp256k1EcdsaFlavor::Sha256,
+          msg: data.to_vec(),
+          private_key: location,
+        };
+        let signature: [u8; 65] = client.execute_procedure(procedure).map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message("stronghold Secp256k1EcdsaSign procedure failed")
+            .with_source(err)
+        })?;
+        // Strip the recovery id, JWS signatures only consist of `r` and `s`.
+        Ok(signature[..64].to_vec())
+      }
+      _ => {
+        let procedure: Ed25519Sign = Ed25519Sign {
+          private_key: location,
+          msg: data.to_vec(),
+        };
 
-    Ok(signature.to_vec())
+        let signature: [u8; 64] = client.execute_procedure(procedure).map_err(|err| {
+          KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+            .with_custom_message("stronghold Ed25519Sign procedure failed")
+            .with_source(err)
+        })?;
+
+        Ok(signature.to_vec())
+      }
+    }
   }
 
   async fn delete(&self, key_id: &KeyId) -> KeyStorageResult<()> {
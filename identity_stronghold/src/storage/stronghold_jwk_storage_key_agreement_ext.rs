@@ -0,0 +1,118 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use async_trait::async_trait;
+use identity_storage::key_storage::JwkStorage;
+use identity_storage::JwkGenOutput;
+use identity_storage::JwkStorageKeyAgreementExt;
+use identity_storage::KeyId;
+use identity_storage::KeyStorageError;
+use identity_storage::KeyStorageErrorKind;
+use identity_storage::KeyStorageResult;
+use identity_storage::KeyType;
+use identity_verification::jwe::diffie_hellman;
+use identity_verification::jwe::JweAlgorithm;
+use identity_verification::jwk::Jwk;
+use iota_stronghold::procedures::FatalProcedureError;
+use iota_stronghold::procedures::Products;
+use iota_stronghold::procedures::Runner as _;
+use iota_stronghold::Location;
+use zeroize::Zeroizing;
+
+use crate::key_agreement::encode_secret_jwk;
+use crate::key_agreement::generate_secret_key;
+use crate::stronghold_key_type::*;
+use crate::utils::*;
+use crate::StrongholdStorage;
+
+#[cfg_attr(not(feature = "send-sync-storage"), async_trait(?Send))]
+#[cfg_attr(feature = "send-sync-storage", async_trait)]
+impl JwkStorageKeyAgreementExt for StrongholdStorage {
+  async fn generate_key_agreement(&self, key_type: KeyType, alg: JweAlgorithm) -> KeyStorageResult<JwkGenOutput> {
+    let key_type = StrongholdKeyType::try_from(&key_type)?;
+    check_key_agreement_alg_compatibility(key_type, &alg)?;
+
+    // Get a key id that's not already used.
+    let mut kid = random_key_id();
+    while self.exists(&kid).await? {
+      kid = random_key_id();
+    }
+
+    let stronghold = self.get_stronghold().await;
+    let client = get_client(&stronghold)?;
+    let target_key_location = Location::generic(
+      IDENTITY_VAULT_PATH.as_bytes().to_vec(),
+      kid.to_string().as_bytes().to_vec(),
+    );
+    let mut jwk: Jwk = client
+      .exec_proc([], &target_key_location, |_| {
+        let secret = generate_secret_key(key_type).map_err(|e| FatalProcedureError::from(e.to_string()))?;
+        let public_jwk = encode_secret_jwk(key_type, &secret)
+          .ok()
+          .and_then(|jwk| jwk.to_public())
+          .ok_or_else(|| FatalProcedureError::from("unable to encode the public key".to_owned()))?;
+
+        Ok(Products {
+          output: public_jwk,
+          secret,
+        })
+      })
+      .map_err(|e| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("Failed to execute stronghold procedure")
+          .with_source(e)
+      })?;
+
+    persist_changes(self.as_secret_manager(), stronghold).await?;
+
+    jwk.set_alg(alg.name());
+    jwk.set_kid(jwk.thumbprint_sha256_b64());
+
+    Ok(JwkGenOutput::new(kid, jwk))
+  }
+
+  async fn key_agreement(
+    &self,
+    key_id: &KeyId,
+    public_key: &Jwk,
+    peer_public_key: &Jwk,
+  ) -> KeyStorageResult<Zeroizing<Vec<u8>>> {
+    // Check that the given public key is a key agreement key.
+    let key_type = StrongholdKeyType::try_from(public_key)?;
+    if !matches!(key_type, StrongholdKeyType::X25519 | StrongholdKeyType::P256) {
+      return Err(
+        KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+          .with_custom_message(format!("{key_type} cannot be used for key agreement")),
+      );
+    }
+
+    // Check `key_id` exists in store.
+    if !self.exists(key_id).await? {
+      return Err(KeyStorageError::new(KeyStorageErrorKind::KeyNotFound));
+    }
+
+    let sk_location = Location::Generic {
+      vault_path: IDENTITY_VAULT_PATH.as_bytes().to_vec(),
+      record_path: key_id.to_string().as_bytes().to_vec(),
+    };
+
+    let stronghold = self.get_stronghold().await;
+    let client = get_client(&stronghold)?;
+    client
+      .get_guards([sk_location], |[sk]| {
+        let secret_jwk = encode_secret_jwk(key_type, &sk.borrow()).map_err(|e| FatalProcedureError::from(e.to_string()))?;
+        // Ensure `sk` and `public_key` match.
+        if secret_jwk.thumbprint_sha256_b64() != public_key.thumbprint_sha256_b64() {
+          return Err(FatalProcedureError::from(
+            "`public_key` is not the public key of key with id `key_id`".to_owned(),
+          ));
+        }
+        diffie_hellman(&secret_jwk, peer_public_key).map_err(|e| FatalProcedureError::from(e.to_string()))
+      })
+      .map_err(|e| {
+        KeyStorageError::new(KeyStorageErrorKind::Unspecified)
+          .with_custom_message("Key agreement failed")
+          .with_source(e)
+      })
+  }
+}
@@ -7,6 +7,8 @@ use identity_storage::KeyStorageError;
 use identity_storage::KeyStorageErrorKind;
 use identity_storage::KeyType;
 use identity_verification::jwk::BlsCurve;
+use identity_verification::jwk::EcCurve;
+use identity_verification::jwk::EcxCurve;
 use identity_verification::jwk::EdCurve;
 use identity_verification::jwk::Jwk;
 use identity_verification::jwk::JwkType;
@@ -17,12 +19,26 @@ pub const ED25519_KEY_TYPE: KeyType = KeyType::from_static_str(ED25519_KEY_TYPE_
 pub const BLS12381G2_KEY_TYPE_STR: &str = "BLS12381G2";
 /// The BLS12381G2 key type
 pub const BLS12381G2_KEY_TYPE: KeyType = KeyType::from_static_str(BLS12381G2_KEY_TYPE_STR);
+pub const X25519_KEY_TYPE_STR: &str = "X25519";
+/// The X25519 key type, used for key agreement.
+pub const X25519_KEY_TYPE: KeyType = KeyType::from_static_str(X25519_KEY_TYPE_STR);
+/// The string representation of the P-256 key type.
+pub const P256_KEY_TYPE_STR: &str = "P-256";
+/// The P-256 key type.
+pub const P256_KEY_TYPE: KeyType = KeyType::from_static_str(P256_KEY_TYPE_STR);
+/// The string representation of the secp256k1 key type.
+pub const SECP256K1_KEY_TYPE_STR: &str = "secp256k1";
+/// The secp256k1 key type.
+pub const SECP256K1_KEY_TYPE: KeyType = KeyType::from_static_str(SECP256K1_KEY_TYPE_STR);
 
 /// Key Types supported by the stronghold storage implementation.
 #[derive(Debug, Copy, Clone)]
 pub enum StrongholdKeyType {
   Ed25519,
   Bls12381G2,
+  X25519,
+  P256,
+  Secp256K1,
 }
 
 impl StrongholdKeyType {
@@ -31,6 +47,9 @@ impl StrongholdKeyType {
     match self {
       StrongholdKeyType::Ed25519 => ED25519_KEY_TYPE_STR,
       StrongholdKeyType::Bls12381G2 => BLS12381G2_KEY_TYPE_STR,
+      StrongholdKeyType::X25519 => X25519_KEY_TYPE_STR,
+      StrongholdKeyType::P256 => P256_KEY_TYPE_STR,
+      StrongholdKeyType::Secp256K1 => SECP256K1_KEY_TYPE_STR,
     }
   }
 }
@@ -48,6 +67,9 @@ impl TryFrom<&KeyType> for StrongholdKeyType {
     match value.as_str() {
       ED25519_KEY_TYPE_STR => Ok(StrongholdKeyType::Ed25519),
       BLS12381G2_KEY_TYPE_STR => Ok(StrongholdKeyType::Bls12381G2),
+      X25519_KEY_TYPE_STR => Ok(StrongholdKeyType::X25519),
+      P256_KEY_TYPE_STR => Ok(StrongholdKeyType::P256),
+      SECP256K1_KEY_TYPE_STR => Ok(StrongholdKeyType::Secp256K1),
       _ => Err(KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)),
     }
   }
@@ -70,6 +92,15 @@ impl TryFrom<&Jwk> for StrongholdKeyType {
             .with_custom_message("expected Okp parameters for a JWK with `kty` Okp")
             .with_source(err)
         })?;
+        if let Ok(curve) = okp_params.try_ecx_curve() {
+          return match curve {
+            EcxCurve::X25519 => Ok(StrongholdKeyType::X25519),
+            curve => Err(
+              KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
+                .with_custom_message(format!("{curve} not supported")),
+            ),
+          };
+        }
         match okp_params.try_ed_curve().map_err(|err| {
           KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
             .with_custom_message("only Ed curves are supported for signing")
@@ -88,6 +119,11 @@ impl TryFrom<&Jwk> for StrongholdKeyType {
             .with_custom_message("expected EC parameters for a JWK with `kty` Ec")
             .with_source(err)
         })?;
+        match ec_params.try_ec_curve() {
+          Ok(EcCurve::P256) => return Ok(StrongholdKeyType::P256),
+          Ok(EcCurve::Secp256K1) => return Ok(StrongholdKeyType::Secp256K1),
+          _ => (),
+        }
         match ec_params.try_bls_curve().map_err(|err| {
           KeyStorageError::new(KeyStorageErrorKind::UnsupportedKeyType)
             .with_custom_message("only Ed curves are supported for signing")
@@ -2,6 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod test_bbs_ext;
+mod test_ecdsa;
 mod test_jwk_storage;
+mod test_key_agreement_ext;
 mod test_key_id_storage;
 pub(crate) mod utils;
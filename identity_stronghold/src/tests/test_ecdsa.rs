@@ -0,0 +1,132 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_ecdsa_verifier::EcDSAJwsVerifier;
+use identity_storage::JwkGenOutput;
+use identity_storage::JwkStorage;
+use identity_storage::KeyStorageErrorKind;
+use identity_verification::jose::jwk::EcCurve;
+use identity_verification::jose::jwk::Jwk;
+use identity_verification::jose::jwk::JwkParamsEc;
+use identity_verification::jose::jws::JwsAlgorithm;
+use identity_verification::jose::jws::JwsVerifier;
+use identity_verification::jose::jws::VerificationInput;
+use identity_verification::jose::jwu;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+
+use crate::stronghold_key_type::StrongholdKeyType;
+use crate::tests::utils::create_stronghold_secret_manager;
+use crate::StrongholdStorage;
+
+const TEST_MSG: &[u8] = b"test";
+
+/// Verifies `signature` with the algorithm of `public_key`.
+fn verify(signature: Vec<u8>, public_key: &Jwk) {
+  let input = VerificationInput {
+    alg: public_key.alg().unwrap().parse().unwrap(),
+    signing_input: TEST_MSG.into(),
+    decoded_signature: signature.into(),
+  };
+  EcDSAJwsVerifier::default().verify(input, public_key).unwrap();
+}
+
+fn encode_ec_jwk(d: &[u8], encoded_point: &[u8], curve: EcCurve, alg: JwsAlgorithm) -> Jwk {
+  // Uncompressed SEC1 encoding: 0x04 || x || y.
+  let (x, y) = encoded_point[1..].split_at(32);
+  let mut params = JwkParamsEc::new();
+  params.x = jwu::encode_b64(x);
+  params.y = jwu::encode_b64(y);
+  params.d = Some(jwu::encode_b64(d));
+  params.crv = curve.name().to_string();
+  let mut jwk = Jwk::from_params(params);
+  jwk.set_alg(alg.name());
+  jwk
+}
+
+#[tokio::test]
+async fn generate_sign_and_verify_ecdsa() {
+  let stronghold_storage = StrongholdStorage::new(create_stronghold_secret_manager());
+
+  for (key_type, alg) in [
+    (StrongholdKeyType::P256, JwsAlgorithm::ES256),
+    (StrongholdKeyType::Secp256K1, JwsAlgorithm::ES256K),
+  ] {
+    let alg_name: String = alg.name().to_string();
+    let JwkGenOutput { key_id, jwk, .. } = stronghold_storage.generate(key_type.into(), alg).await.unwrap();
+    assert!(!jwk.is_private());
+    assert_eq!(jwk.alg(), Some(alg_name.as_str()));
+
+    let public_key: Jwk = stronghold_storage
+      .get_public_key_with_type(&key_id, key_type)
+      .await
+      .unwrap();
+    assert_eq!(public_key, jwk);
+
+    let signature: Vec<u8> = stronghold_storage.sign(&key_id, TEST_MSG, &jwk).await.unwrap();
+    assert_eq!(signature.len(), 64);
+    verify(signature, &jwk);
+
+    stronghold_storage.delete(&key_id).await.unwrap();
+  }
+}
+
+#[tokio::test]
+async fn insert_sign_and_verify_ecdsa() {
+  let stronghold_storage = StrongholdStorage::new(create_stronghold_secret_manager());
+
+  let p256_key = p256::SecretKey::random(&mut rand::rngs::OsRng);
+  let p256_jwk: Jwk = encode_ec_jwk(
+    &p256_key.to_bytes(),
+    p256_key.public_key().to_encoded_point(false).as_bytes(),
+    EcCurve::P256,
+    JwsAlgorithm::ES256,
+  );
+  let k256_key = k256::SecretKey::random(&mut rand::rngs::OsRng);
+  let k256_jwk: Jwk = encode_ec_jwk(
+    &k256_key.to_bytes(),
+    k256_key.public_key().to_encoded_point(false).as_bytes(),
+    EcCurve::Secp256K1,
+    JwsAlgorithm::ES256K,
+  );
+
+  for (jwk, key_type) in [
+    (p256_jwk, StrongholdKeyType::P256),
+    (k256_jwk, StrongholdKeyType::Secp256K1),
+  ] {
+    let key_id = stronghold_storage.insert(jwk.clone()).await.unwrap();
+    let public_jwk: Jwk = jwk.to_public().unwrap();
+
+    let public_key: Jwk = stronghold_storage
+      .get_public_key_with_type(&key_id, key_type)
+      .await
+      .unwrap();
+    assert_eq!(public_key.try_ec_params().unwrap(), public_jwk.try_ec_params().unwrap());
+
+    let signature: Vec<u8> = stronghold_storage.sign(&key_id, TEST_MSG, &public_jwk).await.unwrap();
+    verify(signature, &public_jwk);
+  }
+}
+
+#[tokio::test]
+async fn ecdsa_incompatible_key_alg() {
+  let stronghold_storage = StrongholdStorage::new(create_stronghold_secret_manager());
+
+  // INVALID: P-256 keys cannot be used with ES256K.
+  let err = stronghold_storage
+    .generate(StrongholdKeyType::P256.into(), JwsAlgorithm::ES256K)
+    .await
+    .unwrap_err();
+  assert!(matches!(err.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+  // INVALID: A secp256k1 key cannot sign with a P-256 public key.
+  let JwkGenOutput { key_id, .. } = stronghold_storage
+    .generate(StrongholdKeyType::Secp256K1.into(), JwsAlgorithm::ES256K)
+    .await
+    .unwrap();
+  let JwkGenOutput { mut jwk, .. } = stronghold_storage
+    .generate(StrongholdKeyType::P256.into(), JwsAlgorithm::ES256)
+    .await
+    .unwrap();
+  jwk.set_alg(JwsAlgorithm::ES256K.name());
+  assert!(stronghold_storage.sign(&key_id, TEST_MSG, &jwk).await.is_err());
+}
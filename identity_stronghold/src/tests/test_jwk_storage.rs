@@ -171,7 +171,7 @@ mod jwk_storage_tests {
 
   pub(crate) async fn test_incompatible_key_type(store: impl JwkStorage) {
     let mut ec_params = JwkParamsEc::new();
-    ec_params.crv = EcCurve::P256.name().to_string();
+    ec_params.crv = EcCurve::P521.name().to_string();
     ec_params.x = String::new();
     ec_params.y = String::new();
     ec_params.d = Some(String::new());
@@ -0,0 +1,100 @@
+// Copyright 2020-2024 IOTA Stiftung
+// SPDX-License-Identifier: Apache-2.0
+
+use identity_storage::JwkGenOutput;
+use identity_storage::JwkStorage;
+use identity_storage::JwkStorageKeyAgreementExt;
+use identity_storage::KeyStorageErrorKind;
+use identity_verification::jwe::DecodedJwe;
+use identity_verification::jwe::JweAlgorithm;
+use identity_verification::jwe::JweDecoder;
+use identity_verification::jwe::JweDecryptionItem;
+use identity_verification::jwe::JweEncoder;
+use identity_verification::jwe::JweEncryption;
+use identity_verification::jwe::JweFormat;
+use identity_verification::jwe::JweHeader;
+use identity_verification::jwe::JweRecipient;
+use identity_verification::jws::JwsAlgorithm;
+
+use crate::stronghold_key_type::StrongholdKeyType;
+use crate::tests::utils::create_stronghold_secret_manager;
+use crate::StrongholdStorage;
+
+#[tokio::test]
+async fn stronghold_key_agreement_works() -> anyhow::Result<()> {
+  let stronghold_storage = StrongholdStorage::new(create_stronghold_secret_manager());
+
+  for key_type in [StrongholdKeyType::X25519, StrongholdKeyType::P256] {
+    let JwkGenOutput {
+      key_id: alice_key_id,
+      jwk: alice_jwk,
+      ..
+    } = stronghold_storage
+      .generate_key_agreement(key_type.into(), JweAlgorithm::ECDH_ES)
+      .await?;
+    let JwkGenOutput {
+      key_id: bob_key_id,
+      jwk: bob_jwk,
+      ..
+    } = stronghold_storage
+      .generate_key_agreement(key_type.into(), JweAlgorithm::ECDH_ES_A256KW)
+      .await?;
+
+    assert!(alice_jwk.is_public());
+    assert!(stronghold_storage.exists(&alice_key_id).await?);
+
+    let alice_secret = stronghold_storage
+      .key_agreement(&alice_key_id, &alice_jwk, &bob_jwk)
+      .await?;
+    let bob_secret = stronghold_storage
+      .key_agreement(&bob_key_id, &bob_jwk, &alice_jwk)
+      .await?;
+    assert_eq!(alice_secret, bob_secret);
+
+    // INVALID: The public key does not belong to the key with the given key id.
+    let error = stronghold_storage
+      .key_agreement(&alice_key_id, &bob_jwk, &bob_jwk)
+      .await
+      .unwrap_err();
+    assert!(matches!(error.kind(), KeyStorageErrorKind::Unspecified));
+  }
+
+  Ok(())
+}
+
+#[tokio::test]
+async fn stronghold_key_agreement_fails_with_wrong_key_type() -> anyhow::Result<()> {
+  let stronghold_storage = StrongholdStorage::new(create_stronghold_secret_manager());
+  let error = stronghold_storage
+    .generate_key_agreement(StrongholdKeyType::Ed25519.into(), JweAlgorithm::ECDH_ES)
+    .await
+    .unwrap_err();
+  assert!(matches!(error.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+  let error = stronghold_storage
+    .generate(StrongholdKeyType::X25519.into(), JwsAlgorithm::EdDSA)
+    .await
+    .unwrap_err();
+  assert!(matches!(error.kind(), KeyStorageErrorKind::KeyAlgorithmMismatch));
+
+  Ok(())
+}
+
+#[tokio::test]
+async fn stronghold_decrypt_jwe_works() -> anyhow::Result<()> {
+  let stronghold_storage = StrongholdStorage::new(create_stronghold_secret_manager());
+  let JwkGenOutput { key_id, jwk, .. } = stronghold_storage
+    .generate_key_agreement(StrongholdKeyType::X25519.into(), JweAlgorithm::ECDH_ES_A128KW)
+    .await?;
+
+  let mut header = JweHeader::new();
+  header.set_alg(JweAlgorithm::ECDH_ES_A128KW);
+  header.set_enc(JweEncryption::A256GCM);
+  let jwe: String = JweEncoder::new(&header).encrypt(b"test", &[JweRecipient::new(&jwk)], JweFormat::Compact)?;
+
+  let item: JweDecryptionItem = JweDecoder::new().decode_compact_serialization(jwe.as_bytes())?;
+  let decoded: DecodedJwe = stronghold_storage.decrypt_jwe(&key_id, &jwk, &item, 0).await?;
+  assert_eq!(decoded.plaintext, b"test");
+
+  Ok(())
+}
@@ -5,6 +5,7 @@ use identity_storage::KeyId;
 use identity_storage::KeyStorageError;
 use identity_storage::KeyStorageErrorKind;
 use identity_storage::KeyStorageResult;
+use identity_verification::jwe::JweAlgorithm;
 use identity_verification::jws::JwsAlgorithm;
 use iota_sdk::client::secret::SecretManager;
 use iota_stronghold::Client;
@@ -27,6 +28,22 @@ pub fn random_key_id() -> KeyId {
 pub fn check_key_alg_compatibility(key_type: StrongholdKeyType, alg: &JwsAlgorithm) -> KeyStorageResult<()> {
   match (key_type, alg) {
     (StrongholdKeyType::Ed25519, JwsAlgorithm::EdDSA) => Ok(()),
+    (StrongholdKeyType::P256, JwsAlgorithm::ES256) => Ok(()),
+    (StrongholdKeyType::Secp256K1, JwsAlgorithm::ES256K) => Ok(()),
+    (key_type, alg) => Err(
+      KeyStorageError::new(identity_storage::KeyStorageErrorKind::KeyAlgorithmMismatch)
+        .with_custom_message(format!("cannot use key type `{key_type}` with algorithm `{alg}`")),
+    ),
+  }
+}
+
+/// Check that the key type can be used with the key agreement algorithm.
+pub fn check_key_agreement_alg_compatibility(key_type: StrongholdKeyType, alg: &JweAlgorithm) -> KeyStorageResult<()> {
+  match (key_type, alg) {
+    (
+      StrongholdKeyType::X25519 | StrongholdKeyType::P256,
+      JweAlgorithm::ECDH_ES | JweAlgorithm::ECDH_ES_A128KW | JweAlgorithm::ECDH_ES_A192KW | JweAlgorithm::ECDH_ES_A256KW,
+    ) => Ok(()),
     (key_type, alg) => Err(
       KeyStorageError::new(identity_storage::KeyStorageErrorKind::KeyAlgorithmMismatch)
         .with_custom_message(format!("cannot use key type `{key_type}` with algorithm `{alg}`")),
@@ -1,6 +1,6 @@
 // Copyright 2020-2023 IOTA Stiftung
 // SPDX-License-Identifier: Apache-2.0
-//! Provides JWK and JWS types and functionality.  
+//! Provides JWK, JWS and JWE types and functionality.  
 
 // Re-export necessary types from `identity_jose`.
 
@@ -16,6 +16,12 @@ pub mod jws {
   pub use identity_jose::jws::*;
 }
 
+pub mod jwe {
+  //! Reexport of [identity_jose::jwe].
+
+  pub use identity_jose::jwe::*;
+}
+
 pub mod jwu {
   //! Reexport of [identity_jose::jwu].
 
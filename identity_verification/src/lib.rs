@@ -23,6 +23,7 @@ pub mod jose;
 pub mod verification_method;
 pub use error::Error;
 pub use error::Result;
+pub use jose::jwe;
 pub use jose::jwk;
 pub use jose::jws;
 pub use jose::jwu;
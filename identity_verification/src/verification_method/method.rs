@@ -6,6 +6,8 @@ use core::fmt::Formatter;
 use std::borrow::Cow;
 
 use identity_did::DIDJwk;
+#[cfg(feature = "did-key")]
+use identity_did::DIDKey;
 use identity_jose::jwk::Jwk;
 use serde::de;
 use serde::Deserialize;
@@ -225,6 +227,23 @@ impl VerificationMethod {
       .data(MethodData::PublicKeyJwk(key))
       .build()
   }
+
+  /// Creates a new `Multikey` [`VerificationMethod`] controlled by `did` from the public key encoded in the
+  /// given [`DIDKey`]. As prescribed by the did:key method, the multibase encoded key is used as fragment.
+  #[cfg(feature = "did-key")]
+  pub fn new_from_did_key<D: DID>(did: D, key: &DIDKey) -> Result<Self> {
+    let id: DIDUrl = did
+      .to_url()
+      .join(format!("#{}", key.multibase()))
+      .map_err(Error::DIDUrlConstructionError)?;
+
+    MethodBuilder::default()
+      .id(id)
+      .controller(did.into())
+      .type_(MethodType::MULTIKEY)
+      .data(MethodData::PublicKeyMultibase(key.multibase().to_owned()))
+      .build()
+  }
 }
 
 impl Display for VerificationMethod {
@@ -248,6 +267,15 @@ impl KeyComparable for VerificationMethod {
   }
 }
 
+#[cfg(feature = "did-key")]
+impl TryFrom<DIDKey> for VerificationMethod {
+  type Error = Error;
+  fn try_from(did: DIDKey) -> Result<Self, Self::Error> {
+    let key: DIDKey = did.clone();
+    Self::new_from_did_key(did, &key)
+  }
+}
+
 impl TryFrom<DIDJwk> for VerificationMethod {
   type Error = Error;
   fn try_from(did: DIDJwk) -> Result<Self, Self::Error> {
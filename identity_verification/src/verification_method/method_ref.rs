@@ -13,6 +13,7 @@ use identity_did::DIDUrl;
 /// A reference to a verification method, either a `DID` or embedded `Method`.
 #[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(untagged)]
+#[allow(clippy::large_enum_variant)]
 pub enum MethodRef {
   /// A [`VerificationMethod`] embedded in a verification relationship.
   Embed(VerificationMethod),
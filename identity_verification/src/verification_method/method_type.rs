@@ -13,6 +13,7 @@ const ED25519_VERIFICATION_KEY_2018_STR: &str = "Ed25519VerificationKey2018";
 const X25519_KEY_AGREEMENT_KEY_2019_STR: &str = "X25519KeyAgreementKey2019";
 const JSON_WEB_KEY_METHOD_TYPE: &str = "JsonWebKey";
 const JSON_WEB_KEY_2020_STR: &str = "JsonWebKey2020";
+const MULTIKEY_STR: &str = "Multikey";
 
 /// verification method types.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
@@ -30,6 +31,9 @@ impl MethodType {
   /// A verification method for use with JWT verification as prescribed by the [`Jwk`](::identity_jose::jwk::Jwk)
   /// in the [`publicKeyJwk`](crate::MethodData::PublicKeyJwk) entry.
   pub const JSON_WEB_KEY_2020: Self = Self(Cow::Borrowed(JSON_WEB_KEY_2020_STR));
+  /// A verification method with a multicodec encoded public key in the
+  /// [`publicKeyMultibase`](crate::MethodData::PublicKeyMultibase) entry.
+  pub const MULTIKEY: Self = Self(Cow::Borrowed(MULTIKEY_STR));
   /// Construct a custom method type.
   pub fn custom(type_: impl AsRef<str>) -> Self {
     Self(Cow::Owned(type_.as_ref().to_owned()))
@@ -67,6 +71,7 @@ impl FromStr for MethodType {
         Self::JSON_WEB_KEY,
       ),
       JSON_WEB_KEY_2020_STR => Ok(Self::JSON_WEB_KEY_2020),
+      MULTIKEY_STR => Ok(Self::MULTIKEY),
       _ => Ok(Self(Cow::Owned(string.to_owned()))),
     }
   }
@@ -84,6 +89,7 @@ mod tests {
       MethodType::ED25519_VERIFICATION_KEY_2018,
       MethodType::X25519_KEY_AGREEMENT_KEY_2019,
       MethodType::JSON_WEB_KEY_2020,
+      MethodType::MULTIKEY,
     ] {
       let ser: Value = serde_json::to_value(method_type.clone()).unwrap();
       assert_eq!(ser.as_str().unwrap(), method_type.as_str());